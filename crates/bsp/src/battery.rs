@@ -1,30 +1,159 @@
 //! # Battery Module
 //!
 //! This module provides functionality for interacting with the battery on the board.
-//! It includes methods for initializing the battery interface and retrieving the battery percentage.
+//! It includes methods for initializing the battery interface and retrieving the battery
+//! voltage and percentage.
 //!
+//! The battery voltage is measured by the ADC through a resistor divider. Readings are
+//! smoothed so the percentage does not jump around as the load on the battery changes.
+//! The percentage comes from a typical LiPo discharge curve, which is fairly flat in
+//! the middle, so the voltage is not linear with the remaining charge.
+//!
+//! On boards without a battery measurement it reports a full battery.
 //!
 //! ## Functions
 //!
+//! - `update`: Takes a new reading of the battery voltage. Call about once a second.
 //! - `get_battery_percentage`: Retrieves the battery percentage.
+//! - `get_battery_mv`: Retrieves the battery voltage in millivolts.
+//! - `is_low`: Indicates if the battery is low and the user should be warned.
+//! - `mv_to_percentage`: Converts a LiPo voltage to a percentage.
+//!
+//! ## Example
+//!
+//! ```rust
+//!  use bsp::BSP;
+//!
+//!  let mut bsp = BSP::new();
+//!  bsp.init();
 //!
+//!  bsp.battery.update();
+//!  let percent = bsp.battery.get_battery_percentage();
+//!  if bsp.battery.is_low() {
+//!      // warn the user
+//!  }
+//! ```
 //!
 
 extern crate hal;
 
+use crate::board;
+
 #[cfg(feature = "std")]
 extern crate std;
 
-pub struct Battery {}
+/// Voltage in millivolts reported when the board cannot measure the battery.
+const FULL_MV: u32 = 4200;
+
+/// Below this voltage the battery is considered low.
+const LOW_MV: u32 = 3500;
+
+/// The battery must get back above this voltage to stop being low. This keeps the
+/// flag from flickering when the voltage sags under load.
+const LOW_CLEAR_MV: u32 = 3600;
+
+/// Weight of a new reading in the smoothing as a power of 2, so 3 is 1/8.
+const SMOOTHING_SHIFT: u32 = 3;
+
+/// Typical single cell LiPo discharge curve as millivolts and percentage.
+/// Must be in order of decreasing voltage.
+const LIPO_CURVE: [(u32, u8); 21] = [
+    (4200, 100),
+    (4150, 95),
+    (4110, 90),
+    (4080, 85),
+    (4020, 80),
+    (3980, 75),
+    (3950, 70),
+    (3910, 65),
+    (3870, 60),
+    (3850, 55),
+    (3840, 50),
+    (3820, 45),
+    (3800, 40),
+    (3790, 35),
+    (3770, 30),
+    (3750, 25),
+    (3730, 20),
+    (3710, 15),
+    (3690, 10),
+    (3610, 5),
+    (3270, 0),
+];
+
+pub struct Battery {
+    // smoothed voltage in millivolts shifted left by SMOOTHING_SHIFT to keep precision
+    smoothed: u32,
+    low: bool,
+}
 
 impl crate::battery::Battery {
     #[inline(never)]
     pub fn new() -> Self {
-        crate::battery::Battery {}
+        crate::battery::Battery {
+            smoothed: FULL_MV << SMOOTHING_SHIFT,
+            low: false,
+        }
     }
 
     #[inline(never)]
-    pub fn init(&self) {}
+    pub fn init(&mut self) {
+        if !board::info::HAS_BATTERY {
+            return;
+        }
+
+        board::info::BATTERY_PIN.analog();
+
+        hal::adc::init();
+
+        // the divider is high impedance so use the longest sample time
+        hal::adc::set_sample_time(
+            board::info::BATTERY_ADC_CHANNEL,
+            hal::adc::SampleTime::Cycles480,
+        );
+
+        // the simulated battery starts out full
+        #[cfg(feature = "board-sim")]
+        hal::sim::set_analog(
+            board::info::BATTERY_ADC_CHANNEL,
+            FULL_MV / board::info::BATTERY_DIVIDER,
+        );
+
+        // start the smoothing from the first reading
+        let mv = read_mv();
+        self.smoothed = mv << SMOOTHING_SHIFT;
+        self.low = mv < LOW_MV;
+    }
+
+    /// Takes a new reading of the battery voltage and adds it into the smoothed value.
+    pub fn update(&mut self) {
+        if !board::info::HAS_BATTERY {
+            return;
+        }
+
+        self.add_reading(read_mv());
+    }
+
+    fn add_reading(&mut self, mv: u32) {
+        // exponential moving average
+        self.smoothed = self.smoothed - (self.smoothed >> SMOOTHING_SHIFT) + mv;
+
+        let mv = self.get_battery_mv();
+        if mv < LOW_MV {
+            self.low = true;
+        } else if mv > LOW_CLEAR_MV {
+            self.low = false;
+        }
+    }
+
+    /// Retrieves the battery voltage.
+    ///
+    /// # Returns
+    /// A `u32` with the smoothed battery voltage in millivolts.
+    ///
+    pub fn get_battery_mv(&self) -> u32 {
+        self.smoothed >> SMOOTHING_SHIFT
+    }
 
     /// Retrieves the battery percentage.
     ///
@@ -32,6 +161,104 @@ impl crate::battery::Battery {
     /// A `u8` representing the battery percentage as a number between 0 and 100.
     ///
     pub fn get_battery_percentage(&self) -> u8 {
-        99
+        mv_to_percentage(self.get_battery_mv())
+    }
+
+    /// Indicates if the battery is low.
+    ///
+    /// # Returns
+    /// `true` once the voltage drops below 3.5 V, until it goes back above 3.6 V.
+    ///
+    pub fn is_low(&self) -> bool {
+        self.low
+    }
+}
+
+fn read_mv() -> u32 {
+    hal::adc::read_mv(board::info::BATTERY_ADC_CHANNEL) * board::info::BATTERY_DIVIDER
+}
+
+/// Converts a LiPo battery voltage in millivolts to a percentage between 0 and 100,
+/// interpolating between the points of the discharge curve.
+pub fn mv_to_percentage(mv: u32) -> u8 {
+    let (max_mv, max_pct) = LIPO_CURVE[0];
+    if mv >= max_mv {
+        return max_pct;
+    }
+
+    for pair in LIPO_CURVE.windows(2) {
+        let (hi_mv, hi_pct) = pair[0];
+        let (lo_mv, lo_pct) = pair[1];
+        if mv >= lo_mv {
+            let span = (hi_pct - lo_pct) as u32;
+            let pct = lo_pct as u32 + (mv - lo_mv) * span / (hi_mv - lo_mv);
+            return pct as u8;
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mv_to_percentage() {
+        assert_eq!(mv_to_percentage(4300), 100);
+        assert_eq!(mv_to_percentage(4200), 100);
+        assert_eq!(mv_to_percentage(3840), 50);
+        assert_eq!(mv_to_percentage(3845), 52);
+        assert_eq!(mv_to_percentage(3270), 0);
+        assert_eq!(mv_to_percentage(3000), 0);
+    }
+
+    #[test]
+    fn test_smoothing_and_low() {
+        let mut battery = Battery::new();
+        assert_eq!(battery.get_battery_mv(), FULL_MV);
+        assert_eq!(battery.get_battery_percentage(), 100);
+        assert!(!battery.is_low());
+
+        // a single low reading only moves the value a little
+        battery.add_reading(3400);
+        assert_eq!(battery.get_battery_mv(), 4100);
+        assert!(!battery.is_low());
+
+        for _ in 0..50 {
+            battery.add_reading(3400);
+        }
+        assert!(battery.get_battery_mv() < LOW_MV);
+        assert!(battery.is_low());
+
+        // stays low until well above the low threshold
+        for _ in 0..50 {
+            battery.add_reading(3550);
+        }
+        assert!(battery.is_low());
+        for _ in 0..50 {
+            battery.add_reading(3700);
+        }
+        assert!(!battery.is_low());
+    }
+
+    #[test]
+    #[cfg(feature = "board-sim")]
+    fn test_update() {
+        let mut battery = Battery::new();
+        battery.init();
+        assert!(battery.get_battery_percentage() >= 99);
+        assert!(!battery.is_low());
+
+        // the battery drops to 3.4 V
+        let channel = board::info::BATTERY_ADC_CHANNEL;
+        hal::sim::set_analog(channel, 3400 / board::info::BATTERY_DIVIDER);
+        battery.update();
+        assert!(!battery.is_low());
+        for _ in 0..50 {
+            battery.update();
+        }
+        assert!(battery.is_low());
+        assert!(battery.get_battery_percentage() <= 1);
     }
 }
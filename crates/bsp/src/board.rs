@@ -33,7 +33,10 @@
 //! - `HAS_AI_BUTTON`: Indicates if the board has an AI (Artificial Intelligence) button.
 //! - `AI_BUTTON`: The pin for the AI button.
 //! - `AI_BUTTON_PULL_UP`: Indicates if the AI button has a pull-up resistor.
-//! - `CONSOLE_TX`, `CONSOLE_RX`: Pins for the console serial TX and RX.
+//! - `CONSOLE_UART`, `CONSOLE_TX`, `CONSOLE_RX`: The USART and pins for the console serial TX and RX.
+//! - `HAS_BATTERY`: Indicates if the board can measure the battery voltage.
+//! - `BATTERY_PIN`, `BATTERY_ADC_CHANNEL`: The pin and ADC channel for the battery voltage divider.
+//! - `BATTERY_DIVIDER`: How many times larger the battery voltage is than the voltage at the pin.
 //!
 
 #[cfg(not(any(
@@ -65,6 +68,8 @@ compile_error!(
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::spi;
+    use hal::uart;
 
     pub const CLOCK_HSE_FREQ: u32 = 24_000_000; // set to 0 for simulation
 
@@ -84,9 +89,17 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 1);
     pub const AI_BUTTON_PULL_UP: bool = true;
 
+    pub const CONSOLE_UART: uart::Instance = uart::Instance::Usart1;
     pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
     pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
 
+    // TODO - the battery pin and divider are not checked against the hactar12 schematic yet, so
+    //        the measurement stays off until they are
+    pub const HAS_BATTERY: bool = false;
+    pub const BATTERY_PIN: gpio::Pin = gpio::Pin(cpu::GPIOC, 4); // ADC12_IN14, unverified
+    pub const BATTERY_ADC_CHANNEL: u8 = 14;
+    pub const BATTERY_DIVIDER: u32 = 2; // assumes two equal resistors, unverified
+
     pub const HAS_DISP: bool = true;
     pub const DISP_CS: gpio::Pin = gpio::Pin(cpu::GPIOB, 8); // Chip Select - low to select
     pub const DISP_DC: gpio::Pin = gpio::Pin(cpu::GPIOB, 9); // Data/Command - 0 command, 1 data
@@ -96,13 +109,15 @@ pub mod info {
     pub const DISP_SDA: gpio::Pin = gpio::Pin(cpu::GPIOA, 7); // Uses SPI1 MOSI
     pub const DISP_NUM_ROWS: usize = 320;
     pub const DISP_NUM_COLS: usize = 240;
-    pub const DISP_SPI_FREQ: u32 = 10_000_000; // must be slower if reading
+    pub const DISP_SPI: spi::Spi = spi::Spi(cpu::SPI1);
+    pub const DISP_SPI_FREQ: u32 = 10_500_000; // 84 MHz / 8, must be slower if reading
 }
 
 #[cfg(feature = "board-blinkA")]
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::uart;
 
     pub const CLOCK_HSE_FREQ: u32 = 16_000_000; // set to 0 for simulation
 
@@ -122,14 +137,21 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 13);
     pub const AI_BUTTON_PULL_UP: bool = false;
 
+    pub const CONSOLE_UART: uart::Instance = uart::Instance::Usart1;
     pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
     pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
+
+    pub const HAS_BATTERY: bool = false;
+    pub const BATTERY_PIN: gpio::Pin = gpio::Pin(cpu::GPIOC, 4);
+    pub const BATTERY_ADC_CHANNEL: u8 = 14;
+    pub const BATTERY_DIVIDER: u32 = 2;
 }
 
 #[cfg(feature = "board-qemu")]
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::uart;
 
     pub const CLOCK_HSE_FREQ: u32 = 16_000_000; // set to 0 for simulation
 
@@ -149,14 +171,22 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 1);
     pub const AI_BUTTON_PULL_UP: bool = true;
 
+    pub const CONSOLE_UART: uart::Instance = uart::Instance::Usart1;
     pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
     pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
+
+    pub const HAS_BATTERY: bool = false;
+    pub const BATTERY_PIN: gpio::Pin = gpio::Pin(cpu::GPIOC, 4);
+    pub const BATTERY_ADC_CHANNEL: u8 = 14;
+    pub const BATTERY_DIVIDER: u32 = 2;
 }
 
 #[cfg(feature = "board-sim")]
 pub mod info {
     use hal::cpu;
     use hal::gpio;
+    use hal::spi;
+    use hal::uart;
 
     pub const CLOCK_HSE_FREQ: u32 = 0_000_000; // set to 0 for simulation
 
@@ -176,9 +206,15 @@ pub mod info {
     pub const AI_BUTTON: gpio::Pin = gpio::Pin(cpu::GPIOC, 1);
     pub const AI_BUTTON_PULL_UP: bool = false;
 
+    pub const CONSOLE_UART: uart::Instance = uart::Instance::Usart1;
     pub const CONSOLE_TX: gpio::Pin = gpio::Pin(cpu::GPIOA, 9);
     pub const CONSOLE_RX: gpio::Pin = gpio::Pin(cpu::GPIOA, 10);
 
+    pub const HAS_BATTERY: bool = true; // set with hal::sim::set_analog
+    pub const BATTERY_PIN: gpio::Pin = gpio::Pin(cpu::GPIOC, 4);
+    pub const BATTERY_ADC_CHANNEL: u8 = 14;
+    pub const BATTERY_DIVIDER: u32 = 2;
+
     pub const HAS_DISP: bool = false;
     pub const DISP_CS: gpio::Pin = gpio::Pin(cpu::GPIOB, 8); // Chip Select - low to select
     pub const DISP_DC: gpio::Pin = gpio::Pin(cpu::GPIOB, 9); // Data/Command - 0 command, 1 data
@@ -188,7 +224,8 @@ pub mod info {
     pub const DISP_SDA: gpio::Pin = gpio::Pin(cpu::GPIOA, 7); // Uses SPI1 MOSI
     pub const DISP_NUM_ROWS: usize = 320;
     pub const DISP_NUM_COLS: usize = 240;
-    pub const DISP_SPI_FREQ: u32 = 10_000_000; // must be slower if reading
+    pub const DISP_SPI: spi::Spi = spi::Spi(cpu::SPI1);
+    pub const DISP_SPI_FREQ: u32 = 10_500_000; // 84 MHz / 8, must be slower if reading
 }
 
 #[cfg(test)]
@@ -2,8 +2,17 @@
 //!
 //! This module provides functionality for reading if the buttons on the board are pressed.
 //!
+//! Presses and releases are found by EXTI interrupts on the button pins, and queued with the
+//! time the interrupt came in. An edge within `DEBOUNCE` of the last one is taken as contact
+//! bounce and dropped, then `next_event` checks the pin so the last state is not missed.
+//!
+//! ## Structures
+//!
+//! - `ButtonEvent`: A press or release of a button and when it happened.
+//!
 //! ## Functions
 //!
+//! - `next_event`: Returns the oldest press or release not yet read.
 //! - `read_ptt`: Reads the state of the PTT (Push-To-Talk) button.
 //! - `read_ai`: Reads the state of the AI button.
 //!
@@ -11,6 +20,110 @@
 extern crate hal;
 
 use crate::board;
+use hal::exti;
+use hal::gpio;
+use hal::interrupt;
+use hal::timer::{Duration, Instant};
+
+/// Edges closer than this to the last one are ignored as contact bounce.
+pub const DEBOUNCE: Duration = Duration::from_millis(20);
+
+// number of events that can wait to be read, more are dropped
+const MAX_EVENTS: usize = 8;
+
+/// The buttons on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    /// Push-To-Talk button.
+    Ptt = 0,
+    /// AI button.
+    Ai = 1,
+}
+
+/// A press or release of a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonEvent {
+    pub button: Button,
+    /// True for a press, false for a release.
+    pub pressed: bool,
+    /// When the interrupt for the edge came in.
+    pub time: Instant,
+}
+
+// events from the interrupt, and the last state of each button
+struct Events {
+    queue: [Option<ButtonEvent>; MAX_EVENTS],
+    head: usize,
+    len: usize,
+    pressed: [bool; 2],
+    changed_at: [Instant; 2],
+}
+
+hal::local! {
+    static mut EVENTS: Events = Events {
+        queue: [None; MAX_EVENTS],
+        head: 0,
+        len: 0,
+        pressed: [false; 2],
+        changed_at: [Instant::from_micros(0); 2],
+    };
+}
+
+// the EXTI line is the pin number, so the buttons must be on different numbers
+const _: () = assert!(
+    !(board::info::HAS_PTT_BUTTON && board::info::HAS_AI_BUTTON)
+        || board::info::AI_BUTTON.1 != board::info::PTT_BUTTON.1,
+    "PTT and AI buttons share an EXTI line"
+);
+
+// pin, and if it is pulled up, for each button
+fn pin(button: Button) -> (gpio::Pin, bool) {
+    match button {
+        Button::Ptt => (board::info::PTT_BUTTON, board::info::PTT_BUTTON_PULL_UP),
+        Button::Ai => (board::info::AI_BUTTON, board::info::AI_BUTTON_PULL_UP),
+    }
+}
+
+fn is_pressed(button: Button) -> bool {
+    let (pin, pull_up) = pin(button);
+    pin.read() != pull_up
+}
+
+// queues a change of state, unless it is bounce. Must be in a critical section.
+fn record(events: &mut Events, button: Button, pressed: bool, time: Instant) {
+    let i = button as usize;
+    if pressed == events.pressed[i] || time.duration_since(events.changed_at[i]) < DEBOUNCE {
+        return;
+    }
+    events.pressed[i] = pressed;
+    events.changed_at[i] = time;
+
+    if events.len < MAX_EVENTS {
+        let tail = (events.head + events.len) % MAX_EVENTS;
+        events.queue[tail] = Some(ButtonEvent {
+            button,
+            pressed,
+            time,
+        });
+        events.len += 1;
+    }
+}
+
+// EXTI callback for both buttons
+fn on_edge(line: u8) {
+    let time = Instant::now();
+    let button = if board::info::HAS_PTT_BUTTON && line == board::info::PTT_BUTTON.1 {
+        Button::Ptt
+    } else {
+        Button::Ai
+    };
+    let pressed = is_pressed(button);
+
+    interrupt::free(|_cs| {
+        let events = unsafe { hal::local!(mut EVENTS) };
+        record(events, button, pressed, time);
+    });
+}
 
 pub struct Buttons {
     prev_ptt: bool,
@@ -36,6 +149,8 @@ impl Buttons {
             } else {
                 board::info::PTT_BUTTON.pulldown();
             }
+
+            exti::listen(board::info::PTT_BUTTON, exti::Edge::Both, on_edge);
         }
 
         if board::info::HAS_AI_BUTTON {
@@ -46,12 +161,41 @@ impl Buttons {
             } else {
                 board::info::AI_BUTTON.pulldown();
             }
+
+            exti::listen(board::info::AI_BUTTON, exti::Edge::Both, on_edge);
         }
     }
 
     #[inline(never)]
     pub fn validate(&self) {}
 
+    /// Returns the oldest button press or release that has not been read yet, with the time it
+    /// happened. Once the queue is empty this checks the pins, and returns an event for a button
+    /// whose last edge was dropped as bounce.
+    pub fn next_event(&mut self) -> Option<ButtonEvent> {
+        interrupt::free(|_cs| {
+            let events = unsafe { hal::local!(mut EVENTS) };
+
+            if events.len == 0 {
+                let now = Instant::now();
+                if board::info::HAS_PTT_BUTTON {
+                    record(events, Button::Ptt, is_pressed(Button::Ptt), now);
+                }
+                if board::info::HAS_AI_BUTTON {
+                    record(events, Button::Ai, is_pressed(Button::Ai), now);
+                }
+            }
+
+            if events.len == 0 {
+                return None;
+            }
+            let event = events.queue[events.head].take();
+            events.head = (events.head + 1) % MAX_EVENTS;
+            events.len -= 1;
+            event
+        })
+    }
+
     /// Reads the state of the PTT (Push-To-Talk) button.
     ///
     /// This function checks the current state of the PTT button and compares it with the state when it was previously called.
@@ -7,6 +7,22 @@
 //! - `Print`: A trait for printing data to the console.
 //! Implemented for various data types such as `[u8]`, and `u64`
 //!
+//! ## Functions
+//!
+//! - `flush`: Waits until everything printed has been sent.
+//! - `wake_on_rx`: Makes a byte arriving on the console wake the chip from STOP mode.
+//!
+//! ## Usage
+//!
+//! After `Console::init`, printing puts the bytes in a `TX_BUF_SIZE` byte buffer
+//! that is sent on the UART by interrupts, so printing does not hold up the tasks.
+//! If the buffer is full, printing waits for room. Call `flush` before stopping,
+//! for example in the panic handler, so nothing is lost.
+//!
+//! The UART is not clocked in STOP mode, so while stopped a falling edge on the RX
+//! pin wakes the chip instead. The byte whose start bit woke it is lost, as the UART
+//! is not running again until part way through it.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -24,11 +40,31 @@
 
 extern crate hal;
 
+use hal::exti;
 use hal::uart;
 
+use crate::board;
+
 #[cfg(feature = "std")]
 extern crate std;
 
+/// The UART the console is on.
+pub const UART: uart::Uart = uart::Uart::new(board::info::CONSOLE_UART);
+
+/// Size of the buffer for console output waiting to be sent.
+pub const TX_BUF_SIZE: usize = 1024;
+
+hal::local! {
+    static mut TX_BUF: [u8; TX_BUF_SIZE] = [0; TX_BUF_SIZE];
+}
+
+// the EXTI line is the pin number, so the console RX must not share it with a button
+const _: () = assert!(
+    (!board::info::HAS_PTT_BUTTON || board::info::PTT_BUTTON.1 != board::info::CONSOLE_RX.1)
+        && (!board::info::HAS_AI_BUTTON || board::info::AI_BUTTON.1 != board::info::CONSOLE_RX.1),
+    "Console RX shares an EXTI line with a button"
+);
+
 pub struct Console {}
 
 impl Console {
@@ -38,7 +74,32 @@ impl Console {
     }
 
     #[inline(never)]
-    pub fn init(&self) {}
+    pub fn init(&self) {
+        // the UART keeps the buffer, which is only handed over here
+        let buf = unsafe { hal::local!(mut TX_BUF) };
+        UART.init_tx(buf, uart::TxPolicy::Block);
+    }
+}
+
+/// Waits until everything printed to the console has been sent.
+#[inline(never)]
+pub fn flush() {
+    UART.flush();
+}
+
+// an edge on RX only has to wake the chip, the UART receives the bytes after it
+fn on_rx_edge(_line: u8) {}
+
+/// Makes the start bit of a byte arriving on the console wake the chip from STOP mode, or stops
+/// it doing so. Turn it on only while stopping, as otherwise every falling edge of RX would
+/// interrupt.
+#[inline(never)]
+pub fn wake_on_rx(on: bool) {
+    if on {
+        exti::listen(board::info::CONSOLE_RX, exti::Edge::Falling, on_rx_edge);
+    } else {
+        exti::unlisten(board::info::CONSOLE_RX);
+    }
 }
 
 pub trait Print {
@@ -58,7 +119,7 @@ impl Print for [u8] {
             return;
         }
         for c in s {
-            uart::write1(*c);
+            UART.write(*c);
         }
     }
 }
@@ -15,7 +15,8 @@
 //! let display = Display::new();
 //! display.init();
 //!
-//! display.draw_bitmap( &[0xFFFF; 240 * 320/10], 0,0, 240, 320/10 );
+//! static BITMAP: [u16; 240 * 320 / 10] = [0xFFFF; 240 * 320 / 10];
+//! unsafe { display.draw_bitmap(&BITMAP, 0, 0, 240, 320 / 10) };
 //!
 //! while !display.ready() {}
 //! ```
@@ -54,16 +55,15 @@ impl Display {
 
         board::info::DISP_NRST.output();
         board::info::DISP_NRST.low(); // put into reset
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 50_000 {} // TODO needed ?
+        hal::timer::delay_ms(50); // TODO needed ?
         board::info::DISP_NRST.high(); // take out of reset
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 120_000 {} // TODO needed ?
+        hal::timer::delay_ms(120); // TODO needed ?
 
-        hal::spi::init1(
-            board::info::DISP_SPI_FREQ,
+        board::info::DISP_SPI.init(
+            &hal::spi::Config::new(board::info::DISP_SPI_FREQ, hal::spi::Mode::Mode0),
             board::info::DISP_SCL,
             board::info::DISP_SDA,
+            None,
         );
 
         ili9341::setup();
@@ -79,14 +79,29 @@ impl Display {
 
     /// Returns whether the display is ready to accept the next draw_bitmap command.
     pub fn ready(&self) -> bool {
-        true
+        if !board::info::HAS_DISP {
+            return true;
+        }
+        !board::info::DISP_SPI.busy()
     }
 
     /// Draws a bitmap at a specified position.
     ///
     /// This is an async call that starts a DMA transfer to the display controller and
-    /// ready will return false until the transfer is complete.
-    pub fn draw_bitmap(&self, bitmap: &[u16], x: usize, y: usize, width: usize, height: usize) {
+    /// ready will return false until the transfer is complete. The bitmap must not be
+    /// changed until ready returns true.
+    ///
+    /// # Safety
+    ///
+    /// The bitmap must not be changed or freed until ready returns true.
+    pub unsafe fn draw_bitmap(
+        &self,
+        bitmap: &[u16],
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) {
         if !board::info::HAS_DISP {
             return;
         }
@@ -119,8 +134,9 @@ impl Display {
             &[start_row_high, start_row_low, end_row_high, end_row_low],
         );
 
-        ili9341::command_wide(ili9341::Command::MemoryWrite, &bitmap);
-        ili9341::command(ili9341::Command::NoOp, &[]);
+        // the next command ends the memory write so no NoOp is sent here, that
+        // would have to wait for the DMA to finish
+        unsafe { ili9341::command_wide(ili9341::Command::MemoryWrite, bitmap) };
     }
 }
 
@@ -174,29 +190,39 @@ mod ili9341 {
         // Send a command to the display
         let c: [u8; 1] = [cmd as u8];
 
+        // DC must not change until the last DMA transfer is done
+        board::info::DISP_SPI.wait();
+
         board::info::DISP_DC.low(); // command
-        hal::spi::write1(&c);
+        board::info::DISP_SPI.write(&c);
 
         board::info::DISP_DC.high(); // data
-        hal::spi::write1(paramters);
+        board::info::DISP_SPI.write(paramters);
     }
 
-    pub fn command_wide(cmd: Command, paramters: &[u16]) {
-        // Send a command to the display
+    /// Sends a command with parameters that are sent using DMA, so this returns
+    /// before they are all sent.
+    ///
+    /// # Safety
+    ///
+    /// The parameters must not be changed or freed until `DISP_SPI.wait` returns.
+    pub unsafe fn command_wide(cmd: Command, paramters: &[u16]) {
         let c: [u8; 1] = [cmd as u8];
 
+        // DC must not change until the last DMA transfer is done
+        board::info::DISP_SPI.wait();
+
         board::info::DISP_DC.low(); // command
-        hal::spi::write1(&c);
+        board::info::DISP_SPI.write(&c);
 
         board::info::DISP_DC.high(); // data
-        hal::spi::write1_wide(paramters);
+        unsafe { board::info::DISP_SPI.write_wide(paramters) };
     }
 
     pub fn setup() {
         //LCD_2IN4_Write_Command(0x01); //Software reset
         command(Command::SwReset, &[]);
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 5_000 {}
+        hal::timer::delay_ms(5);
 
         command(Command::PowerCtrlA, &[0x39, 0x2C, 0x00, 0x34, 0x02]);
 
@@ -267,8 +293,7 @@ mod ili9341 {
         command(Command::NormalMode, &[]);
 
         command(Command::SleepOut, &[]);
-        let now = hal::timer::current_time();
-        while hal::timer::current_time().sub(now).as_u64() < 120_000 {}
+        hal::timer::delay_ms(120);
 
         command(Command::DisplayOn, &[]);
 
@@ -283,8 +308,9 @@ mod ili9341 {
 
                 command(Command::PageAddrSet, &[r1, r0, r1, r0]); // 13F=319
 
+                // the NoOp waits for the DMA, so data is not dropped before it is sent
                 let data = [0x0000u16; 240];
-                command_wide(Command::MemoryWrite, &data);
+                unsafe { command_wide(Command::MemoryWrite, &data) };
                 command(Command::NoOp, &[]);
             }
         }
@@ -305,7 +331,7 @@ mod ili9341 {
             //let data = [ 0xF800u16; 50 * 50]; // red
             let data = [0x03E0u16; 50 * 50]; // green
                                              //let data = [0x001Fu16; 50 * 50]; // blue
-            command_wide(Command::MemoryWrite, &data);
+            unsafe { command_wide(Command::MemoryWrite, &data) };
             command(Command::NoOp, &[]);
         }
     }
@@ -43,7 +43,8 @@
 
 extern crate hal;
 
-use hal::{spi, timer, watch_dog};
+use hal::timer::Instant;
+use hal::{dma, power, spi, timer, watch_dog};
 
 pub mod battery;
 pub mod board;
@@ -93,18 +94,26 @@ impl BSP {
         hal::init(board::info::CLOCK_HSE_FREQ);
 
         // do soon after clock is up so we  can use console
-        hal::uart::init1(115_200, board::info::CONSOLE_TX, board::info::CONSOLE_RX);
+        console::UART.init(
+            &hal::uart::Config::new(115_200),
+            board::info::CONSOLE_TX,
+            board::info::CONSOLE_RX,
+        );
 
         // do after uart is up
         watch_dog::init();
 
         // must be after GPIO is initialized
-        spi::init1(
-            board::info::DISP_SPI_FREQ,
+        board::info::DISP_SPI.init(
+            &spi::Config::new(board::info::DISP_SPI_FREQ, spi::Mode::Mode0),
             board::info::DISP_SCL,
             board::info::DISP_SDA,
+            None,
         );
 
+        // cycle counter for profiling
+        hal::dwt::init();
+
         // Do last as this starts timer events
         timer::init2();
 
@@ -117,6 +126,8 @@ impl BSP {
 
         self.buttons.init();
 
+        self.battery.init();
+
         self.random.init();
 
         self.inject.init();
@@ -125,6 +136,24 @@ impl BSP {
         self.display.init();
     }
 
+    /// Returns true if nothing is in progress that STOP mode would stall, such as console
+    /// output being sent, console input not yet read, or a DMA transfer.
+    pub fn can_stop(&self) -> bool {
+        console::UART.tx().is_empty()
+            && console::UART.rx().is_empty()
+            && !dma::any_enabled()
+            && self.display.ready()
+    }
+
+    /// Enters STOP mode until `deadline`, a button press, or a byte arriving on the console,
+    /// whichever is first. The console byte that wakes the chip is lost. Only call this when
+    /// `can_stop` is true.
+    pub fn stop_until(&mut self, deadline: Instant) {
+        console::wake_on_rx(true);
+        power::stop_until(deadline);
+        console::wake_on_rx(false);
+    }
+
     pub fn validate(&self) {
         hal::validate();
 
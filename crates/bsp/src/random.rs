@@ -1,22 +1,155 @@
 //! # Random Module
+//!
+//! This module provides random numbers for keys, nonces, and anything else that
+//! needs them. On the hardware the numbers come from the RNG peripheral and are
+//! suitable for cryptography.
+//!
+//! In the simulator the numbers come from a seeded SplitMix64 generator so that
+//! tests get the same numbers every run. These are NOT random enough for
+//! cryptography.
+//!
+//! ## Functions
+//!
+//! - `u32`, `u64`, `u128`: Returns a random number of that size.
+//! - `fill`: Fills a byte slice with random bytes.
+//! - `seed`: Restarts the simulator generator from a given seed (board-sim only).
+//!
+//! ## Example
+//!
+//! ```rust
+//!  use bsp::BSP;
+//!
+//!  let mut bsp = BSP::new();
+//!  bsp.init();
+//!
+//!  let nonce = bsp.random.u64();
+//!  let mut key = [0u8; 16];
+//!  bsp.random.fill(&mut key);
+//! ```
+//!
 
 extern crate hal;
 
 #[cfg(feature = "std")]
 extern crate std;
 
-pub struct Random {}
+/// Seed the simulator generator starts with.
+#[cfg(feature = "board-sim")]
+pub const SIM_SEED: u64 = 0x0123_4567_89AB_CDEF;
+
+/// Number of times to try the hardware RNG before giving up.
+#[cfg(not(feature = "board-sim"))]
+const MAX_TRIES: u32 = 10;
+
+pub struct Random {
+    #[cfg(feature = "board-sim")]
+    state: u64,
+}
 
 impl crate::random::Random {
     #[inline(never)]
     pub fn new() -> Self {
-        crate::random::Random {}
+        crate::random::Random {
+            #[cfg(feature = "board-sim")]
+            state: SIM_SEED,
+        }
     }
 
     #[inline(never)]
-    pub fn init(&self) {}
+    pub fn init(&mut self) {
+        #[cfg(not(feature = "board-sim"))]
+        hal::rng::init();
+    }
+
+    /// Restarts the simulator generator so the same numbers come out again.
+    #[cfg(feature = "board-sim")]
+    pub fn seed(&mut self, seed: u64) {
+        self.state = seed;
+    }
+
+    /// Returns a random `u32`.
+    #[cfg(not(feature = "board-sim"))]
+    pub fn u32(&mut self) -> u32 {
+        for _ in 0..MAX_TRIES {
+            match hal::rng::read() {
+                Ok(r) => return r,
+                Err(hal::rng::Error::Clock) => panic!("RNG clock error"),
+                Err(_) => {} // seed errors and repeats are recovered from by reading again
+            }
+        }
+        panic!("RNG failed");
+    }
+
+    /// Returns a random `u32`.
+    #[cfg(feature = "board-sim")]
+    pub fn u32(&mut self) -> u32 {
+        (self.next_sim() >> 32) as u32
+    }
+
+    /// Returns a random `u64`.
+    pub fn u64(&mut self) -> u64 {
+        let hi = self.u32() as u64;
+        let lo = self.u32() as u64;
+        (hi << 32) | lo
+    }
+
+    /// Returns a random `u128`.
+    pub fn u128(&mut self) -> u128 {
+        let hi = self.u64() as u128;
+        let lo = self.u64() as u128;
+        (hi << 64) | lo
+    }
+
+    /// Fills `buf` with random bytes.
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(4) {
+            let r = self.u32().to_le_bytes();
+            chunk.copy_from_slice(&r[..chunk.len()]);
+        }
+    }
+
+    // SplitMix64, see https://prng.di.unimi.it/splitmix64.c
+    #[cfg(feature = "board-sim")]
+    fn next_sim(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "board-sim")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = Random::new();
+        let mut b = Random::new();
+        a.init();
+        b.init();
+        assert_eq!(a.u128(), b.u128());
+
+        a.seed(42);
+        let first = a.u64();
+        assert_ne!(first, a.u64());
+        a.seed(42);
+        assert_eq!(first, a.u64());
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut random = Random::new();
+
+        let mut buf = [0u8; 7];
+        random.fill(&mut buf);
+        assert_ne!(buf, [0u8; 7]);
 
-    pub fn u128(&self) -> u128 {
-        todo!();
+        // bytes come from the same stream as u32
+        random.seed(SIM_SEED);
+        let r = random.u32().to_le_bytes();
+        assert_eq!(buf[..4], r);
     }
 }
@@ -3,8 +3,14 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::EXTI;
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use bsp::console::Print;
     use bsp::*;
 
@@ -24,6 +30,9 @@ mod tests {
         assert_eq!(_state, false);
         assert_eq!(_changed, false);
 
+        // no presses have happened
+        assert!(bsp.buttons.next_event().is_none());
+
         let message: &[u8] = b"test";
         message.print_console();
 
@@ -35,4 +44,39 @@ mod tests {
 
         led::set(led::Color::Green);
     }
+    #[test]
+    fn test_can_stop() {
+        let mut bsp = BSP::new();
+        bsp.init();
+        assert!(bsp.can_stop());
+
+        // console input that has not been read yet
+        hal::sim::uart_receive(bsp::console::UART.reg(), b'x');
+        hal::uart::handle_irq(board::info::CONSOLE_UART);
+        assert!(!bsp.can_stop());
+        assert_eq!(bsp::console::UART.rx().read(), Some(b'x'));
+        assert!(bsp.can_stop());
+
+        // a DMA transfer that is still going
+        let stream = hal::dma::Stream::claim(hal::cpu::DMA1, 0);
+        stream.start();
+        assert!(!bsp.can_stop());
+        stream.release();
+        assert!(bsp.can_stop());
+    }
+    #[test]
+    fn test_stop_until() {
+        let mut bsp = BSP::new();
+        bsp.init();
+
+        let start = hal::timer::Instant::now();
+        bsp.stop_until(start + hal::timer::Duration::from_millis(2));
+        assert!(start.elapsed() >= hal::timer::Duration::from_millis(1));
+
+        // the console RX line woke the chip on a falling edge, and is quiet again after
+        let line = board::info::CONSOLE_RX.1;
+        assert_eq!(hal::read!(EXTI.ftsr) >> line & 1, 1);
+        assert_eq!(hal::read!(EXTI.rtsr) >> line & 1, 0);
+        assert_eq!(hal::read!(EXTI.imr) >> line & 1, 0);
+    }
 }
@@ -1,5 +1,32 @@
 #!/bin/bash
-set +e
+# Regenerates the register definitions in src/svd_*.rs with svd2rusty. Run it from the top of
+# the repository.
+#
+# The SVD files are not kept in the repository. Copy STM32F405.svd and STM32F0x2.svd from the
+# STM32F4 and STM32F0 system view description packages on the ST web site into doc/ first.
+set -eo pipefail
 
-# TODO svd2rusty doc/STM32F405.svd | rustfmt > crates/hal/src/svd.rs
+gen() {
+    local svd=$1
+    local out=$2
+    shift 2
 
+    if [ ! -f "$svd" ]; then
+        echo "$svd is missing, see the top of $0" >&2
+        exit 1
+    fi
+
+    # a failed run must not leave a truncated file behind
+    local tmp
+    tmp=$(mktemp)
+    trap 'rm -f "$tmp"' EXIT
+    cargo run -q -p svd2rusty -- "$svd" "$@" | rustfmt > "$tmp"
+    mv "$tmp" "$out"
+}
+
+gen doc/STM32F405.svd crates/hal/src/svd_stm32f405.rs \
+    --only RNG,DBG,DMA2,DMA1,RCC,GPIOC,GPIOB,GPIOA,SYSCFG,SPI1,SPI2,SPI3,ADC1,ADC2,ADC3,USART6,USART1,USART2,USART3,PWR,I2C3,I2C2,I2C1,IWDG,WWDG,RTC,C_ADC,TIM1,TIM2,CRC,FLASH,EXTI \
+    --rename TIM1=TIM_ADV --rename TIM2=TIM_GEN
+
+gen doc/STM32F0x2.svd crates/hal/src/svd_stm32f0x2.rs \
+    --only CRC,GPIOB,GPIOA,I2C1,I2C2,IWDG,WWDG,EXTI,RCC,SYSCFG_COMP,USART1,USART2,USART3,USART4,FLASH,DBGMCU
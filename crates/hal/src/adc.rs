@@ -0,0 +1,214 @@
+//! # ADC Module
+//!
+//! This module provides functionality for reading analog voltages with ADC1.
+//! Conversions are single shot and blocking, which is fast enough for slowly
+//! changing signals such as the battery voltage.
+//!
+//! The ADC result is relative to the analog supply VDDA, which is not known
+//! exactly. The internal reference VREFINT is measured against the factory
+//! calibration value to work out VDDA so results can be given in millivolts.
+//!
+//! ## Enums
+//!
+//! - `SampleTime`: Number of ADC clock cycles a channel is sampled for.
+//!
+//! ## Functions
+//!
+//! - `init`: Turns on ADC1 and the internal reference.
+//! - `set_sample_time`: Sets the sample time for a channel.
+//! - `read`: Does a single conversion of a channel and returns the raw 12 bit value.
+//! - `read_vrefint`: Reads the raw value of the internal reference.
+//! - `vdda_mv`: Works out the analog supply voltage in millivolts.
+//! - `read_mv`: Reads a channel and converts it to millivolts.
+//!
+//! ## Usage
+//!
+//! Set the GPIO pin to analog mode with `Pin::analog` then read the ADC
+//! channel connected to that pin. The mapping of pins to channels is in
+//! table 7 "pin definitions" of the datasheet stm32f405rg.pdf.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::adc;
+//! use hal::cpu;
+//! use hal::gpio;
+//!
+//! hal::init(16_000_000);
+//! adc::init();
+//!
+//! gpio::Pin(cpu::GPIOC, 4).analog(); // PC4 is ADC12_IN14
+//! adc::set_sample_time(14, adc::SampleTime::Cycles480);
+//!
+//! let mv = adc::read_mv(14);
+//! ```
+
+#[allow(unused_imports)]
+use core::ptr;
+
+#[allow(unused_imports)]
+use super::clock;
+#[allow(unused_imports)]
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+/// Channel the internal reference voltage is connected to.
+pub const VREFINT_CHANNEL: u8 = 17;
+
+/// Full scale value of a 12 bit conversion.
+pub const FULL_SCALE: u32 = 4095;
+
+#[cfg(feature = "stm32f405")]
+/// VDDA in millivolts the factory calibration of VREFINT was done at.
+const VREFINT_CAL_VDDA_MV: u32 = 3300;
+
+#[cfg(feature = "stm32f405")]
+/// Typical VREFINT in millivolts used if there is no calibration value.
+const VREFINT_TYP_MV: u32 = 1210;
+
+/// ADC clock cycles to sample for. Longer times are needed for sources with
+/// a higher impedance such as a resistor divider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleTime {
+    Cycles3 = 0b000,
+    Cycles15 = 0b001,
+    Cycles28 = 0b010,
+    Cycles56 = 0b011,
+    Cycles84 = 0b100,
+    Cycles112 = 0b101,
+    Cycles144 = 0b110,
+    Cycles480 = 0b111,
+}
+
+#[cfg(feature = "stm32f405")]
+/// Turns on ADC1 for 12 bit single conversions and enables the internal reference.
+#[inline(never)]
+pub fn init() {
+    cpu::write!(RCC.apb2enr[ADC1EN;1], 1);
+
+    // ADC is on APB2 bus. Max ADC clock is 36 MHz, so use the smallest of div by 2, 4, 6 or 8
+    // that gets under that, div by 4 to get 21 MHz with the 84 MHz default
+    let Some(adc_pre) = (0..4).find(|pre| clock::pclk2() / (2 * (pre + 1)) <= 36_000_000) else {
+        panic!("APB2 clock too fast for ADC");
+    };
+    cpu::write!(C_ADC.ccr[ADCPRE;2], adc_pre);
+
+    // enable the internal reference and temperature sensor
+    cpu::write!(C_ADC.ccr[TSVREFE;1], 1);
+
+    cpu::write!(ADC1.cr1[RES;2], 0b00); // 12 bit resolution
+    cpu::write!(ADC1.cr1[SCAN;1], 0); // one channel at a time
+    cpu::write!(ADC1.cr2[ALIGN;1], 0); // right align data
+    cpu::write!(ADC1.cr2[CONT;1], 0); // single conversion
+    cpu::write!(ADC1.cr2[EXTEN;2], 0b00); // software trigger only
+    cpu::write!(ADC1.sqr1[L;4], 0); // one conversion in the sequence
+
+    // VREFINT needs at least 10 us of sampling, 480 cycles at 21 MHz is 23 us
+    set_sample_time(VREFINT_CHANNEL, SampleTime::Cycles480);
+
+    cpu::write!(ADC1.cr2[ADON;1], 1); // turn on ADC
+}
+
+#[cfg(feature = "stm32f405")]
+/// Sets the sample time for a channel.
+#[inline(never)]
+pub fn set_sample_time(channel: u8, sample_time: SampleTime) {
+    assert!(channel <= 18);
+
+    if channel < 10 {
+        cpu::write!(ADC1.smpr2[channel*3;3], sample_time as u32);
+    } else {
+        cpu::write!(ADC1.smpr1[(channel-10)*3;3], sample_time as u32);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// Does a single conversion of `channel` and returns the raw 12 bit value.
+#[inline(never)]
+pub fn read(channel: u8) -> u16 {
+    assert!(channel <= 18);
+
+    cpu::write!(ADC1.sqr3[SQ1;5], channel as u32);
+
+    cpu::write!(ADC1.sr[EOC;1], 0); // clear any old end of conversion
+    cpu::write!(ADC1.cr2[SWSTART;1], 1); // start conversion
+
+    // wait for end of conversion
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(ADC1.sr[EOC;1]) == 0 {}
+
+    // reading the data clears EOC
+    cpu::read!(ADC1.dr[DATA;16]) as u16
+}
+
+#[cfg(feature = "stm32f405")]
+/// Reads the raw value of the internal reference voltage.
+pub fn read_vrefint() -> u16 {
+    read(VREFINT_CHANNEL)
+}
+
+#[cfg(feature = "stm32f405")]
+/// Factory calibration of VREFINT, taken at VDDA = 3.3V, from the system memory,
+/// see table 70 of the datasheet stm32f405rg.pdf
+fn vrefint_cal() -> u16 {
+    #[cfg(not(feature = "std"))]
+    {
+        const VREFINT_CAL_ADDR: u32 = 0x1FFF_7A2A;
+        unsafe { ptr::read_volatile(VREFINT_CAL_ADDR as *const u16) }
+    }
+
+    #[cfg(feature = "std")]
+    {
+        (VREFINT_TYP_MV * FULL_SCALE / VREFINT_CAL_VDDA_MV) as u16
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// Works out the analog supply voltage in millivolts by measuring VREFINT.
+#[inline(never)]
+pub fn vdda_mv() -> u32 {
+    let raw = read_vrefint() as u32;
+    if raw == 0 {
+        // nothing measured, assume nominal supply
+        return VREFINT_CAL_VDDA_MV;
+    }
+
+    let mut cal = vrefint_cal() as u32;
+    if cal == 0 || cal >= FULL_SCALE {
+        // erased or missing calibration
+        cal = VREFINT_TYP_MV * FULL_SCALE / VREFINT_CAL_VDDA_MV;
+    }
+
+    VREFINT_CAL_VDDA_MV * cal / raw
+}
+
+/// Converts a raw 12 bit ADC value to millivolts given VDDA in millivolts.
+pub fn to_mv(raw: u16, vdda_mv: u32) -> u32 {
+    raw as u32 * vdda_mv / FULL_SCALE
+}
+
+#[cfg(feature = "stm32f405")]
+/// Reads `channel` and converts it to millivolts at the pin.
+#[inline(never)]
+pub fn read_mv(channel: u8) -> u32 {
+    let vdda = vdda_mv();
+    to_mv(read(channel), vdda)
+}
+
+#[cfg(feature = "stm32f072")]
+pub fn init() {
+    panic!("ADC not supported on this CPU");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_mv() {
+        assert_eq!(to_mv(0, 3300), 0);
+        assert_eq!(to_mv(4095, 3300), 3300);
+        assert_eq!(to_mv(2048, 3000), 1500);
+    }
+}
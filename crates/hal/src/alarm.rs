@@ -0,0 +1,498 @@
+//! # Alarm Module
+//!
+//! This module runs callbacks at a given time using the compare channels of TIM2, the timer
+//! that `timer::now` reads.
+//!
+//! Compare channels 1 to 3 are hardware alarms, each runs one callback once or periodically
+//! with microsecond resolution. Channel 4 drives a timer wheel that runs up to `MAX_TIMERS`
+//! software timers with millisecond resolution. The wheel only wakes the CPU for ticks that
+//! have timers in them.
+//!
+//! Callbacks run in the TIM2 interrupt, so should be short and only share data with the main
+//! loop through atomics or `interrupt::free`.
+//!
+//! ## Structures
+//!
+//! - `Alarm`: One of the three hardware alarms.
+//! - `Timer`: Handle of a software timer on the timer wheel.
+//!
+//! ## Functions
+//!
+//! - `Alarm::once`, `Alarm::periodic`, `Alarm::cancel`: Start or stop a hardware alarm.
+//! - `Timer::once`, `Timer::periodic`, `Timer::cancel`: Start or stop a software timer.
+//! - `handle_irq`: Runs the callbacks that are due, called by `timer::handle_tim2_irq`.
+//!
+//! ## Usage
+//!
+//! `timer::init2` must be called first to start TIM2, this is done by the bsp. For waits in
+//! straight line code use `timer::delay_us` or `timer::delay_ms` instead.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::alarm::{self, Alarm, Timer};
+//! use hal::timer::{self, Duration, Instant};
+//!
+//! fn blink(_alarm: Alarm) {
+//!     // toggle an LED
+//! }
+//!
+//! fn poll_battery(_timer: Timer) {
+//!     // start an ADC reading
+//! }
+//!
+//! hal::init(16_000_000);
+//! timer::init2();
+//!
+//! Alarm::Alarm1.periodic(Duration::from_millis(500), blink);
+//! let battery = Timer::periodic(Duration::from_secs(10), poll_battery);
+//!
+//! // later
+//! battery.cancel();
+//! Alarm::Alarm1.cancel();
+//! ```
+//!
+
+use core::ptr;
+
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+use super::interrupt;
+use super::timer;
+use super::timer::{Duration, Instant};
+
+/// Number of software timers the timer wheel can hold.
+pub const MAX_TIMERS: usize = 16;
+
+// timer wheel resolution and size, timers further out than the wheel go round more than once
+const TICK_US: u64 = 1_000;
+const WHEEL_SLOTS: usize = 32;
+
+// compare channel used by the timer wheel
+const WHEEL_CHANNEL: usize = 3;
+
+// end of a list of timers in the wheel
+const NONE: u8 = 0xFF;
+
+/// Function an alarm calls, with the alarm that fired.
+pub type Callback = fn(alarm: Alarm);
+
+/// Function a software timer calls, with the timer that fired.
+pub type TimerCallback = fn(timer: Timer);
+
+/// Hardware alarms, on TIM2 compare channels 1 to 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alarm {
+    Alarm1 = 0,
+    Alarm2 = 1,
+    Alarm3 = 2,
+}
+
+const ALARMS: [Alarm; 3] = [Alarm::Alarm1, Alarm::Alarm2, Alarm::Alarm3];
+
+#[derive(Clone, Copy)]
+struct AlarmState {
+    // time to fire in microseconds
+    deadline: u64,
+    // microseconds between runs, 0 to run once
+    period: u64,
+    callback: Option<Callback>,
+}
+
+const IDLE_ALARM: AlarmState = AlarmState {
+    deadline: 0,
+    period: 0,
+    callback: None,
+};
+
+cpu::local! {
+    static mut ALARM_STATE: [AlarmState; 3] = [IDLE_ALARM; 3];
+}
+
+/// A software timer on the timer wheel. The handle stays valid after the timer has run, so
+/// it is safe to cancel a timer that may already have fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timer {
+    index: u8,
+    // changes each time the slot is used, so an old handle does not match a new timer
+    id: u16,
+}
+
+#[derive(Clone, Copy)]
+struct TimerState {
+    // tick to fire on
+    expires: u64,
+    // ticks between runs, 0 to run once
+    period: u64,
+    callback: Option<TimerCallback>,
+    // next timer in the same wheel slot
+    next: u8,
+    // id of the handle for this use of the slot
+    id: u16,
+}
+
+const IDLE_TIMER: TimerState = TimerState {
+    expires: 0,
+    period: 0,
+    callback: None,
+    next: NONE,
+    id: 0,
+};
+
+cpu::local! {
+    static mut TIMERS: [TimerState; MAX_TIMERS] = [IDLE_TIMER; MAX_TIMERS];
+}
+
+cpu::local! {
+    // first timer of each slot, a timer is in slot `expires % WHEEL_SLOTS`
+    static mut SLOTS: [u8; WHEEL_SLOTS] = [NONE; WHEEL_SLOTS];
+}
+
+cpu::local! {
+    // last tick the wheel has processed
+    static mut WHEEL_TICK: u64 = 0;
+}
+
+cpu::local! {
+    // id for the next timer started
+    static mut NEXT_ID: u16 = 0;
+}
+
+// CC1IF to CC4IF, CC1IE to CC4IE and CC1G to CC4G are bits 1 to 4
+fn flag(channel: usize) -> u8 {
+    channel as u8 + 1
+}
+
+// sets the compare channel to interrupt at the deadline. The counter wraps every hour so
+// a deadline further out matches early, `handle_irq` arms it again when that happens.
+fn arm(channel: usize, deadline: u64) {
+    let ccr = (deadline % timer::TIME_WRAP_AROUND as u64) as u32;
+    match channel {
+        0 => {
+            cpu::write!(TIM2.ccr1, ccr);
+        }
+        1 => {
+            cpu::write!(TIM2.ccr2, ccr);
+        }
+        2 => {
+            cpu::write!(TIM2.ccr3, ccr);
+        }
+        _ => {
+            cpu::write!(TIM2.ccr4, ccr);
+        }
+    }
+
+    cpu::write!(TIM2.sr[flag(channel);1], 0);
+    cpu::write!(TIM2.dier[flag(channel);1], 1);
+
+    // the counter may already be past the compare value, fire now instead
+    if deadline <= timer::now().as_micros() {
+        cpu::write!(TIM2.egr[flag(channel);1], 1);
+    }
+}
+
+fn disarm(channel: usize) {
+    cpu::write!(TIM2.dier[flag(channel);1], 0);
+    cpu::write!(TIM2.sr[flag(channel);1], 0);
+}
+
+impl Alarm {
+    /// Runs `callback` once at `at`, or straight away if that has passed.
+    pub fn once(&self, at: Instant, callback: Callback) {
+        self.start(at.as_micros(), 0, callback);
+    }
+
+    /// Runs `callback` every `period`, first one `period` from now. If the interrupt is held
+    /// off for more than a period the missed runs are skipped.
+    pub fn periodic(&self, period: Duration, callback: Callback) {
+        if period == Duration::ZERO {
+            panic!("Alarm period must not be zero");
+        }
+        let first = timer::now() + period;
+        self.start(first.as_micros(), period.as_micros(), callback);
+    }
+
+    fn start(&self, deadline: u64, period: u64, callback: Callback) {
+        let channel = *self as usize;
+        interrupt::free(|_cs| {
+            let state = unsafe { &mut cpu::local!(mut ALARM_STATE)[channel] };
+            *state = AlarmState {
+                deadline,
+                period,
+                callback: Some(callback),
+            };
+            arm(channel, deadline);
+        });
+    }
+
+    /// Stops the alarm, the callback will not run again.
+    pub fn cancel(&self) {
+        let channel = *self as usize;
+        interrupt::free(|_cs| {
+            disarm(channel);
+            unsafe { cpu::local!(mut ALARM_STATE)[channel] = IDLE_ALARM };
+        });
+    }
+
+    /// Returns true if the alarm is waiting to fire.
+    pub fn is_active(&self) -> bool {
+        let channel = *self as usize;
+        interrupt::free(|_cs| {
+            unsafe { cpu::local!(mut ALARM_STATE)[channel] }
+                .callback
+                .is_some()
+        })
+    }
+}
+
+// runs a hardware alarm that has matched, if it is really due
+fn run_alarm(channel: usize) {
+    let now = timer::now().as_micros();
+
+    let callback = interrupt::free(|_cs| {
+        let state = unsafe { &mut cpu::local!(mut ALARM_STATE)[channel] };
+        let callback = state.callback?;
+
+        if now < state.deadline {
+            // matched in an earlier hour of the counter
+            arm(channel, state.deadline);
+            return None;
+        }
+
+        if state.period == 0 {
+            *state = IDLE_ALARM;
+            disarm(channel);
+        } else {
+            while state.deadline <= now {
+                state.deadline += state.period;
+            }
+            arm(channel, state.deadline);
+        }
+        Some(callback)
+    });
+
+    if let Some(callback) = callback {
+        callback(ALARMS[channel]);
+    }
+}
+
+// current tick of the timer wheel
+fn tick_now() -> u64 {
+    timer::now().as_micros() / TICK_US
+}
+
+// adds a timer to the slot for its expiry tick, must be in a critical section
+fn insert(index: usize) {
+    let timers = unsafe { cpu::local!(mut TIMERS) };
+    let slots = unsafe { cpu::local!(mut SLOTS) };
+    let slot = (timers[index].expires % WHEEL_SLOTS as u64) as usize;
+    timers[index].next = slots[slot];
+    slots[slot] = index as u8;
+}
+
+// removes a timer from its slot, must be in a critical section
+fn remove(index: usize) {
+    let timers = unsafe { cpu::local!(mut TIMERS) };
+    let slots = unsafe { cpu::local!(mut SLOTS) };
+    let slot = (timers[index].expires % WHEEL_SLOTS as u64) as usize;
+    let mut link = ptr::addr_of_mut!(slots[slot]);
+    unsafe {
+        while *link != NONE {
+            if *link as usize == index {
+                *link = timers[index].next;
+                break;
+            }
+            link = ptr::addr_of_mut!(timers[*link as usize].next);
+        }
+    }
+    timers[index].next = NONE;
+}
+
+// arms the wheel channel for the next tick that has a timer in its slot, must be in a
+// critical section
+fn arm_wheel() {
+    let now_tick = tick_now();
+    for tick in now_tick + 1..=now_tick + WHEEL_SLOTS as u64 {
+        let slot = (tick % WHEEL_SLOTS as u64) as usize;
+        if unsafe { cpu::local!(mut SLOTS)[slot] } != NONE {
+            arm(WHEEL_CHANNEL, tick * TICK_US);
+            return;
+        }
+    }
+    disarm(WHEEL_CHANNEL);
+}
+
+impl Timer {
+    /// Runs `callback` once after `after`, rounded up to the next millisecond. Panics if all
+    /// `MAX_TIMERS` timers are in use.
+    pub fn once(after: Duration, callback: TimerCallback) -> Timer {
+        Self::start(after, 0, callback)
+    }
+
+    /// Runs `callback` every `period`, rounded up to whole milliseconds. Panics if all
+    /// `MAX_TIMERS` timers are in use.
+    pub fn periodic(period: Duration, callback: TimerCallback) -> Timer {
+        if period == Duration::ZERO {
+            panic!("Timer period must not be zero");
+        }
+        Self::start(period, period.as_micros().div_ceil(TICK_US), callback)
+    }
+
+    fn start(after: Duration, period: u64, callback: TimerCallback) -> Timer {
+        interrupt::free(|_cs| {
+            let timers = unsafe { cpu::local!(mut TIMERS) };
+            let Some(index) = (0..MAX_TIMERS).find(|i| timers[*i].callback.is_none()) else {
+                panic!("Too many timers");
+            };
+
+            // a timer due in the current tick would be missed, so it is at least one tick out
+            let ticks = after.as_micros().div_ceil(TICK_US).max(1);
+            if unsafe { cpu::local!(mut SLOTS) }.iter().all(|s| *s == NONE) {
+                // the wheel has been idle, skip the ticks it has missed
+                unsafe { *cpu::local!(mut WHEEL_TICK) = tick_now() };
+            }
+            let next_id = unsafe { cpu::local!(mut NEXT_ID) };
+            *next_id = next_id.wrapping_add(1);
+            timers[index] = TimerState {
+                expires: tick_now() + ticks,
+                period,
+                callback: Some(callback),
+                next: NONE,
+                id: *next_id,
+            };
+            insert(index);
+            arm_wheel();
+
+            Timer {
+                index: index as u8,
+                id: *next_id,
+            }
+        })
+    }
+
+    /// Stops the timer, the callback will not run again. Does nothing if the timer has already
+    /// run or been cancelled.
+    pub fn cancel(self) {
+        interrupt::free(|_cs| {
+            let index = self.index as usize;
+            if self.is_current() {
+                remove(index);
+                unsafe { cpu::local!(mut TIMERS)[index] = IDLE_TIMER };
+                arm_wheel();
+            }
+        });
+    }
+
+    /// Returns true if the timer is waiting to fire.
+    pub fn is_active(&self) -> bool {
+        interrupt::free(|_cs| self.is_current())
+    }
+
+    // true if the slot still holds this timer, must be in a critical section
+    fn is_current(&self) -> bool {
+        let state = unsafe { cpu::local!(mut TIMERS)[self.index as usize] };
+        state.callback.is_some() && state.id == self.id
+    }
+}
+
+// runs the software timers that are due and arms the wheel for the next one
+fn run_wheel() {
+    let now_tick = tick_now();
+
+    // ticks since the last run, every slot is looked at once at most
+    let last_tick = unsafe { *cpu::local!(mut WHEEL_TICK) };
+    let ticks = (now_tick.saturating_sub(last_tick)).min(WHEEL_SLOTS as u64);
+
+    for tick in now_tick - ticks + 1..=now_tick {
+        let slot = (tick % WHEEL_SLOTS as u64) as usize;
+
+        // take the due timers out of the slot first, the callbacks may add timers
+        let mut due = [Timer { index: NONE, id: 0 }; MAX_TIMERS];
+        let mut num_due = 0;
+        interrupt::free(|_cs| {
+            let mut index = unsafe { cpu::local!(mut SLOTS)[slot] };
+            while index != NONE {
+                // not held across `remove`, which changes the timers too
+                let state = unsafe { cpu::local!(mut TIMERS)[index as usize] };
+                let next = state.next;
+                if state.expires <= now_tick {
+                    remove(index as usize);
+                    due[num_due] = Timer {
+                        index,
+                        id: state.id,
+                    };
+                    num_due += 1;
+                }
+                index = next;
+            }
+        });
+
+        for &timer in &due[..num_due] {
+            let callback = interrupt::free(|_cs| {
+                // an earlier callback may have cancelled this timer, and started another in
+                // the same slot, which is already in the wheel
+                if !timer.is_current() {
+                    return None;
+                }
+                let state = unsafe { &mut cpu::local!(mut TIMERS)[timer.index as usize] };
+                let callback = state.callback;
+                if state.period == 0 {
+                    *state = IDLE_TIMER;
+                } else {
+                    while state.expires <= now_tick {
+                        state.expires += state.period;
+                    }
+                    insert(timer.index as usize);
+                }
+                callback
+            });
+            if let Some(callback) = callback {
+                callback(timer);
+            }
+        }
+    }
+
+    interrupt::free(|_cs| {
+        unsafe { *cpu::local!(mut WHEEL_TICK) = now_tick };
+        arm_wheel();
+    });
+}
+
+#[cfg(target_arch = "arm")]
+// arms the active channels again after `timer::advance` has moved the counter, as it may
+// have jumped over their compare values
+pub(crate) fn resync() {
+    interrupt::free(|_cs| {
+        for channel in 0..ALARMS.len() {
+            let state = unsafe { cpu::local!(mut ALARM_STATE)[channel] };
+            if state.callback.is_some() {
+                arm(channel, state.deadline);
+            }
+        }
+
+        // a deadline of 0 has passed so the wheel runs straight away
+        if unsafe { cpu::local!(mut SLOTS) }.iter().any(|s| *s != NONE) {
+            arm(WHEEL_CHANNEL, 0);
+        }
+    });
+}
+
+#[inline(never)]
+/// Runs the alarms and timers whose compare channel has matched. Called from the TIM2
+/// interrupt by `timer::handle_tim2_irq`.
+pub fn handle_irq() {
+    for channel in 0..4 {
+        if cpu::read!(TIM2.dier[flag(channel);1]) == 0 || cpu::read!(TIM2.sr[flag(channel);1]) == 0
+        {
+            continue;
+        }
+        cpu::write!(TIM2.sr[flag(channel);1], 0);
+
+        if channel == WHEEL_CHANNEL {
+            run_wheel();
+        } else {
+            run_alarm(channel);
+        }
+    }
+}
@@ -3,15 +3,44 @@
 //! This module provides functions to initialize and validate the clock configuration for the system.
 //! It handles setting up the PLL (Phase-Locked Loop), external clock, and various clock dividers.
 //!
+//! ## Structures
+//!
+//! - `ClockConfig`: The HSE frequency, the SYSCLK wanted, and the bus dividers.
+//! - `Pll`: The PLL settings worked out from a `ClockConfig`.
+//!
 //! ## Functions
 //!
-//! - `init`: Initializes the clock configuration based on the board-specific settings.
+//! - `init`: Checks a `ClockConfig`, then sets up the PLL, dividers and flash wait states for it.
 //! - `validate`: Validates the clock configuration to ensure it is set up correctly.
+//! - `restore`: Sets the clocks up again after STOP mode.
+//! - `sysclk`, `hclk`, `pclk1`, `pclk2`: Return the clock frequencies set by `init`.
+//! - `timclk1`, `timclk2`: Return the clock of the timers on APB1 and APB2.
+//! - `pll48clk`: Returns the clock of the RNG.
+//!
+//! The F072 has a single APB bus so it has `pclk1` and `timclk1` but no `pclk2` or `timclk2`.
 //!
 //! ## Usage
 //!
-//! The `init` function should be called during system startup to configure the clock. The `validate` function
-//! can be used to check if the clock configuration is correct.
+//! The `init` function should be called during system startup to configure the clock, this is done
+//! by `hal::init`. Drivers should get the bus clock they are on from `pclk1` or `pclk2` instead of
+//! assuming a frequency. The `validate` function can be used to check if the clock configuration is
+//! correct.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::clock;
+//!
+//! // run at 84 MHz instead of 168 MHz
+//! let mut config = clock::ClockConfig::new(16_000_000);
+//! config.sysclk = 84_000_000;
+//! config.apb1_div = 2;
+//! config.apb2_div = 1;
+//! clock::init(&config);
+//!
+//! assert_eq!(clock::pclk1(), 42_000_000);
+//! assert_eq!(clock::timclk1(), 84_000_000);
+//! ```
 //!
 
 use core::ptr;
@@ -25,22 +54,283 @@ use super::gpio;
 #[cfg(all(feature = "stm32f072", feature = "stm32f405"))]
 compile_error!("Must specify only a single CPU type featre");
 
+#[cfg(feature = "stm32f405")]
+/// Clock settings for `init`. Start from `ClockConfig::new` and change the fields needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// Frequency of the external crystal in Hz, 0 in the simulator.
+    pub hse_freq: u32,
+    /// SYSCLK to make with the PLL in Hz.
+    pub sysclk: u32,
+    /// AHB divider, 1, 2, 4, 8, 16, 64, 128, 256 or 512.
+    pub ahb_div: u32,
+    /// APB1 divider, 1, 2, 4, 8 or 16. APB1 must not be more than 42 MHz.
+    pub apb1_div: u32,
+    /// APB2 divider, 1, 2, 4, 8 or 16. APB2 must not be more than 84 MHz.
+    pub apb2_div: u32,
+}
+
 #[cfg(feature = "stm32f072")]
-#[inline(never)]
-/// Initializes the clock configuration based on the board-specific settings.
-pub fn init(hse_clk_freq: u32) {
-    let pll_m: u32;
-    match hse_clk_freq {
-        16_000_000 => {
-            // pll_m = 0b0001; // this is a x3 pll mult for 48MHz
-            // pll_m = 0b1010; // hack x12 TODO - fails to boot
-            pll_m = 0b0100; // hack x6 TODO
+/// Clock settings for `init`. Start from `ClockConfig::new` and change the fields needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// Frequency of the external crystal in Hz.
+    pub hse_freq: u32,
+    /// SYSCLK to make with the PLL in Hz.
+    pub sysclk: u32,
+    /// AHB divider, 1, 2, 4, 8, 16, 64, 128, 256 or 512.
+    pub ahb_div: u32,
+    /// APB divider, 1, 2, 4, 8 or 16.
+    pub apb1_div: u32,
+}
+
+#[cfg(feature = "stm32f405")]
+/// Main PLL settings. SYSCLK is HSE / M * N / P and PLL48CLK is HSE / M * N / Q.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pll {
+    pub m: u32,
+    pub n: u32,
+    pub p: u32,
+    pub q: u32,
+}
+
+#[cfg(feature = "stm32f072")]
+/// PLL settings. SYSCLK is the 8 MHz HSI times `mul`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pll {
+    pub mul: u32,
+}
+
+#[cfg(feature = "stm32f405")]
+const DEFAULT_CONFIG: ClockConfig = ClockConfig {
+    hse_freq: 16_000_000,
+    sysclk: 168_000_000,
+    ahb_div: 1,
+    apb1_div: 4,
+    apb2_div: 2,
+};
+
+#[cfg(feature = "stm32f072")]
+const DEFAULT_CONFIG: ClockConfig = ClockConfig {
+    hse_freq: 16_000_000,
+    sysclk: 48_000_000,
+    ahb_div: 1,
+    apb1_div: 1,
+};
+
+cpu::local! {
+    /// The configuration given to `init`, used by the frequency getters.
+    static mut CONFIG: ClockConfig = DEFAULT_CONFIG;
+}
+
+#[cfg(feature = "stm32f405")]
+impl ClockConfig {
+    /// 168 MHz SYSCLK and HCLK, 42 MHz APB1 and 84 MHz APB2.
+    pub fn new(hse_freq: u32) -> Self {
+        ClockConfig {
+            hse_freq,
+            ..DEFAULT_CONFIG
         }
+    }
 
-        _ => {
+    /// Works out the PLL settings, panics if this configuration is not possible.
+    pub fn pll(&self) -> Pll {
+        // the simulator uses the settings for a 16 MHz crystal
+        let hse_freq = if self.hse_freq == 0 {
+            16_000_000
+        } else {
+            self.hse_freq
+        };
+
+        // the PLL input should be 2 MHz to reduce jitter
+        if !(4_000_000..=26_000_000).contains(&hse_freq) || !hse_freq.is_multiple_of(2_000_000) {
             panic!("HSE Clk Freq not supported");
         }
+        let m = hse_freq / 2_000_000;
+
+        if self.sysclk > 168_000_000 {
+            panic!("SYSCLK too fast");
+        }
+
+        // the VCO must be between 100 and 432 MHz, use the smallest P that gets there
+        for p in [2, 4, 6, 8] {
+            let vco = self.sysclk * p;
+            if !(100_000_000..=432_000_000).contains(&vco) || !vco.is_multiple_of(2_000_000) {
+                continue;
+            }
+            let n = vco / 2_000_000;
+
+            // PLL48CLK must not be more than 48 MHz
+            let q = vco.div_ceil(48_000_000);
+            if q > 15 {
+                continue;
+            }
+
+            return Pll { m, n, p, q };
+        }
+        panic!("SYSCLK not supported");
+    }
+
+    // checks the dividers and bus clocks, then returns the PLL settings
+    fn check(&self) -> Pll {
+        let pll = self.pll();
+
+        ahb_prescaler(self.ahb_div);
+        apb_prescaler(self.apb1_div);
+        apb_prescaler(self.apb2_div);
+
+        let hclk = self.sysclk / self.ahb_div;
+        if hclk / self.apb1_div > 42_000_000 {
+            panic!("APB1 clock too fast");
+        }
+        if hclk / self.apb2_div > 84_000_000 {
+            panic!("APB2 clock too fast");
+        }
+
+        pll
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+impl ClockConfig {
+    /// 48 MHz SYSCLK, HCLK and APB.
+    pub fn new(hse_freq: u32) -> Self {
+        ClockConfig {
+            hse_freq,
+            ..DEFAULT_CONFIG
+        }
+    }
+
+    /// Works out the PLL settings, panics if this configuration is not possible.
+    pub fn pll(&self) -> Pll {
+        match self.hse_freq {
+            16_000_000 => {}
+            _ => {
+                panic!("HSE Clk Freq not supported");
+            }
+        }
+
+        // the PLL runs from HSI / PREDIV with PREDIV left at 1
+        if self.sysclk > 48_000_000 || !self.sysclk.is_multiple_of(8_000_000) {
+            panic!("SYSCLK not supported");
+        }
+        let mul = self.sysclk / 8_000_000;
+        if mul < 2 {
+            panic!("SYSCLK not supported");
+        }
+
+        Pll { mul }
+    }
+
+    // checks the dividers, then returns the PLL settings
+    fn check(&self) -> Pll {
+        let pll = self.pll();
+
+        ahb_prescaler(self.ahb_div);
+        apb_prescaler(self.apb1_div);
+
+        pll
+    }
+}
+
+// HPRE field for an AHB divider
+fn ahb_prescaler(div: u32) -> u32 {
+    match div {
+        1 => 0b0000,
+        2 => 0b1000,
+        4 => 0b1001,
+        8 => 0b1010,
+        16 => 0b1011,
+        64 => 0b1100,
+        128 => 0b1101,
+        256 => 0b1110,
+        512 => 0b1111,
+        _ => {
+            panic!("AHB divider not supported");
+        }
+    }
+}
+
+// PPRE field for an APB divider
+fn apb_prescaler(div: u32) -> u32 {
+    match div {
+        1 => 0b000,
+        2 => 0b100,
+        4 => 0b101,
+        8 => 0b110,
+        16 => 0b111,
+        _ => {
+            panic!("APB divider not supported");
+        }
     }
+}
+
+// the configuration given to init
+fn config() -> ClockConfig {
+    unsafe { *cpu::local!(mut CONFIG) }
+}
+
+#[inline(never)]
+/// Sets the clocks up again as the last `init` did. Leaving STOP mode switches the system
+/// clock back to the HSI, so call this after waking.
+pub fn restore() {
+    init(&config());
+}
+
+/// SYSCLK frequency in Hz.
+pub fn sysclk() -> u32 {
+    config().sysclk
+}
+
+/// AHB clock frequency in Hz, this is the CPU clock.
+pub fn hclk() -> u32 {
+    let config = config();
+    config.sysclk / config.ahb_div
+}
+
+/// APB1 clock frequency in Hz. On the F072 this is the only APB bus.
+pub fn pclk1() -> u32 {
+    hclk() / config().apb1_div
+}
+
+/// Clock of the timers on APB1 in Hz, twice `pclk1` unless the APB1 divider is 1.
+pub fn timclk1() -> u32 {
+    if config().apb1_div == 1 {
+        pclk1()
+    } else {
+        2 * pclk1()
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// APB2 clock frequency in Hz.
+pub fn pclk2() -> u32 {
+    hclk() / config().apb2_div
+}
+
+#[cfg(feature = "stm32f405")]
+/// Clock of the timers on APB2 in Hz, twice `pclk2` unless the APB2 divider is 1.
+pub fn timclk2() -> u32 {
+    if config().apb2_div == 1 {
+        pclk2()
+    } else {
+        2 * pclk2()
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// PLL48CLK frequency in Hz, used by the RNG.
+pub fn pll48clk() -> u32 {
+    let pll = config().pll();
+    2_000_000 * pll.n / pll.q
+}
+
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+/// Initializes the clock configuration based on the board-specific settings.
+pub fn init(config: &ClockConfig) {
+    let pll = config.check();
+    unsafe { *cpu::local!(mut CONFIG) = *config };
 
     // Enable HSE
     cpu::write!(RCC.cr[HSEON;1], 1);
@@ -49,9 +339,10 @@ pub fn init(hse_clk_freq: u32) {
     while cpu::read!(RCC.cr[HSERDY;1]) == 0 {}
 
     // Configure PLL
-    cpu::write!(RCC.cfgr[PLLSRC;2], 1); // HSE as PLL source
-    cpu::write!(RCC.cfgr[PLLMUL;4], pll_m); // PLL multiplier
-    cpu::write!(RCC.cfgr[PPRE;3], 0b000); // No AHB prescaler
+    cpu::write!(RCC.cfgr[PLLSRC;2], 1); // HSI / PREDIV as PLL source
+    cpu::write!(RCC.cfgr[PLLMUL;4], pll.mul - 2); // PLL multiplier
+    cpu::write!(RCC.cfgr[HPRE;4], ahb_prescaler(config.ahb_div)); // AHB prescaler
+    cpu::write!(RCC.cfgr[PPRE;3], apb_prescaler(config.apb1_div)); // APB prescaler
 
     // Enable PLL
     cpu::write!(RCC.cr[PLLON;1], 1);
@@ -98,48 +389,56 @@ pub fn configure_mco(pin: super::gpio::Pin, mco_freq: u32) {
 
 #[cfg(feature = "stm32f072")]
 #[inline(never)]
-pub fn validate() {}
+/// Validates the clock configuration to ensure it is set up correctly.
+pub fn validate() {
+    let pll = config().pll();
+
+    // Check if HSE is ready
+    if cpu::read!(RCC.cr[HSERDY;1]) != 1 {
+        panic!("HSE not ready");
+    }
+
+    // Check if PLL is ready
+    if cpu::read!(RCC.cr[PLLRDY;1]) != 1 {
+        panic!("PLL not ready");
+    }
+
+    // Check if PLL multiplier is set correctly
+    if cpu::read!(RCC.cfgr[PLLMUL;4]) != pll.mul - 2 {
+        panic!("PLL multiplier not set correctly");
+    }
+
+    // Check if system clock mux is set to PLL
+    if cpu::read!(RCC.cfgr[SWS;2]) != 0b10 {
+        panic!("System clock not set to PLL");
+    }
+}
 
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
 /// Initializes the clock configuration based on the board-specific settings.
-pub fn init(_hse_clk_freq: u32) {
+pub fn init(config: &ClockConfig) {
+    let pll = config.check();
+    unsafe { *cpu::local!(mut CONFIG) = *config };
+
     #[cfg(feature = "std")]
     let clk_freq: u32 = 0;
     #[cfg(not(feature = "std"))]
-    let clk_freq: u32 = _hse_clk_freq;
-
-    let pll_m: u32;
-    match clk_freq {
-        0 => {
-            pll_m = 8;
-        }
-
-        16_000_000 => {
-            pll_m = 8;
-        }
-
-        24_000_000 => {
-            pll_m = 12;
-        }
-
-        _ => {
-            panic!("HSE Clk Freq not supported");
-        }
-    }
+    let clk_freq: u32 = config.hse_freq;
 
     if clk_freq != 0 {
         cpu::write!(RCC.cfgr[SWS0;2], 0b10);
         cpu::write!(RCC.cr[PLLRDY;1], 1);
         cpu::write!(RCC.cr[HSERDY;1], 1);
         cpu::write!(RCC.pllcfgr[PLLSRC;1], 1);
-        cpu::write!(RCC.pllcfgr[PLLM0;6], pll_m);
+        cpu::write!(RCC.pllcfgr[PLLM0;6], pll.m);
     }
 
     // setup flash wait states and cache
     {
-        // set latency to 5 wait states - NOTE, if voltage is changed, need to change this
-        cpu::write!(FLASH.acr[LATENCY;3], 5);
+        // one wait state for every 30 MHz of HCLK - NOTE, if voltage is changed, need to change this
+        let hclk = config.sysclk / config.ahb_div;
+        cpu::write!(FLASH.acr[LATENCY;3], (hclk - 1) / 30_000_000);
 
         // enable data, instruction, prefetch cache
         cpu::write!(FLASH.acr[PRFTEN;1], 1);
@@ -158,24 +457,21 @@ pub fn init(_hse_clk_freq: u32) {
         }
 
         // setup main PLL timing for external HSE
-        let pll_m: u32 = pll_m;
-
-        let pll_n: u32 = 168;
-        let pll_q: u32 = 4;
-
-        assert!(pll_q >= 2);
-        assert!(pll_q <= 0xF);
-        assert!(pll_n >= 50);
-        assert!(pll_n <= 432);
-        assert!(pll_m >= 2);
-        assert!(pll_m <= 63);
-
-        cpu::write!(RCC.pllcfgr[PLLQ0;4], pll_q );
-        cpu::write!(RCC.pllcfgr[PLLM0;5], pll_m );
-        cpu::write!(RCC.pllcfgr[PLLN0;9], pll_n );
-
-        // set main division factor to 2
-        cpu::write!(RCC.pllcfgr[PLLP0;2], 0b00);
+        // VCO input is HSE / M = 2 MHz, for 168 MHz the VCO is 2 MHz * 168 = 336 MHz,
+        // SYSCLK is VCO / 2 and PLL48CLK for the RNG is VCO / 7 = 48 MHz
+        assert!(pll.q >= 2);
+        assert!(pll.q <= 0xF);
+        assert!(pll.n >= 50);
+        assert!(pll.n <= 432);
+        assert!(pll.m >= 2);
+        assert!(pll.m <= 63);
+
+        cpu::write!(RCC.pllcfgr[PLLQ0;4], pll.q );
+        cpu::write!(RCC.pllcfgr[PLLM0;5], pll.m );
+        cpu::write!(RCC.pllcfgr[PLLN0;9], pll.n );
+
+        // set main division factor, 0b00 is 2 up to 0b11 is 8
+        cpu::write!(RCC.pllcfgr[PLLP0;2], pll.p / 2 - 1);
 
         // select HSE
         cpu::write!(RCC.pllcfgr[PLLSRC;1], 0b1);
@@ -188,12 +484,9 @@ pub fn init(_hse_clk_freq: u32) {
         }
 
         // setup clock usage and dividers
-        // sys clock div 1
-        cpu::write!(RCC.cfgr[HPRE;4], 0b0000);
-        // APB1 Clk Div = 4
-        cpu::write!(RCC.cfgr[PPRE1;3], 0b101);
-        // APB2 Clk Div = 2
-        cpu::write!(RCC.cfgr[PPRE2;3], 0b100);
+        cpu::write!(RCC.cfgr[HPRE;4], ahb_prescaler(config.ahb_div));
+        cpu::write!(RCC.cfgr[PPRE1;3], apb_prescaler(config.apb1_div));
+        cpu::write!(RCC.cfgr[PPRE2;3], apb_prescaler(config.apb2_div));
 
         // switch clock to PLL
         cpu::write!(RCC.cfgr[SW0;2], 0b10 );
@@ -209,45 +502,34 @@ pub fn init(_hse_clk_freq: u32) {
 #[inline(never)]
 /// Validates the clock configuration to ensure it is set up correctly.
 pub fn validate() {
+    let config = config();
+    let pll = config.pll();
+
     // Check if HSE is ready
-    //if board::info::HAS_RCC {
-    //    if cpu::read!(RCC.cr[HSERDY;1]) != 1 {
-    //        panic!("HSE not ready");
-    //    }
-    //}
+    if cpu::read!(RCC.cr[HSERDY;1]) != 1 {
+        panic!("HSE not ready");
+    }
 
     // Check if PLL is ready
-    //if board::info::HAS_RCC {
-    //    if cpu::read!(RCC.cr[PLLRDY;1]) != 1 {
-    //        panic!("PLL not ready");
-    //    }
-    //}
-
-    // Check if PLL source is HSE
-    // seems like this can not be read after it is enabled
-    //if cpu::read!(RCC.pllcfgr[PLLSRC;1]) != 1 {
-    //    panic!("PLL source not set to HSE");
-    //}
-
-    // Check if PLL M is set correctly
-    // seems like this can not be read after it is enabled
-    //if read!(RCC.pllcfgr[PLLM0;6]) != board::info::CLOCK_PLL_M {
-    //    panic!("PLL M not set correctly");
-    //}
-
-    // Check if PLL N is set to 168
-    if read!(RCC.pllcfgr[PLLN0;9]) != 168 {
-        panic!("PLL N not set to 168");
+    if cpu::read!(RCC.cr[PLLRDY;1]) != 1 {
+        panic!("PLL not ready");
+    }
+
+    // PLL source and M can not be read back after the PLL is enabled
+
+    // Check if PLL N is set correctly
+    if read!(RCC.pllcfgr[PLLN0;9]) != pll.n {
+        panic!("PLL N not set correctly");
     }
 
-    // Check if PLL P is set to 2
-    if read!(RCC.pllcfgr[PLLP0;2]) != 0 {
-        panic!("PLL P not set to 2");
+    // Check if PLL P is set correctly
+    if read!(RCC.pllcfgr[PLLP0;2]) != pll.p / 2 - 1 {
+        panic!("PLL P not set correctly");
     }
 
-    // Check if PLL Q is set to 4
-    if read!(RCC.pllcfgr[PLLQ0;4]) != 4 {
-        panic!("PLL Q not set to 4");
+    // Check if PLL Q is set correctly for 48 MHz
+    if read!(RCC.pllcfgr[PLLQ0;4]) != pll.q {
+        panic!("PLL Q not set correctly");
     }
 
     // Check if system clock mux is set to PLL
@@ -256,17 +538,17 @@ pub fn validate() {
     }
 
     // Check AHB prescaler
-    if cpu::read!(RCC.cfgr[HPRE;4]) != 0b0000 {
-        panic!("AHB prescaler not set to 1");
+    if cpu::read!(RCC.cfgr[HPRE;4]) != ahb_prescaler(config.ahb_div) {
+        panic!("AHB prescaler not set correctly");
     }
 
     // Check APB1 prescaler
-    if cpu::read!(RCC.cfgr[PPRE1;3]) != 0b101 {
-        panic!("APB1 prescaler not set to 4");
+    if cpu::read!(RCC.cfgr[PPRE1;3]) != apb_prescaler(config.apb1_div) {
+        panic!("APB1 prescaler not set correctly");
     }
 
     // Check APB2 prescaler
-    if cpu::read!(RCC.cfgr[PPRE2;3]) != 0b100 {
-        panic!("APB2 prescaler not set to 2");
+    if cpu::read!(RCC.cfgr[PPRE2;3]) != apb_prescaler(config.apb2_div) {
+        panic!("APB2 prescaler not set correctly");
     }
 }
@@ -10,6 +10,9 @@
 //! - `FlashReg`: Represents the Flash memory interface registers.
 //! - `RccReg`: Represents the Reset and Clock Control registers.
 //! - `NVICReg`: Represents the Nested Vectored Interrupt Controller registers.
+//! - `ScbReg`: Represents the System Control Block registers.
+//! - `DcbReg`: Represents the Debug Exception and Monitor Control registers.
+//! - `DwtReg`: Represents the Data Watchpoint and Trace registers.
 //! - `GpioReg`: Represents the General-Purpose Input/Output registers.
 //! - `UsartReg`: Represents the Universal Synchronous/Asynchronous Receiver/Transmitter registers.
 //! - `TimAdvReg`: Represents the Advanced Control Timer registers.
@@ -20,6 +23,8 @@
 //! - `FLASH`: Base address for the Flash registers.
 //! - `RCC`: Base address for the RCC registers.
 //! - `NVIC`: Base address for the NVIC registers.
+//! - `SCB`: Base address for the SCB registers.
+//! - `DCB`, `DWT`: Base addresses for the DCB and DWT registers.
 //! - `GPIOA`, `GPIOB`, `GPIOC`: Base addresses for the GPIO registers.
 //! - `USART1`: Base address for the USART1 registers.
 //! - `TIM1`, `TIM2`: Base addresses for the TIM1 and TIM2 registers.
@@ -28,10 +33,22 @@
 //!
 //! - `write!`: Macro for writing to a register.
 //! - `read!`: Macro for reading from a register.
+//! - `local!`: Macro for declaring and using statics that hold driver state.
+//!
+//! `write!` and `read!` take a whole register, `cpu::write!(RCC.cr, val)`, or a field with its width,
+//! `cpu::write!(RCC.cr[HSEON;1], 1)`. Fields of registers from a generated definition with a
+//! `width` module can leave the width out, `cpu::write!(RCC.cr.HSEON, 1)`, and it is looked up.
+//!
+//! Statics declared with `cpu::local! { static mut NAME: T = init; }` are plain statics on the
+//! chip. In the simulator each thread has its own copy, along with its own registers, so tests
+//! can run in parallel. `cpu::local!(ref NAME)` returns a `&` to one declared without `mut`.
+//! `cpu::local!(mut NAME)` returns a `&mut` to one declared with `mut` and, like taking a `&mut`
+//! to a `static mut`, it must be used in an `unsafe` block. The caller makes sure the reference
+//! is the only one in use, normally by holding it only inside `interrupt::free`.
 //!
 //! ## Functions
 //!
-//! - `init`: Initializes the simulator memory (for `board-sim` feature).
+//! - `init`: Initializes the simulator registers, see the `sim` module (for `board-sim` feature).
 //!
 //! The following functions should not be use and instead use the macros.
 //!
@@ -46,6 +63,9 @@
 //! behavior if used incorrectly.
 //!
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "stm32f405")]
 use super::svd_stm32f405 as svd;
 
@@ -55,47 +75,136 @@ use super::svd_stm32f0x2 as svd;
 #[cfg(any(feature = "stm32f072", feature = "stm32f405"))]
 pub use svd::*;
 
-#[cfg(feature = "std")]
-extern crate std;
-
-#[cfg(feature = "std")]
-use std::collections::HashMap;
-#[cfg(feature = "std")]
-use std::sync::Mutex;
-
 #[cfg(feature = "stm32f405")]
 #[repr(C)]
 pub struct NVICReg {
     pub iser: [u32; 8], // Interrupt Set-Enable Registers
+    _reserved0: [u32; 24],
     pub icer: [u32; 8], // Interrupt Clear-Enable Registers
+    _reserved1: [u32; 24],
     pub ispr: [u32; 8], // Interrupt Set-Pending Registers
+    _reserved2: [u32; 24],
     pub icpr: [u32; 8], // Interrupt Clear-Pending Registers
+    _reserved3: [u32; 24],
     pub iabr: [u32; 8], // Interrupt Active Bit Registers
+    _reserved4: [u32; 56],
     pub ipr: [u32; 60], // Interrupt Priority Registers
-    pub stir: u32,      // Software Trigger Interrupt Register
+    _reserved5: [u32; 644],
+    pub stir: u32, // Software Trigger Interrupt Register
 }
 
 #[cfg(feature = "stm32f405")]
 pub const NVIC: *mut NVICReg = 0xE000_E100 as *mut NVICReg;
 
+// The Cortex-M0 has at most 32 interrupts so each NVIC register is a single word
+#[cfg(feature = "stm32f072")]
+#[repr(C)]
+pub struct NVICReg {
+    pub iser: [u32; 1], // Interrupt Set-Enable Register
+    _reserved0: [u32; 31],
+    pub icer: [u32; 1], // Interrupt Clear-Enable Register
+    _reserved1: [u32; 31],
+    pub ispr: [u32; 1], // Interrupt Set-Pending Register
+    _reserved2: [u32; 31],
+    pub icpr: [u32; 1], // Interrupt Clear-Pending Register
+    _reserved3: [u32; 95],
+    pub ipr: [u32; 8], // Interrupt Priority Registers
+}
+
+#[cfg(feature = "stm32f072")]
+pub const NVIC: *mut NVICReg = 0xE000_E100 as *mut NVICReg;
+
+// Only the start of the System Control Block, the same on the Cortex-M0 and M4
+#[repr(C)]
+pub struct ScbReg {
+    pub cpuid: u32, // CPUID Base Register
+    pub icsr: u32,  // Interrupt Control and State Register
+    pub vtor: u32,  // Vector Table Offset Register, reserved on the Cortex-M0
+    pub aircr: u32, // Application Interrupt and Reset Control Register
+    pub scr: u32,   // System Control Register
+    pub ccr: u32,   // Configuration and Control Register
+}
+
+pub const SCB: *mut ScbReg = 0xE000_ED00 as *mut ScbReg;
+
+#[allow(non_snake_case)]
+pub mod SCB {
+    pub mod aircr {
+        pub const VECTKEY: u8 = 16;
+        pub const SYSRESETREQ: u8 = 2;
+    }
+    pub mod scr {
+        pub const SEVONPEND: u8 = 4;
+        pub const SLEEPDEEP: u8 = 2;
+        pub const SLEEPONEXIT: u8 = 1;
+    }
+}
+
+// Debug Exception and Monitor Control registers, DEMCR turns on the DWT
+#[cfg(feature = "stm32f405")]
+#[repr(C)]
+pub struct DcbReg {
+    pub dhcsr: u32, // Debug Halting Control and Status Register
+    pub dcrsr: u32, // Debug Core Register Selector Register
+    pub dcrdr: u32, // Debug Core Register Data Register
+    pub demcr: u32, // Debug Exception and Monitor Control Register
+}
+
+#[cfg(feature = "stm32f405")]
+pub const DCB: *mut DcbReg = 0xE000_EDF0 as *mut DcbReg;
+
+#[cfg(feature = "stm32f405")]
+#[allow(non_snake_case)]
+pub mod DCB {
+    pub mod demcr {
+        pub const TRCENA: u8 = 24;
+    }
+}
+
+// Data Watchpoint and Trace unit, the Cortex-M0 does not have one
+#[cfg(feature = "stm32f405")]
+#[repr(C)]
+pub struct DwtReg {
+    pub ctrl: u32,     // Control Register
+    pub cyccnt: u32,   // Cycle Count Register
+    pub cpicnt: u32,   // CPI Count Register
+    pub exccnt: u32,   // Exception Overhead Count Register
+    pub sleepcnt: u32, // Sleep Count Register
+    pub lsucnt: u32,   // LSU Count Register
+    pub foldcnt: u32,  // Folded-instruction Count Register
+    pub pcsr: u32,     // Program Counter Sample Register
+}
+
+#[cfg(feature = "stm32f405")]
+pub const DWT: *mut DwtReg = 0xE000_1000 as *mut DwtReg;
+
+#[cfg(feature = "stm32f405")]
+#[allow(non_snake_case)]
+pub mod DWT {
+    pub mod ctrl {
+        pub const NOCYCCNT: u8 = 25;
+        pub const CYCCNTENA: u8 = 0;
+    }
+}
+
+#[cfg(not(feature = "std"))]
 #[inline(always)]
 //#[inline(never)]
 pub fn update_reg(addr: *mut u32, mask: u32, val: u32) {
-    if cfg!(feature = "std") {
-        let mut v: u32 = read_reg(addr);
+    unsafe {
+        let mut v: u32 = core::ptr::read_volatile(addr);
         v &= !mask;
         v |= val;
-        write_reg(addr, v);
-    } else {
-        unsafe {
-            let mut v: u32 = core::ptr::read_volatile(addr);
-            v &= !mask;
-            v |= val;
-            core::ptr::write_volatile(addr, v);
-        }
+        core::ptr::write_volatile(addr, v);
     }
 }
 
+#[cfg(feature = "std")]
+#[inline(always)]
+pub fn update_reg(addr: *mut u32, mask: u32, val: u32) {
+    super::sim::update(addr, mask, val);
+}
+
 #[cfg(not(feature = "std"))]
 #[inline(always)]
 pub fn write_reg(addr: *mut u32, val: u32) {
@@ -108,12 +217,7 @@ pub fn write_reg(addr: *mut u32, val: u32) {
 #[inline(always)]
 //#[inline(never)]
 pub fn write_reg(addr: *mut u32, val: u32) {
-    unsafe {
-        if let Some(ref map_mutex) = SIM {
-            let mut map = map_mutex.lock().unwrap();
-            map.insert(addr, val);
-        }
-    }
+    super::sim::write(addr, val);
 }
 
 #[cfg(not(feature = "std"))]
@@ -126,19 +230,7 @@ pub fn read_reg(addr: *mut u32) -> u32 {
 #[inline(always)]
 //#[inline(never)]
 pub fn read_reg(addr: *mut u32) -> u32 {
-    unsafe {
-        if let Some(ref map_mutex) = SIM {
-            let map = map_mutex.lock().unwrap();
-            if let Some(value) = map.get(&addr) {
-                let my_value: u32 = *value;
-                my_value
-            } else {
-                0
-            }
-        } else {
-            0
-        }
-    }
+    super::sim::read(addr)
 }
 
 #[macro_export]
@@ -148,8 +240,8 @@ macro_rules! write {
         //let offset = concat_idents!($x, _, $y, _, $z);
         let mut mask: u32 = (1u32 << $w) - 1;
         let mut val: u32 = $data & mask;
-        mask = mask << offset;
-        val = val << offset;
+        mask <<= offset;
+        val <<= offset;
         unsafe {
             let addr = ptr::addr_of_mut!((*$x).$y);
             cpu::update_reg(addr, mask, val);
@@ -160,8 +252,8 @@ macro_rules! write {
         let offset: u8 = $z;
         let mut mask: u32 = (1u32 << $w) - 1;
         let mut val: u32 = $data & mask;
-        mask = mask << offset;
-        val = val << offset;
+        mask <<= offset;
+        val <<= offset;
         unsafe {
             let addr = ptr::addr_of_mut!((*$x).$y);
             cpu::update_reg(addr, mask, val);
@@ -183,6 +275,20 @@ macro_rules! write {
             cpu::write_reg(addr, val);
         }
     };
+
+    // the width of the field comes from the generated register definitions
+    ( $x:ident.$y:ident.$z:ident ,  $data:expr  ) => {{
+        let offset: u8 = $x::$y::$z;
+        let width: u8 = $x::$y::width::$z;
+        let mut mask: u32 = ((1u64 << width) - 1) as u32;
+        let mut val: u32 = $data & mask;
+        mask <<= offset;
+        val <<= offset;
+        unsafe {
+            let addr = ptr::addr_of_mut!((*$x).$y);
+            cpu::update_reg(addr, mask, val);
+        }
+    }};
 }
 
 pub(crate) use write;
@@ -198,8 +304,8 @@ macro_rules! read {
             let addr = ptr::addr_of_mut!((*$x).$y);
             val = cpu::read_reg(addr);
         }
-        val = val >> offset;
-        val = val & mask;
+        val >>= offset;
+        val &= mask;
         val
     }};
     ( $x:ident.$y:ident[$z:expr;$w:expr] ) => {{
@@ -211,8 +317,8 @@ macro_rules! read {
             let addr = ptr::addr_of_mut!((*$x).$y);
             val = cpu::read_reg(addr);
         }
-        val = val >> offset;
-        val = val & mask;
+        val >>= offset;
+        val &= mask;
         val
     }};
     ( $x:ident.$y:ident ) => {{
@@ -223,25 +329,95 @@ macro_rules! read {
         }
         val
     }};
+    ( $x:ident.$y:ident.$z:ident ) => {{
+        let offset: u8 = $x::$y::$z;
+        let width: u8 = $x::$y::width::$z;
+        let mask: u32 = ((1u64 << width) - 1) as u32;
+        let mut val: u32;
+
+        unsafe {
+            let addr = ptr::addr_of_mut!((*$x).$y);
+            val = cpu::read_reg(addr);
+        }
+        val >>= offset;
+        val &= mask;
+        val
+    }};
 }
 
 pub(crate) use read;
 
 #[cfg(feature = "std")]
-static mut SIM: Option<Mutex<&'static mut HashMap<*mut u32, u32>>> = None;
+#[doc(hidden)]
+pub use std::thread_local;
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+/// Returns a `&mut` to the value of a `local!` static for `local!(mut NAME)`.
+///
+/// # Safety
+///
+/// The caller makes sure no other reference to the value is in use while this one is.
+#[allow(clippy::mut_from_ref)]
+pub unsafe fn local_mut<T>(
+    key: &'static std::thread::LocalKey<core::cell::UnsafeCell<T>>,
+) -> &'static mut T {
+    // the value lives as long as the thread, which is as long as anything can use it
+    key.with(|v| unsafe { &mut *v.get() })
+}
+
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! local {
+    ( $(#[$attr:meta])* static mut $name:ident: $t:ty = $init:expr; ) => {
+        $(#[$attr])*
+        static mut $name: $t = $init;
+    };
+    ( $(#[$attr:meta])* static $name:ident: $t:ty = $init:expr; ) => {
+        $(#[$attr])*
+        static $name: $t = $init;
+    };
+    // the caller's unsafe block covers the dereference
+    ( mut $name:ident ) => {
+        &mut *core::ptr::addr_of_mut!($name)
+    };
+    ( ref $name:ident ) => {
+        &$name
+    };
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! local {
+    ( $(#[$attr:meta])* static mut $name:ident: $t:ty = $init:expr; ) => {
+        $crate::cpu::thread_local! {
+            $(#[$attr])*
+            static $name: core::cell::UnsafeCell<$t> = const { core::cell::UnsafeCell::new($init) };
+        }
+    };
+    ( $(#[$attr:meta])* static $name:ident: $t:ty = $init:expr; ) => {
+        $crate::cpu::thread_local! {
+            $(#[$attr])*
+            static $name: $t = const { $init };
+        }
+    };
+    ( mut $name:ident ) => {
+        $crate::cpu::local_mut(&$name)
+    };
+    ( ref $name:ident ) => {
+        $name.with(|v| unsafe { &*core::ptr::from_ref(v) })
+    };
+}
+
+pub(crate) use local;
 
 #[cfg(not(feature = "std"))]
 fn init_sim() {}
 
 #[cfg(feature = "std")]
 fn init_sim() {
-    // initialize the simulator memory
-    static mut MEM: Option<HashMap<*mut u32, u32>> = None;
-    #[allow(static_mut_refs)]
-    unsafe {
-        MEM = Some(HashMap::new());
-        SIM = Some(Mutex::new(MEM.as_mut().unwrap()));
-    }
+    // clear the simulator registers and set up the peripheral models
+    super::sim::init();
 }
 
 pub fn init() {
@@ -0,0 +1,233 @@
+//! # CRC Module
+//!
+//! This module computes 32 bit cyclic redundancy checks (CRC), using the CRC unit when it can
+//! and software when it can not.
+//!
+//! The CRC unit of the STM32F405 has a fixed polynomial (0x04C11DB7) and start value
+//! (0xFFFFFFFF), and takes whole words. Other settings use software, and the bytes after the
+//! last whole word are done in software too. The STM32F072 CRC unit can be set to any 32 bit
+//! polynomial and start value, and can reverse the bits in and out, so it does every setting.
+//! The simulator always uses software.
+//!
+//! The software version gives exactly the same results, and `self_test` checks the two agree on
+//! `TEST_VECTORS`.
+//!
+//! The CRC unit is shared, so `compute` must not be called from an interrupt handler while the
+//! main loop could be using it too.
+//!
+//! ## Structures
+//!
+//! - `Config`: The parameters of a CRC, in the usual catalogue form.
+//!
+//! ## Functions
+//!
+//! - `compute`: Computes a CRC, using the CRC unit if it can.
+//! - `compute_sw`: Computes a CRC in software.
+//! - `self_test`: Checks both versions against the test vectors.
+//!
+//! ## Usage
+//!
+//! Use `CRC32` for checks that other systems need to match, it is the one used by Ethernet, zip
+//! and zlib. `CRC32_MPEG2` is the fastest on the STM32F405 as it needs no bit reversal.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::crc;
+//!
+//! hal::init(16_000_000);
+//!
+//! let check = crc::compute(&crc::CRC32, b"123456789");
+//! assert_eq!(check, 0xCBF43926);
+//! ```
+//!
+
+#[allow(unused_imports)]
+use core::ptr;
+
+#[allow(unused_imports)]
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+/// Parameters of a 32 bit CRC, as given in catalogues of CRCs. The polynomial and start value are
+/// in the normal, most significant bit first, form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Polynomial, without the top bit.
+    pub poly: u32,
+    /// Value the CRC starts with.
+    pub init: u32,
+    /// Take the bits of each byte least significant first.
+    pub reflect_in: bool,
+    /// Reverse the bits of the result.
+    pub reflect_out: bool,
+    /// Value the result is XORed with.
+    pub xor_out: u32,
+}
+
+/// The CRC used by Ethernet, zip and zlib.
+pub const CRC32: Config = Config {
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    reflect_in: true,
+    reflect_out: true,
+    xor_out: 0xFFFF_FFFF,
+};
+
+/// The CRC that the STM32F405 CRC unit computes with no changes.
+pub const CRC32_MPEG2: Config = Config {
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    reflect_in: false,
+    reflect_out: false,
+    xor_out: 0,
+};
+
+/// The CRC used by bzip2.
+pub const CRC32_BZIP2: Config = Config {
+    poly: 0x04C1_1DB7,
+    init: 0xFFFF_FFFF,
+    reflect_in: false,
+    reflect_out: false,
+    xor_out: 0xFFFF_FFFF,
+};
+
+/// The Castagnoli CRC used by iSCSI and ext4, which finds more errors in short messages.
+pub const CRC32C: Config = Config {
+    poly: 0x1EDC_6F41,
+    init: 0xFFFF_FFFF,
+    reflect_in: true,
+    reflect_out: true,
+    xor_out: 0xFFFF_FFFF,
+};
+
+/// Known results, as the setting, the data, and the CRC of the data.
+pub const TEST_VECTORS: [(Config, &[u8], u32); 8] = [
+    (CRC32, b"123456789", 0xCBF4_3926),
+    (CRC32, b"", 0x0000_0000),
+    (
+        CRC32,
+        b"The quick brown fox jumps over the lazy dog",
+        0x414F_A339,
+    ),
+    (CRC32_MPEG2, b"123456789", 0x0376_E6E7),
+    (CRC32_MPEG2, b"", 0xFFFF_FFFF),
+    (CRC32_MPEG2, b"1234", 0xA695_C4AA),
+    (CRC32_BZIP2, b"123456789", 0xFC89_1918),
+    (CRC32C, b"123456789", 0xE306_9283),
+];
+
+// runs the CRC on `data` from `state`, which is in the normal form and not yet reflected out
+fn update_sw(config: &Config, mut state: u32, data: &[u8]) -> u32 {
+    for byte in data {
+        let byte = if config.reflect_in {
+            byte.reverse_bits()
+        } else {
+            *byte
+        };
+        state ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            state = if state & 0x8000_0000 != 0 {
+                (state << 1) ^ config.poly
+            } else {
+                state << 1
+            };
+        }
+    }
+    state
+}
+
+// turns the state at the end into the result
+fn finish(config: &Config, state: u32) -> u32 {
+    let out = if config.reflect_out {
+        state.reverse_bits()
+    } else {
+        state
+    };
+    out ^ config.xor_out
+}
+
+#[inline(never)]
+/// Computes the CRC of `data` in software, a bit at a time.
+pub fn compute_sw(config: &Config, data: &[u8]) -> u32 {
+    finish(config, update_sw(config, config.init, data))
+}
+
+#[cfg(feature = "stm32f405")]
+#[cfg(not(feature = "std"))]
+// runs the whole words of `data` through the CRC unit, returning the state and the bytes left
+fn update_hw<'a>(config: &Config, data: &'a [u8]) -> Option<(u32, &'a [u8])> {
+    if config.poly != CRC32_MPEG2.poly || config.init != CRC32_MPEG2.init {
+        return None;
+    }
+
+    cpu::write!(RCC.ahb1enr[CRCEN;1], 1);
+    cpu::write!(CRC.cr[RESET;1], 1);
+
+    let (words, rest) = data.as_chunks::<4>();
+    for word in words {
+        // the CRC unit takes the most significant bit of the word first
+        let word = if config.reflect_in {
+            u32::from_le_bytes(*word).reverse_bits()
+        } else {
+            u32::from_be_bytes(*word)
+        };
+        cpu::write!(CRC.dr, word);
+    }
+
+    Some((cpu::read!(CRC.dr), rest))
+}
+
+#[cfg(feature = "stm32f072")]
+#[cfg(not(feature = "std"))]
+// runs all of `data` through the CRC unit, returning the state and the bytes left
+fn update_hw<'a>(config: &Config, data: &'a [u8]) -> Option<(u32, &'a [u8])> {
+    cpu::write!(RCC.ahbenr[CRCEN;1], 1);
+
+    // 32 bit polynomial, reversing the bits of each byte for reflect in
+    cpu::write!(CRC.pol, config.poly);
+    cpu::write!(CRC.init, config.init);
+    cpu::write!(CRC.cr, (config.reflect_in as u32) << CRC::cr::REV_IN);
+    cpu::write!(CRC.cr[RESET;1], 1);
+
+    let dr = unsafe { ptr::addr_of_mut!((*CRC).dr) };
+    let (words, rest) = data.as_chunks::<4>();
+    for word in words {
+        // the bytes are taken most significant first
+        cpu::write_reg(dr, u32::from_be_bytes(*word));
+    }
+    for byte in rest {
+        // a byte write runs the CRC on just that byte
+        unsafe {
+            ptr::write_volatile(dr as *mut u8, *byte);
+        }
+    }
+
+    Some((cpu::read_reg(dr), &[]))
+}
+
+#[cfg(feature = "std")]
+// the simulator has no CRC unit
+fn update_hw<'a>(_config: &Config, _data: &'a [u8]) -> Option<(u32, &'a [u8])> {
+    None
+}
+
+#[inline(never)]
+/// Computes the CRC of `data`, using the CRC unit where it can.
+pub fn compute(config: &Config, data: &[u8]) -> u32 {
+    let state = match update_hw(config, data) {
+        Some((state, rest)) => update_sw(config, state, rest),
+        None => update_sw(config, config.init, data),
+    };
+    finish(config, state)
+}
+
+#[inline(never)]
+/// Returns true if `compute` and `compute_sw` both give the right result for all the
+/// `TEST_VECTORS`.
+pub fn self_test() -> bool {
+    TEST_VECTORS.iter().all(|(config, data, crc)| {
+        compute(config, data) == *crc && compute_sw(config, data) == *crc
+    })
+}
@@ -0,0 +1,480 @@
+//! # DMA Module
+//!
+//! This module provides a driver for the DMA1 and DMA2 controllers of the
+//! STM32F405. Each controller has 8 streams and each stream can be connected
+//! to one of 8 channels, which picks the peripheral request that drives it.
+//! The stream and channel for each peripheral are in tables 42 and 43 of the
+//! RM0090 Reference Manual.
+//!
+//! A driver claims a stream, configures it, then starts transfers. Completion
+//! can be found by polling the stream or by a callback run from the shared
+//! interrupt handler `handle_irq`.
+//!
+//! All streams are used in direct mode, the FIFO is not used.
+//!
+//! ## Structures
+//!
+//! - `Stream`: One stream of a DMA controller.
+//! - `Config`: Channel, direction, widths, and modes for a stream.
+//!
+//! ## Methods for `Stream`
+//!
+//! - `claim`: Takes ownership of a stream, panics if it is already in use.
+//! - `release`: Gives the stream back.
+//! - `configure`: Sets up the stream from a `Config`.
+//! - `set_peripheral`, `set_memory`, `set_memory1`, `set_count`: Set the transfer addresses and length.
+//! - `start`, `stop`: Enable and disable the stream.
+//! - `poll`: Returns and clears the next event of the stream.
+//! - `wait`: Blocks until the transfer is complete or fails.
+//! - `set_callback`: Enables interrupts and sets the function `handle_irq` calls.
+//!
+//! ## Functions
+//!
+//! - `handle_irq`: Called from the interrupt handler of a stream.
+//! - `any_enabled`: Returns true if a claimed stream is running.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::dma;
+//!
+//! hal::init(16_000_000);
+//!
+//! static DATA: [u8; 4] = *b"1234";
+//!
+//! // USART1 TX is DMA2 stream 7 channel 4
+//! let stream = dma::Stream::claim(cpu::DMA2, 7);
+//! stream.configure(&dma::Config::new(4, dma::Direction::MemoryToPeripheral));
+//! stream.set_peripheral(unsafe { core::ptr::addr_of!((*cpu::USART1).dr) } as u32);
+//! stream.set_memory(DATA.as_ptr() as u32);
+//! stream.set_count(DATA.len() as u16);
+//! stream.start();
+//! let result = stream.wait();
+//! stream.release();
+//! ```
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+use super::interrupt::{self, Irq};
+
+/// Direction of a transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    PeripheralToMemory = 0b00,
+    MemoryToPeripheral = 0b01,
+    MemoryToMemory = 0b10,
+}
+
+/// Size of each item transferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    Byte = 0b00,
+    HalfWord = 0b01,
+    Word = 0b10,
+}
+
+/// Priority between streams on the same controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    Low = 0b00,
+    Medium = 0b01,
+    High = 0b10,
+    VeryHigh = 0b11,
+}
+
+/// Things that happen on a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Half the items have been transferred.
+    HalfTransfer,
+    /// All the items have been transferred. In circular mode this repeats.
+    TransferComplete,
+    /// A bus error. The stream has been disabled by the hardware.
+    TransferError,
+    /// The peripheral requested a transfer before the last one finished.
+    DirectModeError,
+}
+
+/// Called from `handle_irq` with the stream and what happened.
+pub type Callback = fn(stream: Stream, event: Event);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Channel 0 to 7 that selects the peripheral request.
+    pub channel: u8,
+    pub direction: Direction,
+    pub peripheral_width: Width,
+    pub memory_width: Width,
+    pub peripheral_increment: bool,
+    pub memory_increment: bool,
+    /// Restart from the beginning when the count reaches zero.
+    pub circular: bool,
+    /// Swap between the two memory addresses at the end of each transfer.
+    pub double_buffer: bool,
+    pub priority: Priority,
+}
+
+impl Config {
+    /// Byte wide transfer that increments the memory address, which is what
+    /// most peripherals need.
+    pub const fn new(channel: u8, direction: Direction) -> Self {
+        Config {
+            channel,
+            direction,
+            peripheral_width: Width::Byte,
+            memory_width: Width::Byte,
+            peripheral_increment: false,
+            memory_increment: true,
+            circular: false,
+            double_buffer: false,
+            priority: Priority::Low,
+        }
+    }
+}
+
+/// One stream of a DMA controller, for example `Stream::claim(cpu::DMA2, 7)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stream {
+    pub dma: *mut cpu::DmaReg,
+    pub num: u8,
+}
+
+// The registers for one stream in the same layout as s0cr to s0fcr
+#[repr(C)]
+struct StreamReg {
+    cr: u32,
+    ndtr: u32,
+    par: u32,
+    m0ar: u32,
+    m1ar: u32,
+    fcr: u32,
+}
+
+// lets `stream.reg[FIELD;w]` find the field offsets, which are the same for all streams
+mod stream {
+    pub use super::cpu::DMA::s0cr as cr;
+    pub use super::cpu::DMA::s0fcr as fcr;
+    pub use super::cpu::DMA::s0ndtr as ndtr;
+}
+
+// bit offsets of the flags in LISR/HISR and LIFCR/HIFCR, relative to the stream
+const FEIF: u8 = 0;
+const DMEIF: u8 = 2;
+const TEIF: u8 = 3;
+const HTIF: u8 = 4;
+const TCIF: u8 = 5;
+
+const ALL_FLAGS: u32 = (1 << FEIF) | (1 << DMEIF) | (1 << TEIF) | (1 << HTIF) | (1 << TCIF);
+
+// one bit per stream, DMA1 in the low byte and DMA2 in the high byte
+cpu::local! {
+    static mut CLAIMED: u16 = 0;
+}
+
+cpu::local! {
+    static mut CALLBACKS: [Option<Callback>; 16] = [None; 16];
+}
+
+impl Stream {
+    /// Takes ownership of stream `num` of `dma` and turns on the clock for the controller.
+    #[inline(never)]
+    pub fn claim(dma: *mut cpu::DmaReg, num: u8) -> Stream {
+        assert!(num < 8);
+        let stream = Stream { dma, num };
+
+        // an interrupt handler claiming the same stream must not get in between the check and
+        // the set
+        let bit: u16 = 1 << stream.index();
+        let was_claimed = interrupt::free(|_cs| {
+            let claimed = unsafe { cpu::local!(mut CLAIMED) };
+            let was_claimed = *claimed & bit != 0;
+            *claimed |= bit;
+            was_claimed
+        });
+        if was_claimed {
+            panic!("DMA stream already claimed");
+        }
+
+        if ptr::eq(dma, DMA2) {
+            cpu::write!(RCC.ahb1enr[DMA2EN;1], 1);
+        } else {
+            cpu::write!(RCC.ahb1enr[DMA1EN;1], 1);
+        }
+
+        stream
+    }
+
+    /// Stops the stream and gives it back so it can be claimed again.
+    #[inline(never)]
+    pub fn release(&self) {
+        self.stop();
+        self.disable_interrupts();
+        interrupt::free(|_cs| unsafe { *cpu::local!(mut CLAIMED) &= !(1 << self.index()) });
+    }
+
+    // index into CLAIMED and CALLBACKS
+    fn index(&self) -> usize {
+        if ptr::eq(self.dma, DMA2) {
+            8 + self.num as usize
+        } else if ptr::eq(self.dma, DMA1) {
+            self.num as usize
+        } else {
+            panic!("Unknown DMA");
+        }
+    }
+
+    fn reg(&self) -> *mut StreamReg {
+        // stream registers start at 0x10 and are 0x18 apart, see 10.5.11 of RM0090
+        (self.dma as usize + 0x10 + 0x18 * self.num as usize) as *mut StreamReg
+    }
+
+    // bit offset of the flags of this stream in the LISR/HISR and LIFCR/HIFCR registers
+    fn flag_offset(&self) -> u8 {
+        match self.num % 4 {
+            0 => 0,
+            1 => 6,
+            2 => 16,
+            _ => 22,
+        }
+    }
+
+    /// The NVIC interrupt number of this stream.
+    pub fn irq_num(&self) -> Irq {
+        const DMA1_IRQ: [Irq; 8] = [
+            Irq::Dma1Stream0,
+            Irq::Dma1Stream1,
+            Irq::Dma1Stream2,
+            Irq::Dma1Stream3,
+            Irq::Dma1Stream4,
+            Irq::Dma1Stream5,
+            Irq::Dma1Stream6,
+            Irq::Dma1Stream7,
+        ];
+        const DMA2_IRQ: [Irq; 8] = [
+            Irq::Dma2Stream0,
+            Irq::Dma2Stream1,
+            Irq::Dma2Stream2,
+            Irq::Dma2Stream3,
+            Irq::Dma2Stream4,
+            Irq::Dma2Stream5,
+            Irq::Dma2Stream6,
+            Irq::Dma2Stream7,
+        ];
+
+        if ptr::eq(self.dma, DMA2) {
+            DMA2_IRQ[self.num as usize]
+        } else {
+            DMA1_IRQ[self.num as usize]
+        }
+    }
+
+    /// Sets up the stream. Stops the stream first if it is running.
+    #[inline(never)]
+    pub fn configure(&self, config: &Config) {
+        let stream = self.reg();
+
+        assert!(config.channel < 8);
+
+        self.stop();
+        self.clear_flags(ALL_FLAGS);
+
+        cpu::write!(stream.cr[CHSEL;3], config.channel as u32);
+        cpu::write!(stream.cr[MBURST;2], 0b00); // single transfer
+        cpu::write!(stream.cr[PBURST;2], 0b00); // single transfer
+        cpu::write!(stream.cr[CT;1], 0); // start with memory 0
+        cpu::write!(stream.cr[DBM;1], config.double_buffer as u32);
+        cpu::write!(stream.cr[PL;2], config.priority as u32);
+        cpu::write!(stream.cr[MSIZE;2], config.memory_width as u32);
+        cpu::write!(stream.cr[PSIZE;2], config.peripheral_width as u32);
+        cpu::write!(stream.cr[MINC;1], config.memory_increment as u32);
+        cpu::write!(stream.cr[PINC;1], config.peripheral_increment as u32);
+        cpu::write!(stream.cr[CIRC;1], config.circular as u32);
+        cpu::write!(stream.cr[DIR;2], config.direction as u32);
+        cpu::write!(stream.cr[PFCTRL;1], 0); // DMA is flow controller
+
+        cpu::write!(stream.fcr[DMDIS;1], 0); // direct mode
+    }
+
+    /// Sets the peripheral address, or the source address for memory to memory.
+    pub fn set_peripheral(&self, addr: u32) {
+        let stream = self.reg();
+        cpu::write!(stream.par, addr);
+    }
+
+    /// Sets the memory address, the first buffer in double buffer mode.
+    pub fn set_memory(&self, addr: u32) {
+        let stream = self.reg();
+        cpu::write!(stream.m0ar, addr);
+    }
+
+    /// Sets the second buffer address for double buffer mode.
+    pub fn set_memory1(&self, addr: u32) {
+        let stream = self.reg();
+        cpu::write!(stream.m1ar, addr);
+    }
+
+    /// Sets the number of items to transfer.
+    pub fn set_count(&self, count: u16) {
+        let stream = self.reg();
+        cpu::write!(stream.ndtr[NDT;16], count as u32);
+    }
+
+    /// Number of items left to transfer.
+    pub fn remaining(&self) -> u16 {
+        let stream = self.reg();
+        cpu::read!(stream.ndtr[NDT;16]) as u16
+    }
+
+    /// In double buffer mode, the memory buffer 0 or 1 the DMA is using now.
+    pub fn current_target(&self) -> u8 {
+        let stream = self.reg();
+        cpu::read!(stream.cr[CT;1]) as u8
+    }
+
+    /// Clears old events and enables the stream.
+    #[inline(never)]
+    pub fn start(&self) {
+        let stream = self.reg();
+        self.clear_flags(ALL_FLAGS);
+        cpu::write!(stream.cr[EN;1], 1);
+    }
+
+    /// Disables the stream and waits for the current item to finish.
+    #[inline(never)]
+    pub fn stop(&self) {
+        let stream = self.reg();
+        cpu::write!(stream.cr[EN;1], 0);
+
+        #[cfg(not(feature = "std"))]
+        while cpu::read!(stream.cr[EN;1]) != 0 {}
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        let stream = self.reg();
+        cpu::read!(stream.cr[EN;1]) != 0
+    }
+
+    fn read_flags(&self) -> u32 {
+        let dma = self.dma;
+        let isr = if self.num < 4 {
+            cpu::read!(dma.lisr)
+        } else {
+            cpu::read!(dma.hisr)
+        };
+        (isr >> self.flag_offset()) & ALL_FLAGS
+    }
+
+    fn clear_flags(&self, flags: u32) {
+        let dma = self.dma;
+        let val = flags << self.flag_offset();
+        if self.num < 4 {
+            cpu::write!(dma.lifcr, val);
+        } else {
+            cpu::write!(dma.hifcr, val);
+        }
+    }
+
+    /// Returns and clears the most important event that has happened, if any.
+    /// Errors come first, then half transfer, then transfer complete. Only the
+    /// flag of the event returned is cleared, so if both halves are done the
+    /// next call returns the transfer complete.
+    #[inline(never)]
+    pub fn poll(&self) -> Option<Event> {
+        let flags = self.read_flags();
+
+        let (event, flag) = if flags & (1 << TEIF) != 0 {
+            (Event::TransferError, TEIF)
+        } else if flags & (1 << DMEIF) != 0 {
+            (Event::DirectModeError, DMEIF)
+        } else if flags & (1 << HTIF) != 0 {
+            // the first half was done before the second
+            (Event::HalfTransfer, HTIF)
+        } else if flags & (1 << TCIF) != 0 {
+            (Event::TransferComplete, TCIF)
+        } else {
+            // FIFO errors are not real errors in direct mode
+            if flags & (1 << FEIF) != 0 {
+                self.clear_flags(1 << FEIF);
+            }
+            return None;
+        };
+
+        self.clear_flags(1 << flag);
+        Some(event)
+    }
+
+    /// Blocks until the transfer is complete. In the simulator there is no
+    /// DMA so it returns straight away.
+    #[inline(never)]
+    pub fn wait(&self) -> Result<(), Event> {
+        loop {
+            match self.poll() {
+                Some(Event::TransferComplete) => return Ok(()),
+                Some(Event::HalfTransfer) => {}
+                Some(err) => return Err(err),
+                None => {
+                    #[cfg(feature = "std")]
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Enables the transfer complete, half transfer, and error interrupts of the
+    /// stream and sets the function `handle_irq` calls for them.
+    #[inline(never)]
+    pub fn set_callback(&self, callback: Callback) {
+        let stream = self.reg();
+
+        unsafe { cpu::local!(mut CALLBACKS)[self.index()] = Some(callback) };
+
+        cpu::write!(stream.cr[TCIE;1], 1);
+        cpu::write!(stream.cr[HTIE;1], 1);
+        cpu::write!(stream.cr[TEIE;1], 1);
+        cpu::write!(stream.cr[DMEIE;1], 1);
+
+        interrupt::enable(self.irq_num());
+    }
+
+    fn disable_interrupts(&self) {
+        let stream = self.reg();
+
+        interrupt::disable(self.irq_num());
+
+        cpu::write!(stream.cr[TCIE;1], 0);
+        cpu::write!(stream.cr[HTIE;1], 0);
+        cpu::write!(stream.cr[TEIE;1], 0);
+        cpu::write!(stream.cr[DMEIE;1], 0);
+
+        unsafe { cpu::local!(mut CALLBACKS)[self.index()] = None };
+    }
+}
+
+/// Returns true if any claimed stream is enabled, so a transfer is still going. STOP mode would
+/// pause it.
+#[inline(never)]
+pub fn any_enabled() -> bool {
+    let claimed = unsafe { *cpu::local!(mut CLAIMED) };
+    (0..16u8).filter(|i| claimed & (1 << i) != 0).any(|i| {
+        let dma = if i < 8 { DMA1 } else { DMA2 };
+        Stream { dma, num: i % 8 }.is_enabled()
+    })
+}
+
+/// Handles the interrupt for stream `num` of `dma`. Call this from the
+/// interrupt handler of each stream that uses a callback.
+#[inline(never)]
+pub fn handle_irq(dma: *mut cpu::DmaReg, num: u8) {
+    let stream = Stream { dma, num };
+    let callback = unsafe { cpu::local!(mut CALLBACKS)[stream.index()] };
+
+    // any other event left keeps the interrupt pending so it comes back
+    if let Some(event) = stream.poll() {
+        if let Some(callback) = callback {
+            callback(stream, event);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! # DWT Module
+//!
+//! This module counts CPU cycles with the cycle counter (CYCCNT) of the Data Watchpoint and
+//! Trace unit in the Cortex-M4. At 168 MHz a cycle is about 6 ns, so it can time code that is
+//! far too short for the 1 us ticks of `timer`, such as drawing one glyph.
+//!
+//! The counter is 32 bits and wraps about every 25 seconds at 168 MHz. Taking the difference of
+//! two counts with `wrapping_sub`, as `measure` and `since` do, is right as long as the code
+//! timed is shorter than that.
+//!
+//! In the simulator the count is worked out from the host clock and `clock::hclk`, so it is
+//! only a rough guide to the count on the chip, but it shows which code is slowest.
+//!
+//! ## Functions
+//!
+//! - `init`: Turns on the trace unit and starts the cycle counter from 0.
+//! - `cycles`: Returns the cycle counter.
+//! - `since`: Returns the cycles since an earlier count.
+//! - `measure`: Runs a closure and returns how many cycles it took.
+//! - `to_nanos`: Converts a number of cycles to nanoseconds.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::dwt;
+//!
+//! hal::init(16_000_000);
+//! dwt::init();
+//!
+//! let (sum, cycles) = dwt::measure(|| (0..100u32).sum::<u32>());
+//! assert_eq!(sum, 4950);
+//! let ns = dwt::to_nanos(cycles);
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // host time the simulated counter started from
+    static START: core::cell::Cell<Option<std::time::Instant>> = const { core::cell::Cell::new(None) };
+}
+
+#[inline(never)]
+/// Turns on the trace unit and starts the cycle counter from 0.
+pub fn init() {
+    // the DWT registers do nothing until trace is enabled
+    cpu::write!(DCB.demcr[TRCENA;1], 1);
+
+    if cpu::read!(DWT.ctrl[NOCYCCNT;1]) != 0 {
+        panic!("DWT has no cycle counter");
+    }
+
+    cpu::write!(DWT.cyccnt, 0);
+    cpu::write!(DWT.ctrl[CYCCNTENA;1], 1);
+
+    #[cfg(feature = "std")]
+    START.set(Some(std::time::Instant::now()));
+}
+
+#[cfg(not(feature = "std"))]
+#[inline(always)]
+/// Returns the cycle counter. It stays at 0 until `init` is called.
+pub fn cycles() -> u32 {
+    cpu::read!(DWT.cyccnt)
+}
+
+#[cfg(feature = "std")]
+/// Returns the cycle counter. It stays at 0 until `init` is called.
+pub fn cycles() -> u32 {
+    match START.get() {
+        Some(start) if cpu::read!(DWT.ctrl[CYCCNTENA;1]) != 0 => {
+            let cycles = start.elapsed().as_nanos() * clock::hclk() as u128 / 1_000_000_000;
+            cycles as u32
+        }
+        _ => cpu::read!(DWT.cyccnt),
+    }
+}
+
+#[inline(always)]
+/// Returns the number of cycles since `start`, a value from `cycles`.
+pub fn since(start: u32) -> u32 {
+    cycles().wrapping_sub(start)
+}
+
+#[inline(always)]
+/// Runs `f` and returns its result with the number of cycles it took.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, u32) {
+    let start = cycles();
+    let result = f();
+    (result, since(start))
+}
+
+/// Converts a number of cycles at the current CPU clock to nanoseconds.
+pub fn to_nanos(cycles: u32) -> u64 {
+    cycles as u64 * 1_000_000_000 / clock::hclk() as u64
+}
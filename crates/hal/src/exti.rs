@@ -0,0 +1,200 @@
+//! # EXTI Module
+//!
+//! This module runs a callback when a GPIO pin changes, using the external interrupt (EXTI)
+//! controller.
+//!
+//! Each pin number has one EXTI line, shared by all the ports, so PA3 and PC3 can not both be
+//! used at once. EXTI interrupts also wake the CPU from sleep and STOP mode.
+//!
+//! ## Enums
+//!
+//! - `Edge`: Which change of the pin runs the callback.
+//!
+//! ## Functions
+//!
+//! - `listen`: Connects a pin to its EXTI line and runs a callback on the chosen edges.
+//! - `unlisten`: Stops the callback for a pin.
+//! - `handle_irq`: Runs the callbacks for the lines that are pending, call from the EXTI
+//!   interrupt handlers.
+//!
+//! ## Usage
+//!
+//! Set the pin up as an input first. The callback runs in the interrupt, so should be short, and
+//! it is given the line number so one function can serve several pins.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::exti::{self, Edge};
+//! use hal::gpio;
+//!
+//! fn on_button(_line: u8) {
+//!     // note the time of the press
+//! }
+//!
+//! hal::init(16_000_000);
+//! let button = gpio::Pin(cpu::GPIOC, 0);
+//! button.input();
+//! button.pullup();
+//! exti::listen(button, Edge::Falling, on_button);
+//! ```
+//!
+
+use core::ptr;
+
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+use super::gpio;
+use super::interrupt::{self, Irq};
+
+/// Number of EXTI lines that can be connected to GPIO pins.
+pub const NUM_LINES: usize = 16;
+
+// distance between the GPIO ports in memory
+const PORT_SIZE: usize = 0x400;
+
+/// Function run when a line triggers, with the line number.
+pub type Callback = fn(line: u8);
+
+/// Which change of the pin triggers the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Low to high.
+    Rising,
+    /// High to low.
+    Falling,
+    /// Both directions.
+    Both,
+}
+
+cpu::local! {
+    static mut CALLBACKS: [Option<Callback>; NUM_LINES] = [None; NUM_LINES];
+}
+
+#[cfg(feature = "stm32f405")]
+// the interrupt for a line, lines 5 to 9 and 10 to 15 share one
+fn irq(line: u8) -> Irq {
+    match line {
+        0 => Irq::Exti0,
+        1 => Irq::Exti1,
+        2 => Irq::Exti2,
+        3 => Irq::Exti3,
+        4 => Irq::Exti4,
+        5..=9 => Irq::Exti9_5,
+        _ => Irq::Exti15_10,
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+// the interrupt for a line, the lines share them in groups
+fn irq(line: u8) -> Irq {
+    match line {
+        0..=1 => Irq::Exti0_1,
+        2..=3 => Irq::Exti2_3,
+        _ => Irq::Exti4_15,
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+// connects a line to the pin of the same number on a port, 0 for port A
+fn select_port(line: u8, port: u32) {
+    match line / 4 {
+        0 => {
+            cpu::write!(SYSCFG.exticr1[(line % 4) * 4;4], port);
+        }
+        1 => {
+            cpu::write!(SYSCFG.exticr2[(line % 4) * 4;4], port);
+        }
+        2 => {
+            cpu::write!(SYSCFG.exticr3[(line % 4) * 4;4], port);
+        }
+        _ => {
+            cpu::write!(SYSCFG.exticr4[(line % 4) * 4;4], port);
+        }
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+// connects a line to the pin of the same number on a port, 0 for port A
+fn select_port(line: u8, port: u32) {
+    match line / 4 {
+        0 => {
+            cpu::write!(SYSCFG_COMP.exticr1[(line % 4) * 4;4], port);
+        }
+        1 => {
+            cpu::write!(SYSCFG_COMP.exticr2[(line % 4) * 4;4], port);
+        }
+        2 => {
+            cpu::write!(SYSCFG_COMP.exticr3[(line % 4) * 4;4], port);
+        }
+        _ => {
+            cpu::write!(SYSCFG_COMP.exticr4[(line % 4) * 4;4], port);
+        }
+    }
+}
+
+#[inline(never)]
+/// Runs `callback` when `pin` changes in the direction of `edge`. Replaces any callback already
+/// on the line of the pin, which may be for a pin on another port.
+pub fn listen(pin: gpio::Pin, edge: Edge, callback: Callback) {
+    let line = pin.1;
+    if line as usize >= NUM_LINES {
+        panic!("EXTI line out of range");
+    }
+    let port = (pin.0 as usize - GPIOA as usize) / PORT_SIZE;
+
+    // SYSCFG has the port select registers
+    cpu::write!(RCC.apb2enr[SYSCFGEN;1], 1);
+
+    interrupt::free(|_cs| {
+        unsafe { cpu::local!(mut CALLBACKS)[line as usize] = Some(callback) };
+
+        select_port(line, port as u32);
+
+        let rising = edge != Edge::Falling;
+        let falling = edge != Edge::Rising;
+        cpu::write!(EXTI.rtsr[pin.1;1], rising as u32);
+        cpu::write!(EXTI.ftsr[pin.1;1], falling as u32);
+
+        // an edge from before now should not run the callback
+        cpu::write!(EXTI.pr, 1 << line);
+        cpu::write!(EXTI.imr[pin.1;1], 1);
+    });
+
+    interrupt::enable(irq(line));
+}
+
+#[inline(never)]
+/// Stops running the callback for the line of `pin`. The interrupt stays enabled in the NVIC as
+/// other lines may share it.
+pub fn unlisten(pin: gpio::Pin) {
+    let line = pin.1;
+    interrupt::free(|_cs| {
+        cpu::write!(EXTI.imr[pin.1;1], 0);
+        cpu::write!(EXTI.pr, 1 << line);
+        unsafe { cpu::local!(mut CALLBACKS)[line as usize] = None };
+    });
+}
+
+#[inline(never)]
+/// Runs the callbacks for the lines that have triggered. Call this from all the EXTI interrupt
+/// handlers, it works out the lines from the pending register.
+pub fn handle_irq() {
+    let pending = cpu::read!(EXTI.pr) & cpu::read!(EXTI.imr);
+
+    for line in 0..NUM_LINES as u8 {
+        if pending & (1 << line) == 0 {
+            continue;
+        }
+
+        // the pending bits are cleared by writing a 1
+        cpu::write!(EXTI.pr, 1 << line);
+
+        let callback = interrupt::free(|_cs| unsafe { cpu::local!(mut CALLBACKS)[line as usize] });
+        if let Some(callback) = callback {
+            callback(line);
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! # Flash Module
+//!
+//! This module erases and programs the internal flash, so data can be kept across power cycles.
+//!
+//! Flash bits can only be changed from 1 to 0 by programming. Erasing sets a whole sector (STM32F405)
+//! or page (STM32F072) back to all 1s. The flash is locked after reset, and has to be unlocked
+//! before it can be changed.
+//!
+//! While the flash is being erased or programmed any read of it stalls the CPU, including fetching
+//! code, so interrupts are delayed. Erasing a 128K sector of the STM32F405 takes one to two
+//! seconds, so the watchdog may need to be fed first.
+//!
+//! On the STM32F405 the size of each write is limited by the supply voltage, set with
+//! `set_parallelism`. The STM32F072 always programs half words.
+//!
+//! In the simulator the flash is an image in memory that starts erased and is kept until the
+//! thread exits, so each test has its own.
+//!
+//! ## Enums
+//!
+//! - `Error`: The reasons an erase or program failed.
+//! - `Parallelism`: The largest write the flash takes at the supply voltage (STM32F405 only).
+//!
+//! ## Functions
+//!
+//! - `unlock`: Allows the flash to be erased and programmed.
+//! - `lock`: Stops the flash being changed.
+//! - `is_locked`: Returns true if the flash is locked.
+//! - `set_parallelism`: Sets the largest write for the supply voltage (STM32F405 only).
+//! - `sector_address`: Returns the start address of a sector (STM32F405 only).
+//! - `sector_size`: Returns the size of a sector in bytes (STM32F405 only).
+//! - `erase_sector`: Erases a sector (STM32F405 only).
+//! - `page_address`: Returns the start address of a page (STM32F072 only).
+//! - `erase_page`: Erases a page (STM32F072 only).
+//! - `program_half_word`: Programs 16 bits.
+//! - `program_word`: Programs 32 bits.
+//! - `read`: Copies bytes out of the flash.
+//!
+//! ## Usage
+//!
+//! Keep data in a sector or page the firmware does not use, erase it before programming, and
+//! lock the flash again when done.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::flash;
+//!
+//! hal::init(16_000_000);
+//!
+//! let addr = flash::sector_address(11);
+//! flash::unlock();
+//! flash::erase_sector(11).unwrap();
+//! flash::program_word(addr, 0x1234_5678).unwrap();
+//! flash::lock();
+//!
+//! let mut buf = [0u8; 4];
+//! flash::read(addr, &mut buf);
+//! assert_eq!(u32::from_le_bytes(buf), 0x1234_5678);
+//! ```
+//!
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+
+/// Address of the start of the flash.
+pub const BASE: u32 = 0x0800_0000;
+
+#[cfg(feature = "stm32f405")]
+/// Size of the flash in bytes.
+pub const SIZE: usize = 1024 * 1024;
+
+#[cfg(feature = "stm32f072")]
+/// Size of the flash in bytes.
+pub const SIZE: usize = 128 * 1024;
+
+#[cfg(feature = "stm32f405")]
+/// Number of sectors in the flash.
+pub const NUM_SECTORS: u8 = 12;
+
+#[cfg(feature = "stm32f072")]
+/// Size of a page in bytes.
+pub const PAGE_SIZE: usize = 2 * 1024;
+
+#[cfg(feature = "stm32f072")]
+/// Number of pages in the flash.
+pub const NUM_PAGES: u16 = (SIZE / PAGE_SIZE) as u16;
+
+// keys written to KEYR to unlock the flash
+const KEY1: u32 = 0x4567_0123;
+const KEY2: u32 = 0xCDEF_89AB;
+
+/// Reasons an erase or program failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The flash has not been unlocked.
+    Locked,
+    /// The address, sector or page is not in the flash.
+    OutOfRange,
+    /// The address is not aligned to the size of the write.
+    Alignment,
+    /// The sector or page is write protected by the option bytes.
+    WriteProtected,
+    /// The write was larger than the parallelism (STM32F405 only).
+    Parallelism,
+    /// The program sequence was wrong (STM32F405 only).
+    Sequence,
+    /// The operation did not complete (STM32F405 only).
+    Operation,
+    /// The location was not erased before programming (STM32F072 only).
+    NotErased,
+}
+
+#[cfg(feature = "stm32f405")]
+/// Largest write the flash can take, which depends on the supply voltage. See table 7 of RM0090.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parallelism {
+    /// Bytes, for 1.8 V to 2.1 V.
+    X8 = 0b00,
+    /// Half words, for 2.1 V to 2.7 V.
+    X16 = 0b01,
+    /// Words, for 2.7 V to 3.6 V.
+    X32 = 0b10,
+}
+
+#[cfg(feature = "stm32f405")]
+// boards run from 3.3 V
+cpu::local! {
+    static mut PARALLELISM: Parallelism = Parallelism::X32;
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // contents of the flash in the simulator, erased when first used
+    static IMAGE: core::cell::RefCell<Option<std::vec::Vec<u8>>> = const { core::cell::RefCell::new(None) };
+}
+
+#[inline(never)]
+/// Unlocks the flash so it can be erased and programmed.
+pub fn unlock() {
+    if cpu::read!(FLASH.cr[LOCK;1]) == 0 {
+        // writing the keys again while unlocked locks up the flash until reset
+        return;
+    }
+
+    cpu::write!(FLASH.keyr, KEY1);
+    cpu::write!(FLASH.keyr, KEY2);
+
+    // the simulator does not check the keys
+    #[cfg(feature = "std")]
+    {
+        cpu::write!(FLASH.cr[LOCK;1], 0);
+    }
+}
+
+#[inline(never)]
+/// Locks the flash. It stays locked until `unlock` is called.
+pub fn lock() {
+    cpu::write!(FLASH.cr[LOCK;1], 1);
+}
+
+/// Returns true if the flash is locked.
+pub fn is_locked() -> bool {
+    cpu::read!(FLASH.cr[LOCK;1]) == 1
+}
+
+#[cfg(feature = "stm32f405")]
+/// Sets the largest write used for the supply voltage. Larger writes are split up, and erasing
+/// is slower at lower parallelism.
+pub fn set_parallelism(parallelism: Parallelism) {
+    unsafe { *cpu::local!(mut PARALLELISM) = parallelism };
+}
+
+#[cfg(feature = "stm32f405")]
+/// Returns the start address of `sector`. Sectors 0 to 3 are 16K, sector 4 is 64K, and the rest
+/// are 128K.
+pub fn sector_address(sector: u8) -> u32 {
+    match sector {
+        0..=3 => BASE + sector as u32 * 0x4000,
+        4 => BASE + 0x1_0000,
+        5..=11 => BASE + (sector as u32 - 4) * 0x2_0000,
+        _ => panic!("Flash sector out of range"),
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// Returns the size of `sector` in bytes.
+pub fn sector_size(sector: u8) -> usize {
+    match sector {
+        0..=3 => 0x4000,
+        4 => 0x1_0000,
+        5..=11 => 0x2_0000,
+        _ => panic!("Flash sector out of range"),
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+/// Returns the start address of `page`.
+pub fn page_address(page: u16) -> u32 {
+    if page >= NUM_PAGES {
+        panic!("Flash page out of range");
+    }
+    BASE + page as u32 * PAGE_SIZE as u32
+}
+
+// waits for an erase or program to finish
+fn wait_ready() {
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(FLASH.sr[BSY;1]) == 1 {}
+}
+
+#[cfg(feature = "stm32f405")]
+// error flags in SR, cleared by writing a 1
+const ERROR_FLAGS: u32 = (1 << FLASH::sr::OPERR)
+    | (1 << FLASH::sr::WRPERR)
+    | (1 << FLASH::sr::PGAERR)
+    | (1 << FLASH::sr::PGPERR)
+    | (1 << FLASH::sr::PGSERR);
+
+#[cfg(feature = "stm32f072")]
+// error flags in SR, cleared by writing a 1
+const ERROR_FLAGS: u32 = (1 << FLASH::sr::WRPRT) | (1 << FLASH::sr::PGERR);
+
+// clears the flags left from an earlier operation
+fn clear_flags() {
+    let sr = cpu::read!(FLASH.sr) & (ERROR_FLAGS | (1 << FLASH::sr::EOP));
+    if sr != 0 {
+        cpu::write!(FLASH.sr, sr);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+// turns the error flags of the last operation into an error
+fn check_errors() -> Result<(), Error> {
+    let sr = cpu::read!(FLASH.sr);
+    if sr & (1 << FLASH::sr::WRPERR) != 0 {
+        Err(Error::WriteProtected)
+    } else if sr & (1 << FLASH::sr::PGAERR) != 0 {
+        Err(Error::Alignment)
+    } else if sr & (1 << FLASH::sr::PGPERR) != 0 {
+        Err(Error::Parallelism)
+    } else if sr & (1 << FLASH::sr::PGSERR) != 0 {
+        Err(Error::Sequence)
+    } else if sr & (1 << FLASH::sr::OPERR) != 0 {
+        Err(Error::Operation)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+// turns the error flags of the last operation into an error
+fn check_errors() -> Result<(), Error> {
+    let sr = cpu::read!(FLASH.sr);
+    if sr & (1 << FLASH::sr::WRPRT) != 0 {
+        Err(Error::WriteProtected)
+    } else if sr & (1 << FLASH::sr::PGERR) != 0 {
+        Err(Error::NotErased)
+    } else {
+        Ok(())
+    }
+}
+
+// checks a write of `size` bytes at `addr` can be done
+fn check_write(addr: u32, size: u32) -> Result<(), Error> {
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+    if addr < BASE || addr - BASE > SIZE as u32 - size {
+        return Err(Error::OutOfRange);
+    }
+    if !addr.is_multiple_of(size) {
+        return Err(Error::Alignment);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "std"))]
+// writes `size` bytes of `data` to the flash, PG must be set
+fn write_flash(addr: u32, data: u32, size: u32) {
+    unsafe {
+        match size {
+            1 => ptr::write_volatile(addr as *mut u8, data as u8),
+            2 => ptr::write_volatile(addr as *mut u16, data as u16),
+            _ => ptr::write_volatile(addr as *mut u32, data),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+// runs `f` on the simulated flash, erasing it the first time
+fn with_image<R>(f: impl FnOnce(&mut [u8]) -> R) -> R {
+    IMAGE.with_borrow_mut(|image| f(image.get_or_insert_with(|| std::vec![0xFF; SIZE])))
+}
+
+#[cfg(feature = "std")]
+// programs the simulated flash, which like the real one can only clear bits
+fn write_flash(addr: u32, data: u32, size: u32) {
+    let offset = (addr - BASE) as usize;
+    let bytes = data.to_le_bytes();
+    let erased = with_image(|image| {
+        let old = &mut image[offset..offset + size as usize];
+        let erased = old.iter().all(|b| *b == 0xFF);
+        for (old, new) in old.iter_mut().zip(bytes) {
+            *old &= new;
+        }
+        erased
+    });
+
+    // the STM32F072 refuses to program a half word that is not erased
+    #[cfg(feature = "stm32f072")]
+    if !erased {
+        cpu::write!(FLASH.sr[PGERR;1], 1);
+    }
+    #[cfg(feature = "stm32f405")]
+    let _ = erased;
+}
+
+#[cfg(feature = "std")]
+// sets `size` bytes of the simulated flash at `addr` back to 1s
+fn sim_erase(addr: u32, size: usize) {
+    let offset = (addr - BASE) as usize;
+    with_image(|image| image[offset..offset + size].fill(0xFF));
+}
+
+#[cfg(feature = "stm32f405")]
+// flushes the data cache, which can hold what was in the flash before an erase
+fn reset_data_cache() {
+    if cpu::read!(FLASH.acr[DCEN;1]) == 1 {
+        cpu::write!(FLASH.acr[DCEN;1], 0);
+        cpu::write!(FLASH.acr[DCRST;1], 1);
+        cpu::write!(FLASH.acr[DCRST;1], 0);
+        cpu::write!(FLASH.acr[DCEN;1], 1);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+/// Erases `sector`, setting every byte in it to 0xFF. Blocks until the erase is done.
+pub fn erase_sector(sector: u8) -> Result<(), Error> {
+    if sector >= NUM_SECTORS {
+        return Err(Error::OutOfRange);
+    }
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+
+    wait_ready();
+    clear_flags();
+
+    let parallelism = unsafe { *cpu::local!(mut PARALLELISM) };
+    cpu::write!(FLASH.cr[PSIZE;2], parallelism as u32);
+    cpu::write!(FLASH.cr[SNB;4], sector as u32);
+    cpu::write!(FLASH.cr[SER;1], 1);
+    cpu::write!(FLASH.cr[STRT;1], 1);
+
+    #[cfg(feature = "std")]
+    sim_erase(sector_address(sector), sector_size(sector));
+
+    wait_ready();
+    cpu::write!(FLASH.cr[SER;1], 0);
+
+    reset_data_cache();
+
+    check_errors()
+}
+
+#[cfg(feature = "stm32f072")]
+#[inline(never)]
+/// Erases `page`, setting every byte in it to 0xFF. Blocks until the erase is done.
+pub fn erase_page(page: u16) -> Result<(), Error> {
+    if page >= NUM_PAGES {
+        return Err(Error::OutOfRange);
+    }
+    if is_locked() {
+        return Err(Error::Locked);
+    }
+
+    wait_ready();
+    clear_flags();
+
+    cpu::write!(FLASH.cr[PER;1], 1);
+    cpu::write!(FLASH.ar, page_address(page));
+    cpu::write!(FLASH.cr[STRT;1], 1);
+
+    #[cfg(feature = "std")]
+    sim_erase(page_address(page), PAGE_SIZE);
+
+    wait_ready();
+    cpu::write!(FLASH.cr[PER;1], 0);
+
+    check_errors()
+}
+
+#[cfg(feature = "stm32f405")]
+// programs `size` bytes of `data`, which must be no more than the parallelism
+fn program(addr: u32, data: u32, size: u32) -> Result<(), Error> {
+    wait_ready();
+    clear_flags();
+
+    cpu::write!(FLASH.cr[PSIZE;2], size.trailing_zeros());
+    cpu::write!(FLASH.cr[PG;1], 1);
+    write_flash(addr, data, size);
+    wait_ready();
+    cpu::write!(FLASH.cr[PG;1], 0);
+
+    check_errors()
+}
+
+#[cfg(feature = "stm32f072")]
+// programs `size` bytes of `data`, which must be a half word
+fn program(addr: u32, data: u32, size: u32) -> Result<(), Error> {
+    wait_ready();
+    clear_flags();
+
+    cpu::write!(FLASH.cr[PG;1], 1);
+    write_flash(addr, data, size);
+    wait_ready();
+    cpu::write!(FLASH.cr[PG;1], 0);
+
+    check_errors()
+}
+
+// largest write the flash takes in one go
+fn max_write() -> u32 {
+    #[cfg(feature = "stm32f405")]
+    {
+        1 << unsafe { *cpu::local!(mut PARALLELISM) } as u32
+    }
+    #[cfg(feature = "stm32f072")]
+    {
+        2
+    }
+}
+
+// programs `size` bytes at `addr`, split into writes the flash can take
+fn program_split(addr: u32, data: u32, size: u32) -> Result<(), Error> {
+    check_write(addr, size)?;
+
+    let step = size.min(max_write());
+    for i in (0..size).step_by(step as usize) {
+        let part = data >> (i * 8);
+        program(addr + i, part, step)?;
+    }
+    Ok(())
+}
+
+#[inline(never)]
+/// Programs the half word at `addr`, which must be erased and 2 byte aligned.
+pub fn program_half_word(addr: u32, data: u16) -> Result<(), Error> {
+    program_split(addr, data as u32, 2)
+}
+
+#[inline(never)]
+/// Programs the word at `addr`, which must be erased and 4 byte aligned. When the parallelism is
+/// less than a word this is several writes, and an error can leave part of it programmed.
+pub fn program_word(addr: u32, data: u32) -> Result<(), Error> {
+    program_split(addr, data, 4)
+}
+
+#[inline(never)]
+/// Copies `buf.len()` bytes from the flash at `addr` into `buf`.
+pub fn read(addr: u32, buf: &mut [u8]) {
+    if addr < BASE || (addr - BASE) as usize + buf.len() > SIZE {
+        panic!("Flash read out of range");
+    }
+
+    #[cfg(not(feature = "std"))]
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = unsafe { ptr::read_volatile((addr as usize + i) as *const u8) };
+    }
+
+    #[cfg(feature = "std")]
+    {
+        let offset = (addr - BASE) as usize;
+        with_image(|image| buf.copy_from_slice(&image[offset..offset + buf.len()]));
+    }
+}
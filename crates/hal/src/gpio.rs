@@ -18,6 +18,7 @@
 //! - `new`: Creates a new `Pin` instance.
 //! - `output`: Configures the pin as an output.
 //! - `input`: Configures the pin as an input with a pull-down resistor.
+//! - `analog`: Configures the pin as an analog input for the ADC.
 //! - `pulldown`: Configures the pin with a pull-down resistor.
 //! - `pullup`: Configures the pin with a pull-up resistor.
 //! - `low`: Sets the pin state to low.
@@ -40,7 +41,7 @@
 //! fn main() {
 //!     // Initialize things
 //!     cpu::init();
-//!     clock::init( 16_000_000 );
+//!     clock::init(&clock::ClockConfig::new(16_000_000));
 //!     gpio::init();
 //!
 //!     // Create a new pin instance
@@ -143,6 +144,20 @@ impl Pin {
         }
     }
 
+    #[inline(never)]
+    pub fn alt_fun_open_drain(&self, af_mode: u8) {
+        let gpio = self.0;
+        let pin_num = self.1;
+
+        self.alt_fun(af_mode, false);
+
+        // Set output type to open-drain
+        cpu::write!(gpio.otyper[pin_num * 1; 1], 0b1);
+
+        // set  pull up
+        cpu::write!( gpio.pupdr[pin_num*2;2], 0b01);
+    }
+
     #[inline(never)]
     pub fn open_drain(&self) {
         let gpio = self.0;
@@ -176,6 +191,18 @@ impl Pin {
         cpu::write!( gpio.moder[pin_num*2;2], 0b00);
     }
 
+    #[inline(never)]
+    pub fn analog(&self) {
+        let gpio = self.0;
+        let pin_num = self.1;
+
+        // set no pull up , no pull down
+        cpu::write!( gpio.pupdr[pin_num*2;2], 0b00);
+
+        // set mode to analog
+        cpu::write!( gpio.moder[pin_num*2;2], 0b11);
+    }
+
     #[inline(never)]
     #[allow(dead_code)]
     pub fn pulldown(&self) {
@@ -228,37 +255,57 @@ mod tests {
 
     #[test]
     fn test_pin_output() {
+        cpu::init();
         let gpio = 0x40020000 as *mut cpu::GpioReg; // Example GPIO base address
         let pin = Pin::new(gpio, 5);
         pin.output();
 
         // Verify that the pin is set to output mode
-        assert_eq!(cpu::read!(gpio.moder[5 * 2; 2]), 0b00);
+        assert_eq!(cpu::read!(gpio.moder[5 * 2; 2]), 0b01);
 
         // Verify that the pin is set to low
         assert_eq!(cpu::read!(gpio.odr[5 * 1; 1]), 0b0);
+        assert_eq!(pin.read(), false);
+
+        // BSRR sets and resets the output
+        pin.high();
+        assert_eq!(cpu::read!(gpio.odr[5 * 1; 1]), 0b1);
+        assert_eq!(pin.read(), true);
+        pin.low();
+        assert_eq!(cpu::read!(gpio.odr[5 * 1; 1]), 0b0);
     }
 
     #[test]
     fn test_pin_input() {
+        cpu::init();
         let gpio = 0x40020000 as *mut cpu::GpioReg; // Example GPIO base address
         let pin = Pin::new(gpio, 5);
         pin.input();
 
         // Verify that the pin is set to input mode
         assert_eq!(cpu::read!(gpio.moder[5 * 2; 2]), 0b00);
+        assert_eq!(cpu::read!(gpio.pupdr[5 * 2; 2]), 0b10);
     }
 
     #[test]
     fn test_pin_read() {
+        cpu::init();
         let gpio = 0x40020000 as *mut cpu::GpioReg; // Example GPIO base address
         let pin = Pin::new(gpio, 5);
+        pin.input();
 
-        // Simulate pin state
-        cpu::write!(gpio.idr[5 * 1; 1], 1);
+        // the pull down holds the pin low until something drives it
         assert_eq!(pin.read(), false);
 
-        cpu::write!(gpio.idr[5 * 1; 1], 0);
+        // Simulate pin state
+        crate::sim::set_pin(gpio, 5, true);
+        assert_eq!(pin.read(), true);
+
+        crate::sim::set_pin(gpio, 5, false);
         assert_eq!(!pin.read(), true);
+
+        // IDR can not be written
+        cpu::write!(gpio.idr[5 * 1; 1], 1);
+        assert_eq!(pin.read(), false);
     }
 }
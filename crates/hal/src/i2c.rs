@@ -0,0 +1,469 @@
+//! # I2C Module
+//!
+//! This module provides a blocking I2C master driver used to talk to devices
+//! such as the M24C02 EEPROM and the WM8960 audio codec.
+//!
+//! The STM32F405 has the older I2C peripheral (START/ADDR/BTF event
+//! sequence) while the STM32F072 has the newer one where the transfer length
+//! and start condition are programmed into CR2 in one go. Both present the same
+//! API.
+//!
+//! ## Structures
+//!
+//! - `I2c`: Represents one I2C peripheral and provides methods to use it.
+//! - `Error`: The reasons an I2C transaction can fail.
+//!
+//! ## Methods for `I2c`
+//!
+//! - `init`: Sets up the clock, pins and bus speed (100 kHz or 400 kHz).
+//! - `write`: Writes bytes to a device.
+//! - `read`: Reads bytes from a device.
+//! - `write_read`: Writes bytes then reads bytes using a repeated start.
+//!
+//! All transactions are blocking and give up with `Error::Timeout` if the
+//! peripheral gets stuck rather than hanging the system.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::gpio;
+//! use hal::i2c;
+//!
+//! hal::init(16_000_000);
+//!
+//! let scl = gpio::Pin(cpu::GPIOB, 6);
+//! let sda = gpio::Pin(cpu::GPIOB, 7);
+//!
+//! let eeprom = i2c::I2c(cpu::I2C1);
+//! eeprom.init(100_000, scl, sda);
+//!
+//! let mut data = [0u8; 4];
+//! let result = eeprom.write_read(0x50, &[0x00], &mut data);
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+use super::gpio;
+
+// lets `i2c.reg[FIELD;w]` find the field offsets in the I2C register module
+use super::cpu::I2C as i2c;
+
+/// Number of status polls before a transaction is abandoned.
+const TIMEOUT_LOOPS: u32 = 100_000;
+
+/// Reasons an I2C transaction can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The device did not acknowledge its address or a data byte.
+    Nack,
+    /// A misplaced start or stop condition was seen on the bus.
+    BusError,
+    /// Another master won the bus.
+    ArbitrationLost,
+    /// The peripheral did not get to the expected state in time.
+    Timeout,
+}
+
+/// An I2C peripheral, for example `I2c(cpu::I2C1)`.
+#[derive(Copy, Clone)]
+pub struct I2c(pub *mut cpu::I2cReg);
+
+#[cfg(feature = "stm32f405")]
+impl I2c {
+    /// Initializes the I2C peripheral as a master at `speed` Hz.
+    #[inline(never)]
+    pub fn init(&self, speed: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
+        let i2c = self.0;
+
+        // enable clock for the I2C
+        if ptr::eq(i2c, I2C1) {
+            cpu::write!(RCC.apb1enr[I2C1EN;1], 1);
+        } else if ptr::eq(i2c, I2C2) {
+            cpu::write!(RCC.apb1enr[I2C2EN;1], 1);
+        } else if ptr::eq(i2c, I2C3) {
+            cpu::write!(RCC.apb1enr[I2C3EN;1], 1);
+        } else {
+            panic!("Unknown I2C");
+        }
+
+        // AF4 is I2C1 to I2C3 on every pin, see table 9 of the datasheet
+        scl_pin.alt_fun_open_drain(4);
+        sda_pin.alt_fun_open_drain(4);
+
+        // reset the peripheral in case a previous transfer left it busy
+        cpu::write!(i2c.cr1[SWRST;1], 1);
+        cpu::write!(i2c.cr1[SWRST;1], 0);
+
+        cpu::write!(i2c.cr1[PE;1], 0); // must be disabled to set timing
+
+        // I2C is on APB1 bus
+        let apb_freq: u32 = clock::pclk1();
+        let apb_freq_mhz: u32 = apb_freq / 1_000_000;
+        cpu::write!(i2c.cr2[FREQ;6], apb_freq_mhz);
+
+        match speed {
+            100_000 => {
+                // standard mode, SCL high and low are each CCR clocks
+                cpu::write!(i2c.ccr[F_S;1], 0);
+                cpu::write!(i2c.ccr[CCR;12], apb_freq / (2 * speed));
+
+                // max rise time is 1000 ns
+                cpu::write!(i2c.trise[TRISE;6], apb_freq_mhz + 1);
+            }
+            400_000 => {
+                // fast mode with duty 2, SCL low is twice SCL high
+                cpu::write!(i2c.ccr[F_S;1], 1);
+                cpu::write!(i2c.ccr[DUTY;1], 0);
+                cpu::write!(i2c.ccr[CCR;12], apb_freq / (3 * speed));
+
+                // max rise time is 300 ns
+                cpu::write!(i2c.trise[TRISE;6], apb_freq_mhz * 300 / 1000 + 1);
+            }
+            _ => {
+                panic!("I2C speed not supported");
+            }
+        }
+
+        cpu::write!(i2c.cr1[PE;1], 1); // enable I2C
+    }
+
+    /// Writes `data` to the device at 7 bit address `addr`.
+    #[inline(never)]
+    pub fn write(&self, addr: u8, data: &[u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, false)?;
+        clear_addr(i2c);
+        write_bytes(i2c, data)?;
+        cpu::write!(i2c.cr1[STOP;1], 1);
+
+        Ok(())
+    }
+
+    /// Reads `data.len()` bytes from the device at 7 bit address `addr`.
+    #[inline(never)]
+    pub fn read(&self, addr: u8, data: &mut [u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, true)?;
+        read_bytes(i2c, data)?;
+
+        Ok(())
+    }
+
+    /// Writes `wr_data` then reads into `rd_data` using a repeated start so
+    /// no other master can get the bus in between. This is how a register or
+    /// memory address is selected then read back.
+    #[inline(never)]
+    pub fn write_read(&self, addr: u8, wr_data: &[u8], rd_data: &mut [u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, false)?;
+        clear_addr(i2c);
+        write_bytes(i2c, wr_data)?;
+
+        start(i2c, addr, true)?;
+        read_bytes(i2c, rd_data)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+fn check_errors(i2c: *mut cpu::I2cReg) -> Result<(), Error> {
+    // the error flags are cleared by writing 0
+    if cpu::read!(i2c.sr1[AF;1]) == 1 {
+        cpu::write!(i2c.sr1[AF;1], 0);
+        cpu::write!(i2c.cr1[STOP;1], 1); // release the bus
+        return Err(Error::Nack);
+    }
+    if cpu::read!(i2c.sr1[BERR;1]) == 1 {
+        cpu::write!(i2c.sr1[BERR;1], 0);
+        cpu::write!(i2c.cr1[STOP;1], 1); // release the bus
+        return Err(Error::BusError);
+    }
+    if cpu::read!(i2c.sr1[ARLO;1]) == 1 {
+        // hardware has already released the bus
+        cpu::write!(i2c.sr1[ARLO;1], 0);
+        return Err(Error::ArbitrationLost);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "stm32f405")]
+fn wait_sr1(i2c: *mut cpu::I2cReg, flag: u8) -> Result<(), Error> {
+    for _ in 0..TIMEOUT_LOOPS {
+        check_errors(i2c)?;
+        if cpu::read!(i2c.sr1) & (1 << flag) != 0 {
+            return Ok(());
+        }
+    }
+    cpu::write!(i2c.cr1[STOP;1], 1); // try to release the bus
+    Err(Error::Timeout)
+}
+
+#[cfg(feature = "stm32f405")]
+fn wait_idle(i2c: *mut cpu::I2cReg) -> Result<(), Error> {
+    for _ in 0..TIMEOUT_LOOPS {
+        if cpu::read!(i2c.sr2[BUSY;1]) == 0 {
+            return Ok(());
+        }
+    }
+    Err(Error::Timeout)
+}
+
+#[cfg(feature = "stm32f405")]
+fn start(i2c: *mut cpu::I2cReg, addr: u8, read: bool) -> Result<(), Error> {
+    cpu::write!(i2c.cr1[START;1], 1);
+    wait_sr1(i2c, i2c::sr1::SB)?;
+
+    cpu::write!(i2c.dr, ((addr as u32) << 1) | (read as u32));
+    wait_sr1(i2c, i2c::sr1::ADDR)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "stm32f405")]
+fn clear_addr(i2c: *mut cpu::I2cReg) {
+    // ADDR is cleared by reading SR1 then SR2
+    let _ = cpu::read!(i2c.sr1);
+    let _ = cpu::read!(i2c.sr2);
+}
+
+#[cfg(feature = "stm32f405")]
+fn write_bytes(i2c: *mut cpu::I2cReg, data: &[u8]) -> Result<(), Error> {
+    // BTF never sets if no byte is sent, such as when probing for a device
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    for &d in data {
+        wait_sr1(i2c, i2c::sr1::TxE)?;
+        cpu::write!(i2c.dr, d as u32);
+    }
+
+    // wait for last byte to be sent
+    wait_sr1(i2c, i2c::sr1::BTF)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "stm32f405")]
+fn read_bytes(i2c: *mut cpu::I2cReg, data: &mut [u8]) -> Result<(), Error> {
+    let len = data.len();
+
+    // a single byte must be NACKed, which has to be set up before ADDR is cleared
+    if len == 1 {
+        cpu::write!(i2c.cr1[ACK;1], 0);
+    } else {
+        cpu::write!(i2c.cr1[ACK;1], 1);
+    }
+    clear_addr(i2c);
+
+    for (i, d) in data.iter_mut().enumerate() {
+        if i + 1 == len {
+            // NACK the last byte then stop
+            cpu::write!(i2c.cr1[ACK;1], 0);
+            cpu::write!(i2c.cr1[STOP;1], 1);
+        }
+        wait_sr1(i2c, i2c::sr1::RxNE)?;
+        *d = cpu::read!(i2c.dr[DR;8]) as u8;
+    }
+
+    if len == 0 {
+        cpu::write!(i2c.cr1[STOP;1], 1);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "stm32f072")]
+impl I2c {
+    /// Initializes the I2C peripheral as a master at `speed` Hz.
+    #[inline(never)]
+    pub fn init(&self, speed: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
+        let i2c = self.0;
+
+        // enable clock for the I2C
+        // I2C1 is clocked from the 8 MHz HSI and I2C2 from the APB
+        let i2c_freq: u32 = if ptr::eq(i2c, I2C1) {
+            cpu::write!(RCC.apb1enr[I2C1EN;1], 1);
+            8_000_000
+        } else if ptr::eq(i2c, I2C2) {
+            cpu::write!(RCC.apb1enr[I2C2EN;1], 1);
+            clock::pclk1()
+        } else {
+            panic!("Unknown I2C");
+        };
+
+        scl_pin.alt_fun_open_drain(af_mode(scl_pin));
+        sda_pin.alt_fun_open_drain(af_mode(sda_pin));
+
+        cpu::write!(i2c.cr1[PE;1], 0); // must be disabled to set timing
+
+        // values from table 83 of RM0091 Reference Manual
+        let timing: u32 = match (i2c_freq, speed) {
+            (8_000_000, 100_000) => 0x1042_0F13,
+            (8_000_000, 400_000) => 0x0031_0309,
+            (48_000_000, 100_000) => 0xB042_0F13,
+            (48_000_000, 400_000) => 0x5033_0309,
+            _ => {
+                panic!("I2C speed not supported");
+            }
+        };
+        cpu::write!(i2c.timingr, timing);
+
+        cpu::write!(i2c.cr1[PE;1], 1); // enable I2C
+    }
+
+    /// Writes `data` to the device at 7 bit address `addr`.
+    #[inline(never)]
+    pub fn write(&self, addr: u8, data: &[u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, data.len(), false, true);
+        write_bytes(i2c, data)?;
+        wait_stop(i2c)?;
+
+        Ok(())
+    }
+
+    /// Reads `data.len()` bytes from the device at 7 bit address `addr`.
+    #[inline(never)]
+    pub fn read(&self, addr: u8, data: &mut [u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, data.len(), true, true);
+        read_bytes(i2c, data)?;
+        wait_stop(i2c)?;
+
+        Ok(())
+    }
+
+    /// Writes `wr_data` then reads into `rd_data` using a repeated start so
+    /// no other master can get the bus in between. This is how a register or
+    /// memory address is selected then read back.
+    #[inline(never)]
+    pub fn write_read(&self, addr: u8, wr_data: &[u8], rd_data: &mut [u8]) -> Result<(), Error> {
+        let i2c = self.0;
+
+        wait_idle(i2c)?;
+        start(i2c, addr, wr_data.len(), false, false);
+        write_bytes(i2c, wr_data)?;
+        wait_isr(i2c, i2c::isr::TC)?;
+
+        start(i2c, addr, rd_data.len(), true, true);
+        read_bytes(i2c, rd_data)?;
+        wait_stop(i2c)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+fn af_mode(pin: gpio::Pin) -> u8 {
+    // see table 14 and 15 "Alternate functions" of the datasheet stm32f072c8.pdf
+    if ptr::eq(pin.0, GPIOA) && (pin.1 == 9 || pin.1 == 10) {
+        4 // I2C1 on PA9, PA10
+    } else if ptr::eq(pin.0, GPIOB) && (pin.1 == 13 || pin.1 == 14) {
+        5 // I2C2 on PB13, PB14
+    } else {
+        1 // I2C1 on PB6 to PB9, I2C2 on PB10, PB11
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+fn check_errors(i2c: *mut cpu::I2cReg) -> Result<(), Error> {
+    if cpu::read!(i2c.isr[NACKF;1]) == 1 {
+        cpu::write!(i2c.icr[NACKCF;1], 1);
+        cpu::write!(i2c.cr2[STOP;1], 1); // release the bus if not AUTOEND
+
+        // clear STOPF so the next transfer does not take it for its own stop
+        for _ in 0..TIMEOUT_LOOPS {
+            if cpu::read!(i2c.isr[STOPF;1]) == 1 {
+                break;
+            }
+        }
+        cpu::write!(i2c.icr[STOPCF;1], 1);
+        return Err(Error::Nack);
+    }
+    if cpu::read!(i2c.isr[BERR;1]) == 1 {
+        cpu::write!(i2c.icr[BERRCF;1], 1);
+        return Err(Error::BusError);
+    }
+    if cpu::read!(i2c.isr[ARLO;1]) == 1 {
+        cpu::write!(i2c.icr[ARLOCF;1], 1);
+        return Err(Error::ArbitrationLost);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "stm32f072")]
+fn wait_isr(i2c: *mut cpu::I2cReg, flag: u8) -> Result<(), Error> {
+    for _ in 0..TIMEOUT_LOOPS {
+        check_errors(i2c)?;
+        if cpu::read!(i2c.isr) & (1 << flag) != 0 {
+            return Ok(());
+        }
+    }
+    cpu::write!(i2c.cr2[STOP;1], 1); // try to release the bus
+    Err(Error::Timeout)
+}
+
+#[cfg(feature = "stm32f072")]
+fn wait_idle(i2c: *mut cpu::I2cReg) -> Result<(), Error> {
+    for _ in 0..TIMEOUT_LOOPS {
+        if cpu::read!(i2c.isr[BUSY;1]) == 0 {
+            return Ok(());
+        }
+    }
+    Err(Error::Timeout)
+}
+
+#[cfg(feature = "stm32f072")]
+fn wait_stop(i2c: *mut cpu::I2cReg) -> Result<(), Error> {
+    wait_isr(i2c, i2c::isr::STOPF)?;
+    cpu::write!(i2c.icr[STOPCF;1], 1);
+    Ok(())
+}
+
+#[cfg(feature = "stm32f072")]
+fn start(i2c: *mut cpu::I2cReg, addr: u8, len: usize, read: bool, auto_end: bool) {
+    // longer transfers would need RELOAD
+    assert!(len <= 255);
+
+    let mut cr2: u32 = (addr as u32) << (i2c::cr2::SADD + 1); // 7 bit address in SADD[7:1]
+    cr2 |= (len as u32) << i2c::cr2::NBYTES;
+    cr2 |= (read as u32) << i2c::cr2::RD_WRN;
+    cr2 |= (auto_end as u32) << i2c::cr2::AUTOEND;
+    cr2 |= 1 << i2c::cr2::START;
+
+    cpu::write!(i2c.cr2, cr2);
+}
+
+#[cfg(feature = "stm32f072")]
+fn write_bytes(i2c: *mut cpu::I2cReg, data: &[u8]) -> Result<(), Error> {
+    for &d in data {
+        wait_isr(i2c, i2c::isr::TXIS)?;
+        cpu::write!(i2c.txdr, d as u32);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "stm32f072")]
+fn read_bytes(i2c: *mut cpu::I2cReg, data: &mut [u8]) -> Result<(), Error> {
+    for d in data.iter_mut() {
+        wait_isr(i2c, i2c::isr::RXNE)?;
+        *d = cpu::read!(i2c.rxdr[RXDATA;8]) as u8;
+    }
+    Ok(())
+}
@@ -0,0 +1,424 @@
+//! # I2S Module
+//!
+//! This module provides an I2S audio driver for SPI2 and SPI3 running in I2S
+//! master mode. Audio samples move between memory and the codec using circular
+//! DMA over a ping-pong buffer so the audio tasks never block the `TaskMgr` loop.
+//!
+//! The buffer is split in two halves. While the DMA works on one half, the
+//! application fills (transmit) or drains (receive) the other half. The DMA
+//! half-transfer and transfer-complete events tell the application which half
+//! is free.
+//!
+//! ## Structures
+//!
+//! - `I2s`: Represents one SPI peripheral used in I2S mode.
+//! - `Config`: Sample rate, frame format and direction.
+//!
+//! ## Methods for `I2s`
+//!
+//! - `init`: Sets up the I2S clock, pins and frame format, and returns the only
+//!   `I2s` for the peripheral. The master clock is always output as the codec
+//!   uses it to run.
+//! - `start`: Starts the circular DMA over a ping-pong buffer.
+//! - `stop`: Stops the DMA and the I2S.
+//! - `poll`: Returns the half of the buffer that is free, if any.
+//!
+//! ## Usage
+//!
+//! Either call `poll` from a task and fill or drain the returned half, or pass a
+//! callback to `start` and call `dma::handle_irq` from the interrupt handler of
+//! the DMA1 stream. The callback runs in the interrupt so it must be short.
+//!
+//! A half is only handed out when the DMA has just finished with it, and the
+//! DMA comes back to it after it has been through the other half, so the
+//! samples must be filled or drained before then.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::gpio;
+//! use hal::i2s;
+//!
+//! hal::init(16_000_000);
+//!
+//! static mut AUDIO_BUF: [i16; 320] = [0; 320];
+//!
+//! let config = i2s::Config {
+//!     sample_rate: 16_000,
+//!     frame: i2s::Frame::Stereo,
+//!     direction: i2s::Direction::Transmit,
+//! };
+//! let mut speaker = i2s::I2s::init(
+//!     cpu::SPI2,
+//!     &config,
+//!     gpio::Pin(cpu::GPIOB, 12), // WS
+//!     gpio::Pin(cpu::GPIOB, 13), // CK
+//!     gpio::Pin(cpu::GPIOB, 15), // SD
+//!     gpio::Pin(cpu::GPIOC, 6),  // MCK
+//! );
+//!
+//! #[allow(static_mut_refs)]
+//! speaker.start(unsafe { &mut AUDIO_BUF }, None);
+//!
+//! if let Some((_half, samples)) = speaker.poll() {
+//!     samples.fill(0); // play silence
+//! }
+//! ```
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+use super::dma;
+use super::gpio;
+
+// lets `spi.reg[FIELD;w]` find the field offsets in the SPI register module
+use super::cpu::SPI1::{self as spi};
+
+/// Frame format on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frame {
+    /// Philips I2S with 16 bit left and right samples interleaved in the buffer.
+    Stereo,
+    /// PCM short frame sync with one 16 bit sample per frame.
+    Mono,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Samples go from memory to the codec.
+    Transmit,
+    /// Samples go from the codec to memory.
+    Receive,
+}
+
+/// One half of the ping-pong buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Half {
+    First,
+    Second,
+}
+
+/// Called from the DMA interrupt with the half of the buffer that is free.
+pub type Callback = fn(half: Half, samples: &mut [i16]);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Sample rate in Hz. 8000, 16000, and 48000 are supported.
+    pub sample_rate: u32,
+    pub frame: Frame,
+    pub direction: Direction,
+}
+
+/// An SPI peripheral used in I2S mode, made by `init`. There is only one for
+/// each peripheral and it is not `Copy` as `poll` lends out part of the buffer.
+pub struct I2s(*mut cpu::Spi1Reg);
+
+#[derive(Copy, Clone)]
+struct State {
+    // `init` has made the `I2s`
+    initialized: bool,
+    direction: Direction,
+    buf: *mut i16,
+    len: usize,
+    callback: Option<Callback>,
+    // the stream claimed by `start`, until `stop` releases it
+    stream: Option<dma::Stream>,
+}
+
+cpu::local! {
+    static mut STATE: [State; 2] = [State {
+        initialized: false,
+        direction: Direction::Transmit,
+        buf: ptr::null_mut(),
+        len: 0,
+        callback: None,
+        stream: None,
+    }; 2];
+}
+
+impl I2s {
+    // index into STATE
+    fn index(&self) -> usize {
+        if ptr::eq(self.0, SPI2) {
+            0
+        } else if ptr::eq(self.0, SPI3) {
+            1
+        } else {
+            panic!("I2S only supported on SPI2 and SPI3");
+        }
+    }
+
+    // DMA1 stream for this peripheral and direction, all on channel 0,
+    // see table 42 of RM0090 Reference Manual
+    fn stream(&self) -> dma::Stream {
+        let direction = unsafe { cpu::local!(mut STATE)[self.index()].direction };
+        let num = match (self.index(), direction) {
+            (0, Direction::Transmit) => 4,
+            (0, Direction::Receive) => 3,
+            (1, Direction::Transmit) => 5,
+            (_, _) => 0,
+        };
+        dma::Stream { dma: DMA1, num }
+    }
+
+    /// Initializes SPI2 or SPI3 as an I2S master and returns it. Panics if the
+    /// peripheral has already been initialized.
+    #[inline(never)]
+    pub fn init(
+        spi: *mut cpu::Spi1Reg,
+        config: &Config,
+        ws_pin: gpio::Pin,
+        ck_pin: gpio::Pin,
+        sd_pin: gpio::Pin,
+        mck_pin: gpio::Pin,
+    ) -> I2s {
+        let i2s = I2s(spi);
+        let state = unsafe { &mut cpu::local!(mut STATE)[i2s.index()] };
+        assert!(!state.initialized, "I2S already initialized");
+        state.initialized = true;
+
+        i2s.configure(config, ws_pin, ck_pin, sd_pin, mck_pin);
+        i2s
+    }
+
+    fn configure(
+        &self,
+        config: &Config,
+        ws_pin: gpio::Pin,
+        ck_pin: gpio::Pin,
+        sd_pin: gpio::Pin,
+        mck_pin: gpio::Pin,
+    ) {
+        let spi = self.0;
+        let index = self.index();
+
+        // enable clock for the SPI
+        if index == 0 {
+            cpu::write!(RCC.apb1enr[SPI2EN;1], 1);
+        } else {
+            cpu::write!(RCC.apb1enr[SPI3EN;1], 1);
+        }
+
+        // AF5 for SPI2 and AF6 for SPI3 on all the I2S pins,
+        // see table 9 "Alternate function mapping" table of the datasheet stm32f405zg.pdf
+        let af_mode = if index == 0 { 5 } else { 6 };
+        ws_pin.alt_fun(af_mode, true);
+        ck_pin.alt_fun(af_mode, true);
+        sd_pin.alt_fun(af_mode, true);
+        mck_pin.alt_fun(af_mode, true);
+
+        init_pll_i2s();
+
+        cpu::write!(spi.i2scfgr[I2SE;1], 0); // must be disabled to configure
+        cpu::write!(spi.i2scfgr[I2SMOD;1], 1); // I2S mode, not SPI
+
+        match config.direction {
+            Direction::Transmit => {
+                cpu::write!(spi.i2scfgr[I2SCFG;2], 0b10); // master transmit
+            }
+            Direction::Receive => {
+                cpu::write!(spi.i2scfgr[I2SCFG;2], 0b11); // master receive
+            }
+        }
+
+        match config.frame {
+            Frame::Stereo => {
+                cpu::write!(spi.i2scfgr[I2SSTD;2], 0b00); // Philips standard
+            }
+            Frame::Mono => {
+                cpu::write!(spi.i2scfgr[I2SSTD;2], 0b11); // PCM standard
+                cpu::write!(spi.i2scfgr[PCMSYNC;1], 0); // short frame sync
+            }
+        }
+        cpu::write!(spi.i2scfgr[CKPOL;1], 0); // clock low when idle
+        cpu::write!(spi.i2scfgr[DATLEN;2], 0b00); // 16 bit data
+        cpu::write!(spi.i2scfgr[CHLEN;1], 0); // 16 bit channel
+
+        // with the master clock on, MCK is 256 times the frame rate whatever the
+        // frame format, see the I2S clock generator section of RM0090
+        let mck_per_sample: u32 = 256;
+        match config.sample_rate {
+            8_000 | 16_000 | 48_000 => {}
+            _ => {
+                panic!("I2S sample rate not supported");
+            }
+        }
+        let div: u32 = (I2S_CLK + mck_per_sample * config.sample_rate / 2)
+            / (mck_per_sample * config.sample_rate);
+        assert!(div >= 4);
+        assert!(div <= 511);
+
+        cpu::write!(spi.i2spr[I2SDIV;8], div / 2);
+        cpu::write!(spi.i2spr[ODD;1], div % 2);
+        cpu::write!(spi.i2spr[MCKOE;1], 1); // master clock output enable
+
+        unsafe { cpu::local!(mut STATE)[index].direction = config.direction };
+    }
+
+    /// Starts circular DMA over `buf`, which must have an even number of
+    /// samples. The callback, if given, is called from `dma::handle_irq` each
+    /// time a half of the buffer becomes free.
+    #[inline(never)]
+    pub fn start(&self, buf: &'static mut [i16], callback: Option<Callback>) {
+        let spi = self.0;
+
+        assert!(buf.len() >= 2);
+        assert!(buf.len().is_multiple_of(2));
+        assert!(buf.len() <= 0xFFFF);
+
+        let num = self.stream().num;
+        let state = unsafe { &mut cpu::local!(mut STATE)[self.index()] };
+        assert!(state.stream.is_none(), "I2S already started");
+        let direction = state.direction;
+
+        let dma_direction = match direction {
+            Direction::Transmit => dma::Direction::MemoryToPeripheral,
+            Direction::Receive => dma::Direction::PeripheralToMemory,
+        };
+        let config = dma::Config {
+            channel: 0,
+            direction: dma_direction,
+            peripheral_width: dma::Width::HalfWord,
+            memory_width: dma::Width::HalfWord,
+            peripheral_increment: false,
+            memory_increment: true,
+            circular: true,
+            double_buffer: false,              // halves are in one buffer
+            priority: dma::Priority::VeryHigh, // audio can not wait
+        };
+
+        let stream = dma::Stream::claim(DMA1, num);
+        *state = State {
+            initialized: true,
+            direction,
+            buf: buf.as_mut_ptr(),
+            len: buf.len(),
+            callback,
+            stream: Some(stream),
+        };
+        stream.configure(&config);
+
+        let dr_addr = unsafe { ptr::addr_of_mut!((*spi).dr) } as u32;
+        stream.set_peripheral(dr_addr);
+        stream.set_memory(buf.as_ptr() as u32);
+        stream.set_count(buf.len() as u16);
+
+        match direction {
+            Direction::Transmit => {
+                cpu::write!(spi.cr2[TXDMAEN;1], 1);
+            }
+            Direction::Receive => {
+                cpu::write!(spi.cr2[RXDMAEN;1], 1);
+            }
+        }
+
+        if callback.is_some() {
+            stream.set_callback(dma_callback);
+        }
+
+        stream.start();
+        cpu::write!(spi.i2scfgr[I2SE;1], 1); // enable I2S
+    }
+
+    /// Stops the I2S and the DMA. Does nothing to the DMA stream if `start` has
+    /// not claimed it.
+    #[inline(never)]
+    pub fn stop(&self) {
+        let spi = self.0;
+
+        cpu::write!(spi.i2scfgr[I2SE;1], 0);
+        let state = unsafe { &mut cpu::local!(mut STATE)[self.index()] };
+        if let Some(stream) = state.stream.take() {
+            stream.release();
+        }
+        state.buf = ptr::null_mut();
+        state.callback = None;
+        cpu::write!(spi.cr2[TXDMAEN;1], 0);
+        cpu::write!(spi.cr2[RXDMAEN;1], 0);
+    }
+
+    /// Returns the half of the buffer the DMA has finished with, if any, and
+    /// clears the event. Use this when not using a callback.
+    #[inline(never)]
+    pub fn poll(&mut self) -> Option<(Half, &mut [i16])> {
+        let state = unsafe { cpu::local!(mut STATE)[self.index()] };
+        let stream = state.stream?;
+        assert!(
+            state.callback.is_none(),
+            "I2S buffer is given to the callback"
+        );
+
+        let half = match stream.poll() {
+            Some(dma::Event::HalfTransfer) => Half::First,
+            Some(dma::Event::TransferComplete) => Half::Second,
+            Some(_) => {
+                panic!("I2S DMA transfer error");
+            }
+            None => return None,
+        };
+        Some((half, unsafe { half_buffer(&state, half) }))
+    }
+}
+
+// the samples in one half of the buffer given to `start`, which the DMA has just
+// finished with, so the caller holds the only reference to them
+unsafe fn half_buffer<'a>(state: &State, half: Half) -> &'a mut [i16] {
+    let half_len = state.len / 2;
+    let offset = match half {
+        Half::First => 0,
+        Half::Second => half_len,
+    };
+    unsafe { core::slice::from_raw_parts_mut(state.buf.add(offset), half_len) }
+}
+
+/// I2S clock from the PLLI2S in Hz.
+const I2S_CLK: u32 = 86_000_000;
+
+fn init_pll_i2s() {
+    // The PLL input is HSE / PLLM which clock::init sets to 2 MHz.
+    // 2 MHz * 172 / 4 = 86 MHz, which divides to within 0.02% of
+    // 8k, 16k, and 48k samples per second.
+    let pll_n: u32 = 172;
+    let pll_r: u32 = 4;
+
+    assert!(pll_n >= 50);
+    assert!(pll_n <= 432);
+    assert!(pll_r >= 2);
+    assert!(pll_r <= 7);
+
+    if cpu::read!(RCC.cr[PLLI2SON;1]) == 1 {
+        return; // already set up by the other I2S
+    }
+
+    cpu::write!(RCC.cfgr[I2SSRC;1], 0); // PLLI2S clocks the I2S
+    cpu::write!(RCC.plli2scfgr[PLLI2SNx;9], pll_n);
+    cpu::write!(RCC.plli2scfgr[PLLI2SRx;3], pll_r);
+
+    cpu::write!(RCC.cr[PLLI2SON;1], 1);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RCC.cr[PLLI2SRDY;1]) == 0 {}
+}
+
+// called from dma::handle_irq for the stream of either I2S
+fn dma_callback(stream: dma::Stream, event: dma::Event) {
+    for i2s in [I2s(SPI2), I2s(SPI3)] {
+        let state = unsafe { cpu::local!(mut STATE)[i2s.index()] };
+        if state.stream != Some(stream) {
+            continue;
+        }
+        let Some(callback) = state.callback else {
+            return;
+        };
+        let half = match event {
+            dma::Event::HalfTransfer => Half::First,
+            dma::Event::TransferComplete => Half::Second,
+            _ => {
+                panic!("I2S DMA transfer error");
+            }
+        };
+        callback(half, unsafe { half_buffer(&state, half) });
+        return;
+    }
+}
@@ -0,0 +1,495 @@
+//! # Interrupt Module
+//!
+//! This module controls the NVIC (Nested Vectored Interrupt Controller) and provides critical
+//! sections for data shared between the main loop and interrupt handlers.
+//!
+//! ## Structures
+//!
+//! - `Irq`: The interrupt numbers of the chip, in vector table order.
+//! - `CriticalSection`: Token that shows the code is running with interrupts masked.
+//!
+//! ## Functions
+//!
+//! - `enable`, `disable`, `is_enabled`: Turn an interrupt on or off in the NVIC.
+//! - `pend`, `unpend`, `is_pending`: Set or clear the pending flag of an interrupt.
+//! - `set_priority`, `priority`: Priority of an interrupt, 0 is the most urgent.
+//! - `free`: Runs a closure with all interrupts masked using PRIMASK.
+//! - `mask_below`: Runs a closure with the less urgent interrupts masked using BASEPRI.
+//! - `is_masked`: Returns true inside `free`.
+//!
+//! ## Usage
+//!
+//! Drivers enable their interrupt with `enable` after setting up the peripheral. Any `static mut`
+//! that an interrupt handler also uses should only be touched inside `free`. Critical sections
+//! can be nested, interrupts are only unmasked again when the outermost one ends.
+//!
+//! In the simulator there are no real interrupts, `free` takes a lock instead so that other
+//! threads can not see a half updated static. Driver state declared with `cpu::local!` is per
+//! thread there, so only statics shared on purpose need it.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::interrupt;
+//! use hal::interrupt::Irq;
+//!
+//! hal::init(16_000_000);
+//! interrupt::set_priority(Irq::Usart1, 2);
+//! interrupt::enable(Irq::Usart1);
+//!
+//! let count = interrupt::free(|_cs| {
+//!     // read and update data shared with the USART1 handler
+//!     1
+//! });
+//! assert_eq!(count, 1);
+//! ```
+//!
+
+use core::ptr;
+
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Number of priority bits the NVIC implements, priorities go from 0 to `2^PRIORITY_BITS - 1`.
+#[cfg(feature = "stm32f405")]
+pub const PRIORITY_BITS: u8 = 4;
+#[cfg(feature = "stm32f072")]
+pub const PRIORITY_BITS: u8 = 2;
+
+#[cfg(feature = "stm32f405")]
+/// Interrupt numbers of the STM32F405, from the vector table in RM0090.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Irq {
+    Wwdg = 0,
+    Pvd = 1,
+    TampStamp = 2,
+    RtcWkup = 3,
+    Flash = 4,
+    Rcc = 5,
+    Exti0 = 6,
+    Exti1 = 7,
+    Exti2 = 8,
+    Exti3 = 9,
+    Exti4 = 10,
+    Dma1Stream0 = 11,
+    Dma1Stream1 = 12,
+    Dma1Stream2 = 13,
+    Dma1Stream3 = 14,
+    Dma1Stream4 = 15,
+    Dma1Stream5 = 16,
+    Dma1Stream6 = 17,
+    Adc = 18,
+    Can1Tx = 19,
+    Can1Rx0 = 20,
+    Can1Rx1 = 21,
+    Can1Sce = 22,
+    Exti9_5 = 23,
+    Tim1BrkTim9 = 24,
+    Tim1UpTim10 = 25,
+    Tim1TrgComTim11 = 26,
+    Tim1Cc = 27,
+    Tim2 = 28,
+    Tim3 = 29,
+    Tim4 = 30,
+    I2c1Ev = 31,
+    I2c1Er = 32,
+    I2c2Ev = 33,
+    I2c2Er = 34,
+    Spi1 = 35,
+    Spi2 = 36,
+    Usart1 = 37,
+    Usart2 = 38,
+    Usart3 = 39,
+    Exti15_10 = 40,
+    RtcAlarm = 41,
+    OtgFsWkup = 42,
+    Tim8BrkTim12 = 43,
+    Tim8UpTim13 = 44,
+    Tim8TrgComTim14 = 45,
+    Tim8Cc = 46,
+    Dma1Stream7 = 47,
+    Fsmc = 48,
+    Sdio = 49,
+    Tim5 = 50,
+    Spi3 = 51,
+    Uart4 = 52,
+    Uart5 = 53,
+    Tim6Dac = 54,
+    Tim7 = 55,
+    Dma2Stream0 = 56,
+    Dma2Stream1 = 57,
+    Dma2Stream2 = 58,
+    Dma2Stream3 = 59,
+    Dma2Stream4 = 60,
+    Eth = 61,
+    EthWkup = 62,
+    Can2Tx = 63,
+    Can2Rx0 = 64,
+    Can2Rx1 = 65,
+    Can2Sce = 66,
+    OtgFs = 67,
+    Dma2Stream5 = 68,
+    Dma2Stream6 = 69,
+    Dma2Stream7 = 70,
+    Usart6 = 71,
+    I2c3Ev = 72,
+    I2c3Er = 73,
+    OtgHsEp1Out = 74,
+    OtgHsEp1In = 75,
+    OtgHsWkup = 76,
+    OtgHs = 77,
+    Dcmi = 78,
+    Cryp = 79,
+    HashRng = 80,
+    Fpu = 81,
+}
+
+#[cfg(feature = "stm32f072")]
+/// Interrupt numbers of the STM32F072, from the vector table in RM0091.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Irq {
+    Wwdg = 0,
+    PvdVddio2 = 1,
+    Rtc = 2,
+    Flash = 3,
+    RccCrs = 4,
+    Exti0_1 = 5,
+    Exti2_3 = 6,
+    Exti4_15 = 7,
+    Tsc = 8,
+    Dma1Ch1 = 9,
+    Dma1Ch2_3 = 10,
+    Dma1Ch4_7 = 11,
+    AdcComp = 12,
+    Tim1BrkUpTrgCom = 13,
+    Tim1Cc = 14,
+    Tim2 = 15,
+    Tim3 = 16,
+    Tim6Dac = 17,
+    Tim7 = 18,
+    Tim14 = 19,
+    Tim15 = 20,
+    Tim16 = 21,
+    Tim17 = 22,
+    I2c1 = 23,
+    I2c2 = 24,
+    Spi1 = 25,
+    Spi2 = 26,
+    Usart1 = 27,
+    Usart2 = 28,
+    Usart3_4 = 29,
+    CecCan = 30,
+    Usb = 31,
+}
+
+impl Irq {
+    /// Position of the interrupt in the vector table, after the 16 system exceptions.
+    pub fn number(self) -> usize {
+        self as usize
+    }
+
+    // register and bit of this interrupt in the NVIC bit arrays
+    fn word(self) -> usize {
+        self.number() / 32
+    }
+
+    fn bit(self) -> u32 {
+        1 << (self.number() % 32)
+    }
+}
+
+/// Token passed to the closure of `free`. Only code holding one can be sure no interrupt
+/// handler runs part way through it.
+pub struct CriticalSection {
+    _private: (),
+}
+
+// The set and clear registers only change the bits written as 1. The simulator memory is
+// plain storage so there the state is kept in the set register.
+fn set_bit(set: *mut u32, bit: u32) {
+    if cfg!(feature = "std") {
+        cpu::update_reg(set, bit, bit);
+    } else {
+        cpu::write_reg(set, bit);
+    }
+}
+
+fn clear_bit(set: *mut u32, clear: *mut u32, bit: u32) {
+    if cfg!(feature = "std") {
+        cpu::update_reg(set, bit, 0);
+    } else {
+        cpu::write_reg(clear, bit);
+    }
+}
+
+/// Enables an interrupt in the NVIC.
+pub fn enable(irq: Irq) {
+    unsafe {
+        set_bit(ptr::addr_of_mut!((*NVIC).iser[irq.word()]), irq.bit());
+    }
+}
+
+/// Disables an interrupt in the NVIC. It may still run once if it was already taken.
+pub fn disable(irq: Irq) {
+    unsafe {
+        clear_bit(
+            ptr::addr_of_mut!((*NVIC).iser[irq.word()]),
+            ptr::addr_of_mut!((*NVIC).icer[irq.word()]),
+            irq.bit(),
+        );
+    }
+}
+
+/// Returns true if the interrupt is enabled in the NVIC.
+pub fn is_enabled(irq: Irq) -> bool {
+    let v = unsafe { cpu::read_reg(ptr::addr_of_mut!((*NVIC).iser[irq.word()])) };
+    v & irq.bit() != 0
+}
+
+/// Sets the pending flag so the handler runs as soon as its priority allows, even if the
+/// peripheral did not raise it.
+pub fn pend(irq: Irq) {
+    unsafe {
+        set_bit(ptr::addr_of_mut!((*NVIC).ispr[irq.word()]), irq.bit());
+    }
+}
+
+/// Clears the pending flag of an interrupt.
+pub fn unpend(irq: Irq) {
+    unsafe {
+        clear_bit(
+            ptr::addr_of_mut!((*NVIC).ispr[irq.word()]),
+            ptr::addr_of_mut!((*NVIC).icpr[irq.word()]),
+            irq.bit(),
+        );
+    }
+}
+
+/// Returns true if the interrupt is waiting to run.
+pub fn is_pending(irq: Irq) -> bool {
+    let v = unsafe { cpu::read_reg(ptr::addr_of_mut!((*NVIC).ispr[irq.word()])) };
+    v & irq.bit() != 0
+}
+
+// each IPR word holds four interrupts, a byte each, with the implemented bits at the top
+fn priority_reg(irq: Irq) -> (*mut u32, u8) {
+    let addr = unsafe { ptr::addr_of_mut!((*NVIC).ipr[irq.number() / 4]) };
+    let shift = (irq.number() % 4) as u8 * 8 + (8 - PRIORITY_BITS);
+    (addr, shift)
+}
+
+/// Sets the priority of an interrupt, 0 is the most urgent. Panics if the priority is more
+/// than `PRIORITY_BITS` can hold.
+pub fn set_priority(irq: Irq, priority: u8) {
+    if priority >= 1 << PRIORITY_BITS {
+        panic!("Interrupt priority too large");
+    }
+    let (addr, shift) = priority_reg(irq);
+    let mask: u32 = ((1 << PRIORITY_BITS) - 1) << shift;
+
+    // the Cortex-M0 can only write whole words, so update just this byte of the word
+    free(|_cs| {
+        cpu::update_reg(addr, mask, (priority as u32) << shift);
+    });
+}
+
+/// Returns the priority of an interrupt.
+pub fn priority(irq: Irq) -> u8 {
+    let (addr, shift) = priority_reg(irq);
+    let v = cpu::read_reg(addr) >> shift;
+    (v & ((1 << PRIORITY_BITS) - 1)) as u8
+}
+
+#[cfg(target_arch = "arm")]
+mod mask {
+    use core::arch::asm;
+
+    // masks all interrupts and returns true if they were already masked
+    #[inline(always)]
+    pub fn disable() -> bool {
+        let primask: u32;
+        // no nomem option, these must not be reordered with the memory accesses they protect
+        unsafe {
+            asm!("mrs {}, PRIMASK", out(reg) primask, options(nostack, preserves_flags));
+            asm!("cpsid i", options(nostack, preserves_flags));
+        }
+        primask & 1 == 1
+    }
+
+    #[inline(always)]
+    pub fn restore(was_masked: bool) {
+        if !was_masked {
+            unsafe {
+                asm!("cpsie i", options(nostack, preserves_flags));
+            }
+        }
+    }
+
+    pub fn is_masked() -> bool {
+        let primask: u32;
+        unsafe {
+            asm!("mrs {}, PRIMASK", out(reg) primask, options(nomem, nostack, preserves_flags));
+        }
+        primask & 1 == 1
+    }
+
+    // raises BASEPRI to the given value if that masks more, returns the old value
+    #[cfg(feature = "stm32f405")]
+    #[inline(always)]
+    pub fn raise_basepri(basepri: u8) -> u8 {
+        let old: u32;
+        unsafe {
+            asm!("mrs {}, BASEPRI", out(reg) old, options(nostack, preserves_flags));
+            asm!("msr BASEPRI_MAX, {}", in(reg) basepri as u32, options(nostack, preserves_flags));
+        }
+        old as u8
+    }
+
+    #[cfg(feature = "stm32f405")]
+    #[inline(always)]
+    pub fn restore_basepri(basepri: u8) {
+        unsafe {
+            asm!("msr BASEPRI, {}", in(reg) basepri as u32, options(nostack, preserves_flags));
+        }
+    }
+}
+
+#[cfg(all(not(target_arch = "arm"), feature = "std"))]
+mod mask {
+    extern crate std;
+
+    use core::cell::{Cell, RefCell};
+    use std::sync::{Mutex, MutexGuard};
+
+    // held by the thread that is inside the outermost critical section
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    std::thread_local! {
+        static DEPTH: Cell<u32> = const { Cell::new(0) };
+        static GUARD: RefCell<Option<MutexGuard<'static, ()>>> = const { RefCell::new(None) };
+    }
+
+    pub fn disable() -> bool {
+        let depth = DEPTH.get();
+        if depth == 0 {
+            // a test that panicked inside a critical section poisons the lock, carry on anyway
+            let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            GUARD.set(Some(guard));
+        }
+        DEPTH.set(depth + 1);
+        depth != 0
+    }
+
+    pub fn restore(was_masked: bool) {
+        DEPTH.set(DEPTH.get() - 1);
+        if !was_masked {
+            GUARD.set(None);
+        }
+    }
+
+    pub fn is_masked() -> bool {
+        DEPTH.get() != 0
+    }
+
+    // there are no priorities in the simulator, mask everything
+    #[cfg(feature = "stm32f405")]
+    pub fn raise_basepri(_basepri: u8) -> u8 {
+        disable() as u8
+    }
+
+    #[cfg(feature = "stm32f405")]
+    pub fn restore_basepri(basepri: u8) {
+        restore(basepri != 0)
+    }
+}
+
+// no interrupts when built for the host without the simulator
+#[cfg(all(not(target_arch = "arm"), not(feature = "std")))]
+mod mask {
+    pub fn disable() -> bool {
+        true
+    }
+
+    pub fn restore(_was_masked: bool) {}
+
+    pub fn is_masked() -> bool {
+        false
+    }
+
+    #[cfg(feature = "stm32f405")]
+    pub fn raise_basepri(_basepri: u8) -> u8 {
+        0
+    }
+
+    #[cfg(feature = "stm32f405")]
+    pub fn restore_basepri(_basepri: u8) {}
+}
+
+// puts the mask back if the closure panics, so a failed sim test does not block the others
+struct Restore(bool);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        mask::restore(self.0);
+    }
+}
+
+/// Runs `f` with all interrupts masked and returns its result.
+#[inline(always)]
+pub fn free<F, R>(f: F) -> R
+where
+    F: FnOnce(&CriticalSection) -> R,
+{
+    let _restore = Restore(mask::disable());
+    f(&CriticalSection { _private: () })
+}
+
+/// Returns true if interrupts are masked by `free`.
+pub fn is_masked() -> bool {
+    mask::is_masked()
+}
+
+#[cfg(feature = "stm32f405")]
+// puts BASEPRI back, also if the closure panics
+struct RestoreBasepri(u8);
+
+#[cfg(feature = "stm32f405")]
+impl Drop for RestoreBasepri {
+    fn drop(&mut self) {
+        mask::restore_basepri(self.0);
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+/// Runs `f` with the interrupts of `priority` and less urgent masked, the more urgent ones can
+/// still run. Panics if `priority` is 0 as that would mask nothing, use `free` instead.
+#[inline(always)]
+pub fn mask_below<F, R>(priority: u8, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    if priority == 0 || priority >= 1 << PRIORITY_BITS {
+        panic!("Interrupt priority out of range");
+    }
+    let _restore = RestoreBasepri(mask::raise_basepri(priority << (8 - PRIORITY_BITS)));
+    f()
+}
+
+#[cfg(feature = "stm32f072")]
+/// The Cortex-M0 has no BASEPRI so this masks all interrupts, like `free`.
+#[inline(always)]
+pub fn mask_below<F, R>(priority: u8, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    if priority == 0 || priority >= 1 << PRIORITY_BITS {
+        panic!("Interrupt priority out of range");
+    }
+    free(|_cs| f())
+}
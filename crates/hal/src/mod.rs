@@ -25,14 +25,31 @@
 //!
 //! ## Modules
 //!
+//! - `adc`: Analog to Digital Converter (ADC) readings.
+//! - `alarm`: Alarms and software timers on TIM2.
 //! - `board`: Board-specific configurations and initializations.
 //! - `clock`: Clock configuration and management.
 //! - `cpu`: Function to access registers on the CPU
+//! - `crc`: Cyclic Redundancy Check (CRC) with a software fallback.
+//! - `dma`: Direct Memory Access (DMA) streams.
+//! - `dwt`: CPU cycle counter for timing short code.
+//! - `exti`: GPIO pin change interrupts.
+//! - `flash`: Internal flash erase and programming.
 //! - `gpio`: General Purpose Input/Output (GPIO) management.
+//! - `i2c`: Inter-Integrated Circuit (I2C) bus master.
+//! - `i2s`: Inter-IC Sound (I2S) audio with DMA.
+//! - `interrupt`: NVIC control and critical sections.
+//! - `power`: Sleep and STOP low power modes.
+//! - `reset`: Reset cause and software reset.
+//! - `rng`: True Random Number Generator (RNG).
+//! - `rtc`: Real time clock, backup registers and backup SRAM.
 //! - `semihost`: Semihosting support.
+//! - `sim`: Simulated registers and peripheral models, with the `std` feature.
+//! - `spi`: Serial Peripheral Interface (SPI) master.
 //! - `svd`: Constants from the System View Description (SVD)
 //! - `timer`: Timer configuration and management.
 //! - `uart`: Serial port Receiver/Transmitter (UART) management.
+//! - `watch_dog`: Window and independent watchdogs.
 //!
 //! ## Usage
 //!
@@ -50,26 +67,57 @@
 //! ```
 //!
 
+pub mod adc;
+#[cfg(feature = "stm32f405")]
+#[cfg(any(target_arch = "arm", feature = "std"))]
+pub mod alarm;
 //pub mod board;
 pub mod clock;
 pub mod cpu;
+pub mod crc;
+#[cfg(feature = "stm32f405")]
+pub mod dma;
+#[cfg(feature = "stm32f405")]
+pub mod dwt;
+pub mod exti;
+pub mod flash;
 pub mod gpio;
+pub mod i2c;
+#[cfg(feature = "stm32f405")]
+pub mod i2s;
+pub mod interrupt;
+#[cfg(any(target_arch = "arm", feature = "std"))]
+pub mod power;
+pub mod reset;
+#[cfg(feature = "std")]
+pub mod sim;
 
+#[cfg(feature = "stm32f405")]
+pub mod rng;
+#[cfg(feature = "stm32f405")]
+pub mod rtc;
 pub mod timer;
 pub mod uart;
 
+#[cfg(feature = "stm32f405")]
 pub mod spi;
 pub mod svd_stm32f0x2;
 pub mod svd_stm32f405;
 pub mod watch_dog;
 
 #[inline(never)]
-/// Initializes the hardware.
+/// Initializes the hardware with the default clocks for the given HSE crystal frequency.
 pub fn init(hse_clk_freq: u32) {
+    init_with(&clock::ClockConfig::new(hse_clk_freq));
+}
+
+#[inline(never)]
+/// Initializes the hardware with the given clock configuration.
+pub fn init_with(clock_config: &clock::ClockConfig) {
     cpu::init();
 
     // always set up clocks first
-    clock::init(hse_clk_freq);
+    clock::init(clock_config);
 
     // Do after clock and memory is set up
     gpio::init();
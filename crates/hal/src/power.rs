@@ -0,0 +1,185 @@
+//! # Power Module
+//!
+//! This module puts the CPU to sleep while there is nothing to do.
+//!
+//! In sleep mode the core clock stops but the peripherals keep running, and any interrupt wakes
+//! the core within a few cycles. In STOP mode all the clocks stop as well, so only the RTC and
+//! EXTI lines can wake it. STOP uses far less power but takes longer to wake, as the PLL has to
+//! be started again. While stopped the peripherals do not run: UART bytes that arrive are lost,
+//! DMA transfers pause, and TIM2 does not count. The independent watchdog keeps running. To hear
+//! from a UART while stopped, listen with `exti` for the falling edge of its RX pin, which wakes
+//! the chip but loses that first byte.
+//!
+//! STOP mode is woken by the RTC wakeup timer, which is clocked from the LSI unless the RTC is
+//! already running. The LSI is only accurate to about 30%, so until the RTC runs from the LSE
+//! `timer::now` can be off by that much of the time spent stopped.
+//!
+//! ## Functions
+//!
+//! - `sleep`: Sleeps until the next interrupt.
+//! - `sleep_until`: Sleeps until a time or an interrupt.
+//! - `stop_until`: Enters STOP mode until a time or an EXTI interrupt.
+//!
+//! ## Usage
+//!
+//! Call `sleep_until` from the main loop with the time the next work is due. Use `stop_until`
+//! instead when that is long enough to make up for the slower wake, and no peripheral is busy.
+//! Both can return early, so check the time after.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::power;
+//! use hal::timer::{self, Duration, Instant};
+//!
+//! hal::init(16_000_000);
+//! timer::init2();
+//!
+//! let deadline = Instant::now() + Duration::from_millis(2);
+//! while Instant::now() < deadline {
+//!     power::sleep_until(deadline);
+//! }
+//! ```
+//!
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[allow(unused_imports)]
+use core::ptr;
+
+#[allow(unused_imports)]
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+#[cfg(feature = "stm32f405")]
+#[cfg(not(feature = "std"))]
+use super::alarm::Timer;
+#[cfg(feature = "stm32f405")]
+use super::clock;
+#[cfg(feature = "stm32f405")]
+use super::interrupt;
+#[cfg(feature = "stm32f405")]
+use super::rtc;
+#[cfg(feature = "stm32f405")]
+use super::timer::{Duration, Instant};
+
+// waits for an interrupt. An interrupt that is masked still ends the wait, it runs once it is
+// unmasked.
+fn wait_for_interrupt() {
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        core::arch::asm!("wfi", options(nostack, preserves_flags));
+    }
+}
+
+/// Sleeps until the next interrupt. In the simulator this only yields to other threads.
+pub fn sleep() {
+    wait_for_interrupt();
+
+    #[cfg(feature = "std")]
+    std::thread::yield_now();
+}
+
+#[cfg(feature = "stm32f405")]
+#[cfg(not(feature = "std"))]
+// nothing to do, the interrupt has already woken the core
+fn wake(_timer: Timer) {}
+
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+/// Sleeps until `deadline`, or an interrupt comes first. Uses a timer from `alarm` to wake up
+/// so the deadline is rounded up to the next millisecond.
+pub fn sleep_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline <= now {
+        return;
+    }
+
+    #[cfg(feature = "std")]
+    std::thread::sleep(std::time::Duration::from_micros(
+        (deadline - now).as_micros(),
+    ));
+
+    #[cfg(not(feature = "std"))]
+    {
+        let timer = Timer::once(deadline - now, wake);
+
+        // checking the time with interrupts masked means the wake can not be missed
+        interrupt::free(|_cs| {
+            if Instant::now() < deadline {
+                wait_for_interrupt();
+            }
+        });
+
+        timer.cancel();
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+#[inline(never)]
+/// Enters STOP mode until `deadline`, or an EXTI interrupt comes first, then sets the clocks up
+/// again. The wakeup timer has a tick of about half a millisecond and stops after at most 32
+/// seconds, so this can return a little early. The time stopped is added to `timer::now`, from
+/// the wakeup timer when that woke the chip, or else from the RTC calendar to within about 4 ms.
+pub fn stop_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline <= now {
+        return;
+    }
+
+    let wakeup_freq = (rtc::clock_freq() / rtc::WAKEUP_DIV) as u64;
+    let ticks =
+        ((deadline - now).as_micros() * wakeup_freq / 1_000_000).min(rtc::MAX_WAKEUP_TICKS as u64);
+    if ticks == 0 {
+        // less than a tick away
+        sleep_until(deadline);
+        return;
+    }
+    #[allow(unused_variables)]
+    let stopped = Duration::from_micros(ticks * 1_000_000 / wakeup_freq);
+
+    // the calendar keeps counting while stopped, whatever ends it
+    #[allow(unused_variables)]
+    let rtc_before = rtc::time_of_day_us();
+    #[allow(unused_variables)]
+    let before = Instant::now();
+
+    rtc::start_wakeup(ticks as u32);
+
+    #[allow(unused_variables)]
+    let woken_by_rtc = interrupt::free(|_cs| {
+        // STOP, not standby, with the regulator in low power mode
+        cpu::write!(PWR.cr[PDDS;1], 0);
+        cpu::write!(PWR.cr[LPDS;1], 1);
+        cpu::write!(SCB.scr[SLEEPDEEP;1], 1);
+
+        wait_for_interrupt();
+
+        cpu::write!(SCB.scr[SLEEPDEEP;1], 0);
+        clock::restore();
+
+        // read before the interrupt handler clears it
+        cpu::read!(RTC.isr[WUTF;1]) == 1
+    });
+
+    rtc::stop_wakeup();
+
+    // TIM2 did not count while stopped
+    #[cfg(target_arch = "arm")]
+    {
+        let stopped = if woken_by_rtc {
+            stopped
+        } else {
+            const DAY_US: u64 = 24 * 60 * 60 * 1_000_000;
+            let on_rtc = (rtc::time_of_day_us() + DAY_US - rtc_before) % DAY_US;
+            // TIM2 counted the time before and after STOP
+            Duration::from_micros(on_rtc.saturating_sub(before.elapsed().as_micros()))
+        };
+        super::timer::advance(stopped);
+    }
+
+    #[cfg(feature = "std")]
+    std::thread::sleep(std::time::Duration::from_micros(stopped.as_micros()));
+}
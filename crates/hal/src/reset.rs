@@ -0,0 +1,152 @@
+//! # Reset Module
+//!
+//! This module reports why the system last reset, and can reset the system from software.
+//!
+//! ## Enums
+//!
+//! - `ResetCause`: The reason for the last reset, from the RCC CSR flags.
+//!
+//! ## Functions
+//!
+//! - `read_cause`: Reads the reset flags, then clears them so the next reset starts clean.
+//! - `software_reset`: Resets the whole system.
+//!
+//! ## Usage
+//!
+//! Call `read_cause` once at boot, before anything else could reset the system. The flags stay
+//! set across resets until cleared, so a second call returns `ResetCause::Unknown`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::reset;
+//!
+//! hal::init(16_000_000);
+//! if reset::read_cause() == reset::ResetCause::IndependentWatchdog {
+//!     // the last run hung
+//! }
+//! ```
+//!
+
+use core::ptr;
+
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+/// The reason for the last reset. When several flags are set the most specific one is returned,
+/// as a watchdog or power reset also sets the pin flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetCause {
+    /// The power was turned on.
+    PowerOn,
+    /// The supply dropped below the brown-out threshold.
+    #[cfg(feature = "stm32f405")]
+    BrownOut,
+    /// Entered standby or stop when the option bytes do not allow it.
+    LowPower,
+    /// The window watchdog was not fed in time.
+    WindowWatchdog,
+    /// The independent watchdog was not fed in time.
+    IndependentWatchdog,
+    /// `software_reset` was called.
+    Software,
+    /// The option bytes were reloaded.
+    #[cfg(feature = "stm32f072")]
+    OptionByteLoad,
+    /// The reset pin was pulled low.
+    Pin,
+    /// No flag was set, the flags were already cleared.
+    Unknown,
+}
+
+impl ResetCause {
+    /// Short name of the cause, for printing.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResetCause::PowerOn => "power on",
+            #[cfg(feature = "stm32f405")]
+            ResetCause::BrownOut => "brown out",
+            ResetCause::LowPower => "low power",
+            ResetCause::WindowWatchdog => "window watchdog",
+            ResetCause::IndependentWatchdog => "independent watchdog",
+            ResetCause::Software => "software",
+            #[cfg(feature = "stm32f072")]
+            ResetCause::OptionByteLoad => "option byte load",
+            ResetCause::Pin => "pin",
+            ResetCause::Unknown => "unknown",
+        }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+fn cause_from_flags() -> ResetCause {
+    if cpu::read!(RCC.csr[PORRSTF;1]) == 1 {
+        ResetCause::PowerOn
+    } else if cpu::read!(RCC.csr[BORRSTF;1]) == 1 {
+        ResetCause::BrownOut
+    } else if cpu::read!(RCC.csr[LPWRRSTF;1]) == 1 {
+        ResetCause::LowPower
+    } else if cpu::read!(RCC.csr[WWDGRSTF;1]) == 1 {
+        ResetCause::WindowWatchdog
+    } else if cpu::read!(RCC.csr[WDGRSTF;1]) == 1 {
+        ResetCause::IndependentWatchdog
+    } else if cpu::read!(RCC.csr[SFTRSTF;1]) == 1 {
+        ResetCause::Software
+    } else if cpu::read!(RCC.csr[PADRSTF;1]) == 1 {
+        ResetCause::Pin
+    } else {
+        ResetCause::Unknown
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+fn cause_from_flags() -> ResetCause {
+    if cpu::read!(RCC.csr[PORRSTF;1]) == 1 {
+        ResetCause::PowerOn
+    } else if cpu::read!(RCC.csr[LPWRRSTF;1]) == 1 {
+        ResetCause::LowPower
+    } else if cpu::read!(RCC.csr[WWDGRSTF;1]) == 1 {
+        ResetCause::WindowWatchdog
+    } else if cpu::read!(RCC.csr[IWDGRSTF;1]) == 1 {
+        ResetCause::IndependentWatchdog
+    } else if cpu::read!(RCC.csr[SFTRSTF;1]) == 1 {
+        ResetCause::Software
+    } else if cpu::read!(RCC.csr[OBLRSTF;1]) == 1 {
+        ResetCause::OptionByteLoad
+    } else if cpu::read!(RCC.csr[PINRSTF;1]) == 1 {
+        ResetCause::Pin
+    } else {
+        ResetCause::Unknown
+    }
+}
+
+#[inline(never)]
+/// Returns the cause of the last reset and clears the reset flags.
+pub fn read_cause() -> ResetCause {
+    let cause = cause_from_flags();
+
+    // writing RMVF clears all the reset flags
+    cpu::write!(RCC.csr[RMVF;1], 1);
+
+    cause
+}
+
+/// Resets the whole system, as if the reset pin was pulled. The next `read_cause` returns
+/// `ResetCause::Software`. In the simulator this panics instead.
+pub fn software_reset() -> ! {
+    // AIRCR writes are ignored unless the key is in the top half
+    cpu::write!(
+        SCB.aircr,
+        (0x05FA << SCB::aircr::VECTKEY) | (1 << SCB::aircr::SYSRESETREQ)
+    );
+
+    #[cfg(feature = "std")]
+    panic!("software reset");
+
+    // wait for the reset to happen
+    #[cfg(not(feature = "std"))]
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,131 @@
+//! # RNG Module
+//!
+//! This module provides functionality for reading true random numbers from the
+//! RNG peripheral of the STM32F405. The RNG uses analog noise and is suitable
+//! for seeding cryptographic keys.
+//!
+//! The RNG is clocked from PLL48CLK which must not be faster than 48 MHz. The
+//! `clock` module sets the PLL Q divider to give 48 MHz or a little less.
+//!
+//! Every number is checked for errors before it is returned:
+//!
+//! - Seed error: the noise source stopped changing or changed too much.
+//!   The RNG is restarted and the number discarded.
+//! - Clock error: PLL48CLK is too slow compared to the AHB clock.
+//! - Repeated value: the same number came out twice in a row. This is the
+//!   continuous random number generator test from FIPS 140-2.
+//!
+//! ## Enums
+//!
+//! - `Error`: The reasons a random number could not be read.
+//!
+//! ## Functions
+//!
+//! - `init`: Checks the clock, then turns on the RNG.
+//! - `read`: Returns the next checked 32 bit random number.
+//!
+//! ## Usage
+//!
+//! Most code should use `bsp::random::Random` which retries on errors and
+//! provides larger numbers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::rng;
+//!
+//! hal::init(16_000_000);
+//! rng::init();
+//!
+//! match rng::read() {
+//!     Ok(r) => { /* use r */ }
+//!     Err(e) => { /* retry or give up */ }
+//! }
+//! ```
+
+use core::ptr;
+
+use super::clock;
+use super::cpu;
+use super::cpu::*;
+
+/// Reasons a random number could not be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The noise source failed. The RNG has been restarted and can be read again.
+    Seed,
+    /// PLL48CLK is too slow for the RNG.
+    Clock,
+    /// The same number came out twice in a row.
+    Repeated,
+    /// No number became ready in time.
+    Timeout,
+}
+
+/// Number of status polls before giving up. A number takes 40 PLL48CLK cycles.
+const TIMEOUT_LOOPS: u32 = 10_000;
+
+cpu::local! {
+    // last number returned for the continuous test
+    static mut LAST: Option<u32> = None;
+}
+
+/// Checks PLL48CLK is not more than 48 MHz, then turns on the RNG.
+#[inline(never)]
+pub fn init() {
+    if clock::pll48clk() > 48_000_000 {
+        panic!("PLL48CLK too fast for RNG");
+    }
+
+    cpu::write!(RCC.ahb2enr[RNGEN;1], 1);
+
+    cpu::write!(RNG.cr[IE;1], 0); // no interrupts, read is polled
+    cpu::write!(RNG.cr[RNGEN;1], 1);
+
+    unsafe { *cpu::local!(mut LAST) = None };
+}
+
+/// Returns the next 32 bit random number once it has passed the health checks.
+#[inline(never)]
+pub fn read() -> Result<u32, Error> {
+    for _ in 0..TIMEOUT_LOOPS {
+        let sr = cpu::read!(RNG.sr);
+
+        if sr & (1 << RNG::sr::SEIS) != 0 {
+            // seed error, clear flag and restart the RNG, see 24.3.2 of RM0090
+            cpu::write!(RNG.sr[SEIS;1], 0);
+            cpu::write!(RNG.cr[RNGEN;1], 0);
+            cpu::write!(RNG.cr[RNGEN;1], 1);
+            return Err(Error::Seed);
+        }
+
+        if sr & (1 << RNG::sr::CEIS) != 0 {
+            cpu::write!(RNG.sr[CEIS;1], 0);
+            return Err(Error::Clock);
+        }
+
+        if sr & (1 << RNG::sr::DRDY) != 0 {
+            let val = cpu::read!(RNG.dr);
+
+            // the data is only good if no error is current
+            let sr = cpu::read!(RNG.sr);
+            if sr & ((1 << RNG::sr::SECS) | (1 << RNG::sr::CECS)) != 0 {
+                continue;
+            }
+
+            return check_repeat(val);
+        }
+    }
+
+    Err(Error::Timeout)
+}
+
+// continuous random number generator test
+fn check_repeat(val: u32) -> Result<u32, Error> {
+    let last = unsafe { cpu::local!(mut LAST) }.replace(val);
+
+    if last == Some(val) {
+        return Err(Error::Repeated);
+    }
+    Ok(val)
+}
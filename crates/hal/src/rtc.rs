@@ -0,0 +1,592 @@
+//! # RTC Module
+//!
+//! This module keeps the date and time in the real time clock (RTC), and gives access to the
+//! backup registers and backup SRAM.
+//!
+//! The RTC is in the backup domain, which is only cleared at power on or by a backup domain reset.
+//! The calendar keeps counting through a system reset or a watchdog reset, and with a battery on
+//! VBAT it keeps counting while the power is off.
+//!
+//! The RTC is clocked from the LSE crystal, or the LSI if there is no crystal. The LSI is only
+//! accurate to about 30%, so the calendar is no good for long from it. Changing the clock needs a
+//! backup domain reset, which stops the calendar and clears the backup registers.
+//!
+//! The 20 backup registers and the 4K backup SRAM keep their values through a system reset. The
+//! backup registers are also kept on VBAT, and the backup SRAM is too if its regulator is on.
+//!
+//! Only the STM32F405 is supported.
+//!
+//! ## Structures
+//!
+//! - `DateTime`: A date and time, with the year from 2000 to 2099.
+//!
+//! ## Enums
+//!
+//! - `Source`: The clock for the RTC.
+//!
+//! ## Functions
+//!
+//! - `init`: Starts the RTC from a clock, keeping the calendar if it is already running from it.
+//! - `clock_freq`: Returns the RTC clock frequency, starting the RTC from the LSI if it is off.
+//! - `is_set`: Returns true if the calendar has been set since the backup domain was reset.
+//! - `set`: Sets the calendar.
+//! - `now`: Reads the calendar.
+//! - `time_of_day_us`: Reads the time of day to a fraction of a second.
+//! - `set_alarm`: Runs a callback at a day of the month and time.
+//! - `cancel_alarm`: Stops the alarm.
+//! - `start_wakeup`: Starts the wakeup timer.
+//! - `stop_wakeup`: Stops the wakeup timer.
+//! - `read_backup` and `write_backup`: Access the backup registers.
+//! - `enable_backup_sram`: Turns on the backup SRAM.
+//! - `read_backup_sram` and `write_backup_sram`: Access words of the backup SRAM.
+//! - `handle_alarm_irq`: Runs the alarm callback, call from the RTC_Alarm interrupt.
+//! - `handle_wkup_irq`: Clears the wakeup timer flags, call from the RTC_WKUP interrupt.
+//!
+//! ## Usage
+//!
+//! Call `init` at start up, then `set` the calendar if `is_set` is false, for example once the
+//! time is known from the network.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::rtc::{self, DateTime, Source};
+//!
+//! hal::init(16_000_000);
+//! rtc::init(Source::Lse);
+//!
+//! if !rtc::is_set() {
+//!     rtc::set(&DateTime::new(2024, 6, 1, 12, 30, 0));
+//! }
+//! let now = rtc::now();
+//!
+//! rtc::write_backup(0, 0xC0FFEE);
+//! assert_eq!(rtc::read_backup(0), 0xC0FFEE);
+//! ```
+//!
+
+use core::ptr;
+
+use super::cpu;
+use super::cpu::*;
+use super::interrupt::{self, Irq};
+
+/// Number of backup registers.
+pub const NUM_BACKUP: usize = 20;
+
+/// Number of 32 bit words in the backup SRAM.
+pub const BACKUP_SRAM_WORDS: usize = 1024;
+
+const BACKUP_SRAM: *mut u32 = 0x4002_4000 as *mut u32;
+
+/// The wakeup timer counts the RTC clock divided by this.
+pub const WAKEUP_DIV: u32 = 16;
+
+/// Largest count of the wakeup timer.
+pub const MAX_WAKEUP_TICKS: u32 = 0x1_0000;
+
+// RTC write protection keys
+const KEY1: u32 = 0xCA;
+const KEY2: u32 = 0x53;
+const LOCK: u32 = 0xFF;
+
+// RTC clock frequencies for the RTCSEL settings
+const LSE_FREQ: u32 = 32_768;
+const LSI_FREQ: u32 = 32_000;
+
+// RTCSEL values
+const RTCSEL_LSE: u32 = 0b01;
+const RTCSEL_LSI: u32 = 0b10;
+
+// EXTI lines the RTC interrupts reach the NVIC through
+const ALARM_LINE: u8 = 17;
+const WAKEUP_LINE: u8 = 22;
+
+/// Number of polls for the LSE to start before using the LSI. The LSE can take 2 seconds.
+const LSE_TIMEOUT_LOOPS: u32 = 50_000_000;
+
+/// Function run when the alarm goes off.
+pub type Callback = fn();
+
+cpu::local! {
+    static mut ALARM_CALLBACK: Option<Callback> = None;
+}
+
+/// Clock for the RTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The 32.768 kHz crystal.
+    Lse,
+    /// The internal 32 kHz RC oscillator.
+    Lsi,
+}
+
+/// A date and time in 24 hour format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    /// 2000 to 2099.
+    pub year: u16,
+    /// 1 to 12.
+    pub month: u8,
+    /// 1 to 31.
+    pub day: u8,
+    /// 0 to 23.
+    pub hour: u8,
+    /// 0 to 59.
+    pub minute: u8,
+    /// 0 to 59.
+    pub second: u8,
+}
+
+fn is_leap(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    /// Creates a date and time.
+    pub const fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Self {
+        DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+
+    /// Returns true if this is a real date and time the RTC can hold.
+    pub fn is_valid(&self) -> bool {
+        (2000..=2099).contains(&self.year)
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Returns the day of the week, 1 for Monday to 7 for Sunday as the RTC counts them.
+    pub fn weekday(&self) -> u8 {
+        // Sakamoto's method, which gives 0 for Sunday
+        const OFFSETS: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let mut y = self.year;
+        if self.month < 3 {
+            y -= 1;
+        }
+        let day =
+            (y + y / 4 - y / 100 + y / 400 + OFFSETS[self.month as usize - 1] + self.day as u16)
+                % 7;
+        if day == 0 {
+            7
+        } else {
+            day as u8
+        }
+    }
+
+    /// Returns the seconds since the start of 1970, ignoring leap seconds.
+    pub fn to_unix_secs(&self) -> u64 {
+        let mut days = (1970..self.year)
+            .map(|y| if is_leap(y) { 366 } else { 365 })
+            .sum::<u64>();
+        days += (1..self.month)
+            .map(|m| days_in_month(self.year, m) as u64)
+            .sum::<u64>();
+        days += self.day as u64 - 1;
+
+        ((days * 24 + self.hour as u64) * 60 + self.minute as u64) * 60 + self.second as u64
+    }
+}
+
+fn to_bcd(val: u8) -> u32 {
+    (((val / 10) << 4) | (val % 10)) as u32
+}
+
+fn from_bcd(val: u32) -> u8 {
+    ((val >> 4) * 10 + (val & 0xF)) as u8
+}
+
+// allows writes to the backup domain, which is write protected after reset
+fn enable_backup_access() {
+    cpu::write!(RCC.apb1enr[PWREN;1], 1);
+    cpu::write!(PWR.cr[DBP;1], 1);
+}
+
+fn unlock() {
+    cpu::write!(RTC.wpr, KEY1);
+    cpu::write!(RTC.wpr, KEY2);
+}
+
+fn lock() {
+    cpu::write!(RTC.wpr, LOCK);
+}
+
+// starts the LSE, returning false if it does not start in time
+fn start_lse() -> bool {
+    cpu::write!(RCC.bdcr[LSEON;1], 1);
+
+    // the simulator has no crystal to wait for
+    let mut ready = cfg!(feature = "std");
+    let mut loops = 0;
+    while !ready && loops < LSE_TIMEOUT_LOOPS {
+        ready = cpu::read!(RCC.bdcr[LSERDY;1]) == 1;
+        loops += 1;
+    }
+
+    if !ready {
+        cpu::write!(RCC.bdcr[LSEON;1], 0);
+    }
+    ready
+}
+
+fn start_lsi() {
+    cpu::write!(RCC.csr[LSION;1], 1);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RCC.csr[LSIRDY;1]) == 0 {}
+}
+
+// stops the calendar so the prescaler or calendar can be changed, write protection must be off
+fn enter_init() {
+    cpu::write!(RTC.isr[INIT;1], 1);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr[INITF;1]) == 0 {}
+}
+
+fn exit_init() {
+    cpu::write!(RTC.isr[INIT;1], 0);
+}
+
+#[inline(never)]
+/// Starts the RTC from `source`, and returns the source used. If the LSE does not start the LSI
+/// is used instead. When the RTC is already running from the source the calendar and backup
+/// registers are kept, otherwise the backup domain is reset first.
+pub fn init(source: Source) -> Source {
+    enable_backup_access();
+
+    let source = match source {
+        Source::Lse if start_lse() => Source::Lse,
+        _ => {
+            start_lsi();
+            Source::Lsi
+        }
+    };
+    let rtcsel = match source {
+        Source::Lse => RTCSEL_LSE,
+        Source::Lsi => RTCSEL_LSI,
+    };
+
+    let current = cpu::read!(RCC.bdcr[RTCSEL0;2]);
+    if cpu::read!(RCC.bdcr[RTCEN;1]) == 1 && current == rtcsel {
+        return source;
+    }
+
+    // RTCSEL can only be changed by resetting the backup domain, which also stops the LSE
+    if current != 0 && current != rtcsel {
+        let lseon = cpu::read!(RCC.bdcr[LSEON;1]);
+        cpu::write!(RCC.bdcr[BDRST;1], 1);
+        cpu::write!(RCC.bdcr[BDRST;1], 0);
+        if lseon == 1 {
+            start_lse();
+        }
+    }
+
+    cpu::write!(RCC.bdcr[RTCSEL0;2], rtcsel);
+    cpu::write!(RCC.bdcr[RTCEN;1], 1);
+
+    // divide down to 1 Hz, with the asynchronous prescaler as large as possible to save power
+    let prediv_s = clock_freq() / 128 - 1;
+    unlock();
+    enter_init();
+    cpu::write!(RTC.prer, (127 << RTC::prer::PREDIV_A) | prediv_s);
+    cpu::write!(RTC.cr[FMT;1], 0); // 24 hour
+    exit_init();
+    lock();
+
+    source
+}
+
+#[inline(never)]
+/// Returns the frequency of the RTC clock. If the RTC is not running it is started from the LSI.
+pub fn clock_freq() -> u32 {
+    if cpu::read!(RCC.bdcr[RTCEN;1]) == 0 {
+        return match init(Source::Lsi) {
+            Source::Lse => LSE_FREQ,
+            Source::Lsi => LSI_FREQ,
+        };
+    }
+
+    enable_backup_access();
+    if cpu::read!(RCC.bdcr[RTCSEL0;2]) == RTCSEL_LSE {
+        LSE_FREQ
+    } else {
+        LSI_FREQ
+    }
+}
+
+/// Returns true if the calendar has been set since the backup domain was reset.
+pub fn is_set() -> bool {
+    cpu::read!(RTC.isr[INITS;1]) == 1
+}
+
+#[inline(never)]
+/// Sets the calendar to `time`. Panics if `time` is not valid.
+pub fn set(time: &DateTime) {
+    if !time.is_valid() {
+        panic!("Invalid RTC date time");
+    }
+
+    let tr = (to_bcd(time.hour) << RTC::tr::HU)
+        | (to_bcd(time.minute) << RTC::tr::MNU)
+        | (to_bcd(time.second) << RTC::tr::SU);
+    let dr = (to_bcd((time.year - 2000) as u8) << RTC::dr::YU)
+        | ((time.weekday() as u32) << RTC::dr::WDU)
+        | (to_bcd(time.month) << RTC::dr::MU)
+        | (to_bcd(time.day) << RTC::dr::DU);
+
+    enable_backup_access();
+    unlock();
+    enter_init();
+    cpu::write!(RTC.tr, tr);
+    cpu::write!(RTC.dr, dr);
+    exit_init();
+    lock();
+
+    // the simulator has no RTC to set the flag
+    #[cfg(feature = "std")]
+    {
+        cpu::write!(RTC.isr[INITS;1], 1);
+    }
+}
+
+#[inline(never)]
+/// Returns the current date and time. Takes up to two RTC clock cycles to wait for the calendar
+/// to be copied to the registers the CPU reads, as this may not have happened since a STOP.
+pub fn now() -> DateTime {
+    sync_shadow();
+
+    // reading TR holds DR until it is read, so the two match
+    let tr = cpu::read!(RTC.tr);
+    let dr = cpu::read!(RTC.dr);
+
+    DateTime {
+        year: 2000 + from_bcd((dr >> RTC::dr::YU) & 0xFF) as u16,
+        month: from_bcd((dr >> RTC::dr::MU) & 0x1F),
+        day: from_bcd((dr >> RTC::dr::DU) & 0x3F),
+        hour: from_bcd((tr >> RTC::tr::HU) & 0x3F),
+        minute: from_bcd((tr >> RTC::tr::MNU) & 0x7F),
+        second: from_bcd((tr >> RTC::tr::SU) & 0x7F),
+    }
+}
+
+#[inline(never)]
+/// Returns the time of day on the calendar in microseconds. The fraction of a second comes from
+/// the synchronous prescaler, so the time moves in steps of about 4 ms. Like `now` this waits
+/// for the calendar to be copied to the registers the CPU reads.
+pub fn time_of_day_us() -> u64 {
+    sync_shadow();
+
+    // reading SSR holds TR and DR until DR is read, so they all match
+    let ss = cpu::read!(RTC.ssr[SS;16]);
+    let tr = cpu::read!(RTC.tr);
+    cpu::read!(RTC.dr);
+
+    // SS counts down from PREDIV_S once a second
+    let prediv_s = cpu::read!(RTC.prer[PREDIV_S;15]);
+    let fraction = prediv_s.saturating_sub(ss) as u64 * 1_000_000 / (prediv_s as u64 + 1);
+
+    let hour = from_bcd((tr >> RTC::tr::HU) & 0x3F) as u64;
+    let minute = from_bcd((tr >> RTC::tr::MNU) & 0x7F) as u64;
+    let second = from_bcd((tr >> RTC::tr::SU) & 0x7F) as u64;
+    ((hour * 60 + minute) * 60 + second) * 1_000_000 + fraction
+}
+
+// waits for the calendar to be copied to the shadow registers the CPU reads, which takes up to
+// two RTC clock cycles
+fn sync_shadow() {
+    enable_backup_access();
+    unlock();
+    cpu::write!(RTC.isr[RSF;1], 0);
+    lock();
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr[RSF;1]) == 0 {}
+}
+
+#[inline(never)]
+/// Runs `callback` when the calendar reaches the day of the month and time in `time`. The year
+/// and month are ignored, so the alarm goes off again each month until cancelled.
+pub fn set_alarm(time: &DateTime, callback: Callback) {
+    if !time.is_valid() {
+        panic!("Invalid RTC date time");
+    }
+
+    // match the date, hours, minutes and seconds, none of the MSK bits set
+    let alrmar = (to_bcd(time.day) << RTC::alrmar::DU)
+        | (to_bcd(time.hour) << RTC::alrmar::HU)
+        | (to_bcd(time.minute) << RTC::alrmar::MNU)
+        | (to_bcd(time.second) << RTC::alrmar::SU);
+
+    interrupt::free(|_cs| {
+        unsafe { *cpu::local!(mut ALARM_CALLBACK) = Some(callback) };
+    });
+
+    enable_backup_access();
+    unlock();
+    cpu::write!(RTC.cr[ALRAE;1], 0);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr[ALRAWF;1]) == 0 {}
+    cpu::write!(RTC.alrmar, alrmar);
+    cpu::write!(RTC.isr[ALRAF;1], 0);
+    cpu::write!(RTC.cr[ALRAIE;1], 1);
+    cpu::write!(RTC.cr[ALRAE;1], 1);
+    lock();
+
+    cpu::write!(EXTI.pr, 1 << ALARM_LINE);
+    cpu::write!(EXTI.rtsr[TR17;1], 1);
+    cpu::write!(EXTI.imr[MR17;1], 1);
+    interrupt::enable(Irq::RtcAlarm);
+}
+
+#[inline(never)]
+/// Stops the alarm.
+pub fn cancel_alarm() {
+    enable_backup_access();
+    unlock();
+    cpu::write!(RTC.cr[ALRAIE;1], 0);
+    cpu::write!(RTC.cr[ALRAE;1], 0);
+    lock();
+
+    cpu::write!(EXTI.imr[MR17;1], 0);
+    interrupt::free(|_cs| {
+        unsafe { *cpu::local!(mut ALARM_CALLBACK) = None };
+    });
+}
+
+#[inline(never)]
+/// Runs the alarm callback if the alarm has gone off. Call this from the RTC_Alarm interrupt
+/// handler.
+pub fn handle_alarm_irq() {
+    cpu::write!(EXTI.pr, 1 << ALARM_LINE);
+    if cpu::read!(RTC.isr[ALRAF;1]) == 0 {
+        return;
+    }
+
+    // ALRAF is not write protected
+    cpu::write!(RTC.isr[ALRAF;1], 0);
+
+    let callback = interrupt::free(|_cs| unsafe { *cpu::local!(mut ALARM_CALLBACK) });
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+#[inline(never)]
+/// Starts the wakeup timer to interrupt after `ticks` counts of the RTC clock divided by
+/// `WAKEUP_DIV`. `ticks` must be from 1 to `MAX_WAKEUP_TICKS`. Starts the RTC from the LSI if it
+/// is not running.
+pub fn start_wakeup(ticks: u32) {
+    if ticks == 0 || ticks > MAX_WAKEUP_TICKS {
+        panic!("RTC wakeup ticks out of range");
+    }
+    clock_freq();
+
+    unlock();
+
+    // the reload value can only be changed once the timer has stopped
+    cpu::write!(RTC.cr[WUTE;1], 0);
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(RTC.isr[WUTWF;1]) == 0 {}
+
+    cpu::write!(RTC.wutr, ticks - 1);
+    cpu::write!(RTC.cr[WCKSEL;3], 0b000); // RTCCLK / 16
+    cpu::write!(RTC.isr[WUTF;1], 0);
+    cpu::write!(RTC.cr[WUTIE;1], 1);
+    cpu::write!(RTC.cr[WUTE;1], 1);
+
+    lock();
+
+    cpu::write!(EXTI.pr, 1 << WAKEUP_LINE);
+    cpu::write!(EXTI.rtsr[TR22;1], 1);
+    cpu::write!(EXTI.imr[MR22;1], 1);
+    interrupt::enable(Irq::RtcWkup);
+}
+
+#[inline(never)]
+/// Stops the wakeup timer and clears its flags.
+pub fn stop_wakeup() {
+    unlock();
+    cpu::write!(RTC.cr[WUTIE;1], 0);
+    cpu::write!(RTC.cr[WUTE;1], 0);
+    lock();
+
+    cpu::write!(EXTI.imr[MR22;1], 0);
+    handle_wkup_irq();
+}
+
+#[inline(never)]
+/// Clears the RTC wakeup timer flags. Call this from the RTC_WKUP interrupt handler.
+pub fn handle_wkup_irq() {
+    cpu::write!(RTC.isr[WUTF;1], 0);
+    cpu::write!(EXTI.pr, 1 << WAKEUP_LINE);
+}
+
+fn backup_reg(index: usize) -> *mut u32 {
+    if index >= NUM_BACKUP {
+        panic!("Backup register out of range");
+    }
+    unsafe { ptr::addr_of_mut!((*RTC).bkp0r).add(index) }
+}
+
+#[inline(never)]
+/// Returns the value of backup register `index`.
+pub fn read_backup(index: usize) -> u32 {
+    cpu::read_reg(backup_reg(index))
+}
+
+#[inline(never)]
+/// Sets backup register `index` to `value`.
+pub fn write_backup(index: usize, value: u32) {
+    enable_backup_access();
+    cpu::write_reg(backup_reg(index), value);
+}
+
+#[inline(never)]
+/// Turns on the clock of the backup SRAM. If `keep_on_vbat` is true the backup regulator is
+/// turned on as well, so the contents are kept on the battery while the power is off.
+pub fn enable_backup_sram(keep_on_vbat: bool) {
+    enable_backup_access();
+    cpu::write!(RCC.ahb1enr[BKPSRAMEN;1], 1);
+
+    cpu::write!(PWR.csr[BRE;1], keep_on_vbat as u32);
+    #[cfg(not(feature = "std"))]
+    if keep_on_vbat {
+        while cpu::read!(PWR.csr[BRR;1]) == 0 {}
+    }
+}
+
+fn backup_sram_word(index: usize) -> *mut u32 {
+    if index >= BACKUP_SRAM_WORDS {
+        panic!("Backup SRAM out of range");
+    }
+    unsafe { BACKUP_SRAM.add(index) }
+}
+
+#[inline(never)]
+/// Returns word `index` of the backup SRAM. `enable_backup_sram` must have been called.
+pub fn read_backup_sram(index: usize) -> u32 {
+    cpu::read_reg(backup_sram_word(index))
+}
+
+#[inline(never)]
+/// Sets word `index` of the backup SRAM to `value`. `enable_backup_sram` must have been called.
+pub fn write_backup_sram(index: usize, value: u32) {
+    cpu::write_reg(backup_sram_word(index), value);
+}
@@ -0,0 +1,1219 @@
+//! # Sim Module
+//!
+//! This module is the register map of the simulator, which stands in for the hardware when the
+//! `std` feature is on. `cpu::read_reg` and `cpu::write_reg` read and write it.
+//!
+//! A register on its own keeps the last value written to it. A peripheral can also have a model,
+//! which decides what reads and writes of its registers do so they behave more like the
+//! hardware. `init` adds these models:
+//!
+//! - GPIO: BSRR and BRR set and clear bits in ODR, and IDR reads the level of each pin, which is
+//!   ODR for outputs and the level set with `set_pin` or the pull resistor for inputs.
+//! - USART: bytes written to the data register are kept for `uart_sent`, bytes from
+//!   `uart_receive` set RXNE until the data register is read, and TXE and TC are always set as
+//!   a byte is sent straight away.
+//! - TIM: CNT counts up by one each time it is read while CEN is set, wrapping at ARR and setting
+//!   UIF, and EGR sets the flags for the events it generates.
+//! - RCC: each clock ready flag follows its enable bit, and SWS follows SW.
+//! - SPI: TXE is always set and BSY never is. Each byte written sets RXNE and comes back in DR,
+//!   unless bytes to receive were given with `drive`.
+//! - I2C: bytes written to the data register are kept for `i2c_sent`, and reads of the data
+//!   register return the bytes from `i2c_receive`. On the STM32F405 the status flags are left
+//!   to the test. On the STM32F072 TXIS, RXNE, TC and STOPF follow the transfer set up in CR2,
+//!   a STOP request sets STOPF, and ICR clears the flags.
+//! - ADC: SWSTART converts the first channel in SQR3 at once and sets EOC, for channels given a
+//!   voltage with `set_analog`. VREFINT is at its typical 1.21 V and VDDA is 3.3 V. Other
+//!   channels leave DR as it was, so a test can still write the result itself.
+//!
+//! The status flags can still be written like any other register, so a test can make an event
+//! the models do not, such as a framing error.
+//!
+//! Models for other peripherals, or for chips on the board, can be added with `add_model`.
+//!
+//! Each thread has its own register map and models, made the first time it touches a register,
+//! so tests running in parallel do not see each other's hardware.
+//!
+//! `trace` records every register read, write and update made by some code, so a test can check
+//! the order things were done in as well as where they ended up. `hal::expect!` describes a write
+//! to look for in a `Trace`.
+//!
+//! ## Structures
+//!
+//! - `Regs`: The values of the registers.
+//! - `Trace`: The register accesses recorded by `trace`, with checks on them.
+//! - `Expect`: Bits of a register and the value they should be written with.
+//!
+//! ## Enums
+//!
+//! - `Access`: A read, write or update of a register.
+//!
+//! ## Traits
+//!
+//! - `Model`: What the registers of one peripheral do.
+//!
+//! ## Functions
+//!
+//! - `init`: Clears the registers and adds the models for the chip. This is also done the first
+//!   time a register is used, so the registers start out as after a reset.
+//! - `add_model`: Adds a model for a peripheral.
+//! - `read`: Reads a register.
+//! - `write`: Writes a register.
+//! - `update`: Changes some bits of a register.
+//! - `drive`: Changes a register from outside the chip, such as a byte arriving.
+//! - `set_pin`: Sets the level of a GPIO input pin.
+//! - `uart_receive`: Gives a USART a received byte.
+//! - `uart_sent`: Returns the bytes a USART has sent.
+//! - `i2c_receive`: Gives an I2C bytes to read.
+//! - `i2c_sent`: Returns the bytes an I2C has sent.
+//! - `set_analog`: Sets the voltage at an ADC input.
+//! - `trace`: Runs some code and returns the register accesses it made.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::gpio::Pin;
+//! use hal::sim;
+//!
+//! hal::init(16_000_000);
+//!
+//! let led = Pin::new(cpu::GPIOA, 5);
+//! led.output();
+//! led.high();
+//! assert!(led.read());
+//!
+//! let button = Pin::new(cpu::GPIOA, 0);
+//! button.input();
+//! sim::set_pin(cpu::GPIOA, 0, true);
+//! assert!(button.read());
+//! ```
+//!
+
+extern crate std;
+
+use core::any::Any;
+use core::cell::RefCell;
+use core::ptr;
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+use super::cpu;
+#[allow(unused_imports)]
+use super::cpu::*;
+
+// the address of register `field` of the peripheral at `base`
+macro_rules! reg {
+    ( $base:expr, $field:ident ) => {
+        unsafe { ptr::addr_of_mut!((*$base).$field) }
+    };
+}
+
+// the registers of each peripheral are in a 1 KB block
+const BLOCK_SIZE: usize = 0x400;
+
+fn in_block<T>(base: *mut T, addr: *mut u32) -> bool {
+    let base = base as usize;
+    (base..base + BLOCK_SIZE).contains(&(addr as usize))
+}
+
+fn bit(v: u32, n: u8) -> bool {
+    v & (1 << n) != 0
+}
+
+// copies bit `from` of `v` to bit `to`, for a ready flag that follows an enable bit
+fn follow(v: u32, from: u8, to: u8) -> u32 {
+    (v & !(1 << to)) | (((v >> from) & 1) << to)
+}
+
+/// The values of the registers, which are 0 until written.
+#[derive(Default)]
+pub struct Regs(HashMap<*mut u32, u32>);
+
+impl Regs {
+    /// Returns the value of the register at `addr`.
+    pub fn get(&self, addr: *mut u32) -> u32 {
+        self.0.get(&addr).copied().unwrap_or(0)
+    }
+
+    /// Sets the value of the register at `addr`.
+    pub fn set(&mut self, addr: *mut u32, value: u32) {
+        self.0.insert(addr, value);
+    }
+
+    /// Sets the bits in `bits` of the register at `addr`.
+    pub fn set_bits(&mut self, addr: *mut u32, bits: u32) {
+        self.set(addr, self.get(addr) | bits);
+    }
+
+    /// Clears the bits in `bits` of the register at `addr`.
+    pub fn clear_bits(&mut self, addr: *mut u32, bits: u32) {
+        self.set(addr, self.get(addr) & !bits);
+    }
+}
+
+/// What the registers of one peripheral do. The default methods treat them as plain memory.
+pub trait Model: Any {
+    /// Returns true if the register at `addr` belongs to this model.
+    fn owns(&self, addr: *mut u32) -> bool;
+
+    /// Sets the registers to their values after a reset.
+    fn reset(&mut self, _regs: &mut Regs) {}
+
+    /// Returns the value the code reads from the register at `addr`.
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        regs.get(addr)
+    }
+
+    /// Does what the code writing `value` to the register at `addr` does.
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        regs.set(addr, value);
+    }
+
+    /// Does what something outside the chip, such as a byte arriving, does to the register at
+    /// `addr`.
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        regs.set(addr, value);
+    }
+
+    /// Returns the model as `Any` so the helper functions can find their model.
+    fn as_any(&mut self) -> &mut dyn Any;
+}
+
+struct Sim {
+    regs: Regs,
+    models: Vec<Box<dyn Model>>,
+    // the accesses so far while `trace` is running
+    trace: Option<Vec<Access>>,
+}
+
+impl Sim {
+    fn read(&mut self, addr: *mut u32) -> u32 {
+        match self.models.iter_mut().find(|m| m.owns(addr)) {
+            Some(model) => model.read(&mut self.regs, addr),
+            None => self.regs.get(addr),
+        }
+    }
+
+    fn write(&mut self, addr: *mut u32, value: u32) {
+        match self.models.iter_mut().find(|m| m.owns(addr)) {
+            Some(model) => model.write(&mut self.regs, addr, value),
+            None => self.regs.set(addr, value),
+        }
+    }
+
+    fn record(&mut self, access: Access) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(access);
+        }
+    }
+}
+
+std::thread_local! {
+    // each thread has its own simulator so tests can run in parallel
+    static SIM: RefCell<Option<Sim>> = const { RefCell::new(None) };
+}
+
+// runs `f` on the simulator, which starts out as after a reset if `init` has not been called
+fn with<R>(f: impl FnOnce(&mut Sim) -> R) -> R {
+    if SIM.with_borrow(|sim| sim.is_none()) {
+        init();
+    }
+    SIM.with_borrow_mut(|sim| f(sim.as_mut().unwrap()))
+}
+
+// runs `f` on the model of type `M` that owns `addr`
+fn with_model<M: Model, R>(addr: *mut u32, f: impl FnOnce(&mut M, &mut Regs) -> R) -> Option<R> {
+    with(|sim| {
+        let model = sim.models.iter_mut().find(|m| m.owns(addr))?;
+        let model = model.as_any().downcast_mut::<M>()?;
+        Some(f(model, &mut sim.regs))
+    })
+}
+
+/// Clears all the registers and adds the models for the peripherals of the chip. A `trace` that
+/// is running carries on.
+pub fn init() {
+    SIM.with_borrow_mut(|sim| {
+        let trace = sim.take().and_then(|old| old.trace);
+        *sim = Some(Sim {
+            regs: Regs::default(),
+            models: Vec::new(),
+            trace,
+        });
+    });
+    add_default_models();
+}
+
+/// Adds a model for a peripheral, which then sets its reset values. A later model for the same
+/// registers takes over from an earlier one.
+pub fn add_model(mut model: Box<dyn Model>) {
+    with(|sim| {
+        model.reset(&mut sim.regs);
+        sim.models.insert(0, model);
+    });
+}
+
+/// Reads the register at `addr`.
+pub fn read(addr: *mut u32) -> u32 {
+    with(|sim| {
+        let value = sim.read(addr);
+        sim.record(Access::Read { addr, value });
+        value
+    })
+}
+
+/// Writes the register at `addr`.
+pub fn write(addr: *mut u32, value: u32) {
+    with(|sim| {
+        sim.write(addr, value);
+        sim.record(Access::Write { addr, value });
+    });
+}
+
+/// Changes the bits in `mask` of the register at `addr` to those in `value`, by reading it and
+/// writing it back.
+pub fn update(addr: *mut u32, mask: u32, value: u32) {
+    with(|sim| {
+        let old = sim.read(addr);
+        sim.write(addr, (old & !mask) | value);
+        sim.record(Access::Update { addr, mask, value });
+    });
+}
+
+/// Changes the register at `addr` the way something outside the chip would. For a register with
+/// no model this is the same as `write`.
+pub fn drive(addr: *mut u32, value: u32) {
+    with(|sim| match sim.models.iter_mut().find(|m| m.owns(addr)) {
+        Some(model) => model.drive(&mut sim.regs, addr, value),
+        None => sim.regs.set(addr, value),
+    });
+}
+
+/// Sets the level of input pin `pin` of a GPIO port.
+pub fn set_pin(gpio: *mut cpu::GpioReg, pin: u8, high: bool) {
+    assert!(pin < 16);
+    with_model(gpio as *mut u32, |model: &mut GpioModel, _regs| {
+        model.driven |= 1 << pin;
+        if high {
+            model.levels |= 1 << pin;
+        } else {
+            model.levels &= !(1 << pin);
+        }
+    });
+}
+
+/// Gives a USART a byte as if it had arrived on its RX pin.
+pub fn uart_receive(usart: *mut cpu::UsartReg, c: u8) {
+    with_model(usart as *mut u32, |model: &mut UsartModel, regs| {
+        model.receive(regs, c as u32)
+    });
+}
+
+/// Returns the bytes a USART has sent since this was last called.
+pub fn uart_sent(usart: *mut cpu::UsartReg) -> Vec<u8> {
+    with_model(usart as *mut u32, |model: &mut UsartModel, _regs| {
+        core::mem::take(&mut model.sent)
+    })
+    .unwrap_or_default()
+}
+
+/// Gives an I2C bytes to read, as if they came from the device it is talking to.
+pub fn i2c_receive(i2c: *mut cpu::I2cReg, data: &[u8]) {
+    with_model(i2c as *mut u32, |model: &mut I2cModel, regs| {
+        for &d in data {
+            model.receive(regs, d as u32);
+        }
+    });
+}
+
+/// Returns the bytes an I2C has sent since this was last called.
+pub fn i2c_sent(i2c: *mut cpu::I2cReg) -> Vec<u8> {
+    with_model(i2c as *mut u32, |model: &mut I2cModel, _regs| {
+        core::mem::take(&mut model.sent)
+    })
+    .unwrap_or_default()
+}
+
+/// Sets the voltage in millivolts the ADC measures on `channel`.
+#[cfg(feature = "stm32f405")]
+pub fn set_analog(channel: u8, mv: u32) {
+    assert!(channel <= 18);
+    with_model(ADC1 as *mut u32, |model: &mut AdcModel, _regs| {
+        model.levels[channel as usize] = Some(mv);
+    });
+}
+
+/// One access to a register, as recorded by `trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// `cpu::read_reg` read `value`.
+    Read { addr: *mut u32, value: u32 },
+    /// `cpu::write_reg` wrote `value`.
+    Write { addr: *mut u32, value: u32 },
+    /// `cpu::update_reg` changed the bits in `mask` to those in `value`.
+    Update {
+        addr: *mut u32,
+        mask: u32,
+        value: u32,
+    },
+}
+
+impl Access {
+    /// Returns the address of the register.
+    pub fn addr(&self) -> *mut u32 {
+        match *self {
+            Access::Read { addr, .. } => addr,
+            Access::Write { addr, .. } => addr,
+            Access::Update { addr, .. } => addr,
+        }
+    }
+
+    /// Returns true if this sets all the bits of `expect` to its value.
+    pub fn writes(&self, expect: &Expect) -> bool {
+        match *self {
+            Access::Read { .. } => false,
+            Access::Write { addr, value } => {
+                addr == expect.addr && value & expect.mask == expect.value
+            }
+            Access::Update { addr, mask, value } => {
+                addr == expect.addr
+                    && mask & expect.mask == expect.mask
+                    && value & expect.mask == expect.value
+            }
+        }
+    }
+}
+
+/// Some bits of a register and the value they should be written with, for checking a `Trace`.
+/// These are easiest to make with `hal::expect!`, which takes the same forms as `cpu::write!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expect {
+    pub addr: *mut u32,
+    pub mask: u32,
+    pub value: u32,
+}
+
+impl Expect {
+    /// The `width` bits from bit `offset` of the register at `addr` written with `value`.
+    pub fn field(addr: *mut u32, offset: u8, width: u32, value: u32) -> Self {
+        let mask = ((1u64 << width) - 1) as u32;
+        Expect {
+            addr,
+            mask: mask << offset,
+            value: (value & mask) << offset,
+        }
+    }
+
+    /// The whole register at `addr` written with `value`.
+    pub fn register(addr: *mut u32, value: u32) -> Self {
+        Expect {
+            addr,
+            mask: 0xFFFF_FFFF,
+            value,
+        }
+    }
+}
+
+/// The register accesses made while `trace` ran, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace(pub Vec<Access>);
+
+impl Trace {
+    /// Returns the accesses to the register at `addr`.
+    pub fn accesses_to(&self, addr: *mut u32) -> Vec<Access> {
+        self.0
+            .iter()
+            .filter(|a| a.addr() == addr)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the index of the first access that writes `expect`.
+    pub fn find(&self, expect: &Expect) -> Option<usize> {
+        self.0.iter().position(|a| a.writes(expect))
+    }
+
+    /// Returns true if an access writes `expect`.
+    pub fn was_written(&self, expect: &Expect) -> bool {
+        self.find(expect).is_some()
+    }
+
+    /// Panics unless an access writes `expect`.
+    pub fn assert_written(&self, expect: &Expect) {
+        if !self.was_written(expect) {
+            panic!(
+                "{:?} was not written, the accesses to it were {:?}",
+                expect,
+                self.accesses_to(expect.addr)
+            );
+        }
+    }
+
+    /// Panics unless each of `expected` is written after the one before it. Other accesses can
+    /// come in between.
+    pub fn assert_sequence(&self, expected: &[Expect]) {
+        let mut start = 0;
+        for (i, expect) in expected.iter().enumerate() {
+            match self.0[start..].iter().position(|a| a.writes(expect)) {
+                Some(n) => start += n + 1,
+                None => panic!(
+                    "step {} of the sequence, {:?}, was not written after step {}, the accesses \
+                     to it were {:?}",
+                    i,
+                    expect,
+                    i as i32 - 1,
+                    self.accesses_to(expect.addr)
+                ),
+            }
+        }
+    }
+}
+
+/// Runs `f` and returns all the register accesses it made. Traces can not be nested.
+pub fn trace(f: impl FnOnce()) -> Trace {
+    with(|sim| sim.trace = Some(Vec::new()));
+    f();
+    Trace(with(|sim| sim.trace.take()).unwrap_or_default())
+}
+
+/// Makes an `Expect` for checking a `Trace`, from a register and value in any of the forms
+/// `cpu::write!` takes.
+#[macro_export]
+macro_rules! expect {
+    ( $x:ident.$y:ident[$z:ident;$w:expr], $data:expr ) => {{
+        let base = $x;
+        let addr = unsafe { core::ptr::addr_of_mut!((*base).$y) };
+        $crate::sim::Expect::field(addr, $x::$y::$z, $w, $data)
+    }};
+    ( $x:ident.$y:ident[$z:expr;$w:expr], $data:expr ) => {{
+        let base = $x;
+        let addr = unsafe { core::ptr::addr_of_mut!((*base).$y) };
+        $crate::sim::Expect::field(addr, $z, $w, $data)
+    }};
+    ( $x:ident.$y:ident.$z:ident, $data:expr ) => {{
+        let base = $x;
+        let addr = unsafe { core::ptr::addr_of_mut!((*base).$y) };
+        $crate::sim::Expect::field(addr, $x::$y::$z, $x::$y::width::$z as u32, $data)
+    }};
+    ( $x:ident.$y:ident, $data:expr ) => {{
+        let base = $x;
+        let addr = unsafe { core::ptr::addr_of_mut!((*base).$y) };
+        $crate::sim::Expect::register(addr, $data)
+    }};
+}
+
+/// A GPIO port.
+pub struct GpioModel {
+    base: *mut cpu::GpioReg,
+    // pins with a level set by `set_pin`, and those levels
+    driven: u32,
+    levels: u32,
+}
+
+impl GpioModel {
+    /// Returns a model of the GPIO port at `base`.
+    pub fn new(base: *mut cpu::GpioReg) -> Self {
+        GpioModel {
+            base,
+            driven: 0,
+            levels: 0,
+        }
+    }
+
+    fn idr(&self, regs: &Regs) -> u32 {
+        let moder = regs.get(reg!(self.base, moder));
+        let pupdr = regs.get(reg!(self.base, pupdr));
+        let odr = regs.get(reg!(self.base, odr));
+
+        let mut idr = 0;
+        for pin in 0..16 {
+            let high = match (moder >> (pin * 2)) & 0b11 {
+                0b01 => bit(odr, pin),
+                0b11 => false, // analog pins read as 0
+                _ if bit(self.driven, pin) => bit(self.levels, pin),
+                _ => (pupdr >> (pin * 2)) & 0b11 == 0b01,
+            };
+            idr |= (high as u32) << pin;
+        }
+        idr
+    }
+}
+
+impl Model for GpioModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        if addr == reg!(self.base, idr) {
+            self.idr(regs)
+        } else if addr == reg!(self.base, bsrr) {
+            0
+        } else {
+            regs.get(addr)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        let odr = reg!(self.base, odr);
+        if addr == reg!(self.base, bsrr) {
+            // setting wins if a bit is both set and reset
+            regs.clear_bits(odr, value >> 16);
+            regs.set_bits(odr, value & 0xFFFF);
+            return;
+        }
+        #[cfg(feature = "stm32f072")]
+        if addr == reg!(self.base, brr) {
+            regs.clear_bits(odr, value & 0xFFFF);
+            return;
+        }
+        if addr == reg!(self.base, idr) {
+            return; // read only
+        }
+        regs.set(addr, value);
+    }
+
+    fn drive(&mut self, _regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, idr) {
+            self.driven = 0xFFFF;
+            self.levels = value & 0xFFFF;
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A USART, which sends each byte as soon as it is written.
+pub struct UsartModel {
+    base: *mut cpu::UsartReg,
+    // the last byte received
+    rx: u32,
+    sent: Vec<u8>,
+}
+
+impl UsartModel {
+    /// Returns a model of the USART at `base`.
+    pub fn new(base: *mut cpu::UsartReg) -> Self {
+        UsartModel {
+            base,
+            rx: 0,
+            sent: Vec::new(),
+        }
+    }
+
+    // a byte arrives, and is lost if the one before has not been read
+    #[cfg(feature = "stm32f405")]
+    fn receive(&mut self, regs: &mut Regs, value: u32) {
+        let sr = reg!(self.base, sr);
+        if bit(regs.get(sr), USART::sr::RXNE) {
+            regs.set_bits(sr, 1 << USART::sr::ORE);
+        } else {
+            self.rx = value;
+            regs.set_bits(sr, 1 << USART::sr::RXNE);
+        }
+    }
+
+    // a byte arrives, and is lost if the one before has not been read
+    #[cfg(feature = "stm32f072")]
+    fn receive(&mut self, regs: &mut Regs, value: u32) {
+        let isr = reg!(self.base, isr);
+        if bit(regs.get(isr), USART::isr::RXNE) {
+            regs.set_bits(isr, 1 << USART::isr::ORE);
+        } else {
+            self.rx = value;
+            regs.set_bits(isr, 1 << USART::isr::RXNE);
+        }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for UsartModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(
+            reg!(self.base, sr),
+            (1 << USART::sr::TXE) | (1 << USART::sr::TC),
+        );
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        if addr != reg!(self.base, dr) {
+            return regs.get(addr);
+        }
+
+        // reading DR after SR clears RXNE and the error flags
+        let flags = (1 << USART::sr::RXNE)
+            | (1 << USART::sr::ORE)
+            | (1 << USART::sr::NF)
+            | (1 << USART::sr::FE)
+            | (1 << USART::sr::PE);
+        regs.clear_bits(reg!(self.base, sr), flags);
+        self.rx
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr != reg!(self.base, dr) {
+            regs.set(addr, value);
+            return;
+        }
+
+        self.sent.push(value as u8);
+        regs.set_bits(
+            reg!(self.base, sr),
+            (1 << USART::sr::TXE) | (1 << USART::sr::TC),
+        );
+    }
+
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, dr) {
+            self.receive(regs, value);
+        } else {
+            regs.set(addr, value);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+impl Model for UsartModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(
+            reg!(self.base, isr),
+            (1 << USART::isr::TXE) | (1 << USART::isr::TC),
+        );
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        if addr == reg!(self.base, rdr) {
+            regs.clear_bits(reg!(self.base, isr), 1 << USART::isr::RXNE);
+            self.rx
+        } else if addr == reg!(self.base, tdr) || addr == reg!(self.base, icr) {
+            0
+        } else {
+            regs.get(addr)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        let isr = reg!(self.base, isr);
+        if addr == reg!(self.base, tdr) {
+            self.sent.push(value as u8);
+            regs.set_bits(isr, (1 << USART::isr::TXE) | (1 << USART::isr::TC));
+        } else if addr == reg!(self.base, icr) {
+            // each clear flag is at the same bit as the flag it clears
+            regs.clear_bits(isr, value);
+        } else if addr == reg!(self.base, rqr) {
+            if bit(value, USART::rqr::RXFRQ) {
+                regs.clear_bits(isr, 1 << USART::isr::RXNE);
+            }
+        } else if addr != reg!(self.base, rdr) {
+            regs.set(addr, value);
+        }
+    }
+
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, rdr) {
+            self.receive(regs, value);
+        } else {
+            regs.set(addr, value);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A timer, which counts once for each read of CNT so code waiting on it makes progress.
+#[cfg(feature = "stm32f405")]
+pub struct TimerModel {
+    base: *mut cpu::TimGenReg,
+}
+
+#[cfg(feature = "stm32f405")]
+impl TimerModel {
+    /// Returns a model of the general purpose timer at `base`.
+    pub fn new(base: *mut cpu::TimGenReg) -> Self {
+        TimerModel { base }
+    }
+
+    /// Returns a model of the advanced timer at `base`, which has the same registers in the
+    /// same places.
+    pub fn new_adv(base: *mut cpu::TimAdvReg) -> Self {
+        Self::new(base as *mut cpu::TimGenReg)
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for TimerModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(reg!(self.base, arr), 0xFFFF_FFFF);
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        if addr == reg!(self.base, egr) {
+            return 0;
+        }
+        let value = regs.get(addr);
+        let cr1 = regs.get(reg!(self.base, cr1));
+        if addr == reg!(self.base, cnt) && bit(cr1, TIM_GEN::cr1::CEN) {
+            if value >= regs.get(reg!(self.base, arr)) {
+                regs.set(addr, 0);
+                regs.set_bits(reg!(self.base, sr), 1 << TIM_GEN::sr::UIF);
+            } else {
+                regs.set(addr, value + 1);
+            }
+        }
+        value
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr != reg!(self.base, egr) {
+            regs.set(addr, value);
+            return;
+        }
+
+        let sr = reg!(self.base, sr);
+        if bit(value, TIM_GEN::egr::UG) {
+            regs.set(reg!(self.base, cnt), 0);
+            if !bit(regs.get(reg!(self.base, cr1)), TIM_GEN::cr1::URS) {
+                regs.set_bits(sr, 1 << TIM_GEN::sr::UIF);
+            }
+        }
+        // the compare, trigger and break events set the flag at the same bit
+        let events = (0b1111 << TIM_GEN::egr::CC1G) | (0b11 << TIM_GEN::egr::TG);
+        regs.set_bits(sr, value & events);
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The reset and clock control, where clocks are ready as soon as they are turned on.
+pub struct RccModel;
+
+#[cfg(feature = "stm32f405")]
+impl Model for RccModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(RCC, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(
+            reg!(RCC, cr),
+            (1 << RCC::cr::HSION) | (1 << RCC::cr::HSIRDY),
+        );
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, mut value: u32) {
+        if addr == reg!(RCC, cr) {
+            value = follow(value, RCC::cr::HSION, RCC::cr::HSIRDY);
+            value = follow(value, RCC::cr::HSEON, RCC::cr::HSERDY);
+            value = follow(value, RCC::cr::PLLON, RCC::cr::PLLRDY);
+            value = follow(value, RCC::cr::PLLI2SON, RCC::cr::PLLI2SRDY);
+        } else if addr == reg!(RCC, cfgr) {
+            value = follow(value, RCC::cfgr::SW0, RCC::cfgr::SWS0);
+            value = follow(value, RCC::cfgr::SW1, RCC::cfgr::SWS1);
+        } else if addr == reg!(RCC, bdcr) {
+            value = follow(value, RCC::bdcr::LSEON, RCC::bdcr::LSERDY);
+        } else if addr == reg!(RCC, csr) {
+            value = follow(value, RCC::csr::LSION, RCC::csr::LSIRDY);
+        }
+        regs.set(addr, value);
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+impl Model for RccModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(RCC, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(
+            reg!(RCC, cr),
+            (1 << RCC::cr::HSION) | (1 << RCC::cr::HSIRDY),
+        );
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, mut value: u32) {
+        if addr == reg!(RCC, cr) {
+            value = follow(value, RCC::cr::HSION, RCC::cr::HSIRDY);
+            value = follow(value, RCC::cr::HSEON, RCC::cr::HSERDY);
+            value = follow(value, RCC::cr::PLLON, RCC::cr::PLLRDY);
+        } else if addr == reg!(RCC, cr2) {
+            value = follow(value, RCC::cr2::HSI14ON, RCC::cr2::HSI14RDY);
+            value = follow(value, RCC::cr2::HSI48ON, RCC::cr2::HSI48RDY);
+        } else if addr == reg!(RCC, cfgr) {
+            value = follow(value, RCC::cfgr::SW, RCC::cfgr::SWS);
+            value = follow(value, RCC::cfgr::SW + 1, RCC::cfgr::SWS + 1);
+        } else if addr == reg!(RCC, bdcr) {
+            value = follow(value, RCC::bdcr::LSEON, RCC::bdcr::LSERDY);
+        } else if addr == reg!(RCC, csr) {
+            value = follow(value, RCC::csr::LSION, RCC::csr::LSIRDY);
+        }
+        regs.set(addr, value);
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// An SPI, which sends each byte as soon as it is written. With nothing attached MISO follows
+/// MOSI, so each byte sent is also received.
+#[cfg(feature = "stm32f405")]
+pub struct SpiModel {
+    base: *mut cpu::Spi1Reg,
+    // bytes from `drive` to receive, and the last byte received
+    to_receive: VecDeque<u32>,
+    rx: u32,
+    // DR was read, so the next read of SR clears OVR
+    dr_read: bool,
+}
+
+#[cfg(feature = "stm32f405")]
+impl SpiModel {
+    /// Returns a model of the SPI at `base`.
+    pub fn new(base: *mut cpu::Spi1Reg) -> Self {
+        SpiModel {
+            base,
+            to_receive: VecDeque::new(),
+            rx: 0,
+            dr_read: false,
+        }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for SpiModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(reg!(self.base, sr), 1 << SPI1::sr::TXE);
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        let sr = reg!(self.base, sr);
+        if addr == reg!(self.base, dr) {
+            regs.clear_bits(sr, 1 << SPI1::sr::RXNE);
+            self.dr_read = true;
+            self.rx
+        } else if addr == sr {
+            let value = regs.get(sr);
+            if self.dr_read {
+                regs.clear_bits(sr, 1 << SPI1::sr::OVR);
+                self.dr_read = false;
+            }
+            value
+        } else {
+            regs.get(addr)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr != reg!(self.base, dr) {
+            regs.set(addr, value);
+            return;
+        }
+
+        // a byte received before the last one is read is lost
+        let sr = reg!(self.base, sr);
+        if bit(regs.get(sr), SPI1::sr::RXNE) {
+            regs.set_bits(sr, 1 << SPI1::sr::OVR);
+        } else {
+            self.rx = self.to_receive.pop_front().unwrap_or(value);
+        }
+        regs.set_bits(sr, (1 << SPI1::sr::TXE) | (1 << SPI1::sr::RXNE));
+    }
+
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, dr) {
+            self.to_receive.push_back(value);
+        } else {
+            regs.set(addr, value);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// An I2C master with a device that acknowledges everything. The bytes it sends are kept for
+/// `i2c_sent` and the bytes it reads are the ones from `i2c_receive`.
+pub struct I2cModel {
+    base: *mut cpu::I2cReg,
+    to_receive: VecDeque<u32>,
+    sent: Vec<u8>,
+    // bytes left in the transfer, and whether it ends with a stop
+    #[cfg(feature = "stm32f072")]
+    left: u32,
+    #[cfg(feature = "stm32f072")]
+    auto_end: bool,
+}
+
+impl I2cModel {
+    /// Returns a model of the I2C at `base`.
+    pub fn new(base: *mut cpu::I2cReg) -> Self {
+        I2cModel {
+            base,
+            to_receive: VecDeque::new(),
+            sent: Vec::new(),
+            #[cfg(feature = "stm32f072")]
+            left: 0,
+            #[cfg(feature = "stm32f072")]
+            auto_end: false,
+        }
+    }
+
+    // the device has a byte for the master to read
+    #[cfg(feature = "stm32f405")]
+    fn receive(&mut self, _regs: &mut Regs, value: u32) {
+        self.to_receive.push_back(value);
+    }
+
+    // the device has a byte for the master to read
+    #[cfg(feature = "stm32f072")]
+    fn receive(&mut self, regs: &mut Regs, value: u32) {
+        self.to_receive.push_back(value);
+        regs.set_bits(reg!(self.base, isr), 1 << I2C::isr::RXNE);
+    }
+
+    // one byte of the transfer is done, which may end it
+    #[cfg(feature = "stm32f072")]
+    fn transferred(&mut self, regs: &mut Regs) {
+        let isr = reg!(self.base, isr);
+        self.left = self.left.saturating_sub(1);
+        if self.left > 0 {
+            return;
+        }
+        regs.clear_bits(isr, 1 << I2C::isr::TXIS);
+        if self.auto_end {
+            regs.set_bits(isr, 1 << I2C::isr::STOPF);
+        } else {
+            regs.set_bits(isr, 1 << I2C::isr::TC);
+        }
+    }
+}
+
+// the status flags are left to the test, as they depend on what the device does
+#[cfg(feature = "stm32f405")]
+impl Model for I2cModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        let dr = reg!(self.base, dr);
+        if addr == dr {
+            self.to_receive.pop_front().unwrap_or(regs.get(dr))
+        } else {
+            regs.get(addr)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, dr) {
+            self.sent.push(value as u8);
+        }
+        regs.set(addr, value);
+    }
+
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, dr) {
+            self.receive(regs, value);
+        } else {
+            regs.set(addr, value);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+impl Model for I2cModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(self.base, addr)
+    }
+
+    fn reset(&mut self, regs: &mut Regs) {
+        regs.set(reg!(self.base, isr), 1 << I2C::isr::TXE);
+    }
+
+    fn read(&mut self, regs: &mut Regs, addr: *mut u32) -> u32 {
+        if addr == reg!(self.base, rxdr) {
+            let value = self.to_receive.pop_front().unwrap_or(0);
+            if self.to_receive.is_empty() {
+                regs.clear_bits(reg!(self.base, isr), 1 << I2C::isr::RXNE);
+            }
+            self.transferred(regs);
+            value
+        } else if addr == reg!(self.base, icr) {
+            0
+        } else {
+            regs.get(addr)
+        }
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        let isr = reg!(self.base, isr);
+        if addr == reg!(self.base, txdr) {
+            self.sent.push(value as u8);
+            self.transferred(regs);
+        } else if addr == reg!(self.base, icr) {
+            // each clear flag is at the same bit as the flag it clears
+            regs.clear_bits(isr, value);
+        } else if addr == reg!(self.base, cr2) {
+            // START and STOP clear themselves once the condition is on the bus
+            let cr2 = value & !((1 << I2C::cr2::START) | (1 << I2C::cr2::STOP));
+            regs.set(addr, cr2);
+            if bit(value, I2C::cr2::START) {
+                self.left = (value >> I2C::cr2::NBYTES) & 0xFF;
+                self.auto_end = bit(value, I2C::cr2::AUTOEND);
+                regs.clear_bits(isr, 1 << I2C::isr::TC);
+                if self.left == 0 {
+                    self.left = 1;
+                    self.transferred(regs);
+                } else if bit(value, I2C::cr2::RD_WRN) {
+                    if !self.to_receive.is_empty() {
+                        regs.set_bits(isr, 1 << I2C::isr::RXNE);
+                    }
+                } else {
+                    regs.set_bits(isr, 1 << I2C::isr::TXIS);
+                }
+            }
+            if bit(value, I2C::cr2::STOP) {
+                self.left = 0;
+                regs.clear_bits(isr, (1 << I2C::isr::TXIS) | (1 << I2C::isr::TC));
+                regs.set_bits(isr, 1 << I2C::isr::STOPF);
+            }
+        } else if addr != reg!(self.base, rxdr) {
+            regs.set(addr, value);
+        }
+    }
+
+    fn drive(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr == reg!(self.base, rxdr) {
+            self.receive(regs, value);
+        } else {
+            regs.set(addr, value);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The ADC, which converts a channel as soon as it is started.
+#[cfg(feature = "stm32f405")]
+pub struct AdcModel {
+    // the voltage in millivolts at each input, if it has been set
+    levels: [Option<u32>; 19],
+}
+
+#[cfg(feature = "stm32f405")]
+impl AdcModel {
+    // the supply the conversions are relative to, and the typical internal reference
+    const VDDA_MV: u32 = 3300;
+    const VREFINT_MV: u32 = 1210;
+}
+
+#[cfg(feature = "stm32f405")]
+impl Default for AdcModel {
+    /// Returns a model of ADC1 with only VREFINT set.
+    fn default() -> Self {
+        let mut levels = [None; 19];
+        levels[17] = Some(Self::VREFINT_MV);
+        AdcModel { levels }
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+impl Model for AdcModel {
+    fn owns(&self, addr: *mut u32) -> bool {
+        in_block(ADC1, addr)
+    }
+
+    fn write(&mut self, regs: &mut Regs, addr: *mut u32, value: u32) {
+        if addr != reg!(ADC1, cr2) || !bit(value, ADC1::cr2::SWSTART) {
+            regs.set(addr, value);
+            return;
+        }
+
+        // SWSTART clears itself once the conversion has started
+        regs.set(addr, value & !(1 << ADC1::cr2::SWSTART));
+        let channel = (regs.get(reg!(ADC1, sqr3)) >> ADC1::sqr3::SQ1) & 0x1F;
+        if let Some(Some(mv)) = self.levels.get(channel as usize) {
+            let raw = (mv * 4095 / Self::VDDA_MV).min(4095);
+            regs.set(reg!(ADC1, dr), raw);
+            regs.set_bits(reg!(ADC1, sr), 1 << ADC1::sr::EOC);
+        }
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "stm32f405")]
+fn add_default_models() {
+    add_model(Box::<AdcModel>::default());
+    for gpio in [GPIOA, GPIOB, GPIOC] {
+        add_model(Box::new(GpioModel::new(gpio)));
+    }
+    for usart in [USART1, USART2, USART3, USART6] {
+        add_model(Box::new(UsartModel::new(usart)));
+    }
+    add_model(Box::new(TimerModel::new_adv(TIM1)));
+    add_model(Box::new(TimerModel::new(TIM2)));
+    add_model(Box::new(RccModel));
+    for spi in [SPI1, SPI2, SPI3] {
+        add_model(Box::new(SpiModel::new(spi)));
+    }
+    for i2c in [I2C1, I2C2, I2C3] {
+        add_model(Box::new(I2cModel::new(i2c)));
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+fn add_default_models() {
+    for gpio in [GPIOA, GPIOB] {
+        add_model(Box::new(GpioModel::new(gpio)));
+    }
+    for usart in [USART1, USART2, USART3, USART4] {
+        add_model(Box::new(UsartModel::new(usart)));
+    }
+    for i2c in [I2C1, I2C2] {
+        add_model(Box::new(I2cModel::new(i2c)));
+    }
+    add_model(Box::new(RccModel));
+}
@@ -1,98 +1,358 @@
-#[allow(unused_imports)]
+//! # SPI Module
+//!
+//! This module provides an SPI master driver for SPI1, SPI2 and SPI3 on the STM32F405.
+//!
+//! ## Structures
+//!
+//! - `Spi`: Represents one SPI peripheral and provides methods to use it, for example `Spi(cpu::SPI1)`.
+//! - `Config`: The clock frequency and `Mode` to use.
+//! - `Mode`: Clock polarity and phase, `Mode0` to `Mode3`.
+//!
+//! ## Methods for `Spi`
+//!
+//! - `init`: Sets up the clock, pins, prescaler and mode.
+//! - `freq`: Returns the clock frequency actually used.
+//! - `write`: Sends bytes and waits for them to be sent.
+//! - `transfer`: Sends bytes and replaces them with the bytes received at the same time.
+//! - `read`: Receives bytes while sending 0xFF.
+//! - `write_wide`: Starts sending 16 bit frames using DMA and returns straight away.
+//! - `busy`, `wait`: Check for and wait for the end of a `write_wide` transfer.
+//!
+//! ## Usage
+//!
+//! The prescaler is the smallest one that does not make the clock faster than the
+//! frequency asked for. SPI1 is on the 84 MHz APB2 bus and SPI2 and SPI3 are on the
+//! 42 MHz APB1 bus so the clock can be the bus clock divided by 2, 4, 8 up to 256.
+//!
+//! With no MISO pin the SPI only transmits, using the MOSI line in one direction. This
+//! is how the display is wired. `transfer` and `read` need a MISO pin.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::cpu;
+//! use hal::gpio;
+//! use hal::spi;
+//!
+//! let flash = spi::Spi(cpu::SPI2);
+//! let config = spi::Config::new(10_000_000, spi::Mode::Mode0);
+//! flash.init(
+//!     &config,
+//!     gpio::Pin(cpu::GPIOB, 13),
+//!     gpio::Pin(cpu::GPIOB, 15),
+//!     Some(gpio::Pin(cpu::GPIOB, 14)),
+//! );
+//!
+//! // read the JEDEC ID
+//! let mut buf = [0x9F, 0, 0, 0];
+//! flash.transfer(&mut buf);
+//! ```
+
 use core::ptr;
 
-#[allow(unused_imports)]
+use super::clock;
 use super::cpu;
 pub use super::cpu::*;
+use super::dma;
 use super::gpio;
 
-#[cfg(feature = "stm32f072")]
-#[inline(never)]
-pub fn init1(spi_freq: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
-    let _ = (spi_freq, scl_pin, sda_pin);
-    assert!(false);
+// lets `spi.reg[FIELD;w]` find the field offsets in the SPI register module
+use super::cpu::SPI1::{self as spi};
+
+/// Clock polarity and phase. CPOL is the idle level of the clock and with
+/// CPHA set data is sampled on the second edge instead of the first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// CPOL 0, CPHA 0
+    Mode0,
+    /// CPOL 0, CPHA 1
+    Mode1,
+    /// CPOL 1, CPHA 0
+    Mode2,
+    /// CPOL 1, CPHA 1
+    Mode3,
 }
 
-#[cfg(feature = "stm32f405")]
-#[inline(never)]
-pub fn init1(spi_freq: u32, scl_pin: gpio::Pin, sda_pin: gpio::Pin) {
-    scl_pin.output();
-    sda_pin.output();
-
-    sda_pin.pullup();
-    scl_pin.pulldown();
-
-    // enable clock for SPI1
-    cpu::write!(RCC.apb2enr[SPI1EN;1], 0b1);
-
-    // set up pins - AF from Figur 26 of RM0090 Reference Manual
-    // also see table 9 "Alternate function mapping" table of the datasheet stm32f405zg.pdf
-    scl_pin.alt_fun(5, true); // AF5 for PA5 is SPI1_SCK
-    sda_pin.alt_fun(5, true); // AF5 for PA7 is SPI1_MOSI
-    assert!(scl_pin.0 == gpio::GPIOA);
-    assert!(sda_pin.0 == gpio::GPIOA);
-    assert!(scl_pin.1 == 5);
-    assert!(sda_pin.1 == 7);
-
-    // set up the SPI
-    cpu::write!(SPI1.cr1[DFF;1], 0b0); // set to 8 bit frame
-    cpu::write!(SPI1.cr1[LSBFIRST;1], 0b0); // set MSB send first
-    cpu::write!(SPI1.cr1[RXONLY;1], 0b0); // set to full duplex
-    cpu::write!(SPI1.cr1[CRCEN;1], 0b0); // disable CRC
-
-    // TODO - Brett has SPI at about 2.5 Mhz not 10 MHz
-    assert!(spi_freq >= 48_000_000 / 8);
-    //cpu::write!( SPI1.cr1[BR;3] , 0b100 ); // set baud rate to 2.5 Mhz (0b100 )
-    cpu::write!( SPI1.cr1[BR;3] , 0b010 ); // set baud rate to 10 Mhz (0b010 )
-
-    cpu::write!( SPI1.cr1[MSTR;1] , 0b1 ); // set to master mode
-    cpu::write!(SPI1.cr1[BIDIMODE;1], 0b1); // set BIDIMODE to 1 line both directions
-    cpu::write!(SPI1.cr1[BIDIOE;1], 0b1); // set BIDIOE to output
-
-    cpu::write!( SPI1.cr1[SSM;1] , 0b1 );
-    cpu::write!( SPI1.cr1[SSI;1] , 0b1 );
-
-    cpu::write!( SPI1.cr1[CPOL;1] , 0b0 ); // low when idle
-    cpu::write!( SPI1.cr1[CPHA;1] , 0b0 ); // sample on rising edge
-
-    cpu::write!( SPI1.cr1[SPE;1] , 0b0 ); // do ot enable SPI yet
-    cpu::write!( SPI1.cr1[SPE;1] , 0b1 ); // enable SPI
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Fastest clock frequency in Hz to use.
+    pub freq: u32,
+    pub mode: Mode,
 }
 
-#[cfg(feature = "stm32f405")]
-#[inline(never)]
-pub fn write1(data: &[u8]) {
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+impl Config {
+    pub fn new(freq: u32, mode: Mode) -> Self {
+        Config { freq, mode }
+    }
+}
 
-    for &d in data {
-        cpu::write!(SPI1.dr, d as u32); // send 8 buts of data
+/// An SPI peripheral, for example `Spi(cpu::SPI1)`.
+#[derive(Copy, Clone)]
+pub struct Spi(pub *mut cpu::Spi1Reg);
 
-        // wait for transmit buffer to be empty
-        while cpu::read!(SPI1.sr[TXE;1]) == 0 {}
+cpu::local! {
+    /// DMA stream of the transfer started by `write_wide` on each SPI, if one is running.
+    static mut TX_STREAM: [Option<dma::Stream>; 3] = [None; 3];
+}
+
+impl Spi {
+    // index into TX_STREAM
+    fn index(&self) -> usize {
+        if ptr::eq(self.0, SPI1) {
+            0
+        } else if ptr::eq(self.0, SPI2) {
+            1
+        } else if ptr::eq(self.0, SPI3) {
+            2
+        } else {
+            panic!("SPI not supported");
+        }
     }
 
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
-}
+    // SPI1 is on APB2, SPI2 and SPI3 are on APB1
+    fn bus_freq(&self) -> u32 {
+        match self.index() {
+            0 => clock::pclk2(),
+            _ => clock::pclk1(),
+        }
+    }
+
+    // DMA controller, stream and channel for transmit, see table 42 and 43 of RM0090
+    fn tx_dma(&self) -> (*mut cpu::DmaReg, u8, u8) {
+        match self.index() {
+            0 => (DMA2, 3, 3),
+            1 => (DMA1, 4, 0),
+            _ => (DMA1, 5, 0),
+        }
+    }
+
+    /// Sets up the SPI as a master with the pins given. With no `miso_pin`
+    /// it only transmits.
+    #[inline(never)]
+    pub fn init(
+        &self,
+        config: &Config,
+        sck_pin: gpio::Pin,
+        mosi_pin: gpio::Pin,
+        miso_pin: Option<gpio::Pin>,
+    ) {
+        let spi = self.0;
+
+        // enable clock for the SPI
+        match self.index() {
+            0 => {
+                cpu::write!(RCC.apb2enr[SPI1EN;1], 0b1);
+            }
+            1 => {
+                cpu::write!(RCC.apb1enr[SPI2EN;1], 0b1);
+            }
+            _ => {
+                cpu::write!(RCC.apb1enr[SPI3EN;1], 0b1);
+            }
+        }
+
+        // set up pins - AF5 for SPI1 and SPI2, AF6 for SPI3
+        // see table 9 "Alternate function mapping" table of the datasheet stm32f405zg.pdf
+        let af = if self.index() == 2 { 6 } else { 5 };
+        sck_pin.alt_fun(af, true);
+        mosi_pin.alt_fun(af, true);
+        if let Some(pin) = miso_pin {
+            pin.alt_fun(af, true);
+        }
+
+        cpu::write!(spi.cr1[SPE;1], 0b0); // disable while setting up
+
+        // set up the SPI
+        cpu::write!(spi.cr1[DFF;1], 0b0); // set to 8 bit frame
+        cpu::write!(spi.cr1[LSBFIRST;1], 0b0); // set MSB send first
+        cpu::write!(spi.cr1[RXONLY;1], 0b0); // set to full duplex
+        cpu::write!(spi.cr1[CRCEN;1], 0b0); // disable CRC
+
+        cpu::write!(spi.cr1[BR;3], self.prescaler(config.freq));
+
+        cpu::write!(spi.cr1[MSTR;1], 0b1); // set to master mode
+        if miso_pin.is_some() {
+            cpu::write!(spi.cr1[BIDIMODE;1], 0b0); // set BIDIMODE to 2 lines one direction each
+        } else {
+            cpu::write!(spi.cr1[BIDIMODE;1], 0b1); // set BIDIMODE to 1 line both directions
+            cpu::write!(spi.cr1[BIDIOE;1], 0b1); // set BIDIOE to output
+        }
+
+        cpu::write!(spi.cr1[SSM;1], 0b1);
+        cpu::write!(spi.cr1[SSI;1], 0b1);
+
+        let (cpol, cpha) = match config.mode {
+            Mode::Mode0 => (0, 0),
+            Mode::Mode1 => (0, 1),
+            Mode::Mode2 => (1, 0),
+            Mode::Mode3 => (1, 1),
+        };
+        cpu::write!(spi.cr1[CPOL;1], cpol); // clock level when idle
+        cpu::write!(spi.cr1[CPHA;1], cpha); // sample on first or second edge
+
+        cpu::write!(spi.cr1[SPE;1], 0b1); // enable SPI
+    }
+
+    // BR field for the fastest clock that is not faster than freq, the clock
+    // is the bus clock divided by 2 << BR
+    fn prescaler(&self, freq: u32) -> u32 {
+        let bus_freq = self.bus_freq();
+        for br in 0..8 {
+            if bus_freq / (2 << br) <= freq {
+                return br;
+            }
+        }
+        panic!("SPI frequency too low");
+    }
+
+    /// The SPI clock frequency set by `init`.
+    pub fn freq(&self) -> u32 {
+        let spi = self.0;
+        self.bus_freq() / (2 << cpu::read!(spi.cr1[BR;3]))
+    }
+
+    /// Sends `data` and waits for it to be sent.
+    #[inline(never)]
+    pub fn write(&self, data: &[u8]) {
+        let spi = self.0;
+
+        // finish any DMA transfer first
+        self.wait();
+
+        // wait for SPI to not be busy
+        while cpu::read!(spi.sr[BSY;1]) != 0 {}
 
-#[cfg(feature = "stm32f405")]
-#[inline(never)]
-pub fn write1_wide(data: &[u16]) {
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+        for &d in data {
+            cpu::write!(spi.dr, d as u32); // send 8 bits of data
 
-    cpu::write!(SPI1.cr1[DFF;1], 0b1); // set to 16 bit frame
+            // wait for transmit buffer to be empty
+            while cpu::read!(spi.sr[TXE;1]) == 0 {}
+        }
 
-    for &d in data {
-        cpu::write!(SPI1.dr, d as u32); // send 16 bits of data
+        // wait for SPI to not be busy
+        while cpu::read!(spi.sr[BSY;1]) != 0 {}
 
-        // wait for transmit buffer to be empty
-        while cpu::read!(SPI1.sr[TXE;1]) == 0 {}
+        // in full duplex the received bytes are not wanted and would set OVR
+        if cpu::read!(spi.cr1[BIDIMODE;1]) == 0 {
+            let _ = cpu::read!(spi.dr);
+            let _ = cpu::read!(spi.sr);
+        }
     }
 
-    // wait for SPI to not be busy
-    while cpu::read!(SPI1.sr[BSY;1]) != 0 {}
+    /// Sends the bytes in `data` and replaces each with the byte received
+    /// while it was sent.
+    #[inline(never)]
+    pub fn transfer(&self, data: &mut [u8]) {
+        let spi = self.0;
 
-    cpu::write!(SPI1.cr1[DFF;1], 0b0); // set to 8 bit frame
+        if cpu::read!(spi.cr1[BIDIMODE;1]) != 0 {
+            panic!("SPI transfer needs a MISO pin");
+        }
+
+        // finish any DMA transfer first
+        self.wait();
+
+        // wait for SPI to not be busy
+        while cpu::read!(spi.sr[BSY;1]) != 0 {}
+
+        // throw away anything left from before
+        let _ = cpu::read!(spi.dr);
+
+        for d in data.iter_mut() {
+            while cpu::read!(spi.sr[TXE;1]) == 0 {}
+            cpu::write!(spi.dr, *d as u32);
+
+            // one byte comes back for each one sent
+            while cpu::read!(spi.sr[RXNE;1]) == 0 {}
+            *d = cpu::read!(spi.dr) as u8;
+        }
+
+        // wait for SPI to not be busy
+        while cpu::read!(spi.sr[BSY;1]) != 0 {}
+    }
+
+    /// Fills `data` with received bytes, sending 0xFF for each one.
+    pub fn read(&self, data: &mut [u8]) {
+        data.fill(0xFF);
+        self.transfer(data);
+    }
+
+    /// Starts sending `data` as 16 bit frames using DMA and returns straight away.
+    ///
+    /// # Safety
+    ///
+    /// The DMA keeps reading `data` after this returns, so it must not be changed or freed
+    /// until `busy` returns false or `wait` returns.
+    #[inline(never)]
+    pub unsafe fn write_wide(&self, data: &[u16]) {
+        let spi = self.0;
+
+        // finish any DMA transfer first
+        self.wait();
+
+        if data.is_empty() {
+            return;
+        }
+        assert!(data.len() <= 0xFFFF);
+
+        // wait for SPI to not be busy
+        while cpu::read!(spi.sr[BSY;1]) != 0 {}
+
+        cpu::write!(spi.cr1[DFF;1], 0b1); // set to 16 bit frame
+
+        let (dma_reg, num, channel) = self.tx_dma();
+        let stream = dma::Stream::claim(dma_reg, num);
+
+        let mut config = dma::Config::new(channel, dma::Direction::MemoryToPeripheral);
+        config.peripheral_width = dma::Width::HalfWord;
+        config.memory_width = dma::Width::HalfWord;
+        config.priority = dma::Priority::Medium;
+        stream.configure(&config);
+
+        let dr_addr = unsafe { ptr::addr_of_mut!((*spi).dr) } as u32;
+        stream.set_peripheral(dr_addr);
+        stream.set_memory(data.as_ptr() as u32);
+        stream.set_count(data.len() as u16);
+
+        cpu::write!(spi.cr2[TXDMAEN;1], 0b1); // TX requests go to the DMA
+        stream.start();
+
+        unsafe { cpu::local!(mut TX_STREAM)[self.index()] = Some(stream) };
+    }
+
+    /// Returns true while the transfer started by `write_wide` is still sending.
+    /// Once it is done this cleans up the DMA and puts the SPI back to 8 bit frames.
+    #[inline(never)]
+    pub fn busy(&self) -> bool {
+        let spi = self.0;
+
+        let stream = unsafe { cpu::local!(mut TX_STREAM)[self.index()] };
+        let Some(stream) = stream else {
+            return false;
+        };
+
+        // the last frame is still being sent after the DMA finishes
+        #[cfg(not(feature = "std"))]
+        if stream.is_enabled() || cpu::read!(spi.sr[TXE;1]) == 0 || cpu::read!(spi.sr[BSY;1]) != 0 {
+            return true;
+        }
+
+        let event = stream.poll();
+        stream.release();
+        cpu::write!(spi.cr2[TXDMAEN;1], 0b0);
+        cpu::write!(spi.cr1[DFF;1], 0b0); // set to 8 bit frame
+        unsafe { cpu::local!(mut TX_STREAM)[self.index()] = None };
+
+        match event {
+            Some(dma::Event::TransferError) | Some(dma::Event::DirectModeError) => {
+                panic!("SPI DMA transfer error");
+            }
+            _ => false,
+        }
+    }
+
+    /// Blocks until the transfer started by `write_wide` is done.
+    pub fn wait(&self) {
+        while self.busy() {}
+    }
 }
@@ -23,6 +23,24 @@ pub mod GPIO {
         pub const MODER2: u8 = 4;
         pub const MODER1: u8 = 2;
         pub const MODER0: u8 = 0;
+        pub mod width {
+            pub const MODER15: u8 = 2;
+            pub const MODER14: u8 = 2;
+            pub const MODER13: u8 = 2;
+            pub const MODER12: u8 = 2;
+            pub const MODER11: u8 = 2;
+            pub const MODER10: u8 = 2;
+            pub const MODER9: u8 = 2;
+            pub const MODER8: u8 = 2;
+            pub const MODER7: u8 = 2;
+            pub const MODER6: u8 = 2;
+            pub const MODER5: u8 = 2;
+            pub const MODER4: u8 = 2;
+            pub const MODER3: u8 = 2;
+            pub const MODER2: u8 = 2;
+            pub const MODER1: u8 = 2;
+            pub const MODER0: u8 = 2;
+        }
     }
     pub mod otyper {
         pub const OT15: u8 = 15;
@@ -41,6 +59,24 @@ pub mod GPIO {
         pub const OT2: u8 = 2;
         pub const OT1: u8 = 1;
         pub const OT0: u8 = 0;
+        pub mod width {
+            pub const OT15: u8 = 1;
+            pub const OT14: u8 = 1;
+            pub const OT13: u8 = 1;
+            pub const OT12: u8 = 1;
+            pub const OT11: u8 = 1;
+            pub const OT10: u8 = 1;
+            pub const OT9: u8 = 1;
+            pub const OT8: u8 = 1;
+            pub const OT7: u8 = 1;
+            pub const OT6: u8 = 1;
+            pub const OT5: u8 = 1;
+            pub const OT4: u8 = 1;
+            pub const OT3: u8 = 1;
+            pub const OT2: u8 = 1;
+            pub const OT1: u8 = 1;
+            pub const OT0: u8 = 1;
+        }
     }
     pub mod ospeedr {
         pub const OSPEEDR15: u8 = 30;
@@ -59,6 +95,24 @@ pub mod GPIO {
         pub const OSPEEDR2: u8 = 4;
         pub const OSPEEDR1: u8 = 2;
         pub const OSPEEDR0: u8 = 0;
+        pub mod width {
+            pub const OSPEEDR15: u8 = 2;
+            pub const OSPEEDR14: u8 = 2;
+            pub const OSPEEDR13: u8 = 2;
+            pub const OSPEEDR12: u8 = 2;
+            pub const OSPEEDR11: u8 = 2;
+            pub const OSPEEDR10: u8 = 2;
+            pub const OSPEEDR9: u8 = 2;
+            pub const OSPEEDR8: u8 = 2;
+            pub const OSPEEDR7: u8 = 2;
+            pub const OSPEEDR6: u8 = 2;
+            pub const OSPEEDR5: u8 = 2;
+            pub const OSPEEDR4: u8 = 2;
+            pub const OSPEEDR3: u8 = 2;
+            pub const OSPEEDR2: u8 = 2;
+            pub const OSPEEDR1: u8 = 2;
+            pub const OSPEEDR0: u8 = 2;
+        }
     }
     pub mod pupdr {
         pub const PUPDR15: u8 = 30;
@@ -77,6 +131,24 @@ pub mod GPIO {
         pub const PUPDR2: u8 = 4;
         pub const PUPDR1: u8 = 2;
         pub const PUPDR0: u8 = 0;
+        pub mod width {
+            pub const PUPDR15: u8 = 2;
+            pub const PUPDR14: u8 = 2;
+            pub const PUPDR13: u8 = 2;
+            pub const PUPDR12: u8 = 2;
+            pub const PUPDR11: u8 = 2;
+            pub const PUPDR10: u8 = 2;
+            pub const PUPDR9: u8 = 2;
+            pub const PUPDR8: u8 = 2;
+            pub const PUPDR7: u8 = 2;
+            pub const PUPDR6: u8 = 2;
+            pub const PUPDR5: u8 = 2;
+            pub const PUPDR4: u8 = 2;
+            pub const PUPDR3: u8 = 2;
+            pub const PUPDR2: u8 = 2;
+            pub const PUPDR1: u8 = 2;
+            pub const PUPDR0: u8 = 2;
+        }
     }
     pub mod idr {
         pub const IDR15: u8 = 15;
@@ -95,6 +167,24 @@ pub mod GPIO {
         pub const IDR2: u8 = 2;
         pub const IDR1: u8 = 1;
         pub const IDR0: u8 = 0;
+        pub mod width {
+            pub const IDR15: u8 = 1;
+            pub const IDR14: u8 = 1;
+            pub const IDR13: u8 = 1;
+            pub const IDR12: u8 = 1;
+            pub const IDR11: u8 = 1;
+            pub const IDR10: u8 = 1;
+            pub const IDR9: u8 = 1;
+            pub const IDR8: u8 = 1;
+            pub const IDR7: u8 = 1;
+            pub const IDR6: u8 = 1;
+            pub const IDR5: u8 = 1;
+            pub const IDR4: u8 = 1;
+            pub const IDR3: u8 = 1;
+            pub const IDR2: u8 = 1;
+            pub const IDR1: u8 = 1;
+            pub const IDR0: u8 = 1;
+        }
     }
     pub mod odr {
         pub const ODR15: u8 = 15;
@@ -113,6 +203,24 @@ pub mod GPIO {
         pub const ODR2: u8 = 2;
         pub const ODR1: u8 = 1;
         pub const ODR0: u8 = 0;
+        pub mod width {
+            pub const ODR15: u8 = 1;
+            pub const ODR14: u8 = 1;
+            pub const ODR13: u8 = 1;
+            pub const ODR12: u8 = 1;
+            pub const ODR11: u8 = 1;
+            pub const ODR10: u8 = 1;
+            pub const ODR9: u8 = 1;
+            pub const ODR8: u8 = 1;
+            pub const ODR7: u8 = 1;
+            pub const ODR6: u8 = 1;
+            pub const ODR5: u8 = 1;
+            pub const ODR4: u8 = 1;
+            pub const ODR3: u8 = 1;
+            pub const ODR2: u8 = 1;
+            pub const ODR1: u8 = 1;
+            pub const ODR0: u8 = 1;
+        }
     }
     pub mod bsrr {
         pub const BR15: u8 = 31;
@@ -147,6 +255,40 @@ pub mod GPIO {
         pub const BS2: u8 = 2;
         pub const BS1: u8 = 1;
         pub const BS0: u8 = 0;
+        pub mod width {
+            pub const BR15: u8 = 1;
+            pub const BR14: u8 = 1;
+            pub const BR13: u8 = 1;
+            pub const BR12: u8 = 1;
+            pub const BR11: u8 = 1;
+            pub const BR10: u8 = 1;
+            pub const BR9: u8 = 1;
+            pub const BR8: u8 = 1;
+            pub const BR7: u8 = 1;
+            pub const BR6: u8 = 1;
+            pub const BR5: u8 = 1;
+            pub const BR4: u8 = 1;
+            pub const BR3: u8 = 1;
+            pub const BR2: u8 = 1;
+            pub const BR1: u8 = 1;
+            pub const BR0: u8 = 1;
+            pub const BS15: u8 = 1;
+            pub const BS14: u8 = 1;
+            pub const BS13: u8 = 1;
+            pub const BS12: u8 = 1;
+            pub const BS11: u8 = 1;
+            pub const BS10: u8 = 1;
+            pub const BS9: u8 = 1;
+            pub const BS8: u8 = 1;
+            pub const BS7: u8 = 1;
+            pub const BS6: u8 = 1;
+            pub const BS5: u8 = 1;
+            pub const BS4: u8 = 1;
+            pub const BS3: u8 = 1;
+            pub const BS2: u8 = 1;
+            pub const BS1: u8 = 1;
+            pub const BS0: u8 = 1;
+        }
     }
     pub mod lckr {
         pub const LCKK: u8 = 16;
@@ -166,6 +308,25 @@ pub mod GPIO {
         pub const LCK2: u8 = 2;
         pub const LCK1: u8 = 1;
         pub const LCK0: u8 = 0;
+        pub mod width {
+            pub const LCKK: u8 = 1;
+            pub const LCK15: u8 = 1;
+            pub const LCK14: u8 = 1;
+            pub const LCK13: u8 = 1;
+            pub const LCK12: u8 = 1;
+            pub const LCK11: u8 = 1;
+            pub const LCK10: u8 = 1;
+            pub const LCK9: u8 = 1;
+            pub const LCK8: u8 = 1;
+            pub const LCK7: u8 = 1;
+            pub const LCK6: u8 = 1;
+            pub const LCK5: u8 = 1;
+            pub const LCK4: u8 = 1;
+            pub const LCK3: u8 = 1;
+            pub const LCK2: u8 = 1;
+            pub const LCK1: u8 = 1;
+            pub const LCK0: u8 = 1;
+        }
     }
     pub mod afrl {
         pub const AFRL7: u8 = 28;
@@ -176,6 +337,16 @@ pub mod GPIO {
         pub const AFRL2: u8 = 8;
         pub const AFRL1: u8 = 4;
         pub const AFRL0: u8 = 0;
+        pub mod width {
+            pub const AFRL7: u8 = 4;
+            pub const AFRL6: u8 = 4;
+            pub const AFRL5: u8 = 4;
+            pub const AFRL4: u8 = 4;
+            pub const AFRL3: u8 = 4;
+            pub const AFRL2: u8 = 4;
+            pub const AFRL1: u8 = 4;
+            pub const AFRL0: u8 = 4;
+        }
     }
     pub mod afrh {
         pub const AFRH15: u8 = 28;
@@ -186,6 +357,16 @@ pub mod GPIO {
         pub const AFRH10: u8 = 8;
         pub const AFRH9: u8 = 4;
         pub const AFRH8: u8 = 0;
+        pub mod width {
+            pub const AFRH15: u8 = 4;
+            pub const AFRH14: u8 = 4;
+            pub const AFRH13: u8 = 4;
+            pub const AFRH12: u8 = 4;
+            pub const AFRH11: u8 = 4;
+            pub const AFRH10: u8 = 4;
+            pub const AFRH9: u8 = 4;
+            pub const AFRH8: u8 = 4;
+        }
     }
     pub mod brr {
         pub const BR0: u8 = 0;
@@ -204,6 +385,500 @@ pub mod GPIO {
         pub const BR13: u8 = 13;
         pub const BR14: u8 = 14;
         pub const BR15: u8 = 15;
+        pub mod width {
+            pub const BR0: u8 = 1;
+            pub const BR1: u8 = 1;
+            pub const BR2: u8 = 1;
+            pub const BR3: u8 = 1;
+            pub const BR4: u8 = 1;
+            pub const BR5: u8 = 1;
+            pub const BR6: u8 = 1;
+            pub const BR7: u8 = 1;
+            pub const BR8: u8 = 1;
+            pub const BR9: u8 = 1;
+            pub const BR10: u8 = 1;
+            pub const BR11: u8 = 1;
+            pub const BR12: u8 = 1;
+            pub const BR13: u8 = 1;
+            pub const BR14: u8 = 1;
+            pub const BR15: u8 = 1;
+        }
+    }
+}
+
+pub mod EXTI {
+    pub mod imr {
+        pub const MR31: u8 = 31;
+        pub const MR30: u8 = 30;
+        pub const MR29: u8 = 29;
+        pub const MR28: u8 = 28;
+        pub const MR27: u8 = 27;
+        pub const MR26: u8 = 26;
+        pub const MR25: u8 = 25;
+        pub const MR24: u8 = 24;
+        pub const MR23: u8 = 23;
+        pub const MR22: u8 = 22;
+        pub const MR21: u8 = 21;
+        pub const MR20: u8 = 20;
+        pub const MR19: u8 = 19;
+        pub const MR18: u8 = 18;
+        pub const MR17: u8 = 17;
+        pub const MR16: u8 = 16;
+        pub const MR15: u8 = 15;
+        pub const MR14: u8 = 14;
+        pub const MR13: u8 = 13;
+        pub const MR12: u8 = 12;
+        pub const MR11: u8 = 11;
+        pub const MR10: u8 = 10;
+        pub const MR9: u8 = 9;
+        pub const MR8: u8 = 8;
+        pub const MR7: u8 = 7;
+        pub const MR6: u8 = 6;
+        pub const MR5: u8 = 5;
+        pub const MR4: u8 = 4;
+        pub const MR3: u8 = 3;
+        pub const MR2: u8 = 2;
+        pub const MR1: u8 = 1;
+        pub const MR0: u8 = 0;
+        pub mod width {
+            pub const MR31: u8 = 1;
+            pub const MR30: u8 = 1;
+            pub const MR29: u8 = 1;
+            pub const MR28: u8 = 1;
+            pub const MR27: u8 = 1;
+            pub const MR26: u8 = 1;
+            pub const MR25: u8 = 1;
+            pub const MR24: u8 = 1;
+            pub const MR23: u8 = 1;
+            pub const MR22: u8 = 1;
+            pub const MR21: u8 = 1;
+            pub const MR20: u8 = 1;
+            pub const MR19: u8 = 1;
+            pub const MR18: u8 = 1;
+            pub const MR17: u8 = 1;
+            pub const MR16: u8 = 1;
+            pub const MR15: u8 = 1;
+            pub const MR14: u8 = 1;
+            pub const MR13: u8 = 1;
+            pub const MR12: u8 = 1;
+            pub const MR11: u8 = 1;
+            pub const MR10: u8 = 1;
+            pub const MR9: u8 = 1;
+            pub const MR8: u8 = 1;
+            pub const MR7: u8 = 1;
+            pub const MR6: u8 = 1;
+            pub const MR5: u8 = 1;
+            pub const MR4: u8 = 1;
+            pub const MR3: u8 = 1;
+            pub const MR2: u8 = 1;
+            pub const MR1: u8 = 1;
+            pub const MR0: u8 = 1;
+        }
+    }
+    pub mod emr {
+        pub const MR31: u8 = 31;
+        pub const MR30: u8 = 30;
+        pub const MR29: u8 = 29;
+        pub const MR28: u8 = 28;
+        pub const MR27: u8 = 27;
+        pub const MR26: u8 = 26;
+        pub const MR25: u8 = 25;
+        pub const MR24: u8 = 24;
+        pub const MR23: u8 = 23;
+        pub const MR22: u8 = 22;
+        pub const MR21: u8 = 21;
+        pub const MR20: u8 = 20;
+        pub const MR19: u8 = 19;
+        pub const MR18: u8 = 18;
+        pub const MR17: u8 = 17;
+        pub const MR16: u8 = 16;
+        pub const MR15: u8 = 15;
+        pub const MR14: u8 = 14;
+        pub const MR13: u8 = 13;
+        pub const MR12: u8 = 12;
+        pub const MR11: u8 = 11;
+        pub const MR10: u8 = 10;
+        pub const MR9: u8 = 9;
+        pub const MR8: u8 = 8;
+        pub const MR7: u8 = 7;
+        pub const MR6: u8 = 6;
+        pub const MR5: u8 = 5;
+        pub const MR4: u8 = 4;
+        pub const MR3: u8 = 3;
+        pub const MR2: u8 = 2;
+        pub const MR1: u8 = 1;
+        pub const MR0: u8 = 0;
+        pub mod width {
+            pub const MR31: u8 = 1;
+            pub const MR30: u8 = 1;
+            pub const MR29: u8 = 1;
+            pub const MR28: u8 = 1;
+            pub const MR27: u8 = 1;
+            pub const MR26: u8 = 1;
+            pub const MR25: u8 = 1;
+            pub const MR24: u8 = 1;
+            pub const MR23: u8 = 1;
+            pub const MR22: u8 = 1;
+            pub const MR21: u8 = 1;
+            pub const MR20: u8 = 1;
+            pub const MR19: u8 = 1;
+            pub const MR18: u8 = 1;
+            pub const MR17: u8 = 1;
+            pub const MR16: u8 = 1;
+            pub const MR15: u8 = 1;
+            pub const MR14: u8 = 1;
+            pub const MR13: u8 = 1;
+            pub const MR12: u8 = 1;
+            pub const MR11: u8 = 1;
+            pub const MR10: u8 = 1;
+            pub const MR9: u8 = 1;
+            pub const MR8: u8 = 1;
+            pub const MR7: u8 = 1;
+            pub const MR6: u8 = 1;
+            pub const MR5: u8 = 1;
+            pub const MR4: u8 = 1;
+            pub const MR3: u8 = 1;
+            pub const MR2: u8 = 1;
+            pub const MR1: u8 = 1;
+            pub const MR0: u8 = 1;
+        }
+    }
+    pub mod rtsr {
+        pub const TR22: u8 = 22;
+        pub const TR21: u8 = 21;
+        pub const TR20: u8 = 20;
+        pub const TR19: u8 = 19;
+        pub const TR17: u8 = 17;
+        pub const TR16: u8 = 16;
+        pub const TR15: u8 = 15;
+        pub const TR14: u8 = 14;
+        pub const TR13: u8 = 13;
+        pub const TR12: u8 = 12;
+        pub const TR11: u8 = 11;
+        pub const TR10: u8 = 10;
+        pub const TR9: u8 = 9;
+        pub const TR8: u8 = 8;
+        pub const TR7: u8 = 7;
+        pub const TR6: u8 = 6;
+        pub const TR5: u8 = 5;
+        pub const TR4: u8 = 4;
+        pub const TR3: u8 = 3;
+        pub const TR2: u8 = 2;
+        pub const TR1: u8 = 1;
+        pub const TR0: u8 = 0;
+        pub mod width {
+            pub const TR22: u8 = 1;
+            pub const TR21: u8 = 1;
+            pub const TR20: u8 = 1;
+            pub const TR19: u8 = 1;
+            pub const TR17: u8 = 1;
+            pub const TR16: u8 = 1;
+            pub const TR15: u8 = 1;
+            pub const TR14: u8 = 1;
+            pub const TR13: u8 = 1;
+            pub const TR12: u8 = 1;
+            pub const TR11: u8 = 1;
+            pub const TR10: u8 = 1;
+            pub const TR9: u8 = 1;
+            pub const TR8: u8 = 1;
+            pub const TR7: u8 = 1;
+            pub const TR6: u8 = 1;
+            pub const TR5: u8 = 1;
+            pub const TR4: u8 = 1;
+            pub const TR3: u8 = 1;
+            pub const TR2: u8 = 1;
+            pub const TR1: u8 = 1;
+            pub const TR0: u8 = 1;
+        }
+    }
+    pub mod ftsr {
+        pub const TR22: u8 = 22;
+        pub const TR21: u8 = 21;
+        pub const TR20: u8 = 20;
+        pub const TR19: u8 = 19;
+        pub const TR17: u8 = 17;
+        pub const TR16: u8 = 16;
+        pub const TR15: u8 = 15;
+        pub const TR14: u8 = 14;
+        pub const TR13: u8 = 13;
+        pub const TR12: u8 = 12;
+        pub const TR11: u8 = 11;
+        pub const TR10: u8 = 10;
+        pub const TR9: u8 = 9;
+        pub const TR8: u8 = 8;
+        pub const TR7: u8 = 7;
+        pub const TR6: u8 = 6;
+        pub const TR5: u8 = 5;
+        pub const TR4: u8 = 4;
+        pub const TR3: u8 = 3;
+        pub const TR2: u8 = 2;
+        pub const TR1: u8 = 1;
+        pub const TR0: u8 = 0;
+        pub mod width {
+            pub const TR22: u8 = 1;
+            pub const TR21: u8 = 1;
+            pub const TR20: u8 = 1;
+            pub const TR19: u8 = 1;
+            pub const TR17: u8 = 1;
+            pub const TR16: u8 = 1;
+            pub const TR15: u8 = 1;
+            pub const TR14: u8 = 1;
+            pub const TR13: u8 = 1;
+            pub const TR12: u8 = 1;
+            pub const TR11: u8 = 1;
+            pub const TR10: u8 = 1;
+            pub const TR9: u8 = 1;
+            pub const TR8: u8 = 1;
+            pub const TR7: u8 = 1;
+            pub const TR6: u8 = 1;
+            pub const TR5: u8 = 1;
+            pub const TR4: u8 = 1;
+            pub const TR3: u8 = 1;
+            pub const TR2: u8 = 1;
+            pub const TR1: u8 = 1;
+            pub const TR0: u8 = 1;
+        }
+    }
+    pub mod swier {
+        pub const SWIER22: u8 = 22;
+        pub const SWIER21: u8 = 21;
+        pub const SWIER20: u8 = 20;
+        pub const SWIER19: u8 = 19;
+        pub const SWIER17: u8 = 17;
+        pub const SWIER16: u8 = 16;
+        pub const SWIER15: u8 = 15;
+        pub const SWIER14: u8 = 14;
+        pub const SWIER13: u8 = 13;
+        pub const SWIER12: u8 = 12;
+        pub const SWIER11: u8 = 11;
+        pub const SWIER10: u8 = 10;
+        pub const SWIER9: u8 = 9;
+        pub const SWIER8: u8 = 8;
+        pub const SWIER7: u8 = 7;
+        pub const SWIER6: u8 = 6;
+        pub const SWIER5: u8 = 5;
+        pub const SWIER4: u8 = 4;
+        pub const SWIER3: u8 = 3;
+        pub const SWIER2: u8 = 2;
+        pub const SWIER1: u8 = 1;
+        pub const SWIER0: u8 = 0;
+        pub mod width {
+            pub const SWIER22: u8 = 1;
+            pub const SWIER21: u8 = 1;
+            pub const SWIER20: u8 = 1;
+            pub const SWIER19: u8 = 1;
+            pub const SWIER17: u8 = 1;
+            pub const SWIER16: u8 = 1;
+            pub const SWIER15: u8 = 1;
+            pub const SWIER14: u8 = 1;
+            pub const SWIER13: u8 = 1;
+            pub const SWIER12: u8 = 1;
+            pub const SWIER11: u8 = 1;
+            pub const SWIER10: u8 = 1;
+            pub const SWIER9: u8 = 1;
+            pub const SWIER8: u8 = 1;
+            pub const SWIER7: u8 = 1;
+            pub const SWIER6: u8 = 1;
+            pub const SWIER5: u8 = 1;
+            pub const SWIER4: u8 = 1;
+            pub const SWIER3: u8 = 1;
+            pub const SWIER2: u8 = 1;
+            pub const SWIER1: u8 = 1;
+            pub const SWIER0: u8 = 1;
+        }
+    }
+    pub mod pr {
+        pub const PR22: u8 = 22;
+        pub const PR21: u8 = 21;
+        pub const PR20: u8 = 20;
+        pub const PR19: u8 = 19;
+        pub const PR17: u8 = 17;
+        pub const PR16: u8 = 16;
+        pub const PR15: u8 = 15;
+        pub const PR14: u8 = 14;
+        pub const PR13: u8 = 13;
+        pub const PR12: u8 = 12;
+        pub const PR11: u8 = 11;
+        pub const PR10: u8 = 10;
+        pub const PR9: u8 = 9;
+        pub const PR8: u8 = 8;
+        pub const PR7: u8 = 7;
+        pub const PR6: u8 = 6;
+        pub const PR5: u8 = 5;
+        pub const PR4: u8 = 4;
+        pub const PR3: u8 = 3;
+        pub const PR2: u8 = 2;
+        pub const PR1: u8 = 1;
+        pub const PR0: u8 = 0;
+        pub mod width {
+            pub const PR22: u8 = 1;
+            pub const PR21: u8 = 1;
+            pub const PR20: u8 = 1;
+            pub const PR19: u8 = 1;
+            pub const PR17: u8 = 1;
+            pub const PR16: u8 = 1;
+            pub const PR15: u8 = 1;
+            pub const PR14: u8 = 1;
+            pub const PR13: u8 = 1;
+            pub const PR12: u8 = 1;
+            pub const PR11: u8 = 1;
+            pub const PR10: u8 = 1;
+            pub const PR9: u8 = 1;
+            pub const PR8: u8 = 1;
+            pub const PR7: u8 = 1;
+            pub const PR6: u8 = 1;
+            pub const PR5: u8 = 1;
+            pub const PR4: u8 = 1;
+            pub const PR3: u8 = 1;
+            pub const PR2: u8 = 1;
+            pub const PR1: u8 = 1;
+            pub const PR0: u8 = 1;
+        }
+    }
+}
+
+pub mod SYSCFG_COMP {
+    pub mod cfgr1 {
+        pub const MEM_MODE: u8 = 0;
+        pub mod width {
+            pub const MEM_MODE: u8 = 2;
+        }
+    }
+    pub mod exticr1 {
+        pub const EXTI3: u8 = 12;
+        pub const EXTI2: u8 = 8;
+        pub const EXTI1: u8 = 4;
+        pub const EXTI0: u8 = 0;
+        pub mod width {
+            pub const EXTI3: u8 = 4;
+            pub const EXTI2: u8 = 4;
+            pub const EXTI1: u8 = 4;
+            pub const EXTI0: u8 = 4;
+        }
+    }
+    pub mod exticr2 {
+        pub const EXTI7: u8 = 12;
+        pub const EXTI6: u8 = 8;
+        pub const EXTI5: u8 = 4;
+        pub const EXTI4: u8 = 0;
+        pub mod width {
+            pub const EXTI7: u8 = 4;
+            pub const EXTI6: u8 = 4;
+            pub const EXTI5: u8 = 4;
+            pub const EXTI4: u8 = 4;
+        }
+    }
+    pub mod exticr3 {
+        pub const EXTI11: u8 = 12;
+        pub const EXTI10: u8 = 8;
+        pub const EXTI9: u8 = 4;
+        pub const EXTI8: u8 = 0;
+        pub mod width {
+            pub const EXTI11: u8 = 4;
+            pub const EXTI10: u8 = 4;
+            pub const EXTI9: u8 = 4;
+            pub const EXTI8: u8 = 4;
+        }
+    }
+    pub mod exticr4 {
+        pub const EXTI15: u8 = 12;
+        pub const EXTI14: u8 = 8;
+        pub const EXTI13: u8 = 4;
+        pub const EXTI12: u8 = 0;
+        pub mod width {
+            pub const EXTI15: u8 = 4;
+            pub const EXTI14: u8 = 4;
+            pub const EXTI13: u8 = 4;
+            pub const EXTI12: u8 = 4;
+        }
+    }
+    pub mod cfgr2 {
+        pub const SRAM_PEF: u8 = 8;
+        pub const PVD_LOCK: u8 = 2;
+        pub const SRAM_PARITY_LOCK: u8 = 1;
+        pub const LOCUP_LOCK: u8 = 0;
+        pub mod width {
+            pub const SRAM_PEF: u8 = 1;
+            pub const PVD_LOCK: u8 = 1;
+            pub const SRAM_PARITY_LOCK: u8 = 1;
+            pub const LOCUP_LOCK: u8 = 1;
+        }
+    }
+}
+
+pub mod CRC {
+    pub mod dr {
+        pub const DR: u8 = 0;
+        pub mod width {
+            pub const DR: u8 = 8;
+        }
+    }
+    pub mod idr {
+        pub const IDR: u8 = 0;
+        pub mod width {
+            pub const IDR: u8 = 8;
+        }
+    }
+    pub mod cr {
+        pub const REV_OUT: u8 = 7;
+        pub const REV_IN: u8 = 5;
+        pub const POLYSIZE: u8 = 3;
+        pub const RESET: u8 = 0;
+        pub mod width {
+            pub const REV_OUT: u8 = 1;
+            pub const REV_IN: u8 = 2;
+            pub const POLYSIZE: u8 = 2;
+            pub const RESET: u8 = 1;
+        }
+    }
+    pub mod init {
+        pub const INIT: u8 = 0;
+        pub mod width {
+            pub const INIT: u8 = 32;
+        }
+    }
+    pub mod pol {
+        pub const POL: u8 = 0;
+        pub mod width {
+            pub const POL: u8 = 32;
+        }
+    }
+}
+
+pub mod IWDG {
+    pub mod kr {
+        pub const KEY: u8 = 0;
+        pub mod width {
+            pub const KEY: u8 = 16;
+        }
+    }
+    pub mod pr {
+        pub const PR: u8 = 0;
+        pub mod width {
+            pub const PR: u8 = 3;
+        }
+    }
+    pub mod rlr {
+        pub const RL: u8 = 0;
+        pub mod width {
+            pub const RL: u8 = 12;
+        }
+    }
+    pub mod sr {
+        pub const PVU: u8 = 0;
+        pub const RVU: u8 = 1;
+        pub const WVU: u8 = 2;
+        pub mod width {
+            pub const PVU: u8 = 1;
+            pub const RVU: u8 = 1;
+            pub const WVU: u8 = 1;
+        }
+    }
+    pub mod winr {
+        pub const WIN: u8 = 0;
+        pub mod width {
+            pub const WIN: u8 = 12;
+        }
     }
 }
 
@@ -211,14 +886,26 @@ pub mod WWDG {
     pub mod cr {
         pub const WDGA: u8 = 7;
         pub const T: u8 = 0;
+        pub mod width {
+            pub const WDGA: u8 = 1;
+            pub const T: u8 = 7;
+        }
     }
     pub mod cfr {
         pub const EWI: u8 = 9;
         pub const WDGTB: u8 = 7;
         pub const W: u8 = 0;
+        pub mod width {
+            pub const EWI: u8 = 1;
+            pub const WDGTB: u8 = 2;
+            pub const W: u8 = 7;
+        }
     }
     pub mod sr {
         pub const EWIF: u8 = 0;
+        pub mod width {
+            pub const EWIF: u8 = 1;
+        }
     }
 }
 
@@ -234,6 +921,18 @@ pub mod RCC {
         pub const CSSON: u8 = 19;
         pub const PLLON: u8 = 24;
         pub const PLLRDY: u8 = 25;
+        pub mod width {
+            pub const HSION: u8 = 1;
+            pub const HSIRDY: u8 = 1;
+            pub const HSITRIM: u8 = 5;
+            pub const HSICAL: u8 = 8;
+            pub const HSEON: u8 = 1;
+            pub const HSERDY: u8 = 1;
+            pub const HSEBYP: u8 = 1;
+            pub const CSSON: u8 = 1;
+            pub const PLLON: u8 = 1;
+            pub const PLLRDY: u8 = 1;
+        }
     }
     pub mod cfgr {
         pub const SW: u8 = 0;
@@ -247,6 +946,19 @@ pub mod RCC {
         pub const MCO: u8 = 24;
         pub const MCOPRE: u8 = 28;
         pub const PLLNODIV: u8 = 31;
+        pub mod width {
+            pub const SW: u8 = 2;
+            pub const SWS: u8 = 2;
+            pub const HPRE: u8 = 4;
+            pub const PPRE: u8 = 3;
+            pub const ADCPRE: u8 = 1;
+            pub const PLLSRC: u8 = 2;
+            pub const PLLXTPRE: u8 = 1;
+            pub const PLLMUL: u8 = 4;
+            pub const MCO: u8 = 4;
+            pub const MCOPRE: u8 = 3;
+            pub const PLLNODIV: u8 = 1;
+        }
     }
     pub mod cir {
         pub const LSIRDYF: u8 = 0;
@@ -272,6 +984,31 @@ pub mod RCC {
         pub const HSI14RDYC: u8 = 21;
         pub const HSI48RDYC: u8 = 22;
         pub const CSSC: u8 = 23;
+        pub mod width {
+            pub const LSIRDYF: u8 = 1;
+            pub const LSERDYF: u8 = 1;
+            pub const HSIRDYF: u8 = 1;
+            pub const HSERDYF: u8 = 1;
+            pub const PLLRDYF: u8 = 1;
+            pub const HSI14RDYF: u8 = 1;
+            pub const HSI48RDYF: u8 = 1;
+            pub const CSSF: u8 = 1;
+            pub const LSIRDYIE: u8 = 1;
+            pub const LSERDYIE: u8 = 1;
+            pub const HSIRDYIE: u8 = 1;
+            pub const HSERDYIE: u8 = 1;
+            pub const PLLRDYIE: u8 = 1;
+            pub const HSI14RDYE: u8 = 1;
+            pub const HSI48RDYIE: u8 = 1;
+            pub const LSIRDYC: u8 = 1;
+            pub const LSERDYC: u8 = 1;
+            pub const HSIRDYC: u8 = 1;
+            pub const HSERDYC: u8 = 1;
+            pub const PLLRDYC: u8 = 1;
+            pub const HSI14RDYC: u8 = 1;
+            pub const HSI48RDYC: u8 = 1;
+            pub const CSSC: u8 = 1;
+        }
     }
     pub mod apb2rstr {
         pub const SYSCFGRST: u8 = 0;
@@ -283,6 +1020,17 @@ pub mod RCC {
         pub const TIM16RST: u8 = 17;
         pub const TIM17RST: u8 = 18;
         pub const DBGMCURST: u8 = 22;
+        pub mod width {
+            pub const SYSCFGRST: u8 = 1;
+            pub const ADCRST: u8 = 1;
+            pub const TIM1RST: u8 = 1;
+            pub const SPI1RST: u8 = 1;
+            pub const USART1RST: u8 = 1;
+            pub const TIM15RST: u8 = 1;
+            pub const TIM16RST: u8 = 1;
+            pub const TIM17RST: u8 = 1;
+            pub const DBGMCURST: u8 = 1;
+        }
     }
     pub mod apb1rstr {
         pub const TIM2RST: u8 = 0;
@@ -303,6 +1051,26 @@ pub mod RCC {
         pub const PWRRST: u8 = 28;
         pub const DACRST: u8 = 29;
         pub const CECRST: u8 = 30;
+        pub mod width {
+            pub const TIM2RST: u8 = 1;
+            pub const TIM3RST: u8 = 1;
+            pub const TIM6RST: u8 = 1;
+            pub const TIM7RST: u8 = 1;
+            pub const TIM14RST: u8 = 1;
+            pub const WWDGRST: u8 = 1;
+            pub const SPI2RST: u8 = 1;
+            pub const USART2RST: u8 = 1;
+            pub const USART3RST: u8 = 1;
+            pub const USART4RST: u8 = 1;
+            pub const I2C1RST: u8 = 1;
+            pub const I2C2RST: u8 = 1;
+            pub const USBRST: u8 = 1;
+            pub const CANRST: u8 = 1;
+            pub const CRSRST: u8 = 1;
+            pub const PWRRST: u8 = 1;
+            pub const DACRST: u8 = 1;
+            pub const CECRST: u8 = 1;
+        }
     }
     pub mod ahbenr {
         pub const DMA1EN: u8 = 0;
@@ -315,6 +1083,18 @@ pub mod RCC {
         pub const IOPDEN: u8 = 20;
         pub const IOPFEN: u8 = 22;
         pub const TSCEN: u8 = 24;
+        pub mod width {
+            pub const DMA1EN: u8 = 1;
+            pub const SRAMEN: u8 = 1;
+            pub const FLITFEN: u8 = 1;
+            pub const CRCEN: u8 = 1;
+            pub const IOPAEN: u8 = 1;
+            pub const IOPBEN: u8 = 1;
+            pub const IOPCEN: u8 = 1;
+            pub const IOPDEN: u8 = 1;
+            pub const IOPFEN: u8 = 1;
+            pub const TSCEN: u8 = 1;
+        }
     }
     pub mod apb2enr {
         pub const SYSCFGEN: u8 = 0;
@@ -326,6 +1106,17 @@ pub mod RCC {
         pub const TIM16EN: u8 = 17;
         pub const TIM17EN: u8 = 18;
         pub const DBGMCUEN: u8 = 22;
+        pub mod width {
+            pub const SYSCFGEN: u8 = 1;
+            pub const ADCEN: u8 = 1;
+            pub const TIM1EN: u8 = 1;
+            pub const SPI1EN: u8 = 1;
+            pub const USART1EN: u8 = 1;
+            pub const TIM15EN: u8 = 1;
+            pub const TIM16EN: u8 = 1;
+            pub const TIM17EN: u8 = 1;
+            pub const DBGMCUEN: u8 = 1;
+        }
     }
     pub mod apb1enr {
         pub const TIM2EN: u8 = 0;
@@ -346,6 +1137,26 @@ pub mod RCC {
         pub const PWREN: u8 = 28;
         pub const DACEN: u8 = 29;
         pub const CECEN: u8 = 30;
+        pub mod width {
+            pub const TIM2EN: u8 = 1;
+            pub const TIM3EN: u8 = 1;
+            pub const TIM6EN: u8 = 1;
+            pub const TIM7EN: u8 = 1;
+            pub const TIM14EN: u8 = 1;
+            pub const WWDGEN: u8 = 1;
+            pub const SPI2EN: u8 = 1;
+            pub const USART2EN: u8 = 1;
+            pub const USART3EN: u8 = 1;
+            pub const USART4EN: u8 = 1;
+            pub const I2C1EN: u8 = 1;
+            pub const I2C2EN: u8 = 1;
+            pub const USBRST: u8 = 1;
+            pub const CANEN: u8 = 1;
+            pub const CRSEN: u8 = 1;
+            pub const PWREN: u8 = 1;
+            pub const DACEN: u8 = 1;
+            pub const CECEN: u8 = 1;
+        }
     }
     pub mod bdcr {
         pub const LSEON: u8 = 0;
@@ -355,6 +1166,15 @@ pub mod RCC {
         pub const RTCSEL: u8 = 8;
         pub const RTCEN: u8 = 15;
         pub const BDRST: u8 = 16;
+        pub mod width {
+            pub const LSEON: u8 = 1;
+            pub const LSERDY: u8 = 1;
+            pub const LSEBYP: u8 = 1;
+            pub const LSEDRV: u8 = 2;
+            pub const RTCSEL: u8 = 2;
+            pub const RTCEN: u8 = 1;
+            pub const BDRST: u8 = 1;
+        }
     }
     pub mod csr {
         pub const LSION: u8 = 0;
@@ -367,6 +1187,18 @@ pub mod RCC {
         pub const IWDGRSTF: u8 = 29;
         pub const WWDGRSTF: u8 = 30;
         pub const LPWRRSTF: u8 = 31;
+        pub mod width {
+            pub const LSION: u8 = 1;
+            pub const LSIRDY: u8 = 1;
+            pub const RMVF: u8 = 1;
+            pub const OBLRSTF: u8 = 1;
+            pub const PINRSTF: u8 = 1;
+            pub const PORRSTF: u8 = 1;
+            pub const SFTRSTF: u8 = 1;
+            pub const IWDGRSTF: u8 = 1;
+            pub const WWDGRSTF: u8 = 1;
+            pub const LPWRRSTF: u8 = 1;
+        }
     }
     pub mod ahbrstr {
         pub const IOPARST: u8 = 17;
@@ -375,9 +1207,20 @@ pub mod RCC {
         pub const IOPDRST: u8 = 20;
         pub const IOPFRST: u8 = 22;
         pub const TSCRST: u8 = 24;
+        pub mod width {
+            pub const IOPARST: u8 = 1;
+            pub const IOPBRST: u8 = 1;
+            pub const IOPCRST: u8 = 1;
+            pub const IOPDRST: u8 = 1;
+            pub const IOPFRST: u8 = 1;
+            pub const TSCRST: u8 = 1;
+        }
     }
     pub mod cfgr2 {
         pub const PREDIV: u8 = 0;
+        pub mod width {
+            pub const PREDIV: u8 = 4;
+        }
     }
     pub mod cfgr3 {
         pub const USART1SW: u8 = 0;
@@ -386,6 +1229,14 @@ pub mod RCC {
         pub const USBSW: u8 = 7;
         pub const ADCSW: u8 = 8;
         pub const USART2SW: u8 = 16;
+        pub mod width {
+            pub const USART1SW: u8 = 2;
+            pub const I2C1SW: u8 = 1;
+            pub const CECSW: u8 = 1;
+            pub const USBSW: u8 = 1;
+            pub const ADCSW: u8 = 1;
+            pub const USART2SW: u8 = 2;
+        }
     }
     pub mod cr2 {
         pub const HSI14ON: u8 = 0;
@@ -396,6 +1247,16 @@ pub mod RCC {
         pub const HSI48ON: u8 = 16;
         pub const HSI48RDY: u8 = 17;
         pub const HSI48CAL: u8 = 24;
+        pub mod width {
+            pub const HSI14ON: u8 = 1;
+            pub const HSI14RDY: u8 = 1;
+            pub const HSI14DIS: u8 = 1;
+            pub const HSI14TRIM: u8 = 5;
+            pub const HSI14CAL: u8 = 8;
+            pub const HSI48ON: u8 = 1;
+            pub const HSI48RDY: u8 = 1;
+            pub const HSI48CAL: u8 = 8;
+        }
     }
 }
 
@@ -422,6 +1283,29 @@ pub mod USART {
         pub const RTOIE: u8 = 26;
         pub const EOBIE: u8 = 27;
         pub const M1: u8 = 28;
+        pub mod width {
+            pub const UE: u8 = 1;
+            pub const UESM: u8 = 1;
+            pub const RE: u8 = 1;
+            pub const TE: u8 = 1;
+            pub const IDLEIE: u8 = 1;
+            pub const RXNEIE: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const TXEIE: u8 = 1;
+            pub const PEIE: u8 = 1;
+            pub const PS: u8 = 1;
+            pub const PCE: u8 = 1;
+            pub const WAKE: u8 = 1;
+            pub const M: u8 = 1;
+            pub const MME: u8 = 1;
+            pub const CMIE: u8 = 1;
+            pub const OVER8: u8 = 1;
+            pub const DEDT: u8 = 5;
+            pub const DEAT: u8 = 5;
+            pub const RTOIE: u8 = 1;
+            pub const EOBIE: u8 = 1;
+            pub const M1: u8 = 1;
+        }
     }
     pub mod cr2 {
         pub const ADD4: u8 = 28;
@@ -443,6 +1327,27 @@ pub mod USART {
         pub const LBDIE: u8 = 6;
         pub const LBDL: u8 = 5;
         pub const ADDM7: u8 = 4;
+        pub mod width {
+            pub const ADD4: u8 = 4;
+            pub const ADD0: u8 = 4;
+            pub const RTOEN: u8 = 1;
+            pub const ABRMOD: u8 = 2;
+            pub const ABREN: u8 = 1;
+            pub const MSBFIRST: u8 = 1;
+            pub const DATAINV: u8 = 1;
+            pub const TXINV: u8 = 1;
+            pub const RXINV: u8 = 1;
+            pub const SWAP: u8 = 1;
+            pub const LINEN: u8 = 1;
+            pub const STOP: u8 = 2;
+            pub const CLKEN: u8 = 1;
+            pub const CPOL: u8 = 1;
+            pub const CPHA: u8 = 1;
+            pub const LBCL: u8 = 1;
+            pub const LBDIE: u8 = 1;
+            pub const LBDL: u8 = 1;
+            pub const ADDM7: u8 = 1;
+        }
     }
     pub mod cr3 {
         pub const WUFIE: u8 = 22;
@@ -464,18 +1369,51 @@ pub mod USART {
         pub const IRLP: u8 = 2;
         pub const IREN: u8 = 1;
         pub const EIE: u8 = 0;
+        pub mod width {
+            pub const WUFIE: u8 = 1;
+            pub const WUS: u8 = 2;
+            pub const SCARCNT: u8 = 3;
+            pub const DEP: u8 = 1;
+            pub const DEM: u8 = 1;
+            pub const DDRE: u8 = 1;
+            pub const OVRDIS: u8 = 1;
+            pub const ONEBIT: u8 = 1;
+            pub const CTSIE: u8 = 1;
+            pub const CTSE: u8 = 1;
+            pub const RTSE: u8 = 1;
+            pub const DMAT: u8 = 1;
+            pub const DMAR: u8 = 1;
+            pub const SCEN: u8 = 1;
+            pub const NACK: u8 = 1;
+            pub const HDSEL: u8 = 1;
+            pub const IRLP: u8 = 1;
+            pub const IREN: u8 = 1;
+            pub const EIE: u8 = 1;
+        }
     }
     pub mod brr {
         pub const DIV_Mantissa: u8 = 4;
         pub const DIV_Fraction: u8 = 0;
+        pub mod width {
+            pub const DIV_Mantissa: u8 = 12;
+            pub const DIV_Fraction: u8 = 4;
+        }
     }
     pub mod gtpr {
         pub const GT: u8 = 8;
         pub const PSC: u8 = 0;
+        pub mod width {
+            pub const GT: u8 = 8;
+            pub const PSC: u8 = 8;
+        }
     }
     pub mod rtor {
         pub const BLEN: u8 = 24;
         pub const RTO: u8 = 0;
+        pub mod width {
+            pub const BLEN: u8 = 8;
+            pub const RTO: u8 = 24;
+        }
     }
     pub mod rqr {
         pub const TXFRQ: u8 = 4;
@@ -483,6 +1421,13 @@ pub mod USART {
         pub const MMRQ: u8 = 2;
         pub const SBKRQ: u8 = 1;
         pub const ABRRQ: u8 = 0;
+        pub mod width {
+            pub const TXFRQ: u8 = 1;
+            pub const RXFRQ: u8 = 1;
+            pub const MMRQ: u8 = 1;
+            pub const SBKRQ: u8 = 1;
+            pub const ABRRQ: u8 = 1;
+        }
     }
     pub mod isr {
         pub const REACK: u8 = 22;
@@ -507,6 +1452,30 @@ pub mod USART {
         pub const NF: u8 = 2;
         pub const FE: u8 = 1;
         pub const PE: u8 = 0;
+        pub mod width {
+            pub const REACK: u8 = 1;
+            pub const TEACK: u8 = 1;
+            pub const WUF: u8 = 1;
+            pub const RWU: u8 = 1;
+            pub const SBKF: u8 = 1;
+            pub const CMF: u8 = 1;
+            pub const BUSY: u8 = 1;
+            pub const ABRF: u8 = 1;
+            pub const ABRE: u8 = 1;
+            pub const EOBF: u8 = 1;
+            pub const RTOF: u8 = 1;
+            pub const CTS: u8 = 1;
+            pub const CTSIF: u8 = 1;
+            pub const LBDF: u8 = 1;
+            pub const TXE: u8 = 1;
+            pub const TC: u8 = 1;
+            pub const RXNE: u8 = 1;
+            pub const IDLE: u8 = 1;
+            pub const ORE: u8 = 1;
+            pub const NF: u8 = 1;
+            pub const FE: u8 = 1;
+            pub const PE: u8 = 1;
+        }
     }
     pub mod icr {
         pub const WUCF: u8 = 20;
@@ -521,12 +1490,231 @@ pub mod USART {
         pub const NCF: u8 = 2;
         pub const FECF: u8 = 1;
         pub const PECF: u8 = 0;
+        pub mod width {
+            pub const WUCF: u8 = 1;
+            pub const CMCF: u8 = 1;
+            pub const EOBCF: u8 = 1;
+            pub const RTOCF: u8 = 1;
+            pub const CTSCF: u8 = 1;
+            pub const LBDCF: u8 = 1;
+            pub const TCCF: u8 = 1;
+            pub const IDLECF: u8 = 1;
+            pub const ORECF: u8 = 1;
+            pub const NCF: u8 = 1;
+            pub const FECF: u8 = 1;
+            pub const PECF: u8 = 1;
+        }
     }
     pub mod rdr {
         pub const RDR: u8 = 0;
+        pub mod width {
+            pub const RDR: u8 = 9;
+        }
     }
     pub mod tdr {
         pub const TDR: u8 = 0;
+        pub mod width {
+            pub const TDR: u8 = 9;
+        }
+    }
+}
+
+pub mod I2C {
+    pub mod cr1 {
+        pub const PE: u8 = 0;
+        pub const TXIE: u8 = 1;
+        pub const RXIE: u8 = 2;
+        pub const ADDRIE: u8 = 3;
+        pub const NACKIE: u8 = 4;
+        pub const STOPIE: u8 = 5;
+        pub const TCIE: u8 = 6;
+        pub const ERRIE: u8 = 7;
+        pub const DNF: u8 = 8;
+        pub const ANFOFF: u8 = 12;
+        pub const TXDMAEN: u8 = 14;
+        pub const RXDMAEN: u8 = 15;
+        pub const SBC: u8 = 16;
+        pub const NOSTRETCH: u8 = 17;
+        pub const WUPEN: u8 = 18;
+        pub const GCEN: u8 = 19;
+        pub const SMBHEN: u8 = 20;
+        pub const SMBDEN: u8 = 21;
+        pub const ALERTEN: u8 = 22;
+        pub const PECEN: u8 = 23;
+        pub mod width {
+            pub const PE: u8 = 1;
+            pub const TXIE: u8 = 1;
+            pub const RXIE: u8 = 1;
+            pub const ADDRIE: u8 = 1;
+            pub const NACKIE: u8 = 1;
+            pub const STOPIE: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const ERRIE: u8 = 1;
+            pub const DNF: u8 = 4;
+            pub const ANFOFF: u8 = 1;
+            pub const TXDMAEN: u8 = 1;
+            pub const RXDMAEN: u8 = 1;
+            pub const SBC: u8 = 1;
+            pub const NOSTRETCH: u8 = 1;
+            pub const WUPEN: u8 = 1;
+            pub const GCEN: u8 = 1;
+            pub const SMBHEN: u8 = 1;
+            pub const SMBDEN: u8 = 1;
+            pub const ALERTEN: u8 = 1;
+            pub const PECEN: u8 = 1;
+        }
+    }
+    pub mod cr2 {
+        pub const SADD: u8 = 0;
+        pub const RD_WRN: u8 = 10;
+        pub const ADD10: u8 = 11;
+        pub const HEAD10R: u8 = 12;
+        pub const START: u8 = 13;
+        pub const STOP: u8 = 14;
+        pub const NACK: u8 = 15;
+        pub const NBYTES: u8 = 16;
+        pub const RELOAD: u8 = 24;
+        pub const AUTOEND: u8 = 25;
+        pub const PECBYTE: u8 = 26;
+        pub mod width {
+            pub const SADD: u8 = 10;
+            pub const RD_WRN: u8 = 1;
+            pub const ADD10: u8 = 1;
+            pub const HEAD10R: u8 = 1;
+            pub const START: u8 = 1;
+            pub const STOP: u8 = 1;
+            pub const NACK: u8 = 1;
+            pub const NBYTES: u8 = 8;
+            pub const RELOAD: u8 = 1;
+            pub const AUTOEND: u8 = 1;
+            pub const PECBYTE: u8 = 1;
+        }
+    }
+    pub mod oar1 {
+        pub const OA1: u8 = 0;
+        pub const OA1MODE: u8 = 10;
+        pub const OA1EN: u8 = 15;
+        pub mod width {
+            pub const OA1: u8 = 10;
+            pub const OA1MODE: u8 = 1;
+            pub const OA1EN: u8 = 1;
+        }
+    }
+    pub mod oar2 {
+        pub const OA2: u8 = 1;
+        pub const OA2MSK: u8 = 8;
+        pub const OA2EN: u8 = 15;
+        pub mod width {
+            pub const OA2: u8 = 7;
+            pub const OA2MSK: u8 = 3;
+            pub const OA2EN: u8 = 1;
+        }
+    }
+    pub mod timingr {
+        pub const SCLL: u8 = 0;
+        pub const SCLH: u8 = 8;
+        pub const SDADEL: u8 = 16;
+        pub const SCLDEL: u8 = 20;
+        pub const PRESC: u8 = 28;
+        pub mod width {
+            pub const SCLL: u8 = 8;
+            pub const SCLH: u8 = 8;
+            pub const SDADEL: u8 = 4;
+            pub const SCLDEL: u8 = 4;
+            pub const PRESC: u8 = 4;
+        }
+    }
+    pub mod timeoutr {
+        pub const TIMEOUTA: u8 = 0;
+        pub const TIDLE: u8 = 12;
+        pub const TIMOUTEN: u8 = 15;
+        pub const TIMEOUTB: u8 = 16;
+        pub const TEXTEN: u8 = 31;
+        pub mod width {
+            pub const TIMEOUTA: u8 = 12;
+            pub const TIDLE: u8 = 1;
+            pub const TIMOUTEN: u8 = 1;
+            pub const TIMEOUTB: u8 = 12;
+            pub const TEXTEN: u8 = 1;
+        }
+    }
+    pub mod isr {
+        pub const TXE: u8 = 0;
+        pub const TXIS: u8 = 1;
+        pub const RXNE: u8 = 2;
+        pub const ADDR: u8 = 3;
+        pub const NACKF: u8 = 4;
+        pub const STOPF: u8 = 5;
+        pub const TC: u8 = 6;
+        pub const TCR: u8 = 7;
+        pub const BERR: u8 = 8;
+        pub const ARLO: u8 = 9;
+        pub const OVR: u8 = 10;
+        pub const PECERR: u8 = 11;
+        pub const TIMEOUT: u8 = 12;
+        pub const ALERT: u8 = 13;
+        pub const BUSY: u8 = 15;
+        pub const DIR: u8 = 16;
+        pub const ADDCODE: u8 = 17;
+        pub mod width {
+            pub const TXE: u8 = 1;
+            pub const TXIS: u8 = 1;
+            pub const RXNE: u8 = 1;
+            pub const ADDR: u8 = 1;
+            pub const NACKF: u8 = 1;
+            pub const STOPF: u8 = 1;
+            pub const TC: u8 = 1;
+            pub const TCR: u8 = 1;
+            pub const BERR: u8 = 1;
+            pub const ARLO: u8 = 1;
+            pub const OVR: u8 = 1;
+            pub const PECERR: u8 = 1;
+            pub const TIMEOUT: u8 = 1;
+            pub const ALERT: u8 = 1;
+            pub const BUSY: u8 = 1;
+            pub const DIR: u8 = 1;
+            pub const ADDCODE: u8 = 7;
+        }
+    }
+    pub mod icr {
+        pub const ADDRCF: u8 = 3;
+        pub const NACKCF: u8 = 4;
+        pub const STOPCF: u8 = 5;
+        pub const BERRCF: u8 = 8;
+        pub const ARLOCF: u8 = 9;
+        pub const OVRCF: u8 = 10;
+        pub const PECCF: u8 = 11;
+        pub const TIMOUTCF: u8 = 12;
+        pub const ALERTCF: u8 = 13;
+        pub mod width {
+            pub const ADDRCF: u8 = 1;
+            pub const NACKCF: u8 = 1;
+            pub const STOPCF: u8 = 1;
+            pub const BERRCF: u8 = 1;
+            pub const ARLOCF: u8 = 1;
+            pub const OVRCF: u8 = 1;
+            pub const PECCF: u8 = 1;
+            pub const TIMOUTCF: u8 = 1;
+            pub const ALERTCF: u8 = 1;
+        }
+    }
+    pub mod pecr {
+        pub const PEC: u8 = 0;
+        pub mod width {
+            pub const PEC: u8 = 8;
+        }
+    }
+    pub mod rxdr {
+        pub const RXDATA: u8 = 0;
+        pub mod width {
+            pub const RXDATA: u8 = 8;
+        }
+    }
+    pub mod txdr {
+        pub const TXDATA: u8 = 0;
+        pub mod width {
+            pub const TXDATA: u8 = 8;
+        }
     }
 }
 
@@ -535,18 +1723,35 @@ pub mod FLASH {
         pub const LATENCY: u8 = 0;
         pub const PRFTBE: u8 = 4;
         pub const PRFTBS: u8 = 5;
+        pub mod width {
+            pub const LATENCY: u8 = 3;
+            pub const PRFTBE: u8 = 1;
+            pub const PRFTBS: u8 = 1;
+        }
     }
     pub mod keyr {
         pub const FKEYR: u8 = 0;
+        pub mod width {
+            pub const FKEYR: u8 = 32;
+        }
     }
     pub mod optkeyr {
         pub const OPTKEYR: u8 = 0;
+        pub mod width {
+            pub const OPTKEYR: u8 = 32;
+        }
     }
     pub mod sr {
         pub const EOP: u8 = 5;
         pub const WRPRT: u8 = 4;
         pub const PGERR: u8 = 2;
         pub const BSY: u8 = 0;
+        pub mod width {
+            pub const EOP: u8 = 1;
+            pub const WRPRT: u8 = 1;
+            pub const PGERR: u8 = 1;
+            pub const BSY: u8 = 1;
+        }
     }
     pub mod cr {
         pub const FORCE_OPTLOAD: u8 = 13;
@@ -560,9 +1765,25 @@ pub mod FLASH {
         pub const MER: u8 = 2;
         pub const PER: u8 = 1;
         pub const PG: u8 = 0;
+        pub mod width {
+            pub const FORCE_OPTLOAD: u8 = 1;
+            pub const EOPIE: u8 = 1;
+            pub const ERRIE: u8 = 1;
+            pub const OPTWRE: u8 = 1;
+            pub const LOCK: u8 = 1;
+            pub const STRT: u8 = 1;
+            pub const OPTER: u8 = 1;
+            pub const OPTPG: u8 = 1;
+            pub const MER: u8 = 1;
+            pub const PER: u8 = 1;
+            pub const PG: u8 = 1;
+        }
     }
     pub mod ar {
         pub const FAR: u8 = 0;
+        pub mod width {
+            pub const FAR: u8 = 32;
+        }
     }
     pub mod obr {
         pub const OPTERR: u8 = 0;
@@ -577,9 +1798,26 @@ pub mod FLASH {
         pub const BOOT_SEL: u8 = 15;
         pub const Data0: u8 = 16;
         pub const Data1: u8 = 24;
+        pub mod width {
+            pub const OPTERR: u8 = 1;
+            pub const RDPRT: u8 = 2;
+            pub const WDG_SW: u8 = 1;
+            pub const nRST_STOP: u8 = 1;
+            pub const nRST_STDBY: u8 = 1;
+            pub const nBOOT0: u8 = 1;
+            pub const nBOOT1: u8 = 1;
+            pub const VDDA_MONITOR: u8 = 1;
+            pub const RAM_PARITY_CHECK: u8 = 1;
+            pub const BOOT_SEL: u8 = 1;
+            pub const Data0: u8 = 8;
+            pub const Data1: u8 = 8;
+        }
     }
     pub mod wrpr {
         pub const WRP: u8 = 0;
+        pub mod width {
+            pub const WRP: u8 = 32;
+        }
     }
 }
 
@@ -588,10 +1826,19 @@ pub mod DBGMCU {
         pub const DEV_ID: u8 = 0;
         pub const DIV_ID: u8 = 12;
         pub const REV_ID: u8 = 16;
+        pub mod width {
+            pub const DEV_ID: u8 = 12;
+            pub const DIV_ID: u8 = 4;
+            pub const REV_ID: u8 = 16;
+        }
     }
     pub mod cr {
         pub const DBG_STOP: u8 = 1;
         pub const DBG_STANDBY: u8 = 2;
+        pub mod width {
+            pub const DBG_STOP: u8 = 1;
+            pub const DBG_STANDBY: u8 = 1;
+        }
     }
     pub mod apb1_fz {
         pub const DBG_TIM2_STOP: u8 = 0;
@@ -604,12 +1851,30 @@ pub mod DBGMCU {
         pub const DBG_IWDG_STOP: u8 = 12;
         pub const DBG_I2C1_SMBUS_TIMEOUT: u8 = 21;
         pub const DBG_CAN_STOP: u8 = 25;
+        pub mod width {
+            pub const DBG_TIM2_STOP: u8 = 1;
+            pub const DBG_TIM3_STOP: u8 = 1;
+            pub const DBG_TIM6_STOP: u8 = 1;
+            pub const DBG_TIM7_STOP: u8 = 1;
+            pub const DBG_TIM14_STOP: u8 = 1;
+            pub const DBG_RTC_STOP: u8 = 1;
+            pub const DBG_WWDG_STOP: u8 = 1;
+            pub const DBG_IWDG_STOP: u8 = 1;
+            pub const DBG_I2C1_SMBUS_TIMEOUT: u8 = 1;
+            pub const DBG_CAN_STOP: u8 = 1;
+        }
     }
     pub mod apb2_fz {
         pub const DBG_TIM1_STOP: u8 = 11;
         pub const DBG_TIM15_STOP: u8 = 16;
         pub const DBG_TIM16_STOP: u8 = 17;
         pub const DBG_TIM17_STOP: u8 = 18;
+        pub mod width {
+            pub const DBG_TIM1_STOP: u8 = 1;
+            pub const DBG_TIM15_STOP: u8 = 1;
+            pub const DBG_TIM16_STOP: u8 = 1;
+            pub const DBG_TIM17_STOP: u8 = 1;
+        }
     }
 }
 
@@ -628,6 +1893,46 @@ pub struct GpioReg {
     pub brr: u32,
 }
 
+#[repr(C)]
+pub struct ExtiReg {
+    pub imr: u32,
+    pub emr: u32,
+    pub rtsr: u32,
+    pub ftsr: u32,
+    pub swier: u32,
+    pub pr: u32,
+}
+
+#[repr(C)]
+pub struct SyscfgCompReg {
+    pub cfgr1: u32,
+    reserved0: u32,
+    pub exticr1: u32,
+    pub exticr2: u32,
+    pub exticr3: u32,
+    pub exticr4: u32,
+    pub cfgr2: u32,
+}
+
+#[repr(C)]
+pub struct CrcReg {
+    pub dr: u32,
+    pub idr: u32,
+    pub cr: u32,
+    reserved0: u32,
+    pub init: u32,
+    pub pol: u32,
+}
+
+#[repr(C)]
+pub struct IwdgReg {
+    pub kr: u32,
+    pub pr: u32,
+    pub rlr: u32,
+    pub sr: u32,
+    pub winr: u32,
+}
+
 #[repr(C)]
 pub struct WwdgReg {
     pub cr: u32,
@@ -668,6 +1973,21 @@ pub struct UsartReg {
     pub tdr: u32,
 }
 
+#[repr(C)]
+pub struct I2cReg {
+    pub cr1: u32,
+    pub cr2: u32,
+    pub oar1: u32,
+    pub oar2: u32,
+    pub timingr: u32,
+    pub timeoutr: u32,
+    pub isr: u32,
+    pub icr: u32,
+    pub pecr: u32,
+    pub rxdr: u32,
+    pub txdr: u32,
+}
+
 #[repr(C)]
 pub struct FlashReg {
     pub acr: u32,
@@ -688,7 +2008,7 @@ pub struct DbgmcuReg {
     pub apb1_fz: u32,
     pub apb2_fz: u32,
 }
-// Skipped peripheral: CRC
+pub const CRC: *mut CrcReg = 0x40023000 as *mut CrcReg;
 // Skipped peripheral: GPIOF
 // Skipped peripheral: GPIOD
 // Skipped peripheral: GPIOC
@@ -701,9 +2021,12 @@ pub const GPIOA: *mut GpioReg = 0x48000000 as *mut GpioReg;
 // Skipped peripheral: SPI2
 // Skipped peripheral: DAC
 // Skipped peripheral: PWR
-// Skipped peripheral: I2C1
-// Skipped peripheral: I2C2
-// Skipped peripheral: IWDG
+
+pub const I2C1: *mut I2cReg = 0x40005400 as *mut I2cReg;
+
+pub const I2C2: *mut I2cReg = 0x40005800 as *mut I2cReg;
+
+pub const IWDG: *mut IwdgReg = 0x40003000 as *mut IwdgReg;
 
 pub const WWDG: *mut WwdgReg = 0x40002C00 as *mut WwdgReg;
 // Skipped peripheral: TIM1
@@ -712,19 +2035,23 @@ pub const WWDG: *mut WwdgReg = 0x40002C00 as *mut WwdgReg;
 // Skipped peripheral: TIM14
 // Skipped peripheral: TIM6
 // Skipped peripheral: TIM7
-// Skipped peripheral: EXTI
+
+pub const EXTI: *mut ExtiReg = 0x40010400 as *mut ExtiReg;
 // Skipped peripheral: NVIC
 // Skipped peripheral: DMA1
 
 pub const RCC: *mut RccReg = 0x40021000 as *mut RccReg;
-// Skipped peripheral: SYSCFG_COMP
+
+pub const SYSCFG_COMP: *mut SyscfgCompReg = 0x40010000 as *mut SyscfgCompReg;
 // Skipped peripheral: ADC
 
 pub const USART1: *mut UsartReg = 0x40013800 as *mut UsartReg;
 
 pub const USART2: *mut UsartReg = 0x40004400 as *mut UsartReg;
-// Skipped peripheral: USART3
-// Skipped peripheral: USART4
+
+pub const USART3: *mut UsartReg = 0x40004800 as *mut UsartReg;
+
+pub const USART4: *mut UsartReg = 0x40004C00 as *mut UsartReg;
 // Skipped peripheral: RTC
 // Skipped peripheral: TIM15
 // Skipped peripheral: TIM16
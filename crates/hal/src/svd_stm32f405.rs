@@ -5,10 +5,44 @@
 #![allow(non_snake_case)]
 #![allow(non_upper_case_globals)]
 
+pub mod RNG {
+    pub mod cr {
+        pub const IE: u8 = 3;
+        pub const RNGEN: u8 = 2;
+        pub mod width {
+            pub const IE: u8 = 1;
+            pub const RNGEN: u8 = 1;
+        }
+    }
+    pub mod sr {
+        pub const SEIS: u8 = 6;
+        pub const CEIS: u8 = 5;
+        pub const SECS: u8 = 2;
+        pub const CECS: u8 = 1;
+        pub const DRDY: u8 = 0;
+        pub mod width {
+            pub const SEIS: u8 = 1;
+            pub const CEIS: u8 = 1;
+            pub const SECS: u8 = 1;
+            pub const CECS: u8 = 1;
+            pub const DRDY: u8 = 1;
+        }
+    }
+    pub mod dr {
+        pub const RNDATA: u8 = 0;
+        pub mod width {
+            pub const RNDATA: u8 = 32;
+        }
+    }
+}
 pub mod DBG {
     pub mod dbgmcu_idcode {
         pub const DEV_ID: u8 = 0;
         pub const REV_ID: u8 = 16;
+        pub mod width {
+            pub const DEV_ID: u8 = 12;
+            pub const REV_ID: u8 = 16;
+        }
     }
     pub mod dbgmcu_cr {
         pub const DBG_SLEEP: u8 = 0;
@@ -21,6 +55,18 @@ pub mod DBG {
         pub const DBG_TIM5_STOP: u8 = 18;
         pub const DBG_TIM6_STOP: u8 = 19;
         pub const DBG_TIM7_STOP: u8 = 20;
+        pub mod width {
+            pub const DBG_SLEEP: u8 = 1;
+            pub const DBG_STOP: u8 = 1;
+            pub const DBG_STANDBY: u8 = 1;
+            pub const TRACE_IOEN: u8 = 1;
+            pub const TRACE_MODE: u8 = 2;
+            pub const DBG_I2C2_SMBUS_TIMEOUT: u8 = 1;
+            pub const DBG_TIM8_STOP: u8 = 1;
+            pub const DBG_TIM5_STOP: u8 = 1;
+            pub const DBG_TIM6_STOP: u8 = 1;
+            pub const DBG_TIM7_STOP: u8 = 1;
+        }
     }
     pub mod dbgmcu_apb1_fz {
         pub const DBG_TIM2_STOP: u8 = 0;
@@ -39,6 +85,24 @@ pub mod DBG {
         pub const DBG_J2C3SMBUS_TIMEOUT: u8 = 23;
         pub const DBG_CAN1_STOP: u8 = 25;
         pub const DBG_CAN2_STOP: u8 = 26;
+        pub mod width {
+            pub const DBG_TIM2_STOP: u8 = 1;
+            pub const DBG_TIM3_STOP: u8 = 1;
+            pub const DBG_TIM4_STOP: u8 = 1;
+            pub const DBG_TIM5_STOP: u8 = 1;
+            pub const DBG_TIM6_STOP: u8 = 1;
+            pub const DBG_TIM7_STOP: u8 = 1;
+            pub const DBG_TIM12_STOP: u8 = 1;
+            pub const DBG_TIM13_STOP: u8 = 1;
+            pub const DBG_TIM14_STOP: u8 = 1;
+            pub const DBG_WWDG_STOP: u8 = 1;
+            pub const DBG_IWDEG_STOP: u8 = 1;
+            pub const DBG_J2C1_SMBUS_TIMEOUT: u8 = 1;
+            pub const DBG_J2C2_SMBUS_TIMEOUT: u8 = 1;
+            pub const DBG_J2C3SMBUS_TIMEOUT: u8 = 1;
+            pub const DBG_CAN1_STOP: u8 = 1;
+            pub const DBG_CAN2_STOP: u8 = 1;
+        }
     }
     pub mod dbgmcu_apb2_fz {
         pub const DBG_TIM1_STOP: u8 = 0;
@@ -46,6 +110,13 @@ pub mod DBG {
         pub const DBG_TIM9_STOP: u8 = 16;
         pub const DBG_TIM10_STOP: u8 = 17;
         pub const DBG_TIM11_STOP: u8 = 18;
+        pub mod width {
+            pub const DBG_TIM1_STOP: u8 = 1;
+            pub const DBG_TIM8_STOP: u8 = 1;
+            pub const DBG_TIM9_STOP: u8 = 1;
+            pub const DBG_TIM10_STOP: u8 = 1;
+            pub const DBG_TIM11_STOP: u8 = 1;
+        }
     }
 }
 
@@ -71,6 +142,28 @@ pub mod DMA {
         pub const TEIF0: u8 = 3;
         pub const DMEIF0: u8 = 2;
         pub const FEIF0: u8 = 0;
+        pub mod width {
+            pub const TCIF3: u8 = 1;
+            pub const HTIF3: u8 = 1;
+            pub const TEIF3: u8 = 1;
+            pub const DMEIF3: u8 = 1;
+            pub const FEIF3: u8 = 1;
+            pub const TCIF2: u8 = 1;
+            pub const HTIF2: u8 = 1;
+            pub const TEIF2: u8 = 1;
+            pub const DMEIF2: u8 = 1;
+            pub const FEIF2: u8 = 1;
+            pub const TCIF1: u8 = 1;
+            pub const HTIF1: u8 = 1;
+            pub const TEIF1: u8 = 1;
+            pub const DMEIF1: u8 = 1;
+            pub const FEIF1: u8 = 1;
+            pub const TCIF0: u8 = 1;
+            pub const HTIF0: u8 = 1;
+            pub const TEIF0: u8 = 1;
+            pub const DMEIF0: u8 = 1;
+            pub const FEIF0: u8 = 1;
+        }
     }
     pub mod hisr {
         pub const TCIF7: u8 = 27;
@@ -93,6 +186,28 @@ pub mod DMA {
         pub const TEIF4: u8 = 3;
         pub const DMEIF4: u8 = 2;
         pub const FEIF4: u8 = 0;
+        pub mod width {
+            pub const TCIF7: u8 = 1;
+            pub const HTIF7: u8 = 1;
+            pub const TEIF7: u8 = 1;
+            pub const DMEIF7: u8 = 1;
+            pub const FEIF7: u8 = 1;
+            pub const TCIF6: u8 = 1;
+            pub const HTIF6: u8 = 1;
+            pub const TEIF6: u8 = 1;
+            pub const DMEIF6: u8 = 1;
+            pub const FEIF6: u8 = 1;
+            pub const TCIF5: u8 = 1;
+            pub const HTIF5: u8 = 1;
+            pub const TEIF5: u8 = 1;
+            pub const DMEIF5: u8 = 1;
+            pub const FEIF5: u8 = 1;
+            pub const TCIF4: u8 = 1;
+            pub const HTIF4: u8 = 1;
+            pub const TEIF4: u8 = 1;
+            pub const DMEIF4: u8 = 1;
+            pub const FEIF4: u8 = 1;
+        }
     }
     pub mod lifcr {
         pub const CTCIF3: u8 = 27;
@@ -115,6 +230,28 @@ pub mod DMA {
         pub const CTEIF0: u8 = 3;
         pub const CDMEIF0: u8 = 2;
         pub const CFEIF0: u8 = 0;
+        pub mod width {
+            pub const CTCIF3: u8 = 1;
+            pub const CHTIF3: u8 = 1;
+            pub const CTEIF3: u8 = 1;
+            pub const CDMEIF3: u8 = 1;
+            pub const CFEIF3: u8 = 1;
+            pub const CTCIF2: u8 = 1;
+            pub const CHTIF2: u8 = 1;
+            pub const CTEIF2: u8 = 1;
+            pub const CDMEIF2: u8 = 1;
+            pub const CFEIF2: u8 = 1;
+            pub const CTCIF1: u8 = 1;
+            pub const CHTIF1: u8 = 1;
+            pub const CTEIF1: u8 = 1;
+            pub const CDMEIF1: u8 = 1;
+            pub const CFEIF1: u8 = 1;
+            pub const CTCIF0: u8 = 1;
+            pub const CHTIF0: u8 = 1;
+            pub const CTEIF0: u8 = 1;
+            pub const CDMEIF0: u8 = 1;
+            pub const CFEIF0: u8 = 1;
+        }
     }
     pub mod hifcr {
         pub const CTCIF7: u8 = 27;
@@ -137,6 +274,28 @@ pub mod DMA {
         pub const CTEIF4: u8 = 3;
         pub const CDMEIF4: u8 = 2;
         pub const CFEIF4: u8 = 0;
+        pub mod width {
+            pub const CTCIF7: u8 = 1;
+            pub const CHTIF7: u8 = 1;
+            pub const CTEIF7: u8 = 1;
+            pub const CDMEIF7: u8 = 1;
+            pub const CFEIF7: u8 = 1;
+            pub const CTCIF6: u8 = 1;
+            pub const CHTIF6: u8 = 1;
+            pub const CTEIF6: u8 = 1;
+            pub const CDMEIF6: u8 = 1;
+            pub const CFEIF6: u8 = 1;
+            pub const CTCIF5: u8 = 1;
+            pub const CHTIF5: u8 = 1;
+            pub const CTEIF5: u8 = 1;
+            pub const CDMEIF5: u8 = 1;
+            pub const CFEIF5: u8 = 1;
+            pub const CTCIF4: u8 = 1;
+            pub const CHTIF4: u8 = 1;
+            pub const CTEIF4: u8 = 1;
+            pub const CDMEIF4: u8 = 1;
+            pub const CFEIF4: u8 = 1;
+        }
     }
     pub mod s0cr {
         pub const CHSEL: u8 = 25;
@@ -158,24 +317,63 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s0ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s0par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s0m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s0m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s0fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s1cr {
         pub const CHSEL: u8 = 25;
@@ -198,24 +396,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s1ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s1par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s1m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s1m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s1fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s2cr {
         pub const CHSEL: u8 = 25;
@@ -238,24 +476,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s2ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s2par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s2m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s2m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s2fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s3cr {
         pub const CHSEL: u8 = 25;
@@ -278,24 +556,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s3ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s3par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s3m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s3m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s3fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s4cr {
         pub const CHSEL: u8 = 25;
@@ -318,24 +636,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s4ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s4par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s4m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s4m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s4fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s5cr {
         pub const CHSEL: u8 = 25;
@@ -358,24 +716,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s5ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s5par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s5m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s5m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s5fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s6cr {
         pub const CHSEL: u8 = 25;
@@ -398,24 +796,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s6ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s6par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s6m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s6m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s6fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
     pub mod s7cr {
         pub const CHSEL: u8 = 25;
@@ -438,24 +876,64 @@ pub mod DMA {
         pub const TEIE: u8 = 2;
         pub const DMEIE: u8 = 1;
         pub const EN: u8 = 0;
+        pub mod width {
+            pub const CHSEL: u8 = 3;
+            pub const MBURST: u8 = 2;
+            pub const PBURST: u8 = 2;
+            pub const ACK: u8 = 1;
+            pub const CT: u8 = 1;
+            pub const DBM: u8 = 1;
+            pub const PL: u8 = 2;
+            pub const PINCOS: u8 = 1;
+            pub const MSIZE: u8 = 2;
+            pub const PSIZE: u8 = 2;
+            pub const MINC: u8 = 1;
+            pub const PINC: u8 = 1;
+            pub const CIRC: u8 = 1;
+            pub const DIR: u8 = 2;
+            pub const PFCTRL: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const HTIE: u8 = 1;
+            pub const TEIE: u8 = 1;
+            pub const DMEIE: u8 = 1;
+            pub const EN: u8 = 1;
+        }
     }
     pub mod s7ndtr {
         pub const NDT: u8 = 0;
+        pub mod width {
+            pub const NDT: u8 = 16;
+        }
     }
     pub mod s7par {
         pub const PA: u8 = 0;
+        pub mod width {
+            pub const PA: u8 = 32;
+        }
     }
     pub mod s7m0ar {
         pub const M0A: u8 = 0;
+        pub mod width {
+            pub const M0A: u8 = 32;
+        }
     }
     pub mod s7m1ar {
         pub const M1A: u8 = 0;
+        pub mod width {
+            pub const M1A: u8 = 32;
+        }
     }
     pub mod s7fcr {
         pub const FEIE: u8 = 7;
         pub const FS: u8 = 3;
         pub const DMDIS: u8 = 2;
         pub const FTH: u8 = 0;
+        pub mod width {
+            pub const FEIE: u8 = 1;
+            pub const FS: u8 = 3;
+            pub const DMDIS: u8 = 1;
+            pub const FTH: u8 = 2;
+        }
     }
 }
 
@@ -473,6 +951,20 @@ pub mod RCC {
         pub const HSITRIM: u8 = 3;
         pub const HSIRDY: u8 = 1;
         pub const HSION: u8 = 0;
+        pub mod width {
+            pub const PLLI2SRDY: u8 = 1;
+            pub const PLLI2SON: u8 = 1;
+            pub const PLLRDY: u8 = 1;
+            pub const PLLON: u8 = 1;
+            pub const CSSON: u8 = 1;
+            pub const HSEBYP: u8 = 1;
+            pub const HSERDY: u8 = 1;
+            pub const HSEON: u8 = 1;
+            pub const HSICAL: u8 = 8;
+            pub const HSITRIM: u8 = 5;
+            pub const HSIRDY: u8 = 1;
+            pub const HSION: u8 = 1;
+        }
     }
     pub mod pllcfgr {
         pub const PLLQ3: u8 = 27;
@@ -497,6 +989,30 @@ pub mod RCC {
         pub const PLLM2: u8 = 2;
         pub const PLLM1: u8 = 1;
         pub const PLLM0: u8 = 0;
+        pub mod width {
+            pub const PLLQ3: u8 = 1;
+            pub const PLLQ2: u8 = 1;
+            pub const PLLQ1: u8 = 1;
+            pub const PLLQ0: u8 = 1;
+            pub const PLLSRC: u8 = 1;
+            pub const PLLP1: u8 = 1;
+            pub const PLLP0: u8 = 1;
+            pub const PLLN8: u8 = 1;
+            pub const PLLN7: u8 = 1;
+            pub const PLLN6: u8 = 1;
+            pub const PLLN5: u8 = 1;
+            pub const PLLN4: u8 = 1;
+            pub const PLLN3: u8 = 1;
+            pub const PLLN2: u8 = 1;
+            pub const PLLN1: u8 = 1;
+            pub const PLLN0: u8 = 1;
+            pub const PLLM5: u8 = 1;
+            pub const PLLM4: u8 = 1;
+            pub const PLLM3: u8 = 1;
+            pub const PLLM2: u8 = 1;
+            pub const PLLM1: u8 = 1;
+            pub const PLLM0: u8 = 1;
+        }
     }
     pub mod cfgr {
         pub const MCO2: u8 = 30;
@@ -512,6 +1028,21 @@ pub mod RCC {
         pub const SWS0: u8 = 2;
         pub const SW1: u8 = 1;
         pub const SW0: u8 = 0;
+        pub mod width {
+            pub const MCO2: u8 = 2;
+            pub const MCO2PRE: u8 = 3;
+            pub const MCO1PRE: u8 = 3;
+            pub const I2SSRC: u8 = 1;
+            pub const MCO1: u8 = 2;
+            pub const RTCPRE: u8 = 5;
+            pub const PPRE2: u8 = 3;
+            pub const PPRE1: u8 = 3;
+            pub const HPRE: u8 = 4;
+            pub const SWS1: u8 = 1;
+            pub const SWS0: u8 = 1;
+            pub const SW1: u8 = 1;
+            pub const SW0: u8 = 1;
+        }
     }
     pub mod cir {
         pub const CSSC: u8 = 23;
@@ -534,6 +1065,28 @@ pub mod RCC {
         pub const HSIRDYF: u8 = 2;
         pub const LSERDYF: u8 = 1;
         pub const LSIRDYF: u8 = 0;
+        pub mod width {
+            pub const CSSC: u8 = 1;
+            pub const PLLI2SRDYC: u8 = 1;
+            pub const PLLRDYC: u8 = 1;
+            pub const HSERDYC: u8 = 1;
+            pub const HSIRDYC: u8 = 1;
+            pub const LSERDYC: u8 = 1;
+            pub const LSIRDYC: u8 = 1;
+            pub const PLLI2SRDYIE: u8 = 1;
+            pub const PLLRDYIE: u8 = 1;
+            pub const HSERDYIE: u8 = 1;
+            pub const HSIRDYIE: u8 = 1;
+            pub const LSERDYIE: u8 = 1;
+            pub const LSIRDYIE: u8 = 1;
+            pub const CSSF: u8 = 1;
+            pub const PLLI2SRDYF: u8 = 1;
+            pub const PLLRDYF: u8 = 1;
+            pub const HSERDYF: u8 = 1;
+            pub const HSIRDYF: u8 = 1;
+            pub const LSERDYF: u8 = 1;
+            pub const LSIRDYF: u8 = 1;
+        }
     }
     pub mod ahb1rstr {
         pub const OTGHSRST: u8 = 29;
@@ -550,14 +1103,38 @@ pub mod RCC {
         pub const GPIOCRST: u8 = 2;
         pub const GPIOBRST: u8 = 1;
         pub const GPIOARST: u8 = 0;
+        pub mod width {
+            pub const OTGHSRST: u8 = 1;
+            pub const ETHMACRST: u8 = 1;
+            pub const DMA2RST: u8 = 1;
+            pub const DMA1RST: u8 = 1;
+            pub const CRCRST: u8 = 1;
+            pub const GPIOIRST: u8 = 1;
+            pub const GPIOHRST: u8 = 1;
+            pub const GPIOGRST: u8 = 1;
+            pub const GPIOFRST: u8 = 1;
+            pub const GPIOERST: u8 = 1;
+            pub const GPIODRST: u8 = 1;
+            pub const GPIOCRST: u8 = 1;
+            pub const GPIOBRST: u8 = 1;
+            pub const GPIOARST: u8 = 1;
+        }
     }
     pub mod ahb2rstr {
         pub const OTGFSRST: u8 = 7;
         pub const RNGRST: u8 = 6;
         pub const DCMIRST: u8 = 0;
+        pub mod width {
+            pub const OTGFSRST: u8 = 1;
+            pub const RNGRST: u8 = 1;
+            pub const DCMIRST: u8 = 1;
+        }
     }
     pub mod ahb3rstr {
         pub const FSMCRST: u8 = 0;
+        pub mod width {
+            pub const FSMCRST: u8 = 1;
+        }
     }
     pub mod apb1rstr {
         pub const DACRST: u8 = 29;
@@ -583,6 +1160,31 @@ pub mod RCC {
         pub const TIM4RST: u8 = 2;
         pub const TIM3RST: u8 = 1;
         pub const TIM2RST: u8 = 0;
+        pub mod width {
+            pub const DACRST: u8 = 1;
+            pub const PWRRST: u8 = 1;
+            pub const CAN2RST: u8 = 1;
+            pub const CAN1RST: u8 = 1;
+            pub const I2C3RST: u8 = 1;
+            pub const I2C2RST: u8 = 1;
+            pub const I2C1RST: u8 = 1;
+            pub const UART5RST: u8 = 1;
+            pub const UART4RST: u8 = 1;
+            pub const UART3RST: u8 = 1;
+            pub const UART2RST: u8 = 1;
+            pub const SPI3RST: u8 = 1;
+            pub const SPI2RST: u8 = 1;
+            pub const WWDGRST: u8 = 1;
+            pub const TIM14RST: u8 = 1;
+            pub const TIM13RST: u8 = 1;
+            pub const TIM12RST: u8 = 1;
+            pub const TIM7RST: u8 = 1;
+            pub const TIM6RST: u8 = 1;
+            pub const TIM5RST: u8 = 1;
+            pub const TIM4RST: u8 = 1;
+            pub const TIM3RST: u8 = 1;
+            pub const TIM2RST: u8 = 1;
+        }
     }
     pub mod apb2rstr {
         pub const TIM11RST: u8 = 18;
@@ -596,6 +1198,19 @@ pub mod RCC {
         pub const USART1RST: u8 = 4;
         pub const TIM8RST: u8 = 1;
         pub const TIM1RST: u8 = 0;
+        pub mod width {
+            pub const TIM11RST: u8 = 1;
+            pub const TIM10RST: u8 = 1;
+            pub const TIM9RST: u8 = 1;
+            pub const SYSCFGRST: u8 = 1;
+            pub const SPI1RST: u8 = 1;
+            pub const SDIORST: u8 = 1;
+            pub const ADCRST: u8 = 1;
+            pub const USART6RST: u8 = 1;
+            pub const USART1RST: u8 = 1;
+            pub const TIM8RST: u8 = 1;
+            pub const TIM1RST: u8 = 1;
+        }
     }
     pub mod ahb1enr {
         pub const OTGHSULPIEN: u8 = 30;
@@ -618,14 +1233,44 @@ pub mod RCC {
         pub const GPIOCEN: u8 = 2;
         pub const GPIOBEN: u8 = 1;
         pub const GPIOAEN: u8 = 0;
+        pub mod width {
+            pub const OTGHSULPIEN: u8 = 1;
+            pub const OTGHSEN: u8 = 1;
+            pub const ETHMACPTPEN: u8 = 1;
+            pub const ETHMACRXEN: u8 = 1;
+            pub const ETHMACTXEN: u8 = 1;
+            pub const ETHMACEN: u8 = 1;
+            pub const DMA2EN: u8 = 1;
+            pub const DMA1EN: u8 = 1;
+            pub const CCMDATARAMEN: u8 = 1;
+            pub const BKPSRAMEN: u8 = 1;
+            pub const CRCEN: u8 = 1;
+            pub const GPIOIEN: u8 = 1;
+            pub const GPIOHEN: u8 = 1;
+            pub const GPIOGEN: u8 = 1;
+            pub const GPIOFEN: u8 = 1;
+            pub const GPIOEEN: u8 = 1;
+            pub const GPIODEN: u8 = 1;
+            pub const GPIOCEN: u8 = 1;
+            pub const GPIOBEN: u8 = 1;
+            pub const GPIOAEN: u8 = 1;
+        }
     }
     pub mod ahb2enr {
         pub const OTGFSEN: u8 = 7;
         pub const RNGEN: u8 = 6;
         pub const DCMIEN: u8 = 0;
+        pub mod width {
+            pub const OTGFSEN: u8 = 1;
+            pub const RNGEN: u8 = 1;
+            pub const DCMIEN: u8 = 1;
+        }
     }
     pub mod ahb3enr {
         pub const FSMCEN: u8 = 0;
+        pub mod width {
+            pub const FSMCEN: u8 = 1;
+        }
     }
     pub mod apb1enr {
         pub const DACEN: u8 = 29;
@@ -651,6 +1296,31 @@ pub mod RCC {
         pub const TIM4EN: u8 = 2;
         pub const TIM3EN: u8 = 1;
         pub const TIM2EN: u8 = 0;
+        pub mod width {
+            pub const DACEN: u8 = 1;
+            pub const PWREN: u8 = 1;
+            pub const CAN2EN: u8 = 1;
+            pub const CAN1EN: u8 = 1;
+            pub const I2C3EN: u8 = 1;
+            pub const I2C2EN: u8 = 1;
+            pub const I2C1EN: u8 = 1;
+            pub const UART5EN: u8 = 1;
+            pub const UART4EN: u8 = 1;
+            pub const USART3EN: u8 = 1;
+            pub const USART2EN: u8 = 1;
+            pub const SPI3EN: u8 = 1;
+            pub const SPI2EN: u8 = 1;
+            pub const WWDGEN: u8 = 1;
+            pub const TIM14EN: u8 = 1;
+            pub const TIM13EN: u8 = 1;
+            pub const TIM12EN: u8 = 1;
+            pub const TIM7EN: u8 = 1;
+            pub const TIM6EN: u8 = 1;
+            pub const TIM5EN: u8 = 1;
+            pub const TIM4EN: u8 = 1;
+            pub const TIM3EN: u8 = 1;
+            pub const TIM2EN: u8 = 1;
+        }
     }
     pub mod apb2enr {
         pub const TIM11EN: u8 = 18;
@@ -666,6 +1336,21 @@ pub mod RCC {
         pub const USART1EN: u8 = 4;
         pub const TIM8EN: u8 = 1;
         pub const TIM1EN: u8 = 0;
+        pub mod width {
+            pub const TIM11EN: u8 = 1;
+            pub const TIM10EN: u8 = 1;
+            pub const TIM9EN: u8 = 1;
+            pub const SYSCFGEN: u8 = 1;
+            pub const SPI1EN: u8 = 1;
+            pub const SDIOEN: u8 = 1;
+            pub const ADC3EN: u8 = 1;
+            pub const ADC2EN: u8 = 1;
+            pub const ADC1EN: u8 = 1;
+            pub const USART6EN: u8 = 1;
+            pub const USART1EN: u8 = 1;
+            pub const TIM8EN: u8 = 1;
+            pub const TIM1EN: u8 = 1;
+        }
     }
     pub mod ahb1lpenr {
         pub const OTGHSULPILPEN: u8 = 30;
@@ -690,14 +1375,46 @@ pub mod RCC {
         pub const GPIOCLPEN: u8 = 2;
         pub const GPIOBLPEN: u8 = 1;
         pub const GPIOALPEN: u8 = 0;
+        pub mod width {
+            pub const OTGHSULPILPEN: u8 = 1;
+            pub const OTGHSLPEN: u8 = 1;
+            pub const ETHMACPTPLPEN: u8 = 1;
+            pub const ETHMACRXLPEN: u8 = 1;
+            pub const ETHMACTXLPEN: u8 = 1;
+            pub const ETHMACLPEN: u8 = 1;
+            pub const DMA2LPEN: u8 = 1;
+            pub const DMA1LPEN: u8 = 1;
+            pub const BKPSRAMLPEN: u8 = 1;
+            pub const SRAM2LPEN: u8 = 1;
+            pub const SRAM1LPEN: u8 = 1;
+            pub const FLITFLPEN: u8 = 1;
+            pub const CRCLPEN: u8 = 1;
+            pub const GPIOILPEN: u8 = 1;
+            pub const GPIOHLPEN: u8 = 1;
+            pub const GPIOGLPEN: u8 = 1;
+            pub const GPIOFLPEN: u8 = 1;
+            pub const GPIOELPEN: u8 = 1;
+            pub const GPIODLPEN: u8 = 1;
+            pub const GPIOCLPEN: u8 = 1;
+            pub const GPIOBLPEN: u8 = 1;
+            pub const GPIOALPEN: u8 = 1;
+        }
     }
     pub mod ahb2lpenr {
         pub const OTGFSLPEN: u8 = 7;
         pub const RNGLPEN: u8 = 6;
         pub const DCMILPEN: u8 = 0;
+        pub mod width {
+            pub const OTGFSLPEN: u8 = 1;
+            pub const RNGLPEN: u8 = 1;
+            pub const DCMILPEN: u8 = 1;
+        }
     }
     pub mod ahb3lpenr {
         pub const FSMCLPEN: u8 = 0;
+        pub mod width {
+            pub const FSMCLPEN: u8 = 1;
+        }
     }
     pub mod apb1lpenr {
         pub const DACLPEN: u8 = 29;
@@ -723,6 +1440,31 @@ pub mod RCC {
         pub const TIM4LPEN: u8 = 2;
         pub const TIM3LPEN: u8 = 1;
         pub const TIM2LPEN: u8 = 0;
+        pub mod width {
+            pub const DACLPEN: u8 = 1;
+            pub const PWRLPEN: u8 = 1;
+            pub const CAN2LPEN: u8 = 1;
+            pub const CAN1LPEN: u8 = 1;
+            pub const I2C3LPEN: u8 = 1;
+            pub const I2C2LPEN: u8 = 1;
+            pub const I2C1LPEN: u8 = 1;
+            pub const UART5LPEN: u8 = 1;
+            pub const UART4LPEN: u8 = 1;
+            pub const USART3LPEN: u8 = 1;
+            pub const USART2LPEN: u8 = 1;
+            pub const SPI3LPEN: u8 = 1;
+            pub const SPI2LPEN: u8 = 1;
+            pub const WWDGLPEN: u8 = 1;
+            pub const TIM14LPEN: u8 = 1;
+            pub const TIM13LPEN: u8 = 1;
+            pub const TIM12LPEN: u8 = 1;
+            pub const TIM7LPEN: u8 = 1;
+            pub const TIM6LPEN: u8 = 1;
+            pub const TIM5LPEN: u8 = 1;
+            pub const TIM4LPEN: u8 = 1;
+            pub const TIM3LPEN: u8 = 1;
+            pub const TIM2LPEN: u8 = 1;
+        }
     }
     pub mod apb2lpenr {
         pub const TIM11LPEN: u8 = 18;
@@ -738,6 +1480,21 @@ pub mod RCC {
         pub const USART1LPEN: u8 = 4;
         pub const TIM8LPEN: u8 = 1;
         pub const TIM1LPEN: u8 = 0;
+        pub mod width {
+            pub const TIM11LPEN: u8 = 1;
+            pub const TIM10LPEN: u8 = 1;
+            pub const TIM9LPEN: u8 = 1;
+            pub const SYSCFGLPEN: u8 = 1;
+            pub const SPI1LPEN: u8 = 1;
+            pub const SDIOLPEN: u8 = 1;
+            pub const ADC3LPEN: u8 = 1;
+            pub const ADC2LPEN: u8 = 1;
+            pub const ADC1LPEN: u8 = 1;
+            pub const USART6LPEN: u8 = 1;
+            pub const USART1LPEN: u8 = 1;
+            pub const TIM8LPEN: u8 = 1;
+            pub const TIM1LPEN: u8 = 1;
+        }
     }
     pub mod bdcr {
         pub const BDRST: u8 = 16;
@@ -747,6 +1504,15 @@ pub mod RCC {
         pub const LSEBYP: u8 = 2;
         pub const LSERDY: u8 = 1;
         pub const LSEON: u8 = 0;
+        pub mod width {
+            pub const BDRST: u8 = 1;
+            pub const RTCEN: u8 = 1;
+            pub const RTCSEL1: u8 = 1;
+            pub const RTCSEL0: u8 = 1;
+            pub const LSEBYP: u8 = 1;
+            pub const LSERDY: u8 = 1;
+            pub const LSEON: u8 = 1;
+        }
     }
     pub mod csr {
         pub const LPWRRSTF: u8 = 31;
@@ -759,16 +1525,38 @@ pub mod RCC {
         pub const RMVF: u8 = 24;
         pub const LSIRDY: u8 = 1;
         pub const LSION: u8 = 0;
+        pub mod width {
+            pub const LPWRRSTF: u8 = 1;
+            pub const WWDGRSTF: u8 = 1;
+            pub const WDGRSTF: u8 = 1;
+            pub const SFTRSTF: u8 = 1;
+            pub const PORRSTF: u8 = 1;
+            pub const PADRSTF: u8 = 1;
+            pub const BORRSTF: u8 = 1;
+            pub const RMVF: u8 = 1;
+            pub const LSIRDY: u8 = 1;
+            pub const LSION: u8 = 1;
+        }
     }
     pub mod sscgr {
         pub const SSCGEN: u8 = 31;
         pub const SPREADSEL: u8 = 30;
         pub const INCSTEP: u8 = 13;
         pub const MODPER: u8 = 0;
+        pub mod width {
+            pub const SSCGEN: u8 = 1;
+            pub const SPREADSEL: u8 = 1;
+            pub const INCSTEP: u8 = 15;
+            pub const MODPER: u8 = 13;
+        }
     }
     pub mod plli2scfgr {
         pub const PLLI2SRx: u8 = 28;
         pub const PLLI2SNx: u8 = 6;
+        pub mod width {
+            pub const PLLI2SRx: u8 = 3;
+            pub const PLLI2SNx: u8 = 9;
+        }
     }
 }
 
@@ -790,6 +1578,24 @@ pub mod GPIO {
         pub const MODER2: u8 = 4;
         pub const MODER1: u8 = 2;
         pub const MODER0: u8 = 0;
+        pub mod width {
+            pub const MODER15: u8 = 2;
+            pub const MODER14: u8 = 2;
+            pub const MODER13: u8 = 2;
+            pub const MODER12: u8 = 2;
+            pub const MODER11: u8 = 2;
+            pub const MODER10: u8 = 2;
+            pub const MODER9: u8 = 2;
+            pub const MODER8: u8 = 2;
+            pub const MODER7: u8 = 2;
+            pub const MODER6: u8 = 2;
+            pub const MODER5: u8 = 2;
+            pub const MODER4: u8 = 2;
+            pub const MODER3: u8 = 2;
+            pub const MODER2: u8 = 2;
+            pub const MODER1: u8 = 2;
+            pub const MODER0: u8 = 2;
+        }
     }
     pub mod otyper {
         pub const OT15: u8 = 15;
@@ -808,6 +1614,24 @@ pub mod GPIO {
         pub const OT2: u8 = 2;
         pub const OT1: u8 = 1;
         pub const OT0: u8 = 0;
+        pub mod width {
+            pub const OT15: u8 = 1;
+            pub const OT14: u8 = 1;
+            pub const OT13: u8 = 1;
+            pub const OT12: u8 = 1;
+            pub const OT11: u8 = 1;
+            pub const OT10: u8 = 1;
+            pub const OT9: u8 = 1;
+            pub const OT8: u8 = 1;
+            pub const OT7: u8 = 1;
+            pub const OT6: u8 = 1;
+            pub const OT5: u8 = 1;
+            pub const OT4: u8 = 1;
+            pub const OT3: u8 = 1;
+            pub const OT2: u8 = 1;
+            pub const OT1: u8 = 1;
+            pub const OT0: u8 = 1;
+        }
     }
     pub mod ospeedr {
         pub const OSPEEDR15: u8 = 30;
@@ -826,6 +1650,24 @@ pub mod GPIO {
         pub const OSPEEDR2: u8 = 4;
         pub const OSPEEDR1: u8 = 2;
         pub const OSPEEDR0: u8 = 0;
+        pub mod width {
+            pub const OSPEEDR15: u8 = 2;
+            pub const OSPEEDR14: u8 = 2;
+            pub const OSPEEDR13: u8 = 2;
+            pub const OSPEEDR12: u8 = 2;
+            pub const OSPEEDR11: u8 = 2;
+            pub const OSPEEDR10: u8 = 2;
+            pub const OSPEEDR9: u8 = 2;
+            pub const OSPEEDR8: u8 = 2;
+            pub const OSPEEDR7: u8 = 2;
+            pub const OSPEEDR6: u8 = 2;
+            pub const OSPEEDR5: u8 = 2;
+            pub const OSPEEDR4: u8 = 2;
+            pub const OSPEEDR3: u8 = 2;
+            pub const OSPEEDR2: u8 = 2;
+            pub const OSPEEDR1: u8 = 2;
+            pub const OSPEEDR0: u8 = 2;
+        }
     }
     pub mod pupdr {
         pub const PUPDR15: u8 = 30;
@@ -844,6 +1686,24 @@ pub mod GPIO {
         pub const PUPDR2: u8 = 4;
         pub const PUPDR1: u8 = 2;
         pub const PUPDR0: u8 = 0;
+        pub mod width {
+            pub const PUPDR15: u8 = 2;
+            pub const PUPDR14: u8 = 2;
+            pub const PUPDR13: u8 = 2;
+            pub const PUPDR12: u8 = 2;
+            pub const PUPDR11: u8 = 2;
+            pub const PUPDR10: u8 = 2;
+            pub const PUPDR9: u8 = 2;
+            pub const PUPDR8: u8 = 2;
+            pub const PUPDR7: u8 = 2;
+            pub const PUPDR6: u8 = 2;
+            pub const PUPDR5: u8 = 2;
+            pub const PUPDR4: u8 = 2;
+            pub const PUPDR3: u8 = 2;
+            pub const PUPDR2: u8 = 2;
+            pub const PUPDR1: u8 = 2;
+            pub const PUPDR0: u8 = 2;
+        }
     }
     pub mod idr {
         pub const IDR15: u8 = 15;
@@ -862,6 +1722,24 @@ pub mod GPIO {
         pub const IDR2: u8 = 2;
         pub const IDR1: u8 = 1;
         pub const IDR0: u8 = 0;
+        pub mod width {
+            pub const IDR15: u8 = 1;
+            pub const IDR14: u8 = 1;
+            pub const IDR13: u8 = 1;
+            pub const IDR12: u8 = 1;
+            pub const IDR11: u8 = 1;
+            pub const IDR10: u8 = 1;
+            pub const IDR9: u8 = 1;
+            pub const IDR8: u8 = 1;
+            pub const IDR7: u8 = 1;
+            pub const IDR6: u8 = 1;
+            pub const IDR5: u8 = 1;
+            pub const IDR4: u8 = 1;
+            pub const IDR3: u8 = 1;
+            pub const IDR2: u8 = 1;
+            pub const IDR1: u8 = 1;
+            pub const IDR0: u8 = 1;
+        }
     }
     pub mod odr {
         pub const ODR15: u8 = 15;
@@ -880,6 +1758,24 @@ pub mod GPIO {
         pub const ODR2: u8 = 2;
         pub const ODR1: u8 = 1;
         pub const ODR0: u8 = 0;
+        pub mod width {
+            pub const ODR15: u8 = 1;
+            pub const ODR14: u8 = 1;
+            pub const ODR13: u8 = 1;
+            pub const ODR12: u8 = 1;
+            pub const ODR11: u8 = 1;
+            pub const ODR10: u8 = 1;
+            pub const ODR9: u8 = 1;
+            pub const ODR8: u8 = 1;
+            pub const ODR7: u8 = 1;
+            pub const ODR6: u8 = 1;
+            pub const ODR5: u8 = 1;
+            pub const ODR4: u8 = 1;
+            pub const ODR3: u8 = 1;
+            pub const ODR2: u8 = 1;
+            pub const ODR1: u8 = 1;
+            pub const ODR0: u8 = 1;
+        }
     }
     pub mod bsrr {
         pub const BR15: u8 = 31;
@@ -914,6 +1810,40 @@ pub mod GPIO {
         pub const BS2: u8 = 2;
         pub const BS1: u8 = 1;
         pub const BS0: u8 = 0;
+        pub mod width {
+            pub const BR15: u8 = 1;
+            pub const BR14: u8 = 1;
+            pub const BR13: u8 = 1;
+            pub const BR12: u8 = 1;
+            pub const BR11: u8 = 1;
+            pub const BR10: u8 = 1;
+            pub const BR9: u8 = 1;
+            pub const BR8: u8 = 1;
+            pub const BR7: u8 = 1;
+            pub const BR6: u8 = 1;
+            pub const BR5: u8 = 1;
+            pub const BR4: u8 = 1;
+            pub const BR3: u8 = 1;
+            pub const BR2: u8 = 1;
+            pub const BR1: u8 = 1;
+            pub const BR0: u8 = 1;
+            pub const BS15: u8 = 1;
+            pub const BS14: u8 = 1;
+            pub const BS13: u8 = 1;
+            pub const BS12: u8 = 1;
+            pub const BS11: u8 = 1;
+            pub const BS10: u8 = 1;
+            pub const BS9: u8 = 1;
+            pub const BS8: u8 = 1;
+            pub const BS7: u8 = 1;
+            pub const BS6: u8 = 1;
+            pub const BS5: u8 = 1;
+            pub const BS4: u8 = 1;
+            pub const BS3: u8 = 1;
+            pub const BS2: u8 = 1;
+            pub const BS1: u8 = 1;
+            pub const BS0: u8 = 1;
+        }
     }
     pub mod lckr {
         pub const LCKK: u8 = 16;
@@ -933,6 +1863,25 @@ pub mod GPIO {
         pub const LCK2: u8 = 2;
         pub const LCK1: u8 = 1;
         pub const LCK0: u8 = 0;
+        pub mod width {
+            pub const LCKK: u8 = 1;
+            pub const LCK15: u8 = 1;
+            pub const LCK14: u8 = 1;
+            pub const LCK13: u8 = 1;
+            pub const LCK12: u8 = 1;
+            pub const LCK11: u8 = 1;
+            pub const LCK10: u8 = 1;
+            pub const LCK9: u8 = 1;
+            pub const LCK8: u8 = 1;
+            pub const LCK7: u8 = 1;
+            pub const LCK6: u8 = 1;
+            pub const LCK5: u8 = 1;
+            pub const LCK4: u8 = 1;
+            pub const LCK3: u8 = 1;
+            pub const LCK2: u8 = 1;
+            pub const LCK1: u8 = 1;
+            pub const LCK0: u8 = 1;
+        }
     }
     pub mod afrl {
         pub const AFRL7: u8 = 28;
@@ -943,6 +1892,16 @@ pub mod GPIO {
         pub const AFRL2: u8 = 8;
         pub const AFRL1: u8 = 4;
         pub const AFRL0: u8 = 0;
+        pub mod width {
+            pub const AFRL7: u8 = 4;
+            pub const AFRL6: u8 = 4;
+            pub const AFRL5: u8 = 4;
+            pub const AFRL4: u8 = 4;
+            pub const AFRL3: u8 = 4;
+            pub const AFRL2: u8 = 4;
+            pub const AFRL1: u8 = 4;
+            pub const AFRL0: u8 = 4;
+        }
     }
     pub mod afrh {
         pub const AFRH15: u8 = 28;
@@ -953,6 +1912,16 @@ pub mod GPIO {
         pub const AFRH10: u8 = 8;
         pub const AFRH9: u8 = 4;
         pub const AFRH8: u8 = 0;
+        pub mod width {
+            pub const AFRH15: u8 = 4;
+            pub const AFRH14: u8 = 4;
+            pub const AFRH13: u8 = 4;
+            pub const AFRH12: u8 = 4;
+            pub const AFRH11: u8 = 4;
+            pub const AFRH10: u8 = 4;
+            pub const AFRH9: u8 = 4;
+            pub const AFRH8: u8 = 4;
+        }
     }
 }
 
@@ -972,6 +1941,22 @@ pub mod SPI1 {
         pub const MSTR: u8 = 2;
         pub const CPOL: u8 = 1;
         pub const CPHA: u8 = 0;
+        pub mod width {
+            pub const BIDIMODE: u8 = 1;
+            pub const BIDIOE: u8 = 1;
+            pub const CRCEN: u8 = 1;
+            pub const CRCNEXT: u8 = 1;
+            pub const DFF: u8 = 1;
+            pub const RXONLY: u8 = 1;
+            pub const SSM: u8 = 1;
+            pub const SSI: u8 = 1;
+            pub const LSBFIRST: u8 = 1;
+            pub const SPE: u8 = 1;
+            pub const BR: u8 = 3;
+            pub const MSTR: u8 = 1;
+            pub const CPOL: u8 = 1;
+            pub const CPHA: u8 = 1;
+        }
     }
     pub mod cr2 {
         pub const TXEIE: u8 = 7;
@@ -981,6 +1966,15 @@ pub mod SPI1 {
         pub const SSOE: u8 = 2;
         pub const TXDMAEN: u8 = 1;
         pub const RXDMAEN: u8 = 0;
+        pub mod width {
+            pub const TXEIE: u8 = 1;
+            pub const RXNEIE: u8 = 1;
+            pub const ERRIE: u8 = 1;
+            pub const FRF: u8 = 1;
+            pub const SSOE: u8 = 1;
+            pub const TXDMAEN: u8 = 1;
+            pub const RXDMAEN: u8 = 1;
+        }
     }
     pub mod sr {
         pub const TIFRFE: u8 = 8;
@@ -992,18 +1986,41 @@ pub mod SPI1 {
         pub const CHSIDE: u8 = 2;
         pub const TXE: u8 = 1;
         pub const RXNE: u8 = 0;
+        pub mod width {
+            pub const TIFRFE: u8 = 1;
+            pub const BSY: u8 = 1;
+            pub const OVR: u8 = 1;
+            pub const MODF: u8 = 1;
+            pub const CRCERR: u8 = 1;
+            pub const UDR: u8 = 1;
+            pub const CHSIDE: u8 = 1;
+            pub const TXE: u8 = 1;
+            pub const RXNE: u8 = 1;
+        }
     }
     pub mod dr {
         pub const DR: u8 = 0;
+        pub mod width {
+            pub const DR: u8 = 8;
+        }
     }
     pub mod crcpr {
         pub const CRCPOLY: u8 = 0;
+        pub mod width {
+            pub const CRCPOLY: u8 = 16;
+        }
     }
     pub mod rxcrcr {
         pub const RxCRC: u8 = 0;
+        pub mod width {
+            pub const RxCRC: u8 = 16;
+        }
     }
     pub mod txcrcr {
         pub const TxCRC: u8 = 0;
+        pub mod width {
+            pub const TxCRC: u8 = 16;
+        }
     }
     pub mod i2scfgr {
         pub const I2SMOD: u8 = 11;
@@ -1014,11 +2031,26 @@ pub mod SPI1 {
         pub const CKPOL: u8 = 3;
         pub const DATLEN: u8 = 1;
         pub const CHLEN: u8 = 0;
+        pub mod width {
+            pub const I2SMOD: u8 = 1;
+            pub const I2SE: u8 = 1;
+            pub const I2SCFG: u8 = 2;
+            pub const PCMSYNC: u8 = 1;
+            pub const I2SSTD: u8 = 2;
+            pub const CKPOL: u8 = 1;
+            pub const DATLEN: u8 = 2;
+            pub const CHLEN: u8 = 1;
+        }
     }
     pub mod i2spr {
         pub const MCKOE: u8 = 9;
         pub const ODD: u8 = 8;
         pub const I2SDIV: u8 = 0;
+        pub mod width {
+            pub const MCKOE: u8 = 1;
+            pub const ODD: u8 = 1;
+            pub const I2SDIV: u8 = 8;
+        }
     }
 }
 
@@ -1034,13 +2066,32 @@ pub mod USART {
         pub const NF: u8 = 2;
         pub const FE: u8 = 1;
         pub const PE: u8 = 0;
+        pub mod width {
+            pub const CTS: u8 = 1;
+            pub const LBD: u8 = 1;
+            pub const TXE: u8 = 1;
+            pub const TC: u8 = 1;
+            pub const RXNE: u8 = 1;
+            pub const IDLE: u8 = 1;
+            pub const ORE: u8 = 1;
+            pub const NF: u8 = 1;
+            pub const FE: u8 = 1;
+            pub const PE: u8 = 1;
+        }
     }
     pub mod dr {
         pub const DR: u8 = 0;
+        pub mod width {
+            pub const DR: u8 = 8;
+        }
     }
     pub mod brr {
         pub const DIV_Mantissa: u8 = 4;
         pub const DIV_Fraction: u8 = 0;
+        pub mod width {
+            pub const DIV_Mantissa: u8 = 12;
+            pub const DIV_Fraction: u8 = 4;
+        }
     }
     pub mod cr1 {
         pub const OVER8: u8 = 15;
@@ -1058,6 +2109,23 @@ pub mod USART {
         pub const RE: u8 = 2;
         pub const RWU: u8 = 1;
         pub const SBK: u8 = 0;
+        pub mod width {
+            pub const OVER8: u8 = 1;
+            pub const UE: u8 = 1;
+            pub const M: u8 = 1;
+            pub const WAKE: u8 = 1;
+            pub const PCE: u8 = 1;
+            pub const PS: u8 = 1;
+            pub const PEIE: u8 = 1;
+            pub const TXEIE: u8 = 1;
+            pub const TCIE: u8 = 1;
+            pub const RXNEIE: u8 = 1;
+            pub const IDLEIE: u8 = 1;
+            pub const TE: u8 = 1;
+            pub const RE: u8 = 1;
+            pub const RWU: u8 = 1;
+            pub const SBK: u8 = 1;
+        }
     }
     pub mod cr2 {
         pub const LINEN: u8 = 14;
@@ -1069,6 +2137,17 @@ pub mod USART {
         pub const LBDIE: u8 = 6;
         pub const LBDL: u8 = 5;
         pub const ADD: u8 = 0;
+        pub mod width {
+            pub const LINEN: u8 = 1;
+            pub const STOP: u8 = 2;
+            pub const CLKEN: u8 = 1;
+            pub const CPOL: u8 = 1;
+            pub const CPHA: u8 = 1;
+            pub const LBCL: u8 = 1;
+            pub const LBDIE: u8 = 1;
+            pub const LBDL: u8 = 1;
+            pub const ADD: u8 = 4;
+        }
     }
     pub mod cr3 {
         pub const ONEBIT: u8 = 11;
@@ -1083,10 +2162,900 @@ pub mod USART {
         pub const IRLP: u8 = 2;
         pub const IREN: u8 = 1;
         pub const EIE: u8 = 0;
+        pub mod width {
+            pub const ONEBIT: u8 = 1;
+            pub const CTSIE: u8 = 1;
+            pub const CTSE: u8 = 1;
+            pub const RTSE: u8 = 1;
+            pub const DMAT: u8 = 1;
+            pub const DMAR: u8 = 1;
+            pub const SCEN: u8 = 1;
+            pub const NACK: u8 = 1;
+            pub const HDSEL: u8 = 1;
+            pub const IRLP: u8 = 1;
+            pub const IREN: u8 = 1;
+            pub const EIE: u8 = 1;
+        }
     }
     pub mod gtpr {
         pub const GT: u8 = 8;
         pub const PSC: u8 = 0;
+        pub mod width {
+            pub const GT: u8 = 8;
+            pub const PSC: u8 = 8;
+        }
+    }
+}
+
+pub mod PWR {
+    pub mod cr {
+        pub const VOS: u8 = 14;
+        pub const FPDS: u8 = 9;
+        pub const DBP: u8 = 8;
+        pub const PLS: u8 = 5;
+        pub const PVDE: u8 = 4;
+        pub const CSBF: u8 = 3;
+        pub const CWUF: u8 = 2;
+        pub const PDDS: u8 = 1;
+        pub const LPDS: u8 = 0;
+        pub mod width {
+            pub const VOS: u8 = 1;
+            pub const FPDS: u8 = 1;
+            pub const DBP: u8 = 1;
+            pub const PLS: u8 = 3;
+            pub const PVDE: u8 = 1;
+            pub const CSBF: u8 = 1;
+            pub const CWUF: u8 = 1;
+            pub const PDDS: u8 = 1;
+            pub const LPDS: u8 = 1;
+        }
+    }
+    pub mod csr {
+        pub const VOSRDY: u8 = 14;
+        pub const BRE: u8 = 9;
+        pub const EWUP: u8 = 8;
+        pub const BRR: u8 = 3;
+        pub const PVDO: u8 = 2;
+        pub const SBF: u8 = 1;
+        pub const WUF: u8 = 0;
+        pub mod width {
+            pub const VOSRDY: u8 = 1;
+            pub const BRE: u8 = 1;
+            pub const EWUP: u8 = 1;
+            pub const BRR: u8 = 1;
+            pub const PVDO: u8 = 1;
+            pub const SBF: u8 = 1;
+            pub const WUF: u8 = 1;
+        }
+    }
+}
+
+pub mod RTC {
+    pub mod tr {
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        pub mod width {
+            pub const PM: u8 = 1;
+            pub const HT: u8 = 2;
+            pub const HU: u8 = 4;
+            pub const MNT: u8 = 3;
+            pub const MNU: u8 = 4;
+            pub const ST: u8 = 3;
+            pub const SU: u8 = 4;
+        }
+    }
+    pub mod dr {
+        pub const YT: u8 = 20;
+        pub const YU: u8 = 16;
+        pub const WDU: u8 = 13;
+        pub const MT: u8 = 12;
+        pub const MU: u8 = 8;
+        pub const DT: u8 = 4;
+        pub const DU: u8 = 0;
+        pub mod width {
+            pub const YT: u8 = 4;
+            pub const YU: u8 = 4;
+            pub const WDU: u8 = 3;
+            pub const MT: u8 = 1;
+            pub const MU: u8 = 4;
+            pub const DT: u8 = 2;
+            pub const DU: u8 = 4;
+        }
+    }
+    pub mod cr {
+        pub const COE: u8 = 23;
+        pub const OSEL: u8 = 21;
+        pub const POL: u8 = 20;
+        pub const COSEL: u8 = 19;
+        pub const BKP: u8 = 18;
+        pub const SUB1H: u8 = 17;
+        pub const ADD1H: u8 = 16;
+        pub const TSIE: u8 = 15;
+        pub const WUTIE: u8 = 14;
+        pub const ALRBIE: u8 = 13;
+        pub const ALRAIE: u8 = 12;
+        pub const TSE: u8 = 11;
+        pub const WUTE: u8 = 10;
+        pub const ALRBE: u8 = 9;
+        pub const ALRAE: u8 = 8;
+        pub const DCE: u8 = 7;
+        pub const FMT: u8 = 6;
+        pub const BYPSHAD: u8 = 5;
+        pub const REFCKON: u8 = 4;
+        pub const TSEDGE: u8 = 3;
+        pub const WCKSEL: u8 = 0;
+        pub mod width {
+            pub const COE: u8 = 1;
+            pub const OSEL: u8 = 2;
+            pub const POL: u8 = 1;
+            pub const COSEL: u8 = 1;
+            pub const BKP: u8 = 1;
+            pub const SUB1H: u8 = 1;
+            pub const ADD1H: u8 = 1;
+            pub const TSIE: u8 = 1;
+            pub const WUTIE: u8 = 1;
+            pub const ALRBIE: u8 = 1;
+            pub const ALRAIE: u8 = 1;
+            pub const TSE: u8 = 1;
+            pub const WUTE: u8 = 1;
+            pub const ALRBE: u8 = 1;
+            pub const ALRAE: u8 = 1;
+            pub const DCE: u8 = 1;
+            pub const FMT: u8 = 1;
+            pub const BYPSHAD: u8 = 1;
+            pub const REFCKON: u8 = 1;
+            pub const TSEDGE: u8 = 1;
+            pub const WCKSEL: u8 = 3;
+        }
+    }
+    pub mod isr {
+        pub const RECALPF: u8 = 16;
+        pub const TAMP2F: u8 = 14;
+        pub const TAMP1F: u8 = 13;
+        pub const TSOVF: u8 = 12;
+        pub const TSF: u8 = 11;
+        pub const WUTF: u8 = 10;
+        pub const ALRBF: u8 = 9;
+        pub const ALRAF: u8 = 8;
+        pub const INIT: u8 = 7;
+        pub const INITF: u8 = 6;
+        pub const RSF: u8 = 5;
+        pub const INITS: u8 = 4;
+        pub const SHPF: u8 = 3;
+        pub const WUTWF: u8 = 2;
+        pub const ALRBWF: u8 = 1;
+        pub const ALRAWF: u8 = 0;
+        pub mod width {
+            pub const RECALPF: u8 = 1;
+            pub const TAMP2F: u8 = 1;
+            pub const TAMP1F: u8 = 1;
+            pub const TSOVF: u8 = 1;
+            pub const TSF: u8 = 1;
+            pub const WUTF: u8 = 1;
+            pub const ALRBF: u8 = 1;
+            pub const ALRAF: u8 = 1;
+            pub const INIT: u8 = 1;
+            pub const INITF: u8 = 1;
+            pub const RSF: u8 = 1;
+            pub const INITS: u8 = 1;
+            pub const SHPF: u8 = 1;
+            pub const WUTWF: u8 = 1;
+            pub const ALRBWF: u8 = 1;
+            pub const ALRAWF: u8 = 1;
+        }
+    }
+    pub mod prer {
+        pub const PREDIV_A: u8 = 16;
+        pub const PREDIV_S: u8 = 0;
+        pub mod width {
+            pub const PREDIV_A: u8 = 7;
+            pub const PREDIV_S: u8 = 15;
+        }
+    }
+    pub mod wutr {
+        pub const WUT: u8 = 0;
+        pub mod width {
+            pub const WUT: u8 = 16;
+        }
+    }
+    pub mod calibr {
+        pub const DCS: u8 = 7;
+        pub const DC: u8 = 0;
+        pub mod width {
+            pub const DCS: u8 = 1;
+            pub const DC: u8 = 5;
+        }
+    }
+    pub mod alrmar {
+        pub const MSK4: u8 = 31;
+        pub const WDSEL: u8 = 30;
+        pub const DT: u8 = 28;
+        pub const DU: u8 = 24;
+        pub const MSK3: u8 = 23;
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MSK2: u8 = 15;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const MSK1: u8 = 7;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        pub mod width {
+            pub const MSK4: u8 = 1;
+            pub const WDSEL: u8 = 1;
+            pub const DT: u8 = 2;
+            pub const DU: u8 = 4;
+            pub const MSK3: u8 = 1;
+            pub const PM: u8 = 1;
+            pub const HT: u8 = 2;
+            pub const HU: u8 = 4;
+            pub const MSK2: u8 = 1;
+            pub const MNT: u8 = 3;
+            pub const MNU: u8 = 4;
+            pub const MSK1: u8 = 1;
+            pub const ST: u8 = 3;
+            pub const SU: u8 = 4;
+        }
+    }
+    pub mod alrmbr {
+        pub const MSK4: u8 = 31;
+        pub const WDSEL: u8 = 30;
+        pub const DT: u8 = 28;
+        pub const DU: u8 = 24;
+        pub const MSK3: u8 = 23;
+        pub const PM: u8 = 22;
+        pub const HT: u8 = 20;
+        pub const HU: u8 = 16;
+        pub const MSK2: u8 = 15;
+        pub const MNT: u8 = 12;
+        pub const MNU: u8 = 8;
+        pub const MSK1: u8 = 7;
+        pub const ST: u8 = 4;
+        pub const SU: u8 = 0;
+        pub mod width {
+            pub const MSK4: u8 = 1;
+            pub const WDSEL: u8 = 1;
+            pub const DT: u8 = 2;
+            pub const DU: u8 = 4;
+            pub const MSK3: u8 = 1;
+            pub const PM: u8 = 1;
+            pub const HT: u8 = 2;
+            pub const HU: u8 = 4;
+            pub const MSK2: u8 = 1;
+            pub const MNT: u8 = 3;
+            pub const MNU: u8 = 4;
+            pub const MSK1: u8 = 1;
+            pub const ST: u8 = 3;
+            pub const SU: u8 = 4;
+        }
+    }
+    pub mod wpr {
+        pub const KEY: u8 = 0;
+        pub mod width {
+            pub const KEY: u8 = 8;
+        }
+    }
+    pub mod ssr {
+        pub const SS: u8 = 0;
+        pub mod width {
+            pub const SS: u8 = 16;
+        }
+    }
+    pub mod shiftr {
+        pub const ADD1S: u8 = 31;
+        pub const SUBFS: u8 = 0;
+        pub mod width {
+            pub const ADD1S: u8 = 1;
+            pub const SUBFS: u8 = 15;
+        }
+    }
+    pub mod calr {
+        pub const CALP: u8 = 15;
+        pub const CALW8: u8 = 14;
+        pub const CALW16: u8 = 13;
+        pub const CALM: u8 = 0;
+        pub mod width {
+            pub const CALP: u8 = 1;
+            pub const CALW8: u8 = 1;
+            pub const CALW16: u8 = 1;
+            pub const CALM: u8 = 9;
+        }
+    }
+    pub mod tafcr {
+        pub const ALARMOUTTYPE: u8 = 18;
+        pub const TSINSEL: u8 = 17;
+        pub const TAMP1INSEL: u8 = 16;
+        pub const TAMPPUDIS: u8 = 15;
+        pub const TAMPPRCH: u8 = 13;
+        pub const TAMPFLT: u8 = 11;
+        pub const TAMPFREQ: u8 = 8;
+        pub const TAMPTS: u8 = 7;
+        pub const TAMP2TRG: u8 = 4;
+        pub const TAMP2E: u8 = 3;
+        pub const TAMPIE: u8 = 2;
+        pub const TAMP1TRG: u8 = 1;
+        pub const TAMP1E: u8 = 0;
+        pub mod width {
+            pub const ALARMOUTTYPE: u8 = 1;
+            pub const TSINSEL: u8 = 1;
+            pub const TAMP1INSEL: u8 = 1;
+            pub const TAMPPUDIS: u8 = 1;
+            pub const TAMPPRCH: u8 = 2;
+            pub const TAMPFLT: u8 = 2;
+            pub const TAMPFREQ: u8 = 3;
+            pub const TAMPTS: u8 = 1;
+            pub const TAMP2TRG: u8 = 1;
+            pub const TAMP2E: u8 = 1;
+            pub const TAMPIE: u8 = 1;
+            pub const TAMP1TRG: u8 = 1;
+            pub const TAMP1E: u8 = 1;
+        }
+    }
+    pub mod alrmassr {
+        pub const MASKSS: u8 = 24;
+        pub const SS: u8 = 0;
+        pub mod width {
+            pub const MASKSS: u8 = 4;
+            pub const SS: u8 = 15;
+        }
+    }
+    pub mod alrmbssr {
+        pub const MASKSS: u8 = 24;
+        pub const SS: u8 = 0;
+        pub mod width {
+            pub const MASKSS: u8 = 4;
+            pub const SS: u8 = 15;
+        }
+    }
+    pub mod bkp0r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp1r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp2r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp3r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp4r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp5r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp6r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp7r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp8r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp9r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp10r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp11r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp12r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp13r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp14r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp15r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp16r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp17r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp18r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+    pub mod bkp19r {
+        pub const BKP: u8 = 0;
+        pub mod width {
+            pub const BKP: u8 = 32;
+        }
+    }
+}
+
+pub mod EXTI {
+    pub mod imr {
+        pub const MR22: u8 = 22;
+        pub const MR21: u8 = 21;
+        pub const MR20: u8 = 20;
+        pub const MR19: u8 = 19;
+        pub const MR18: u8 = 18;
+        pub const MR17: u8 = 17;
+        pub const MR16: u8 = 16;
+        pub const MR15: u8 = 15;
+        pub const MR14: u8 = 14;
+        pub const MR13: u8 = 13;
+        pub const MR12: u8 = 12;
+        pub const MR11: u8 = 11;
+        pub const MR10: u8 = 10;
+        pub const MR9: u8 = 9;
+        pub const MR8: u8 = 8;
+        pub const MR7: u8 = 7;
+        pub const MR6: u8 = 6;
+        pub const MR5: u8 = 5;
+        pub const MR4: u8 = 4;
+        pub const MR3: u8 = 3;
+        pub const MR2: u8 = 2;
+        pub const MR1: u8 = 1;
+        pub const MR0: u8 = 0;
+        pub mod width {
+            pub const MR22: u8 = 1;
+            pub const MR21: u8 = 1;
+            pub const MR20: u8 = 1;
+            pub const MR19: u8 = 1;
+            pub const MR18: u8 = 1;
+            pub const MR17: u8 = 1;
+            pub const MR16: u8 = 1;
+            pub const MR15: u8 = 1;
+            pub const MR14: u8 = 1;
+            pub const MR13: u8 = 1;
+            pub const MR12: u8 = 1;
+            pub const MR11: u8 = 1;
+            pub const MR10: u8 = 1;
+            pub const MR9: u8 = 1;
+            pub const MR8: u8 = 1;
+            pub const MR7: u8 = 1;
+            pub const MR6: u8 = 1;
+            pub const MR5: u8 = 1;
+            pub const MR4: u8 = 1;
+            pub const MR3: u8 = 1;
+            pub const MR2: u8 = 1;
+            pub const MR1: u8 = 1;
+            pub const MR0: u8 = 1;
+        }
+    }
+    pub mod emr {
+        pub const MR22: u8 = 22;
+        pub const MR21: u8 = 21;
+        pub const MR20: u8 = 20;
+        pub const MR19: u8 = 19;
+        pub const MR18: u8 = 18;
+        pub const MR17: u8 = 17;
+        pub const MR16: u8 = 16;
+        pub const MR15: u8 = 15;
+        pub const MR14: u8 = 14;
+        pub const MR13: u8 = 13;
+        pub const MR12: u8 = 12;
+        pub const MR11: u8 = 11;
+        pub const MR10: u8 = 10;
+        pub const MR9: u8 = 9;
+        pub const MR8: u8 = 8;
+        pub const MR7: u8 = 7;
+        pub const MR6: u8 = 6;
+        pub const MR5: u8 = 5;
+        pub const MR4: u8 = 4;
+        pub const MR3: u8 = 3;
+        pub const MR2: u8 = 2;
+        pub const MR1: u8 = 1;
+        pub const MR0: u8 = 0;
+        pub mod width {
+            pub const MR22: u8 = 1;
+            pub const MR21: u8 = 1;
+            pub const MR20: u8 = 1;
+            pub const MR19: u8 = 1;
+            pub const MR18: u8 = 1;
+            pub const MR17: u8 = 1;
+            pub const MR16: u8 = 1;
+            pub const MR15: u8 = 1;
+            pub const MR14: u8 = 1;
+            pub const MR13: u8 = 1;
+            pub const MR12: u8 = 1;
+            pub const MR11: u8 = 1;
+            pub const MR10: u8 = 1;
+            pub const MR9: u8 = 1;
+            pub const MR8: u8 = 1;
+            pub const MR7: u8 = 1;
+            pub const MR6: u8 = 1;
+            pub const MR5: u8 = 1;
+            pub const MR4: u8 = 1;
+            pub const MR3: u8 = 1;
+            pub const MR2: u8 = 1;
+            pub const MR1: u8 = 1;
+            pub const MR0: u8 = 1;
+        }
+    }
+    pub mod rtsr {
+        pub const TR22: u8 = 22;
+        pub const TR21: u8 = 21;
+        pub const TR20: u8 = 20;
+        pub const TR19: u8 = 19;
+        pub const TR18: u8 = 18;
+        pub const TR17: u8 = 17;
+        pub const TR16: u8 = 16;
+        pub const TR15: u8 = 15;
+        pub const TR14: u8 = 14;
+        pub const TR13: u8 = 13;
+        pub const TR12: u8 = 12;
+        pub const TR11: u8 = 11;
+        pub const TR10: u8 = 10;
+        pub const TR9: u8 = 9;
+        pub const TR8: u8 = 8;
+        pub const TR7: u8 = 7;
+        pub const TR6: u8 = 6;
+        pub const TR5: u8 = 5;
+        pub const TR4: u8 = 4;
+        pub const TR3: u8 = 3;
+        pub const TR2: u8 = 2;
+        pub const TR1: u8 = 1;
+        pub const TR0: u8 = 0;
+        pub mod width {
+            pub const TR22: u8 = 1;
+            pub const TR21: u8 = 1;
+            pub const TR20: u8 = 1;
+            pub const TR19: u8 = 1;
+            pub const TR18: u8 = 1;
+            pub const TR17: u8 = 1;
+            pub const TR16: u8 = 1;
+            pub const TR15: u8 = 1;
+            pub const TR14: u8 = 1;
+            pub const TR13: u8 = 1;
+            pub const TR12: u8 = 1;
+            pub const TR11: u8 = 1;
+            pub const TR10: u8 = 1;
+            pub const TR9: u8 = 1;
+            pub const TR8: u8 = 1;
+            pub const TR7: u8 = 1;
+            pub const TR6: u8 = 1;
+            pub const TR5: u8 = 1;
+            pub const TR4: u8 = 1;
+            pub const TR3: u8 = 1;
+            pub const TR2: u8 = 1;
+            pub const TR1: u8 = 1;
+            pub const TR0: u8 = 1;
+        }
+    }
+    pub mod ftsr {
+        pub const TR22: u8 = 22;
+        pub const TR21: u8 = 21;
+        pub const TR20: u8 = 20;
+        pub const TR19: u8 = 19;
+        pub const TR18: u8 = 18;
+        pub const TR17: u8 = 17;
+        pub const TR16: u8 = 16;
+        pub const TR15: u8 = 15;
+        pub const TR14: u8 = 14;
+        pub const TR13: u8 = 13;
+        pub const TR12: u8 = 12;
+        pub const TR11: u8 = 11;
+        pub const TR10: u8 = 10;
+        pub const TR9: u8 = 9;
+        pub const TR8: u8 = 8;
+        pub const TR7: u8 = 7;
+        pub const TR6: u8 = 6;
+        pub const TR5: u8 = 5;
+        pub const TR4: u8 = 4;
+        pub const TR3: u8 = 3;
+        pub const TR2: u8 = 2;
+        pub const TR1: u8 = 1;
+        pub const TR0: u8 = 0;
+        pub mod width {
+            pub const TR22: u8 = 1;
+            pub const TR21: u8 = 1;
+            pub const TR20: u8 = 1;
+            pub const TR19: u8 = 1;
+            pub const TR18: u8 = 1;
+            pub const TR17: u8 = 1;
+            pub const TR16: u8 = 1;
+            pub const TR15: u8 = 1;
+            pub const TR14: u8 = 1;
+            pub const TR13: u8 = 1;
+            pub const TR12: u8 = 1;
+            pub const TR11: u8 = 1;
+            pub const TR10: u8 = 1;
+            pub const TR9: u8 = 1;
+            pub const TR8: u8 = 1;
+            pub const TR7: u8 = 1;
+            pub const TR6: u8 = 1;
+            pub const TR5: u8 = 1;
+            pub const TR4: u8 = 1;
+            pub const TR3: u8 = 1;
+            pub const TR2: u8 = 1;
+            pub const TR1: u8 = 1;
+            pub const TR0: u8 = 1;
+        }
+    }
+    pub mod swier {
+        pub const SWIER22: u8 = 22;
+        pub const SWIER21: u8 = 21;
+        pub const SWIER20: u8 = 20;
+        pub const SWIER19: u8 = 19;
+        pub const SWIER18: u8 = 18;
+        pub const SWIER17: u8 = 17;
+        pub const SWIER16: u8 = 16;
+        pub const SWIER15: u8 = 15;
+        pub const SWIER14: u8 = 14;
+        pub const SWIER13: u8 = 13;
+        pub const SWIER12: u8 = 12;
+        pub const SWIER11: u8 = 11;
+        pub const SWIER10: u8 = 10;
+        pub const SWIER9: u8 = 9;
+        pub const SWIER8: u8 = 8;
+        pub const SWIER7: u8 = 7;
+        pub const SWIER6: u8 = 6;
+        pub const SWIER5: u8 = 5;
+        pub const SWIER4: u8 = 4;
+        pub const SWIER3: u8 = 3;
+        pub const SWIER2: u8 = 2;
+        pub const SWIER1: u8 = 1;
+        pub const SWIER0: u8 = 0;
+        pub mod width {
+            pub const SWIER22: u8 = 1;
+            pub const SWIER21: u8 = 1;
+            pub const SWIER20: u8 = 1;
+            pub const SWIER19: u8 = 1;
+            pub const SWIER18: u8 = 1;
+            pub const SWIER17: u8 = 1;
+            pub const SWIER16: u8 = 1;
+            pub const SWIER15: u8 = 1;
+            pub const SWIER14: u8 = 1;
+            pub const SWIER13: u8 = 1;
+            pub const SWIER12: u8 = 1;
+            pub const SWIER11: u8 = 1;
+            pub const SWIER10: u8 = 1;
+            pub const SWIER9: u8 = 1;
+            pub const SWIER8: u8 = 1;
+            pub const SWIER7: u8 = 1;
+            pub const SWIER6: u8 = 1;
+            pub const SWIER5: u8 = 1;
+            pub const SWIER4: u8 = 1;
+            pub const SWIER3: u8 = 1;
+            pub const SWIER2: u8 = 1;
+            pub const SWIER1: u8 = 1;
+            pub const SWIER0: u8 = 1;
+        }
+    }
+    pub mod pr {
+        pub const PR22: u8 = 22;
+        pub const PR21: u8 = 21;
+        pub const PR20: u8 = 20;
+        pub const PR19: u8 = 19;
+        pub const PR18: u8 = 18;
+        pub const PR17: u8 = 17;
+        pub const PR16: u8 = 16;
+        pub const PR15: u8 = 15;
+        pub const PR14: u8 = 14;
+        pub const PR13: u8 = 13;
+        pub const PR12: u8 = 12;
+        pub const PR11: u8 = 11;
+        pub const PR10: u8 = 10;
+        pub const PR9: u8 = 9;
+        pub const PR8: u8 = 8;
+        pub const PR7: u8 = 7;
+        pub const PR6: u8 = 6;
+        pub const PR5: u8 = 5;
+        pub const PR4: u8 = 4;
+        pub const PR3: u8 = 3;
+        pub const PR2: u8 = 2;
+        pub const PR1: u8 = 1;
+        pub const PR0: u8 = 0;
+        pub mod width {
+            pub const PR22: u8 = 1;
+            pub const PR21: u8 = 1;
+            pub const PR20: u8 = 1;
+            pub const PR19: u8 = 1;
+            pub const PR18: u8 = 1;
+            pub const PR17: u8 = 1;
+            pub const PR16: u8 = 1;
+            pub const PR15: u8 = 1;
+            pub const PR14: u8 = 1;
+            pub const PR13: u8 = 1;
+            pub const PR12: u8 = 1;
+            pub const PR11: u8 = 1;
+            pub const PR10: u8 = 1;
+            pub const PR9: u8 = 1;
+            pub const PR8: u8 = 1;
+            pub const PR7: u8 = 1;
+            pub const PR6: u8 = 1;
+            pub const PR5: u8 = 1;
+            pub const PR4: u8 = 1;
+            pub const PR3: u8 = 1;
+            pub const PR2: u8 = 1;
+            pub const PR1: u8 = 1;
+            pub const PR0: u8 = 1;
+        }
+    }
+}
+
+pub mod SYSCFG {
+    pub mod memrm {
+        pub const MEM_MODE: u8 = 0;
+        pub mod width {
+            pub const MEM_MODE: u8 = 2;
+        }
+    }
+    pub mod pmc {
+        pub const MII_RMII_SEL: u8 = 23;
+        pub mod width {
+            pub const MII_RMII_SEL: u8 = 1;
+        }
+    }
+    pub mod exticr1 {
+        pub const EXTI3: u8 = 12;
+        pub const EXTI2: u8 = 8;
+        pub const EXTI1: u8 = 4;
+        pub const EXTI0: u8 = 0;
+        pub mod width {
+            pub const EXTI3: u8 = 4;
+            pub const EXTI2: u8 = 4;
+            pub const EXTI1: u8 = 4;
+            pub const EXTI0: u8 = 4;
+        }
+    }
+    pub mod exticr2 {
+        pub const EXTI7: u8 = 12;
+        pub const EXTI6: u8 = 8;
+        pub const EXTI5: u8 = 4;
+        pub const EXTI4: u8 = 0;
+        pub mod width {
+            pub const EXTI7: u8 = 4;
+            pub const EXTI6: u8 = 4;
+            pub const EXTI5: u8 = 4;
+            pub const EXTI4: u8 = 4;
+        }
+    }
+    pub mod exticr3 {
+        pub const EXTI11: u8 = 12;
+        pub const EXTI10: u8 = 8;
+        pub const EXTI9: u8 = 4;
+        pub const EXTI8: u8 = 0;
+        pub mod width {
+            pub const EXTI11: u8 = 4;
+            pub const EXTI10: u8 = 4;
+            pub const EXTI9: u8 = 4;
+            pub const EXTI8: u8 = 4;
+        }
+    }
+    pub mod exticr4 {
+        pub const EXTI15: u8 = 12;
+        pub const EXTI14: u8 = 8;
+        pub const EXTI13: u8 = 4;
+        pub const EXTI12: u8 = 0;
+        pub mod width {
+            pub const EXTI15: u8 = 4;
+            pub const EXTI14: u8 = 4;
+            pub const EXTI13: u8 = 4;
+            pub const EXTI12: u8 = 4;
+        }
+    }
+    pub mod cmpcr {
+        pub const READY: u8 = 8;
+        pub const CMP_PD: u8 = 0;
+        pub mod width {
+            pub const READY: u8 = 1;
+            pub const CMP_PD: u8 = 1;
+        }
+    }
+}
+
+pub mod CRC {
+    pub mod dr {
+        pub const DR: u8 = 0;
+        pub mod width {
+            pub const DR: u8 = 8;
+        }
+    }
+    pub mod idr {
+        pub const IDR: u8 = 0;
+        pub mod width {
+            pub const IDR: u8 = 8;
+        }
+    }
+    pub mod cr {
+        pub const RESET: u8 = 0;
+        pub mod width {
+            pub const RESET: u8 = 1;
+        }
+    }
+}
+
+pub mod IWDG {
+    pub mod kr {
+        pub const KEY: u8 = 0;
+        pub mod width {
+            pub const KEY: u8 = 16;
+        }
+    }
+    pub mod pr {
+        pub const PR: u8 = 0;
+        pub mod width {
+            pub const PR: u8 = 3;
+        }
+    }
+    pub mod rlr {
+        pub const RL: u8 = 0;
+        pub mod width {
+            pub const RL: u8 = 12;
+        }
+    }
+    pub mod sr {
+        pub const RVU: u8 = 1;
+        pub const PVU: u8 = 0;
+        pub mod width {
+            pub const RVU: u8 = 1;
+            pub const PVU: u8 = 1;
+        }
     }
 }
 
@@ -1094,15 +3063,28 @@ pub mod WWDG {
     pub mod cr {
         pub const WDGA: u8 = 7;
         pub const T: u8 = 0;
+        pub mod width {
+            pub const WDGA: u8 = 1;
+            pub const T: u8 = 7;
+        }
     }
     pub mod cfr {
         pub const EWI: u8 = 9;
         pub const WDGTB1: u8 = 8;
         pub const WDGTB0: u8 = 7;
         pub const W: u8 = 0;
+        pub mod width {
+            pub const EWI: u8 = 1;
+            pub const WDGTB1: u8 = 1;
+            pub const WDGTB0: u8 = 1;
+            pub const W: u8 = 7;
+        }
     }
     pub mod sr {
         pub const EWIF: u8 = 0;
+        pub mod width {
+            pub const EWIF: u8 = 1;
+        }
     }
 }
 
@@ -1116,6 +3098,16 @@ pub mod TIM_ADV {
         pub const URS: u8 = 2;
         pub const UDIS: u8 = 1;
         pub const CEN: u8 = 0;
+        pub mod width {
+            pub const CKD: u8 = 2;
+            pub const ARPE: u8 = 1;
+            pub const CMS: u8 = 2;
+            pub const DIR: u8 = 1;
+            pub const OPM: u8 = 1;
+            pub const URS: u8 = 1;
+            pub const UDIS: u8 = 1;
+            pub const CEN: u8 = 1;
+        }
     }
     pub mod cr2 {
         pub const OIS4: u8 = 14;
@@ -1130,6 +3122,20 @@ pub mod TIM_ADV {
         pub const CCDS: u8 = 3;
         pub const CCUS: u8 = 2;
         pub const CCPC: u8 = 0;
+        pub mod width {
+            pub const OIS4: u8 = 2;
+            pub const OIS3N: u8 = 1;
+            pub const OIS3: u8 = 1;
+            pub const OIS2N: u8 = 1;
+            pub const OIS2: u8 = 1;
+            pub const OIS1N: u8 = 1;
+            pub const OIS1: u8 = 1;
+            pub const TI1S: u8 = 1;
+            pub const MMS: u8 = 3;
+            pub const CCDS: u8 = 1;
+            pub const CCUS: u8 = 1;
+            pub const CCPC: u8 = 1;
+        }
     }
     pub mod smcr {
         pub const ETP: u8 = 15;
@@ -1139,6 +3145,15 @@ pub mod TIM_ADV {
         pub const MSM: u8 = 7;
         pub const TS: u8 = 4;
         pub const SMS: u8 = 0;
+        pub mod width {
+            pub const ETP: u8 = 1;
+            pub const ECE: u8 = 1;
+            pub const ETPS: u8 = 2;
+            pub const ETF: u8 = 4;
+            pub const MSM: u8 = 1;
+            pub const TS: u8 = 3;
+            pub const SMS: u8 = 3;
+        }
     }
     pub mod dier {
         pub const TDE: u8 = 14;
@@ -1156,6 +3171,23 @@ pub mod TIM_ADV {
         pub const UIE: u8 = 0;
         pub const BIE: u8 = 7;
         pub const COMIE: u8 = 5;
+        pub mod width {
+            pub const TDE: u8 = 1;
+            pub const COMDE: u8 = 1;
+            pub const CC4DE: u8 = 1;
+            pub const CC3DE: u8 = 1;
+            pub const CC2DE: u8 = 1;
+            pub const CC1DE: u8 = 1;
+            pub const UDE: u8 = 1;
+            pub const TIE: u8 = 1;
+            pub const CC4IE: u8 = 1;
+            pub const CC3IE: u8 = 1;
+            pub const CC2IE: u8 = 1;
+            pub const CC1IE: u8 = 1;
+            pub const UIE: u8 = 1;
+            pub const BIE: u8 = 1;
+            pub const COMIE: u8 = 1;
+        }
     }
     pub mod sr {
         pub const CC4OF: u8 = 12;
@@ -1170,6 +3202,20 @@ pub mod TIM_ADV {
         pub const CC2IF: u8 = 2;
         pub const CC1IF: u8 = 1;
         pub const UIF: u8 = 0;
+        pub mod width {
+            pub const CC4OF: u8 = 1;
+            pub const CC3OF: u8 = 1;
+            pub const CC2OF: u8 = 1;
+            pub const CC1OF: u8 = 1;
+            pub const BIF: u8 = 1;
+            pub const TIF: u8 = 1;
+            pub const COMIF: u8 = 1;
+            pub const CC4IF: u8 = 1;
+            pub const CC3IF: u8 = 1;
+            pub const CC2IF: u8 = 1;
+            pub const CC1IF: u8 = 1;
+            pub const UIF: u8 = 1;
+        }
     }
     pub mod egr {
         pub const BG: u8 = 7;
@@ -1180,6 +3226,16 @@ pub mod TIM_ADV {
         pub const CC2G: u8 = 2;
         pub const CC1G: u8 = 1;
         pub const UG: u8 = 0;
+        pub mod width {
+            pub const BG: u8 = 1;
+            pub const TG: u8 = 1;
+            pub const COMG: u8 = 1;
+            pub const CC4G: u8 = 1;
+            pub const CC3G: u8 = 1;
+            pub const CC2G: u8 = 1;
+            pub const CC1G: u8 = 1;
+            pub const UG: u8 = 1;
+        }
     }
     pub mod ccmr1_output {
         pub const OC2CE: u8 = 15;
@@ -1192,6 +3248,18 @@ pub mod TIM_ADV {
         pub const OC1PE: u8 = 3;
         pub const OC1FE: u8 = 2;
         pub const CC1S: u8 = 0;
+        pub mod width {
+            pub const OC2CE: u8 = 1;
+            pub const OC2M: u8 = 3;
+            pub const OC2PE: u8 = 1;
+            pub const OC2FE: u8 = 1;
+            pub const CC2S: u8 = 2;
+            pub const OC1CE: u8 = 1;
+            pub const OC1M: u8 = 3;
+            pub const OC1PE: u8 = 1;
+            pub const OC1FE: u8 = 1;
+            pub const CC1S: u8 = 2;
+        }
     }
     pub mod ccmr1_input {
         pub const IC2F: u8 = 12;
@@ -1200,6 +3268,14 @@ pub mod TIM_ADV {
         pub const IC1F: u8 = 4;
         pub const ICPCS: u8 = 2;
         pub const CC1S: u8 = 0;
+        pub mod width {
+            pub const IC2F: u8 = 4;
+            pub const IC2PCS: u8 = 2;
+            pub const CC2S: u8 = 2;
+            pub const IC1F: u8 = 4;
+            pub const ICPCS: u8 = 2;
+            pub const CC1S: u8 = 2;
+        }
     }
     pub mod ccmr2_output {
         pub const OC4CE: u8 = 15;
@@ -1212,6 +3288,18 @@ pub mod TIM_ADV {
         pub const OC3PE: u8 = 3;
         pub const OC3FE: u8 = 2;
         pub const CC3S: u8 = 0;
+        pub mod width {
+            pub const OC4CE: u8 = 1;
+            pub const OC4M: u8 = 3;
+            pub const OC4PE: u8 = 1;
+            pub const OC4FE: u8 = 1;
+            pub const CC4S: u8 = 2;
+            pub const OC3CE: u8 = 1;
+            pub const OC3M: u8 = 3;
+            pub const OC3PE: u8 = 1;
+            pub const OC3FE: u8 = 1;
+            pub const CC3S: u8 = 2;
+        }
     }
     pub mod ccmr2_input {
         pub const IC4F: u8 = 12;
@@ -1220,6 +3308,14 @@ pub mod TIM_ADV {
         pub const IC3F: u8 = 4;
         pub const IC3PSC: u8 = 2;
         pub const CC3S: u8 = 0;
+        pub mod width {
+            pub const IC4F: u8 = 4;
+            pub const IC4PSC: u8 = 2;
+            pub const CC4S: u8 = 2;
+            pub const IC3F: u8 = 4;
+            pub const IC3PSC: u8 = 2;
+            pub const CC3S: u8 = 2;
+        }
     }
     pub mod ccer {
         pub const CC4P: u8 = 13;
@@ -1236,30 +3332,70 @@ pub mod TIM_ADV {
         pub const CC1NE: u8 = 2;
         pub const CC1P: u8 = 1;
         pub const CC1E: u8 = 0;
+        pub mod width {
+            pub const CC4P: u8 = 1;
+            pub const CC4E: u8 = 1;
+            pub const CC3NP: u8 = 1;
+            pub const CC3NE: u8 = 1;
+            pub const CC3P: u8 = 1;
+            pub const CC3E: u8 = 1;
+            pub const CC2NP: u8 = 1;
+            pub const CC2NE: u8 = 1;
+            pub const CC2P: u8 = 1;
+            pub const CC2E: u8 = 1;
+            pub const CC1NP: u8 = 1;
+            pub const CC1NE: u8 = 1;
+            pub const CC1P: u8 = 1;
+            pub const CC1E: u8 = 1;
+        }
     }
     pub mod cnt {
         pub const CNT: u8 = 0;
+        pub mod width {
+            pub const CNT: u8 = 16;
+        }
     }
     pub mod psc {
         pub const PSC: u8 = 0;
+        pub mod width {
+            pub const PSC: u8 = 16;
+        }
     }
     pub mod arr {
         pub const ARR: u8 = 0;
+        pub mod width {
+            pub const ARR: u8 = 16;
+        }
     }
     pub mod rcr {
         pub const REP: u8 = 0;
+        pub mod width {
+            pub const REP: u8 = 8;
+        }
     }
     pub mod ccr1 {
         pub const CCR1: u8 = 0;
+        pub mod width {
+            pub const CCR1: u8 = 16;
+        }
     }
     pub mod ccr2 {
         pub const CCR2: u8 = 0;
+        pub mod width {
+            pub const CCR2: u8 = 16;
+        }
     }
     pub mod ccr3 {
         pub const CCR3: u8 = 0;
+        pub mod width {
+            pub const CCR3: u8 = 16;
+        }
     }
     pub mod ccr4 {
         pub const CCR4: u8 = 0;
+        pub mod width {
+            pub const CCR4: u8 = 16;
+        }
     }
     pub mod bdtr {
         pub const MOE: u8 = 15;
@@ -1270,13 +3406,30 @@ pub mod TIM_ADV {
         pub const OSSI: u8 = 10;
         pub const LOCK: u8 = 8;
         pub const DTG: u8 = 0;
+        pub mod width {
+            pub const MOE: u8 = 1;
+            pub const AOE: u8 = 1;
+            pub const BKP: u8 = 1;
+            pub const BKE: u8 = 1;
+            pub const OSSR: u8 = 1;
+            pub const OSSI: u8 = 1;
+            pub const LOCK: u8 = 2;
+            pub const DTG: u8 = 8;
+        }
     }
     pub mod dcr {
         pub const DBL: u8 = 8;
         pub const DBA: u8 = 0;
+        pub mod width {
+            pub const DBL: u8 = 5;
+            pub const DBA: u8 = 5;
+        }
     }
     pub mod dmar {
         pub const DMAB: u8 = 0;
+        pub mod width {
+            pub const DMAB: u8 = 16;
+        }
     }
 }
 
@@ -1290,11 +3443,26 @@ pub mod TIM_GEN {
         pub const URS: u8 = 2;
         pub const UDIS: u8 = 1;
         pub const CEN: u8 = 0;
+        pub mod width {
+            pub const CKD: u8 = 2;
+            pub const ARPE: u8 = 1;
+            pub const CMS: u8 = 2;
+            pub const DIR: u8 = 1;
+            pub const OPM: u8 = 1;
+            pub const URS: u8 = 1;
+            pub const UDIS: u8 = 1;
+            pub const CEN: u8 = 1;
+        }
     }
     pub mod cr2 {
         pub const TI1S: u8 = 7;
         pub const MMS: u8 = 4;
         pub const CCDS: u8 = 3;
+        pub mod width {
+            pub const TI1S: u8 = 1;
+            pub const MMS: u8 = 3;
+            pub const CCDS: u8 = 1;
+        }
     }
     pub mod smcr {
         pub const ETP: u8 = 15;
@@ -1304,6 +3472,15 @@ pub mod TIM_GEN {
         pub const MSM: u8 = 7;
         pub const TS: u8 = 4;
         pub const SMS: u8 = 0;
+        pub mod width {
+            pub const ETP: u8 = 1;
+            pub const ECE: u8 = 1;
+            pub const ETPS: u8 = 2;
+            pub const ETF: u8 = 4;
+            pub const MSM: u8 = 1;
+            pub const TS: u8 = 3;
+            pub const SMS: u8 = 3;
+        }
     }
     pub mod dier {
         pub const TDE: u8 = 14;
@@ -1318,6 +3495,20 @@ pub mod TIM_GEN {
         pub const CC2IE: u8 = 2;
         pub const CC1IE: u8 = 1;
         pub const UIE: u8 = 0;
+        pub mod width {
+            pub const TDE: u8 = 1;
+            pub const CC4DE: u8 = 1;
+            pub const CC3DE: u8 = 1;
+            pub const CC2DE: u8 = 1;
+            pub const CC1DE: u8 = 1;
+            pub const UDE: u8 = 1;
+            pub const TIE: u8 = 1;
+            pub const CC4IE: u8 = 1;
+            pub const CC3IE: u8 = 1;
+            pub const CC2IE: u8 = 1;
+            pub const CC1IE: u8 = 1;
+            pub const UIE: u8 = 1;
+        }
     }
     pub mod sr {
         pub const CC4OF: u8 = 12;
@@ -1330,6 +3521,18 @@ pub mod TIM_GEN {
         pub const CC2IF: u8 = 2;
         pub const CC1IF: u8 = 1;
         pub const UIF: u8 = 0;
+        pub mod width {
+            pub const CC4OF: u8 = 1;
+            pub const CC3OF: u8 = 1;
+            pub const CC2OF: u8 = 1;
+            pub const CC1OF: u8 = 1;
+            pub const TIF: u8 = 1;
+            pub const CC4IF: u8 = 1;
+            pub const CC3IF: u8 = 1;
+            pub const CC2IF: u8 = 1;
+            pub const CC1IF: u8 = 1;
+            pub const UIF: u8 = 1;
+        }
     }
     pub mod egr {
         pub const TG: u8 = 6;
@@ -1338,6 +3541,14 @@ pub mod TIM_GEN {
         pub const CC2G: u8 = 2;
         pub const CC1G: u8 = 1;
         pub const UG: u8 = 0;
+        pub mod width {
+            pub const TG: u8 = 1;
+            pub const CC4G: u8 = 1;
+            pub const CC3G: u8 = 1;
+            pub const CC2G: u8 = 1;
+            pub const CC1G: u8 = 1;
+            pub const UG: u8 = 1;
+        }
     }
     pub mod ccmr1_output {
         pub const OC2CE: u8 = 15;
@@ -1350,6 +3561,18 @@ pub mod TIM_GEN {
         pub const OC1PE: u8 = 3;
         pub const OC1FE: u8 = 2;
         pub const CC1S: u8 = 0;
+        pub mod width {
+            pub const OC2CE: u8 = 1;
+            pub const OC2M: u8 = 3;
+            pub const OC2PE: u8 = 1;
+            pub const OC2FE: u8 = 1;
+            pub const CC2S: u8 = 2;
+            pub const OC1CE: u8 = 1;
+            pub const OC1M: u8 = 3;
+            pub const OC1PE: u8 = 1;
+            pub const OC1FE: u8 = 1;
+            pub const CC1S: u8 = 2;
+        }
     }
     pub mod ccmr1_input {
         pub const IC2F: u8 = 12;
@@ -1358,6 +3581,14 @@ pub mod TIM_GEN {
         pub const IC1F: u8 = 4;
         pub const ICPCS: u8 = 2;
         pub const CC1S: u8 = 0;
+        pub mod width {
+            pub const IC2F: u8 = 4;
+            pub const IC2PCS: u8 = 2;
+            pub const CC2S: u8 = 2;
+            pub const IC1F: u8 = 4;
+            pub const ICPCS: u8 = 2;
+            pub const CC1S: u8 = 2;
+        }
     }
     pub mod ccmr2_output {
         pub const OC4CE: u8 = 15;
@@ -1370,6 +3601,18 @@ pub mod TIM_GEN {
         pub const OC3PE: u8 = 3;
         pub const OC3FE: u8 = 2;
         pub const CC3S: u8 = 0;
+        pub mod width {
+            pub const OC4CE: u8 = 1;
+            pub const OC4M: u8 = 3;
+            pub const OC4PE: u8 = 1;
+            pub const OC4FE: u8 = 1;
+            pub const CC4S: u8 = 2;
+            pub const OC3CE: u8 = 1;
+            pub const OC3M: u8 = 3;
+            pub const OC3PE: u8 = 1;
+            pub const OC3FE: u8 = 1;
+            pub const CC3S: u8 = 2;
+        }
     }
     pub mod ccmr2_input {
         pub const IC4F: u8 = 12;
@@ -1378,6 +3621,14 @@ pub mod TIM_GEN {
         pub const IC3F: u8 = 4;
         pub const IC3PSC: u8 = 2;
         pub const CC3S: u8 = 0;
+        pub mod width {
+            pub const IC4F: u8 = 4;
+            pub const IC4PSC: u8 = 2;
+            pub const CC4S: u8 = 2;
+            pub const IC3F: u8 = 4;
+            pub const IC3PSC: u8 = 2;
+            pub const CC3S: u8 = 2;
+        }
     }
     pub mod ccer {
         pub const CC4NP: u8 = 15;
@@ -1392,43 +3643,523 @@ pub mod TIM_GEN {
         pub const CC1NP: u8 = 3;
         pub const CC1P: u8 = 1;
         pub const CC1E: u8 = 0;
+        pub mod width {
+            pub const CC4NP: u8 = 1;
+            pub const CC4P: u8 = 1;
+            pub const CC4E: u8 = 1;
+            pub const CC3NP: u8 = 1;
+            pub const CC3P: u8 = 1;
+            pub const CC3E: u8 = 1;
+            pub const CC2NP: u8 = 1;
+            pub const CC2P: u8 = 1;
+            pub const CC2E: u8 = 1;
+            pub const CC1NP: u8 = 1;
+            pub const CC1P: u8 = 1;
+            pub const CC1E: u8 = 1;
+        }
     }
     pub mod cnt {
         pub const CNT_H: u8 = 16;
         pub const CNT_L: u8 = 0;
+        pub mod width {
+            pub const CNT_H: u8 = 16;
+            pub const CNT_L: u8 = 16;
+        }
     }
     pub mod psc {
         pub const PSC: u8 = 0;
+        pub mod width {
+            pub const PSC: u8 = 16;
+        }
     }
     pub mod arr {
         pub const ARR_H: u8 = 16;
         pub const ARR_L: u8 = 0;
+        pub mod width {
+            pub const ARR_H: u8 = 16;
+            pub const ARR_L: u8 = 16;
+        }
     }
     pub mod ccr1 {
         pub const CCR1_H: u8 = 16;
         pub const CCR1_L: u8 = 0;
+        pub mod width {
+            pub const CCR1_H: u8 = 16;
+            pub const CCR1_L: u8 = 16;
+        }
     }
     pub mod ccr2 {
         pub const CCR2_H: u8 = 16;
         pub const CCR2_L: u8 = 0;
+        pub mod width {
+            pub const CCR2_H: u8 = 16;
+            pub const CCR2_L: u8 = 16;
+        }
     }
     pub mod ccr3 {
         pub const CCR3_H: u8 = 16;
         pub const CCR3_L: u8 = 0;
+        pub mod width {
+            pub const CCR3_H: u8 = 16;
+            pub const CCR3_L: u8 = 16;
+        }
     }
     pub mod ccr4 {
         pub const CCR4_H: u8 = 16;
         pub const CCR4_L: u8 = 0;
+        pub mod width {
+            pub const CCR4_H: u8 = 16;
+            pub const CCR4_L: u8 = 16;
+        }
     }
     pub mod dcr {
         pub const DBL: u8 = 8;
         pub const DBA: u8 = 0;
+        pub mod width {
+            pub const DBL: u8 = 5;
+            pub const DBA: u8 = 5;
+        }
     }
     pub mod dmar {
         pub const DMAB: u8 = 0;
+        pub mod width {
+            pub const DMAB: u8 = 16;
+        }
     }
     pub mod or {
         pub const ITR1_RMP: u8 = 10;
+        pub mod width {
+            pub const ITR1_RMP: u8 = 2;
+        }
+    }
+}
+
+pub mod ADC1 {
+    pub mod sr {
+        pub const OVR: u8 = 5;
+        pub const STRT: u8 = 4;
+        pub const JSTRT: u8 = 3;
+        pub const JEOC: u8 = 2;
+        pub const EOC: u8 = 1;
+        pub const AWD: u8 = 0;
+        pub mod width {
+            pub const OVR: u8 = 1;
+            pub const STRT: u8 = 1;
+            pub const JSTRT: u8 = 1;
+            pub const JEOC: u8 = 1;
+            pub const EOC: u8 = 1;
+            pub const AWD: u8 = 1;
+        }
+    }
+    pub mod cr1 {
+        pub const OVRIE: u8 = 26;
+        pub const RES: u8 = 24;
+        pub const AWDEN: u8 = 23;
+        pub const JAWDEN: u8 = 22;
+        pub const DISCNUM: u8 = 13;
+        pub const JDISCEN: u8 = 12;
+        pub const DISCEN: u8 = 11;
+        pub const JAUTO: u8 = 10;
+        pub const AWDSGL: u8 = 9;
+        pub const SCAN: u8 = 8;
+        pub const JEOCIE: u8 = 7;
+        pub const AWDIE: u8 = 6;
+        pub const EOCIE: u8 = 5;
+        pub const AWDCH: u8 = 0;
+        pub mod width {
+            pub const OVRIE: u8 = 1;
+            pub const RES: u8 = 2;
+            pub const AWDEN: u8 = 1;
+            pub const JAWDEN: u8 = 1;
+            pub const DISCNUM: u8 = 3;
+            pub const JDISCEN: u8 = 1;
+            pub const DISCEN: u8 = 1;
+            pub const JAUTO: u8 = 1;
+            pub const AWDSGL: u8 = 1;
+            pub const SCAN: u8 = 1;
+            pub const JEOCIE: u8 = 1;
+            pub const AWDIE: u8 = 1;
+            pub const EOCIE: u8 = 1;
+            pub const AWDCH: u8 = 5;
+        }
+    }
+    pub mod cr2 {
+        pub const SWSTART: u8 = 30;
+        pub const EXTEN: u8 = 28;
+        pub const EXTSEL: u8 = 24;
+        pub const JSWSTART: u8 = 22;
+        pub const JEXTEN: u8 = 20;
+        pub const JEXTSEL: u8 = 16;
+        pub const ALIGN: u8 = 11;
+        pub const EOCS: u8 = 10;
+        pub const DDS: u8 = 9;
+        pub const DMA: u8 = 8;
+        pub const CONT: u8 = 1;
+        pub const ADON: u8 = 0;
+        pub mod width {
+            pub const SWSTART: u8 = 1;
+            pub const EXTEN: u8 = 2;
+            pub const EXTSEL: u8 = 4;
+            pub const JSWSTART: u8 = 1;
+            pub const JEXTEN: u8 = 2;
+            pub const JEXTSEL: u8 = 4;
+            pub const ALIGN: u8 = 1;
+            pub const EOCS: u8 = 1;
+            pub const DDS: u8 = 1;
+            pub const DMA: u8 = 1;
+            pub const CONT: u8 = 1;
+            pub const ADON: u8 = 1;
+        }
+    }
+    pub mod smpr1 {
+        pub const SMPx_x: u8 = 0;
+        pub mod width {
+            pub const SMPx_x: u8 = 32;
+        }
+    }
+    pub mod smpr2 {
+        pub const SMPx_x: u8 = 0;
+        pub mod width {
+            pub const SMPx_x: u8 = 32;
+        }
+    }
+    pub mod jofr1 {
+        pub const JOFFSET1: u8 = 0;
+        pub mod width {
+            pub const JOFFSET1: u8 = 12;
+        }
+    }
+    pub mod jofr2 {
+        pub const JOFFSET2: u8 = 0;
+        pub mod width {
+            pub const JOFFSET2: u8 = 12;
+        }
+    }
+    pub mod jofr3 {
+        pub const JOFFSET3: u8 = 0;
+        pub mod width {
+            pub const JOFFSET3: u8 = 12;
+        }
+    }
+    pub mod jofr4 {
+        pub const JOFFSET4: u8 = 0;
+        pub mod width {
+            pub const JOFFSET4: u8 = 12;
+        }
+    }
+    pub mod htr {
+        pub const HT: u8 = 0;
+        pub mod width {
+            pub const HT: u8 = 12;
+        }
+    }
+    pub mod ltr {
+        pub const LT: u8 = 0;
+        pub mod width {
+            pub const LT: u8 = 12;
+        }
+    }
+    pub mod sqr1 {
+        pub const L: u8 = 20;
+        pub const SQ16: u8 = 15;
+        pub const SQ15: u8 = 10;
+        pub const SQ14: u8 = 5;
+        pub const SQ13: u8 = 0;
+        pub mod width {
+            pub const L: u8 = 4;
+            pub const SQ16: u8 = 5;
+            pub const SQ15: u8 = 5;
+            pub const SQ14: u8 = 5;
+            pub const SQ13: u8 = 5;
+        }
+    }
+    pub mod sqr2 {
+        pub const SQ12: u8 = 25;
+        pub const SQ11: u8 = 20;
+        pub const SQ10: u8 = 15;
+        pub const SQ9: u8 = 10;
+        pub const SQ8: u8 = 5;
+        pub const SQ7: u8 = 0;
+        pub mod width {
+            pub const SQ12: u8 = 5;
+            pub const SQ11: u8 = 5;
+            pub const SQ10: u8 = 5;
+            pub const SQ9: u8 = 5;
+            pub const SQ8: u8 = 5;
+            pub const SQ7: u8 = 5;
+        }
+    }
+    pub mod sqr3 {
+        pub const SQ6: u8 = 25;
+        pub const SQ5: u8 = 20;
+        pub const SQ4: u8 = 15;
+        pub const SQ3: u8 = 10;
+        pub const SQ2: u8 = 5;
+        pub const SQ1: u8 = 0;
+        pub mod width {
+            pub const SQ6: u8 = 5;
+            pub const SQ5: u8 = 5;
+            pub const SQ4: u8 = 5;
+            pub const SQ3: u8 = 5;
+            pub const SQ2: u8 = 5;
+            pub const SQ1: u8 = 5;
+        }
+    }
+    pub mod jsqr {
+        pub const JL: u8 = 20;
+        pub const JSQ4: u8 = 15;
+        pub const JSQ3: u8 = 10;
+        pub const JSQ2: u8 = 5;
+        pub const JSQ1: u8 = 0;
+        pub mod width {
+            pub const JL: u8 = 2;
+            pub const JSQ4: u8 = 5;
+            pub const JSQ3: u8 = 5;
+            pub const JSQ2: u8 = 5;
+            pub const JSQ1: u8 = 5;
+        }
+    }
+    pub mod jdr1 {
+        pub const JDATA: u8 = 0;
+        pub mod width {
+            pub const JDATA: u8 = 16;
+        }
+    }
+    pub mod jdr2 {
+        pub const JDATA: u8 = 0;
+        pub mod width {
+            pub const JDATA: u8 = 16;
+        }
+    }
+    pub mod jdr3 {
+        pub const JDATA: u8 = 0;
+        pub mod width {
+            pub const JDATA: u8 = 16;
+        }
+    }
+    pub mod jdr4 {
+        pub const JDATA: u8 = 0;
+        pub mod width {
+            pub const JDATA: u8 = 16;
+        }
+    }
+    pub mod dr {
+        pub const DATA: u8 = 0;
+        pub mod width {
+            pub const DATA: u8 = 16;
+        }
+    }
+}
+pub mod C_ADC {
+    pub mod csr {
+        pub const OVR3: u8 = 21;
+        pub const STRT3: u8 = 20;
+        pub const JSTRT3: u8 = 19;
+        pub const JEOC3: u8 = 18;
+        pub const EOC3: u8 = 17;
+        pub const AWD3: u8 = 16;
+        pub const OVR2: u8 = 13;
+        pub const STRT2: u8 = 12;
+        pub const JSTRT2: u8 = 11;
+        pub const JEOC2: u8 = 10;
+        pub const EOC2: u8 = 9;
+        pub const AWD2: u8 = 8;
+        pub const OVR1: u8 = 5;
+        pub const STRT1: u8 = 4;
+        pub const JSTRT1: u8 = 3;
+        pub const JEOC1: u8 = 2;
+        pub const EOC1: u8 = 1;
+        pub const AWD1: u8 = 0;
+        pub mod width {
+            pub const OVR3: u8 = 1;
+            pub const STRT3: u8 = 1;
+            pub const JSTRT3: u8 = 1;
+            pub const JEOC3: u8 = 1;
+            pub const EOC3: u8 = 1;
+            pub const AWD3: u8 = 1;
+            pub const OVR2: u8 = 1;
+            pub const STRT2: u8 = 1;
+            pub const JSTRT2: u8 = 1;
+            pub const JEOC2: u8 = 1;
+            pub const EOC2: u8 = 1;
+            pub const AWD2: u8 = 1;
+            pub const OVR1: u8 = 1;
+            pub const STRT1: u8 = 1;
+            pub const JSTRT1: u8 = 1;
+            pub const JEOC1: u8 = 1;
+            pub const EOC1: u8 = 1;
+            pub const AWD1: u8 = 1;
+        }
+    }
+    pub mod ccr {
+        pub const TSVREFE: u8 = 23;
+        pub const VBATE: u8 = 22;
+        pub const ADCPRE: u8 = 16;
+        pub const DMA: u8 = 14;
+        pub const DDS: u8 = 13;
+        pub const DELAY: u8 = 8;
+        pub const MULT: u8 = 0;
+        pub mod width {
+            pub const TSVREFE: u8 = 1;
+            pub const VBATE: u8 = 1;
+            pub const ADCPRE: u8 = 2;
+            pub const DMA: u8 = 2;
+            pub const DDS: u8 = 1;
+            pub const DELAY: u8 = 4;
+            pub const MULT: u8 = 5;
+        }
+    }
+    pub mod cdr {
+        pub const DATA2: u8 = 16;
+        pub const DATA1: u8 = 0;
+        pub mod width {
+            pub const DATA2: u8 = 16;
+            pub const DATA1: u8 = 16;
+        }
+    }
+}
+pub mod I2C {
+    pub mod cr1 {
+        pub const SWRST: u8 = 15;
+        pub const ALERT: u8 = 13;
+        pub const PEC: u8 = 12;
+        pub const POS: u8 = 11;
+        pub const ACK: u8 = 10;
+        pub const STOP: u8 = 9;
+        pub const START: u8 = 8;
+        pub const NOSTRETCH: u8 = 7;
+        pub const ENGC: u8 = 6;
+        pub const ENPEC: u8 = 5;
+        pub const ENARP: u8 = 4;
+        pub const SMBTYPE: u8 = 3;
+        pub const SMBUS: u8 = 1;
+        pub const PE: u8 = 0;
+        pub mod width {
+            pub const SWRST: u8 = 1;
+            pub const ALERT: u8 = 1;
+            pub const PEC: u8 = 1;
+            pub const POS: u8 = 1;
+            pub const ACK: u8 = 1;
+            pub const STOP: u8 = 1;
+            pub const START: u8 = 1;
+            pub const NOSTRETCH: u8 = 1;
+            pub const ENGC: u8 = 1;
+            pub const ENPEC: u8 = 1;
+            pub const ENARP: u8 = 1;
+            pub const SMBTYPE: u8 = 1;
+            pub const SMBUS: u8 = 1;
+            pub const PE: u8 = 1;
+        }
+    }
+    pub mod cr2 {
+        pub const LAST: u8 = 12;
+        pub const DMAEN: u8 = 11;
+        pub const ITBUFEN: u8 = 10;
+        pub const ITEVTEN: u8 = 9;
+        pub const ITERREN: u8 = 8;
+        pub const FREQ: u8 = 0;
+        pub mod width {
+            pub const LAST: u8 = 1;
+            pub const DMAEN: u8 = 1;
+            pub const ITBUFEN: u8 = 1;
+            pub const ITEVTEN: u8 = 1;
+            pub const ITERREN: u8 = 1;
+            pub const FREQ: u8 = 6;
+        }
+    }
+    pub mod oar1 {
+        pub const ADDMODE: u8 = 15;
+        pub const ADD10: u8 = 8;
+        pub const ADD7: u8 = 1;
+        pub const ADD0: u8 = 0;
+        pub mod width {
+            pub const ADDMODE: u8 = 1;
+            pub const ADD10: u8 = 2;
+            pub const ADD7: u8 = 7;
+            pub const ADD0: u8 = 1;
+        }
+    }
+    pub mod oar2 {
+        pub const ADD2: u8 = 1;
+        pub const ENDUAL: u8 = 0;
+        pub mod width {
+            pub const ADD2: u8 = 7;
+            pub const ENDUAL: u8 = 1;
+        }
+    }
+    pub mod dr {
+        pub const DR: u8 = 0;
+        pub mod width {
+            pub const DR: u8 = 8;
+        }
+    }
+    pub mod sr1 {
+        pub const SMBALERT: u8 = 15;
+        pub const TIMEOUT: u8 = 14;
+        pub const PECERR: u8 = 12;
+        pub const OVR: u8 = 11;
+        pub const AF: u8 = 10;
+        pub const ARLO: u8 = 9;
+        pub const BERR: u8 = 8;
+        pub const TxE: u8 = 7;
+        pub const RxNE: u8 = 6;
+        pub const STOPF: u8 = 4;
+        pub const ADD10: u8 = 3;
+        pub const BTF: u8 = 2;
+        pub const ADDR: u8 = 1;
+        pub const SB: u8 = 0;
+        pub mod width {
+            pub const SMBALERT: u8 = 1;
+            pub const TIMEOUT: u8 = 1;
+            pub const PECERR: u8 = 1;
+            pub const OVR: u8 = 1;
+            pub const AF: u8 = 1;
+            pub const ARLO: u8 = 1;
+            pub const BERR: u8 = 1;
+            pub const TxE: u8 = 1;
+            pub const RxNE: u8 = 1;
+            pub const STOPF: u8 = 1;
+            pub const ADD10: u8 = 1;
+            pub const BTF: u8 = 1;
+            pub const ADDR: u8 = 1;
+            pub const SB: u8 = 1;
+        }
+    }
+    pub mod sr2 {
+        pub const PEC: u8 = 8;
+        pub const DUALF: u8 = 7;
+        pub const SMBHOST: u8 = 6;
+        pub const SMBDEFAULT: u8 = 5;
+        pub const GENCALL: u8 = 4;
+        pub const TRA: u8 = 2;
+        pub const BUSY: u8 = 1;
+        pub const MSL: u8 = 0;
+        pub mod width {
+            pub const PEC: u8 = 8;
+            pub const DUALF: u8 = 1;
+            pub const SMBHOST: u8 = 1;
+            pub const SMBDEFAULT: u8 = 1;
+            pub const GENCALL: u8 = 1;
+            pub const TRA: u8 = 1;
+            pub const BUSY: u8 = 1;
+            pub const MSL: u8 = 1;
+        }
+    }
+    pub mod ccr {
+        pub const F_S: u8 = 15;
+        pub const DUTY: u8 = 14;
+        pub const CCR: u8 = 0;
+        pub mod width {
+            pub const F_S: u8 = 1;
+            pub const DUTY: u8 = 1;
+            pub const CCR: u8 = 12;
+        }
+    }
+    pub mod trise {
+        pub const TRISE: u8 = 0;
+        pub mod width {
+            pub const TRISE: u8 = 6;
+        }
     }
 }
 
@@ -1440,12 +4171,26 @@ pub mod FLASH {
         pub const DCEN: u8 = 10;
         pub const ICRST: u8 = 11;
         pub const DCRST: u8 = 12;
+        pub mod width {
+            pub const LATENCY: u8 = 3;
+            pub const PRFTEN: u8 = 1;
+            pub const ICEN: u8 = 1;
+            pub const DCEN: u8 = 1;
+            pub const ICRST: u8 = 1;
+            pub const DCRST: u8 = 1;
+        }
     }
     pub mod keyr {
         pub const KEY: u8 = 0;
+        pub mod width {
+            pub const KEY: u8 = 32;
+        }
     }
     pub mod optkeyr {
         pub const OPTKEY: u8 = 0;
+        pub mod width {
+            pub const OPTKEY: u8 = 32;
+        }
     }
     pub mod sr {
         pub const EOP: u8 = 0;
@@ -1455,6 +4200,15 @@ pub mod FLASH {
         pub const PGPERR: u8 = 6;
         pub const PGSERR: u8 = 7;
         pub const BSY: u8 = 16;
+        pub mod width {
+            pub const EOP: u8 = 1;
+            pub const OPERR: u8 = 1;
+            pub const WRPERR: u8 = 1;
+            pub const PGAERR: u8 = 1;
+            pub const PGPERR: u8 = 1;
+            pub const PGSERR: u8 = 1;
+            pub const BSY: u8 = 1;
+        }
     }
     pub mod cr {
         pub const PG: u8 = 0;
@@ -1466,6 +4220,17 @@ pub mod FLASH {
         pub const EOPIE: u8 = 24;
         pub const ERRIE: u8 = 25;
         pub const LOCK: u8 = 31;
+        pub mod width {
+            pub const PG: u8 = 1;
+            pub const SER: u8 = 1;
+            pub const MER: u8 = 1;
+            pub const SNB: u8 = 4;
+            pub const PSIZE: u8 = 2;
+            pub const STRT: u8 = 1;
+            pub const EOPIE: u8 = 1;
+            pub const ERRIE: u8 = 1;
+            pub const LOCK: u8 = 1;
+        }
     }
     pub mod optcr {
         pub const OPTLOCK: u8 = 0;
@@ -1476,9 +4241,26 @@ pub mod FLASH {
         pub const nRST_STDBY: u8 = 7;
         pub const RDP: u8 = 8;
         pub const nWRP: u8 = 16;
+        pub mod width {
+            pub const OPTLOCK: u8 = 1;
+            pub const OPTSTRT: u8 = 1;
+            pub const BOR_LEV: u8 = 2;
+            pub const WDG_SW: u8 = 1;
+            pub const nRST_STOP: u8 = 1;
+            pub const nRST_STDBY: u8 = 1;
+            pub const RDP: u8 = 8;
+            pub const nWRP: u8 = 12;
+        }
     }
 }
 
+#[repr(C)]
+pub struct RngReg {
+    pub cr: u32,
+    pub sr: u32,
+    pub dr: u32,
+}
+
 #[repr(C)]
 pub struct DbgReg {
     pub dbgmcu_idcode: u32,
@@ -1619,6 +4401,93 @@ pub struct UsartReg {
     pub gtpr: u32,
 }
 
+#[repr(C)]
+pub struct PwrReg {
+    pub cr: u32,
+    pub csr: u32,
+}
+
+#[repr(C)]
+pub struct RtcReg {
+    pub tr: u32,
+    pub dr: u32,
+    pub cr: u32,
+    pub isr: u32,
+    pub prer: u32,
+    pub wutr: u32,
+    pub calibr: u32,
+    pub alrmar: u32,
+    pub alrmbr: u32,
+    pub wpr: u32,
+    pub ssr: u32,
+    pub shiftr: u32,
+    pub tstr: u32,
+    pub tsdr: u32,
+    pub tsssr: u32,
+    pub calr: u32,
+    pub tafcr: u32,
+    pub alrmassr: u32,
+    pub alrmbssr: u32,
+    reserved0: u32,
+    pub bkp0r: u32,
+    pub bkp1r: u32,
+    pub bkp2r: u32,
+    pub bkp3r: u32,
+    pub bkp4r: u32,
+    pub bkp5r: u32,
+    pub bkp6r: u32,
+    pub bkp7r: u32,
+    pub bkp8r: u32,
+    pub bkp9r: u32,
+    pub bkp10r: u32,
+    pub bkp11r: u32,
+    pub bkp12r: u32,
+    pub bkp13r: u32,
+    pub bkp14r: u32,
+    pub bkp15r: u32,
+    pub bkp16r: u32,
+    pub bkp17r: u32,
+    pub bkp18r: u32,
+    pub bkp19r: u32,
+}
+
+#[repr(C)]
+pub struct ExtiReg {
+    pub imr: u32,
+    pub emr: u32,
+    pub rtsr: u32,
+    pub ftsr: u32,
+    pub swier: u32,
+    pub pr: u32,
+}
+
+#[repr(C)]
+pub struct SyscfgReg {
+    pub memrm: u32,
+    pub pmc: u32,
+    pub exticr1: u32,
+    pub exticr2: u32,
+    pub exticr3: u32,
+    pub exticr4: u32,
+    reserved0: [u32; 2],
+    pub cmpcr: u32,
+}
+
+#[repr(C)]
+pub struct CrcReg {
+    pub dr: u32,
+    pub idr: u32,
+    pub cr: u32,
+}
+
+#[repr(C)]
+pub struct IwdgReg {
+    pub kr: u32,
+    pub pr: u32,
+    pub rlr: u32,
+    pub sr: u32,
+}
+
 #[repr(C)]
 pub struct WwdgReg {
     pub cr: u32,
@@ -1675,6 +4544,50 @@ pub struct TimGenReg {
     pub or: u32,
 }
 
+#[repr(C)]
+pub struct Adc1Reg {
+    pub sr: u32,
+    pub cr1: u32,
+    pub cr2: u32,
+    pub smpr1: u32,
+    pub smpr2: u32,
+    pub jofr1: u32,
+    pub jofr2: u32,
+    pub jofr3: u32,
+    pub jofr4: u32,
+    pub htr: u32,
+    pub ltr: u32,
+    pub sqr1: u32,
+    pub sqr2: u32,
+    pub sqr3: u32,
+    pub jsqr: u32,
+    pub jdr1: u32,
+    pub jdr2: u32,
+    pub jdr3: u32,
+    pub jdr4: u32,
+    pub dr: u32,
+}
+
+#[repr(C)]
+pub struct CAdcReg {
+    pub csr: u32,
+    pub ccr: u32,
+    pub cdr: u32,
+}
+
+#[repr(C)]
+pub struct I2cReg {
+    pub cr1: u32,
+    pub cr2: u32,
+    pub oar1: u32,
+    pub oar2: u32,
+    pub dr: u32,
+    pub sr1: u32,
+    pub sr2: u32,
+    pub ccr: u32,
+    pub trise: u32,
+}
+
 #[repr(C)]
 pub struct FlashReg {
     pub acr: u32,
@@ -1684,14 +4597,16 @@ pub struct FlashReg {
     pub cr: u32,
     pub optcr: u32,
 }
-// Skipped peripheral: RNG
+
+pub const RNG: *mut RngReg = 0x50060800 as *mut RngReg;
 // Skipped peripheral: DCMI
 // Skipped peripheral: FSMC
 
 pub const DBG: *mut DbgReg = 0xE0042000 as *mut DbgReg;
 
 pub const DMA2: *mut DmaReg = 0x40026400 as *mut DmaReg;
-// Skipped peripheral: DMA1
+
+pub const DMA1: *mut DmaReg = 0x40026000 as *mut DmaReg;
 
 pub const RCC: *mut RccReg = 0x40023800 as *mut RccReg;
 // Skipped peripheral: GPIOI
@@ -1708,34 +4623,50 @@ pub const GPIOC: *mut GpioReg = 0x40020800 as *mut GpioReg;
 pub const GPIOB: *mut GpioReg = 0x40020400 as *mut GpioReg;
 
 pub const GPIOA: *mut GpioReg = 0x40020000 as *mut GpioReg;
-// Skipped peripheral: SYSCFG
+
+pub const SYSCFG: *mut SyscfgReg = 0x40013800 as *mut SyscfgReg;
 
 pub const SPI1: *mut Spi1Reg = 0x40013000 as *mut Spi1Reg;
-// Skipped peripheral: SPI2
-// Skipped peripheral: SPI3
+
+pub const SPI2: *mut Spi1Reg = 0x40003800 as *mut Spi1Reg;
+
+pub const SPI3: *mut Spi1Reg = 0x40003C00 as *mut Spi1Reg;
 // Skipped peripheral: I2S2EXT
 // Skipped peripheral: I2S3EXT
 // Skipped peripheral: SDIO
-// Skipped peripheral: ADC1
-// Skipped peripheral: ADC2
-// Skipped peripheral: ADC3
-// Skipped peripheral: USART6
+
+pub const ADC1: *mut Adc1Reg = 0x40012000 as *mut Adc1Reg;
+
+pub const ADC2: *mut Adc1Reg = 0x40012100 as *mut Adc1Reg;
+
+pub const ADC3: *mut Adc1Reg = 0x40012200 as *mut Adc1Reg;
+
+pub const USART6: *mut UsartReg = 0x40011400 as *mut UsartReg;
 
 pub const USART1: *mut UsartReg = 0x40011000 as *mut UsartReg;
-// Skipped peripheral: USART2
-// Skipped peripheral: USART3
+
+pub const USART2: *mut UsartReg = 0x40004400 as *mut UsartReg;
+
+pub const USART3: *mut UsartReg = 0x40004800 as *mut UsartReg;
 // Skipped peripheral: DAC
-// Skipped peripheral: PWR
-// Skipped peripheral: I2C3
-// Skipped peripheral: I2C2
-// Skipped peripheral: I2C1
-// Skipped peripheral: IWDG
+
+pub const PWR: *mut PwrReg = 0x40007000 as *mut PwrReg;
+
+pub const I2C3: *mut I2cReg = 0x40005C00 as *mut I2cReg;
+
+pub const I2C2: *mut I2cReg = 0x40005800 as *mut I2cReg;
+
+pub const I2C1: *mut I2cReg = 0x40005400 as *mut I2cReg;
+
+pub const IWDG: *mut IwdgReg = 0x40003000 as *mut IwdgReg;
 
 pub const WWDG: *mut WwdgReg = 0x40002C00 as *mut WwdgReg;
-// Skipped peripheral: RTC
+
+pub const RTC: *mut RtcReg = 0x40002800 as *mut RtcReg;
 // Skipped peripheral: UART4
 // Skipped peripheral: UART5
-// Skipped peripheral: C_ADC
+
+pub const C_ADC: *mut CAdcReg = 0x40012300 as *mut CAdcReg;
 
 pub const TIM1: *mut TimAdvReg = 0x40010000 as *mut TimAdvReg;
 // Skipped peripheral: TIM8
@@ -1756,7 +4687,7 @@ pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
 // Skipped peripheral: ETHERNET_MMC
 // Skipped peripheral: ETHERNET_PTP
 // Skipped peripheral: ETHERNET_DMA
-// Skipped peripheral: CRC
+pub const CRC: *mut CrcReg = 0x40023000 as *mut CrcReg;
 // Skipped peripheral: OTG_FS_GLOBAL
 // Skipped peripheral: OTG_FS_HOST
 // Skipped peripheral: OTG_FS_DEVICE
@@ -1765,7 +4696,8 @@ pub const TIM2: *mut TimGenReg = 0x40000000 as *mut TimGenReg;
 // Skipped peripheral: CAN2
 
 pub const FLASH: *mut FlashReg = 0x40023C00 as *mut FlashReg;
-// Skipped peripheral: EXTI
+
+pub const EXTI: *mut ExtiReg = 0x40013C00 as *mut ExtiReg;
 // Skipped peripheral: OTG_HS_GLOBAL
 // Skipped peripheral: OTG_HS_HOST
 // Skipped peripheral: OTG_HS_DEVICE
@@ -3,6 +3,9 @@
 //! This module provides functionality for initializing and handling timers, specifically TIM2.
 //! It includes methods for setting up the timer, handling timer interrupts, and retrieving the current time.
 //!
+//! TIM2 counts microseconds and wraps every hour. The update interrupt at each wrap is counted so
+//! `now` gives a 64-bit time since `init2` that does not wrap for over 500,000 years.
+//!
 //! ## Constants
 //!
 //! - `TIME_WRAP_AROUND`: The time duration (in microseconds) after which the timer wraps around (1 hour at 1 MHz).
@@ -10,17 +13,21 @@
 //! ## Functions
 //!
 //! - `init2`: Initializes the TIM2 timer with a 1 MHz prescaler and sets it to wrap around every hour.
-//! - `handle_tim2_irq`: Handles the TIM2 interrupt by clearing the update interrupt flag.
-//! - `current_time`: Retrieves the current time in microseconds from the TIM2 timer.
+//! - `handle_tim2_irq`: Handles the TIM2 interrupt by counting wraps and running due alarms.
+//! - `now`: Returns the current time as an `Instant`.
+//! - `current_time`: Retrieves the current time in microseconds, the same time as `now`.
+//! - `delay_us`, `delay_ms`: Busy wait for a time. Use `alarm` to run code later without waiting.
 //!
 //! ## Structures
 //!
+//! - `Instant`: A point in time, in microseconds since `init2`.
+//! - `Duration`: A span of time in microseconds, with checked arithmetic.
 //! - `MicroSeconds`: Represents a time duration in microseconds and provides methods for time manipulation.
 //!
 //! ## Example
 //!
 //! ```rust
-//! use crate::hal::timer::{self, MicroSeconds};
+//! use crate::hal::timer::{self, Duration, Instant};
 //! use crate::hal::cpu;
 //!
 //! fn main() {
@@ -29,21 +36,31 @@
 //!     timer::init2();
 //!
 //!     // Get the current time
-//!     let current_time = timer::current_time();
+//!     let start = Instant::now();
+//!     let deadline = start + Duration::from_millis(100);
+//!     assert!(deadline.duration_since(start) == Duration::from_millis(100));
 //! }
 //! ```
 
 #[cfg(not(feature = "stm32f072"))]
 use core::ptr;
 
-#[cfg(feature = "std")]
-extern crate std;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+#[cfg(feature = "stm32f405")]
+use core::sync::atomic::{AtomicU32, Ordering};
 
 #[cfg(feature = "std")]
-use std::time::{SystemTime, UNIX_EPOCH};
+extern crate std;
 
+#[cfg(feature = "stm32f405")]
+#[cfg(any(target_arch = "arm", feature = "std"))]
+use super::alarm;
+#[cfg(not(feature = "stm32f072"))]
+use super::clock;
 #[cfg(not(feature = "stm32f072"))]
 use super::cpu;
+#[cfg(not(feature = "stm32f072"))]
+use super::interrupt::{self, Irq};
 
 #[cfg(not(feature = "stm32f072"))]
 use super::cpu::*;
@@ -51,7 +68,13 @@ use super::cpu::*;
 #[cfg(feature = "stm32f405")]
 pub use super::cpu::TIM_GEN as TIM2;
 
-const TIME_WRAP_AROUND: u32 = 3600 * 1000_000; // 1 hour@1Mhz
+pub const TIME_WRAP_AROUND: u32 = 3600 * 1000_000; // 1 hour@1Mhz
+
+// number of times TIM2 has wrapped, only changed by handle_tim2_irq
+#[cfg(feature = "stm32f405")]
+cpu::local! {
+    static WRAPS: AtomicU32 = AtomicU32::new(0);
+}
 
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
@@ -60,11 +83,11 @@ pub fn init2() {
     //cpu::write!( RCC.apb2enr[TIM2EN;1], 1);
     cpu::write!( RCC.apb1enr[TIM2EN;1], 1);
 
-    // TIM1 is apb2 timer clock is 168MHz and 16 bits
-    // TIM2 is apb1 timer clock is 84MHz and 32 bits
+    // TIM1 is on the apb2 timer clock and 16 bits
+    // TIM2 is on the apb1 timer clock and 32 bits
 
     // set prescaler for 1MHz
-    cpu::write!(TIM2.psc, 84 - 1);
+    cpu::write!(TIM2.psc, clock::timclk1() / 1_000_000 - 1);
 
     // set auto-reload for 3600 seconds (1 hour)
     cpu::write!(TIM2.arr, TIME_WRAP_AROUND - 1);
@@ -72,6 +95,10 @@ pub fn init2() {
     // force load of prescaler and auto-reload
     cpu::write!( TIM2.egr[UG;1], 1);
 
+    // the forced load sets the update flag, clear it so it is not counted as a wrap
+    cpu::write!( TIM2.sr[UIF;1], 0);
+    cpu::local!(ref WRAPS).store(0, Ordering::Relaxed);
+
     // enable update interrupt
     cpu::write!( TIM2.dier[UIE;1], 1);
 
@@ -79,18 +106,24 @@ pub fn init2() {
     cpu::write!( TIM2.cr1[CEN;1], 1);
 
     // enable interrupt in NVIC
-    const TIM2_IRQ_NUM: usize = 28;
-    const IRQ_INDEX: usize = TIM2_IRQ_NUM / 32;
-    const IRQ_BIT: usize = TIM2_IRQ_NUM % 32;
-
-    cpu::write!(NVIC.iser[IRQ_INDEX], 1 << IRQ_BIT);
+    interrupt::enable(Irq::Tim2);
 }
 
 #[cfg(feature = "stm32f405")]
 #[inline(never)]
 pub fn handle_tim2_irq() {
-    // clear update interrupt flag
-    cpu::write!(TIM2.sr[UIF;1], 0);
+    if cpu::read!(TIM2.sr[UIF;1]) == 1 {
+        // clear update interrupt flag
+        cpu::write!(TIM2.sr[UIF;1], 0);
+
+        // only this handler writes WRAPS so a load and store is enough
+        let wraps = cpu::local!(ref WRAPS);
+        wraps.store(wraps.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+    }
+
+    // compare channels
+    #[cfg(any(target_arch = "arm", feature = "std"))]
+    alarm::handle_irq();
 }
 
 #[cfg(feature = "stm32f072")]
@@ -100,9 +133,225 @@ pub fn handle_tim2_irq() {}
 #[cfg(feature = "stm32f405")]
 #[cfg(target_arch = "arm")]
 #[inline(never)]
+/// Returns the time since `init2`.
+pub fn now() -> Instant {
+    // with interrupts masked the wrap count can not change while the counter is read
+    interrupt::free(|_cs| {
+        let mut wraps = cpu::local!(ref WRAPS).load(Ordering::Relaxed) as u64;
+        let cnt = cpu::read!(TIM2.cnt);
+
+        // the counter has wrapped but the interrupt has not run yet to count it
+        if cpu::read!(TIM2.sr[UIF;1]) == 1 && cnt < TIME_WRAP_AROUND / 2 {
+            wraps += 1;
+        }
+
+        Instant(wraps * TIME_WRAP_AROUND as u64 + cnt as u64)
+    })
+}
+
+#[cfg(feature = "stm32f405")]
+#[cfg(target_arch = "arm")]
+// moves the clock forward by a time TIM2 was not counting, such as in STOP mode
+pub(crate) fn advance(by: Duration) {
+    interrupt::free(|_cs| {
+        let total = cpu::read!(TIM2.cnt) as u64 + by.as_micros();
+        let wraps = (total / TIME_WRAP_AROUND as u64) as u32;
+        let old = cpu::local!(ref WRAPS);
+        old.store(old.load(Ordering::Relaxed) + wraps, Ordering::Relaxed);
+        cpu::write!(TIM2.cnt, (total % TIME_WRAP_AROUND as u64) as u32);
+    });
+
+    alarm::resync();
+}
+
+#[cfg(feature = "std")]
+/// Returns the time since the first call, from the host clock.
+pub fn now() -> Instant {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    let start = START.get_or_init(std::time::Instant::now);
+    Instant(start.elapsed().as_micros() as u64)
+}
+
+#[cfg(any(target_arch = "arm", feature = "std"))]
+#[inline(never)]
+/// Returns the time since `init2` in microseconds. New code should use `now`.
 pub fn current_time() -> MicroSeconds {
-    let time_us = cpu::read!(TIM2.cnt);
-    MicroSeconds(time_us as u64)
+    MicroSeconds(now().as_micros())
+}
+
+#[cfg(any(target_arch = "arm", feature = "std"))]
+/// Waits for at least `us` microseconds.
+pub fn delay_us(us: u64) {
+    let start = now();
+    while now().duration_since(start) < Duration::from_micros(us) {
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(any(target_arch = "arm", feature = "std"))]
+/// Waits for at least `ms` milliseconds.
+pub fn delay_ms(ms: u64) {
+    delay_us(ms.saturating_mul(1_000));
+}
+
+/// A span of time in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Duration(u64);
+
+impl Duration {
+    pub const ZERO: Duration = Duration(0);
+    pub const MAX: Duration = Duration(u64::MAX);
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Duration(micros)
+    }
+
+    /// Saturates at `Duration::MAX`.
+    pub const fn from_millis(millis: u64) -> Self {
+        Duration(millis.saturating_mul(1_000))
+    }
+
+    /// Saturates at `Duration::MAX`.
+    pub const fn from_secs(secs: u64) -> Self {
+        Duration(secs.saturating_mul(1_000_000))
+    }
+
+    pub const fn as_micros(&self) -> u64 {
+        self.0
+    }
+
+    /// Whole milliseconds, rounded down.
+    pub const fn as_millis(&self) -> u64 {
+        self.0 / 1_000
+    }
+
+    /// Whole seconds, rounded down.
+    pub const fn as_secs(&self) -> u64 {
+        self.0 / 1_000_000
+    }
+
+    pub fn checked_add(self, other: Duration) -> Option<Duration> {
+        self.0.checked_add(other.0).map(Duration)
+    }
+
+    /// Returns `None` if `other` is longer than `self`.
+    pub fn checked_sub(self, other: Duration) -> Option<Duration> {
+        self.0.checked_sub(other.0).map(Duration)
+    }
+
+    pub fn saturating_sub(self, other: Duration) -> Duration {
+        Duration(self.0.saturating_sub(other.0))
+    }
+
+    pub fn checked_mul(self, n: u32) -> Option<Duration> {
+        self.0.checked_mul(n as u64).map(Duration)
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        self.checked_add(other)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, other: Duration) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, other: Duration) -> Duration {
+        self.checked_sub(other)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, other: Duration) {
+        *self = *self - other;
+    }
+}
+
+/// A point in time, in microseconds since `init2`. Only ever increases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Instant(u64);
+
+impl Instant {
+    #[cfg(any(target_arch = "arm", feature = "std"))]
+    pub fn now() -> Instant {
+        now()
+    }
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Instant(micros)
+    }
+
+    pub const fn as_micros(&self) -> u64 {
+        self.0
+    }
+
+    /// Time from `earlier` to `self`, or `None` if `earlier` is later.
+    pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration)
+    }
+
+    /// Time from `earlier` to `self`, zero if `earlier` is later.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        self.checked_duration_since(earlier)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    #[cfg(any(target_arch = "arm", feature = "std"))]
+    /// Time since `self`.
+    pub fn elapsed(&self) -> Duration {
+        now().duration_since(*self)
+    }
+
+    pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        self.0.checked_add(duration.0).map(Instant)
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        self.0.checked_sub(duration.0).map(Instant)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, duration: Duration) -> Instant {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, duration: Duration) -> Instant {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -117,21 +366,15 @@ impl MicroSeconds {
         self.0
     }
 
+    /// Time from `other` to `self`. Times from `current_time` do not wrap, the wrap is only
+    /// handled for 32-bit counter values.
     pub fn sub(self, other: Self) -> MicroSeconds {
         if other.0 > self.0 {
-            // wrap-around occurred (every 1 hour)
-            let max: u64 = TIME_WRAP_AROUND as u64;
-            return MicroSeconds(max - other.0 + self.0 + 1);
+            // wrap-around occurred (every 1 hour), the counter goes from
+            // TIME_WRAP_AROUND - 1 back to 0
+            let modulus: u64 = TIME_WRAP_AROUND as u64;
+            return MicroSeconds(modulus - other.0 + self.0);
         }
         MicroSeconds(self.0 - other.0)
     }
 }
-
-#[cfg(feature = "std")]
-pub fn current_time() -> MicroSeconds {
-    let start = SystemTime::now();
-    match start.duration_since(UNIX_EPOCH) {
-        Ok(n) => MicroSeconds((n.as_micros() as u64) % ((u32::MAX as u64) * 10_000)),
-        Err(_) => panic!("bad system time"),
-    }
-}
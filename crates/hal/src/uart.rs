@@ -1,432 +1,848 @@
 //! # UART Module
 //!
-//! This module provides functionality for initializing and using the USART1 peripheral for serial communication.
-//! It includes methods for setting up the USART1 with a specified baud rate and sending data through the USART1 interface.
+//! This module provides functionality for initializing and using the USART peripherals for serial communication.
+//! Each USART is used through a `Uart` handle that is set up with a `Config` giving the baud rate, parity,
+//! stop bits and flow control. The baud rate divider is worked out from the bus clock given by the clock module.
+//!
+//! ## Types
+//!
+//! - `Instance`: The USARTs that can be used, USART1, 2, 3 and 6 on the F405 and USART1 to 4 on the F072.
+//! - `Config`: The serial settings to use, `Config::new` gives 8 data bits, no parity and 1 stop bit.
+//! - `Uart`: Handle for one USART.
 //!
 //! ## Functions
 //!
-//! - `init1`: Initializes the USART1 peripheral with the specified baud rate.
-//! - `write1`: Sends a byte of data through the USART1 interface.
+//! - `Uart::init`: Sets up the USART and its pins with the given `Config`.
+//! - `Uart::write`: Sends a byte of data.
+//! - `Uart::write_dma`: Sends a buffer using DMA.
+//! - `Uart::init_tx`: Gives `write` a buffer so it queues bytes instead of waiting.
+//! - `Uart::tx`: Returns the buffer of bytes waiting to be sent.
+//! - `Uart::flush`: Waits until all queued bytes have been sent.
+//! - `Uart::rx`: Returns the buffer of received bytes.
+//! - `Uart::empty`, `Uart::read`: Checks for and waits for received bytes.
+//! - `handle_irq`: Moves received bytes into the buffer and sends queued bytes, called from the USART interrupt.
 //!
 //! ## Usage
 //!
-//! The `init1` function should be called during system startup to configure the USART1 peripheral. The `write1` function
-//! can be used to send data through the USART1 interface.
+//! `Uart::init` should be called during system startup after `clock::init`. The `write` function
+//! can be used to send data.
+//!
+//! Received bytes are moved into an `RxBuffer` by the receive interrupt so no bytes are
+//! lost while the application is busy. The USART interrupt handler in the vector table
+//! must call `handle_irq` for that USART. Use `rx().read()` to get bytes without blocking.
+//!
+//! After `init_tx`, `write` puts bytes in a `TxBuffer` and returns straight away. The
+//! same interrupt handler sends them. When the buffer is full the `TxPolicy` decides if
+//! `write` waits or throws the byte away. Call `flush` before anything that stops
+//! interrupts from running, such as a panic or reset.
 //!
 //! ## Example
 //!
 //! ```rust
 //! use crate::hal::*;
 //! use crate::hal::clock;
+//! use crate::hal::uart::{Config, Instance, Uart};
 //!
 //! fn main() {
-//!     clock::init( 16_000_000 );
+//!     clock::init(&clock::ClockConfig::new(16_000_000));
 //!
 //!     let tx = gpio::Pin(cpu::GPIOA, 9);
 //!     let rx = gpio::Pin(cpu::GPIOA, 10);
-//!     let baud_rate: u32 = 115200;
-//!     uart::init1(115200,rx,tx);
+//!     let console = Uart::new(Instance::Usart1);
+//!     console.init(&Config::new(115_200), tx, rx);
 //!
 //!     // Send a byte of data
-//!     uart::write1(b'H');
-//!     uart::write1(b'i');
+//!     console.write(b'H');
+//!     console.write(b'i');
+//!
+//!     // Echo any bytes received
+//!     while let Some(c) = console.rx().read() {
+//!         console.write(c);
+//!     }
 //! }
 //! ```
 
+use core::cell::UnsafeCell;
 use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicUsize, Ordering};
 
 #[cfg(feature = "std")]
 extern crate std;
 
-//use super::board;
+use super::clock;
 use super::cpu;
 use super::cpu::*;
+#[cfg(feature = "stm32f405")]
+use super::dma;
 use super::gpio;
+use super::interrupt::{self, Irq};
+
+// lets `usart.reg[FIELD;w]` find the field offsets in the USART register module
+use super::cpu::USART::{self as usart};
+
+/// Size of the receive buffer of each USART. Must be a power of 2.
+pub const RX_BUF_SIZE: usize = 256;
+
+/// Bytes received by the USART interrupt handler waiting to be read.
+///
+/// This is a single producer, single consumer ring buffer. Only the interrupt
+/// handler moves `head` and only the reader moves `tail` so no lock is needed.
+pub struct RxBuffer {
+    buf: UnsafeCell<[u8; RX_BUF_SIZE]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    overruns: AtomicU32,
+    framing_errors: AtomicU32,
+    dropped: AtomicU32,
+}
 
-pub use super::cpu::USART as USART1;
-pub use super::cpu::USART as USART2;
-
-#[cfg(feature = "stm32f405")]
-pub use super::cpu::DMA as DMA1;
+// only the interrupt handler writes to buf, and only to the slots the reader is not using
+unsafe impl Sync for RxBuffer {}
+
+impl RxBuffer {
+    const fn new() -> Self {
+        RxBuffer {
+            buf: UnsafeCell::new([0; RX_BUF_SIZE]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            overruns: AtomicU32::new(0),
+            framing_errors: AtomicU32::new(0),
+            dropped: AtomicU32::new(0),
+        }
+    }
 
-#[cfg(feature = "stm32f405")]
-pub use super::cpu::DMA as DMA2;
+    // empties the buffer and the counters, only call with the interrupt off
+    fn clear(&self) {
+        self.head.store(0, Ordering::Relaxed);
+        self.tail.store(0, Ordering::Relaxed);
+        self.overruns.store(0, Ordering::Relaxed);
+        self.framing_errors.store(0, Ordering::Relaxed);
+        self.dropped.store(0, Ordering::Relaxed);
+    }
 
-#[cfg(feature = "stm32f072")]
-#[inline(never)]
-pub fn init1(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // Enable USART1 & GPIOA clock
-    cpu::write!(RCC.apb2enr[USART1EN;1], 1);
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
+    // adds a byte, only called from the interrupt handler
+    fn push(&self, c: u8) {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head.wrapping_sub(tail) >= RX_BUF_SIZE {
+            increment(&self.dropped);
+            return;
+        }
+        unsafe {
+            (*self.buf.get())[head % RX_BUF_SIZE] = c;
+        }
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+    }
 
-    tx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
-    rx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
+    /// Returns the next received byte, or `None` if nothing has been received.
+    pub fn read(&self) -> Option<u8> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let c = unsafe { (*self.buf.get())[tail % RX_BUF_SIZE] };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(c)
+    }
 
-    // Set baud rate
-    let apb_freq: u32 = 48_000_000; // APB clock frequency
-    let usart_div: u32 = apb_freq / baud_rate as u32;
-    cpu::write!(USART1.brr, usart_div);
+    /// Fills `data` with as many received bytes as are available and returns
+    /// how many were read.
+    pub fn read_slice(&self, data: &mut [u8]) -> usize {
+        let mut count = 0;
+        for d in data.iter_mut() {
+            match self.read() {
+                Some(c) => *d = c,
+                None => break,
+            }
+            count += 1;
+        }
+        count
+    }
 
-    // Enable USART1, transmitter and receiver
-    cpu::write!(USART1.cr1[UE;1], 1); // USART enable
-    cpu::write!(USART1.cr1[TE;1], 1); // Transmitter enable
-    cpu::write!(USART1.cr1[RE;1], 1); // Receiver enable
-}
+    /// Number of bytes waiting to be read.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        head.wrapping_sub(tail)
+    }
 
-#[cfg(feature = "stm32f072")]
-#[inline(never)]
-pub fn init2(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // Enable USART2 & GPIOA clock
-    cpu::write!(RCC.apb1enr[USART2EN;1], 1);
-    cpu::write!(RCC.ahbenr[IOPAEN;1], 1);
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-    tx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
-    rx_pin.alt_fun(1, false); // AF1 work for USART1 to 3
+    /// Number of times the USART received a byte before the last one was taken
+    /// by the interrupt handler.
+    pub fn overruns(&self) -> u32 {
+        self.overruns.load(Ordering::Relaxed)
+    }
 
-    // Set baud rate
-    let apb_freq: u32 = 48_000_000; // APB clock frequency
-    let usart_div: u32 = apb_freq / baud_rate as u32;
-    cpu::write!(USART2.brr, usart_div);
+    /// Number of bytes thrown away because the stop bit was wrong, usually
+    /// from the wrong baud rate or noise on the line.
+    pub fn framing_errors(&self) -> u32 {
+        self.framing_errors.load(Ordering::Relaxed)
+    }
 
-    cpu::write!( USART2.cr1[M;1], 0); // 8 data bits
-    let even_parity = false;
-    if even_parity {
-        cpu::write!( USART2.cr1[PCE;1], 1); // parity control enable
-        cpu::write!( USART2.cr1[PS;1], 0); // even parity
-    } else {
-        cpu::write!( USART2.cr1[PCE;1], 0); // no parity
+    /// Number of bytes thrown away because the buffer was full.
+    pub fn dropped(&self) -> u32 {
+        self.dropped.load(Ordering::Relaxed)
     }
-    cpu::write!( USART2.cr2[STOP;2], 0b00); // 1 stop bit
+}
 
-    // Enable USART2, transmitter and receiver
-    cpu::write!(USART2.cr1[UE;1], 1); // USART enable
-    cpu::write!(USART2.cr1[TE;1], 1); // Transmitter enable
-    cpu::write!(USART2.cr1[RE;1], 1); // Receiver enable
+// the Cortex-M0 has no atomic add but only the interrupt handler changes the counters
+fn increment(counter: &AtomicU32) {
+    counter.store(
+        counter.load(Ordering::Relaxed).wrapping_add(1),
+        Ordering::Relaxed,
+    );
 }
 
-#[cfg(feature = "stm32f405")]
-#[inline(never)]
-pub fn init1(baud_rate: u64, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
-    // enable USART1 & GPIO clock
-    cpu::write!( RCC.apb2enr[USART1EN;1], 1);
-    cpu::write!( RCC.ahb1enr[GPIOAEN;1], 1);
+/// What `Uart::write` does when the transmit buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxPolicy {
+    /// Throw the byte away and count it in `dropped`.
+    Drop,
+    /// Wait until there is room.
+    Block,
+}
+
+/// Bytes waiting to be sent by the USART interrupt handler.
+///
+/// This is a single producer, single consumer ring buffer like `RxBuffer`, but
+/// the application moves `head` and the interrupt handler moves `tail`. The
+/// memory is given to `Uart::init_tx` so the application picks the size.
+pub struct TxBuffer {
+    buf: AtomicPtr<u8>,
+    size: AtomicUsize,
+    block: AtomicBool,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    dropped: AtomicU32,
+}
+
+impl TxBuffer {
+    const fn new() -> Self {
+        TxBuffer {
+            buf: AtomicPtr::new(ptr::null_mut()),
+            size: AtomicUsize::new(0),
+            block: AtomicBool::new(true),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicU32::new(0),
+        }
+    }
 
-    tx_pin.alt_fun(7, false); // AF7 work for USART1 to 3
-    rx_pin.alt_fun(7, false); // AF7 work for USART1 to 3
+    // true once init_tx has given this a buffer
+    fn is_set_up(&self) -> bool {
+        self.size.load(Ordering::Relaxed) != 0
+    }
+
+    // adds a byte, returns false if there is no room
+    fn push(&self, c: u8) -> bool {
+        let size = self.size.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head.wrapping_sub(tail) >= size {
+            return false;
+        }
+        unsafe {
+            *self.buf.load(Ordering::Relaxed).add(head & (size - 1)) = c;
+        }
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
 
-    // set baud rate
-    // UART 1 is on APB2 bus, which is 84MHz
-    let apb_freq: u64 = 84_000_000;
-    let div_fixed3: u64 = 1000 * apb_freq / (16 * baud_rate);
+    // takes the oldest byte
+    fn pop(&self) -> Option<u8> {
+        let size = self.size.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let c = unsafe { *self.buf.load(Ordering::Relaxed).add(tail & (size - 1)) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(c)
+    }
 
-    let mantissa: u64 = div_fixed3 / 1000;
-    let frac: u64 = (div_fixed3 % 1000) * 16 / 1000;
+    /// Number of bytes waiting to be sent.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Relaxed);
+        head.wrapping_sub(tail)
+    }
 
-    cpu::write!( USART1.brr[DIV_Mantissa;11], mantissa as u32);
-    cpu::write!( USART1.brr[DIV_Fraction;4], frac as u32);
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-    cpu::write!( USART1.cr1[M;1], 0); // 8 data bits
-    let even_parity = false;
-    if even_parity {
-        cpu::write!( USART1.cr1[PCE;1], 1); // parity control enable
-        cpu::write!( USART1.cr1[PS;1], 0); // even parity
-    } else {
-        cpu::write!( USART1.cr1[PCE;1], 0); // no parity
+    /// Most bytes that can wait to be sent.
+    pub fn capacity(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
     }
-    cpu::write!( USART1.cr2[STOP;2], 0b00); // 1 stop bit
 
-    cpu::write!( USART1.cr1[TE;1], 1); // transmit enable
-    cpu::write!( USART1.cr1[RE;1], 1); // receive enable
-    cpu::write!( USART1.cr1[UE;1], 1); // uart enable
+    /// Number of bytes thrown away because the buffer was full with `TxPolicy::Drop`.
+    pub fn dropped(&self) -> u32 {
+        self.dropped.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(feature = "stm32f405")]
-pub fn write1(c: u8) {
-    #[cfg(not(feature = "std"))]
-    while (cpu::read!(USART1.sr[TXE;1]) == 0) {}
-    cpu::write!(USART1.dr[DR;8], c as u32);
+/// The USARTs on the STM32F405. UART4 and UART5 are not supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instance {
+    Usart1,
+    Usart2,
+    Usart3,
+    Usart6,
+}
+
+#[cfg(feature = "stm32f072")]
+/// The USARTs on the STM32F072.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instance {
+    Usart1,
+    Usart2,
+    Usart3,
+    Usart4,
 }
 
-#[cfg(any(feature = "std", not(feature = "stm32f405")))]
-pub fn write1_dma(_data: &[u8]) {}
+const NUM_INSTANCES: usize = 4;
 
-//use core::ptr;
+cpu::local! {
+    static RX: [RxBuffer; NUM_INSTANCES] = [const { RxBuffer::new() }; NUM_INSTANCES];
+}
+cpu::local! {
+    static TX: [TxBuffer; NUM_INSTANCES] = [const { TxBuffer::new() }; NUM_INSTANCES];
+}
 
-#[cfg(not(feature = "std"))]
-#[cfg(feature = "stm32f405")]
-pub unsafe fn write1_dma(data: &[u8]) {
-    let baud_rate: u32 = 115200;
-
-    // checked
-    const USART1_BASE: u32 = 0x40011000;
-    const DMA2_BASE: u32 = 0x40026400;
-    const RCC_BASE: u32 = 0x40023800;
-
-    // checked
-    const USART1_DR: u32 = USART1_BASE + 0x04;
-    const USART1_CR1: u32 = USART1_BASE + 0x0C;
-    const USART1_CR3: u32 = USART1_BASE + 0x14;
-    const USART1_BRR: u32 = USART1_BASE + 0x08;
-    //const USART1_SR: u32 = USART1_BASE + 0x00;
-
-    // checked wrong
-    const DMA2_S7CR: u32 = DMA2_BASE + 0xb8; //0xA0; // b8
-    const DMA2_S7NDTR: u32 = DMA2_BASE + 0xbc; //0xA4; //bc
-    const DMA2_S7PAR: u32 = DMA2_BASE + 0xc0; //0xA8; //C0
-    const DMA2_S7M0AR: u32 = DMA2_BASE + 0xc4; // 0xAC; //C4
-    const DMA2_HISR: u32 = DMA2_BASE + 0x04; // OK
-    const DMA2_HIFCR: u32 = DMA2_BASE + 0x0C; // OK
-
-    // checked
-    const RCC_AHB1ENR: u32 = RCC_BASE + 0x30;
-    const RCC_APB2ENR: u32 = RCC_BASE + 0x44;
-
-    // checked
-    const DMA2EN: u32 = 1 << 22;
-    const USART1EN: u32 = 1 << 4;
-
-    // checked
-    const NVIC_ISER2: u32 = 0xE000E100 + 0x8;
-    const DMA2_STREAM7_IRQ: u32 = 70;
-
-    // Enable DMA2 and USART1 clocks
-    ptr::write_volatile(RCC_AHB1ENR as *mut u32, DMA2EN);
-    ptr::write_volatile(RCC_APB2ENR as *mut u32, USART1EN);
-
-    // Configure USART1
-    let apb_freq: u32 = 84_000_000;
-    let usart_div: u32 = apb_freq / baud_rate;
-    ptr::write_volatile(USART1_BRR as *mut u32, usart_div);
-    // odd parity | transmit enable | transmit enable
-    ptr::write_volatile(USART1_CR1 as *mut u32, 0x2000 | 0x8 | 0x4); // Enable USART, TX, RX
-    ptr::write_volatile(
-        USART1_CR3 as *mut u32,
-        0x40, // DMAT dma transmition mode
-    ); // Enable DMA transmission
-
-    // Configure DMA2 Stream 7
-    ptr::write_volatile(DMA2_S7CR as *mut u32, 0); // Disable stream
-    while ptr::read_volatile(DMA2_S7CR as *mut u32) & 1 != 0 {} // Wait until disabled
-
-    ptr::write_volatile(DMA2_S7PAR as *mut u32, USART1_DR);
-    ptr::write_volatile(DMA2_S7M0AR as *mut u32, data.as_ptr() as u32);
-    ptr::write_volatile(DMA2_S7NDTR as *mut u32, data.len() as u32);
-
-    // Configure DMA stream settings
-    let dma_cr = (4 << 25) // channel 4
-        | (1 << 10) // MINC Memory increment
-        | (1 << 8) //  Circular mode enabled
-        | (1 << 6) // 0x01 this is memory to pher[herial
-        | (1 << 4) // TCIE - transfer complete interupt
-        ;
-    // Channel 4, memory increment, memory-to-peripheral
-    ptr::write_volatile(DMA2_S7CR as *mut u32, dma_cr);
-
-    // Enable DMA2 Stream 7
-    ptr::write_volatile(DMA2_S7CR as *mut u32, dma_cr | 1);
-
-    // Enable DMA2 Stream 7 interrupt in NVIC
-    ptr::write_volatile(NVIC_ISER2 as *mut u32, 1 << (DMA2_STREAM7_IRQ - 64));
-
-    // Wait for transfer to complete
-    while ptr::read_volatile(DMA2_HISR as *mut u32) & (1 << 27) == 0
-    // FIEF5 0x40 seems wrong
-    {
-        if ptr::read_volatile(DMA2_HISR as *mut u32) & (0b1101 << 22) != 0
-        // x0B == 11 = 8+2+1 = TEIF4 | DMEIF4 | FEIF4 - seems wrong
-        {
-            panic!("DMA transfer error");
-        }
-    }
-    // clear transfer complete flag
-    ptr::write_volatile(
-        DMA2_HIFCR as *mut u32,
-        1 << 27, // 0x40 seems wrong
-    );
+/// Parity bit added after the 8 data bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+/// Number of stop bits, the values are the STOP field of CR2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One = 0b00,
+    Half = 0b01,
+    Two = 0b10,
+    OneAndHalf = 0b11,
+}
+
+/// Hardware flow control.
+#[derive(Clone, Copy)]
+pub enum FlowControl {
+    None,
+    /// RTS goes high when the receive data register is full and bytes are only
+    /// sent while CTS is low.
+    RtsCts {
+        rts_pin: gpio::Pin,
+        cts_pin: gpio::Pin,
+    },
+}
+
+/// Settings for `Uart::init`.
+#[derive(Clone, Copy)]
+pub struct Config {
+    pub baud_rate: u32,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub flow_control: FlowControl,
+    /// Alternate function for the pins. `None` uses the usual one for the
+    /// USART, set this for pins that use a different one.
+    pub alt_fun: Option<u8>,
+}
+
+impl Config {
+    /// 8 data bits, no parity, 1 stop bit and no flow control.
+    pub fn new(baud_rate: u32) -> Self {
+        Config {
+            baud_rate,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            flow_control: FlowControl::None,
+            alt_fun: None,
+        }
+    }
+}
+
+/// Handle for one USART. It holds no state so it can be copied and made
+/// wherever it is needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uart {
+    pub instance: Instance,
 }
 
-#[cfg(not(feature = "std"))]
 #[cfg(feature = "stm32f405")]
-pub fn write1_dma_save(data: &[u8]) {
-    // TODO
-    // DMA for USART1_TX
-    // Uses DMA 2, Channel 4, Stream 7
-    // See page 311 of RM0090
+impl Uart {
+    /// USART registers for this instance.
+    pub fn reg(&self) -> *mut cpu::UsartReg {
+        match self.instance {
+            Instance::Usart1 => USART1,
+            Instance::Usart2 => USART2,
+            Instance::Usart3 => USART3,
+            Instance::Usart6 => USART6,
+        }
+    }
+
+    /// NVIC interrupt number for this USART.
+    pub fn irq_num(&self) -> Irq {
+        match self.instance {
+            Instance::Usart1 => Irq::Usart1,
+            Instance::Usart2 => Irq::Usart2,
+            Instance::Usart3 => Irq::Usart3,
+            Instance::Usart6 => Irq::Usart6,
+        }
+    }
+
+    // USART1 and USART6 are on APB2, the others are on APB1
+    fn bus_freq(&self) -> u32 {
+        match self.instance {
+            Instance::Usart1 | Instance::Usart6 => clock::pclk2(),
+            Instance::Usart2 | Instance::Usart3 => clock::pclk1(),
+        }
+    }
+
+    fn enable_clock(&self) {
+        match self.instance {
+            Instance::Usart1 => {
+                cpu::write!(RCC.apb2enr[USART1EN;1], 1);
+            }
+            Instance::Usart2 => {
+                cpu::write!(RCC.apb1enr[USART2EN;1], 1);
+            }
+            Instance::Usart3 => {
+                cpu::write!(RCC.apb1enr[USART3EN;1], 1);
+            }
+            Instance::Usart6 => {
+                cpu::write!(RCC.apb2enr[USART6EN;1], 1);
+            }
+        }
+    }
+
+    // AF7 works for USART1 to 3 and AF8 for USART6 on every pin
+    fn default_alt_fun(&self) -> u8 {
+        match self.instance {
+            Instance::Usart6 => 8,
+            _ => 7,
+        }
+    }
+
+    // DMA controller, stream and channel for transmit, see table 42 and 43 of RM0090
+    fn tx_dma(&self) -> (*mut cpu::DmaReg, u8, u8) {
+        match self.instance {
+            Instance::Usart1 => (DMA2, 7, 4),
+            Instance::Usart2 => (DMA1, 6, 4),
+            Instance::Usart3 => (DMA1, 3, 4),
+            Instance::Usart6 => (DMA2, 6, 5),
+        }
+    }
 
-    // Enable DMA2 clocks
-    cpu::write!( RCC.ahb1enr[DMA2EN;1], 1);
+    /// Sends `data` using DMA and waits for it to finish.
+    #[inline(never)]
+    pub fn write_dma(&self, data: &'static [u8]) {
+        if data.is_empty() {
+            return;
+        }
+        assert!(data.len() <= 0xFFFF);
 
-    cpu::write!(DMA2.s7cr, 0); // Disable DMA2 Stream 7 while configuring
-    while cpu::read!(DMA2.s7cr[EN;1]) != 0 {} // Wait until the stream is disabled
+        // bytes already queued go first
+        self.flush();
 
-    cpu::write!( USART1.cr1[UE;1], 0b0 ); // Disable USART1
+        let usart = self.reg();
+        let (dma_reg, num, channel) = self.tx_dma();
+        let stream = dma::Stream::claim(dma_reg, num);
 
-    // Enable USART1 DMA transmission
-    cpu::write!(USART1.cr3[DMAT;1], 1); // Enable DMA transmission
-    cpu::write!(USART1.sr[TC;1], 1); // Clear the transfer complete flag
+        let mut config = dma::Config::new(channel, dma::Direction::MemoryToPeripheral);
+        config.priority = dma::Priority::High;
+        stream.configure(&config);
 
-    cpu::write!( USART1.cr1[UE;1], 0b1 ); // enable USART1
+        let dr_addr = unsafe { ptr::addr_of_mut!((*usart).dr) } as u32;
+        stream.set_peripheral(dr_addr);
+        stream.set_memory(data.as_ptr() as u32);
+        stream.set_count(data.len() as u16);
 
-    // Setup DMA transfer from memory to USART1
-    let dest: u32 = unsafe { core::ptr::addr_of!((*USART1).dr) as u32 };
-    let len: u32 = data.len() as u32;
-    let src: u32 = data.as_ptr() as u32;
+        cpu::write!(usart.sr[TC;1], 0); // clear the transfer complete flag
+        cpu::write!(usart.cr3[DMAT;1], 1); // enable DMA transmission
 
-    assert!(dest == 0x4001_1000 + 0x04); // USART1->DR
-    assert!(len == 4);
+        stream.start();
+        let result = stream.wait();
 
-    cpu::write!(DMA2.s7ndtr, len);
-    cpu::write!(DMA2.s7par, dest);
-    cpu::write!(DMA2.s7m0ar, src);
+        cpu::write!(usart.cr3[DMAT;1], 0);
+        stream.release();
 
-    //  Configure DMA channel: memory-to-peripheral, increment memory, enable transfer complete interrupt
-    cpu::write!( DMA2.s7cr[CHSEL;2], 0b100); // Using Channel #4
+        if result.is_err() {
+            panic!("UART DMA transfer error");
+        }
+    }
+}
 
-    cpu::write!( DMA2.s7cr[DBM;1], 0b0 ); // Disable double buffer mode
+#[cfg(feature = "stm32f072")]
+impl Uart {
+    /// USART registers for this instance.
+    pub fn reg(&self) -> *mut cpu::UsartReg {
+        match self.instance {
+            Instance::Usart1 => USART1,
+            Instance::Usart2 => USART2,
+            Instance::Usart3 => USART3,
+            Instance::Usart4 => USART4,
+        }
+    }
 
-    cpu::write!( DMA2.s7cr[PL;2], 0b10 ); // Set priority level to low
+    /// NVIC interrupt number for this USART. USART3 and USART4 share one.
+    pub fn irq_num(&self) -> Irq {
+        match self.instance {
+            Instance::Usart1 => Irq::Usart1,
+            Instance::Usart2 => Irq::Usart2,
+            Instance::Usart3 | Instance::Usart4 => Irq::Usart3_4,
+        }
+    }
 
-    cpu::write!( DMA2.s7cr[MSIZE;2], 0b00); // Set peripheral size to 1 byte
-    cpu::write!( DMA2.s7cr[PSIZE;2], 0b00); // Set peripheral size to 1 byte
-    cpu::write!( DMA2.s7cr[PINC;1], 0b0); // Disable peripheral increment mode
-    cpu::write!( DMA2.s7cr[MINC;1], 0b1); // Enable memory increment mode
+    // all the USARTs are on the one APB bus
+    fn bus_freq(&self) -> u32 {
+        clock::pclk1()
+    }
 
-    cpu::write!( DMA2.s7cr[CIRC;1], 0b0); // Disable circular mode
-    cpu::write!( DMA2.s7cr[DIR;2], 0b01); // Set direction as memory to peripheral
+    fn enable_clock(&self) {
+        match self.instance {
+            Instance::Usart1 => {
+                cpu::write!(RCC.apb2enr[USART1EN;1], 1);
+            }
+            Instance::Usart2 => {
+                cpu::write!(RCC.apb1enr[USART2EN;1], 1);
+            }
+            Instance::Usart3 => {
+                cpu::write!(RCC.apb1enr[USART3EN;1], 1);
+            }
+            Instance::Usart4 => {
+                cpu::write!(RCC.apb1enr[USART4EN;1], 1);
+            }
+        }
+    }
 
-    cpu::write!( DMA2.s7cr[PFCTRL;1], 0b0); // disable peripheral flow controller
+    // the alternate function depends on the pin, these are for PA9/PA10,
+    // PA2/PA3, PB10/PB11 and PA0/PA1
+    fn default_alt_fun(&self) -> u8 {
+        match self.instance {
+            Instance::Usart1 | Instance::Usart2 => 1,
+            Instance::Usart3 | Instance::Usart4 => 4,
+        }
+    }
 
-    cpu::write!( DMA2.hifcr[CDMEIF7;1], 0b1); // clear the old direct transfer error flag
-    cpu::write!( DMA2.hifcr[CTEIF7;1], 0b1); // clear the old transfer error flag
-    cpu::write!( DMA2.hifcr[CFEIF7;1], 0b1); // clear the old FIFO error flag
-    cpu::write!( DMA2.hifcr[CTCIF7;1], 0b1); // clear the old transfer complete flag
+    /// There is no DMA driver for this CPU so the data is sent a byte at a time.
+    pub fn write_dma(&self, data: &'static [u8]) {
+        for c in data {
+            self.write(*c);
+        }
+    }
+}
+
+impl Uart {
+    pub const fn new(instance: Instance) -> Self {
+        Uart { instance }
+    }
 
-    cpu::write!( DMA2.s7cr[TCIE;1], 0b1); // Enable transfer complete interrupt
-    cpu::write!( DMA2.s7cr[TEIE;1], 0b1); // Enable transfer error interrupt
-    cpu::write!( DMA2.s7cr[DMEIE;1], 0b1); // Enable transfer error interrupt
+    /// Sets up the USART and its pins and starts receiving into `rx()`.
+    #[inline(never)]
+    pub fn init(&self, config: &Config, tx_pin: gpio::Pin, rx_pin: gpio::Pin) {
+        let usart = self.reg();
 
-    // DMA2 Stream7 global interrupt = 70 // from SVD file
-    // NVIC interrupt enable
-    let itr = 70;
-    cpu::write!(NVIC.iser[itr / 32], 1 << (itr % 32)); // Enable DMA2 Stream 7 interrupt
-    let pri_loc = itr * 4;
-    let pri = 0b0011;
-    cpu::write!(NVIC.ipr[pri_loc / 32], pri << (pri_loc % 32)); // Enable DMA2 Stream 7 interrupt
+        self.enable_clock();
 
-    // Clear prior events flags
+        // most settings can only be changed while the USART is off
+        cpu::write!(usart.cr1, 0);
 
-    // Enable DMA2 Stream 7
-    cpu::write!(DMA2.s7cr[EN;1], 1);
+        let af = config.alt_fun.unwrap_or(self.default_alt_fun());
+        tx_pin.alt_fun(af, false);
+        rx_pin.alt_fun(af, false);
 
-    if true {
-        // Check if the transfer complete flag is set
-        while cpu::read!(DMA2.hisr[TCIF7;1]) == 0 {
-            if cpu::read!( DMA2.hisr[TEIF7; 1]) == 0b1 {
-                panic!("DMA transfer error interrupt");
+        // with 16 times oversampling BRR is the bus clock divided by the baud
+        // rate, on the F405 the low 4 bits are the fraction in 16ths which comes
+        // out the same
+        assert!(config.baud_rate > 0);
+        let div = (self.bus_freq() + config.baud_rate / 2) / config.baud_rate;
+        if !(16..=0xFFFF).contains(&div) {
+            panic!("UART baud rate not supported");
+        }
+        cpu::write!(usart.brr, div);
+
+        // the parity bit takes a 9th bit so the data stays 8 bits
+        match config.parity {
+            Parity::None => {
+                cpu::write!(usart.cr1[M;1], 0); // 8 bit word
+                cpu::write!(usart.cr1[PCE;1], 0); // no parity
+            }
+            Parity::Even => {
+                cpu::write!(usart.cr1[M;1], 1); // 9 bit word
+                cpu::write!(usart.cr1[PCE;1], 1); // parity control enable
+                cpu::write!(usart.cr1[PS;1], 0); // even parity
             }
-            if cpu::read!( DMA2.hisr[FEIF7; 1]) == 0b1 {
-                panic!("DMA transfer FIFO error interrupt");
+            Parity::Odd => {
+                cpu::write!(usart.cr1[M;1], 1); // 9 bit word
+                cpu::write!(usart.cr1[PCE;1], 1); // parity control enable
+                cpu::write!(usart.cr1[PS;1], 1); // odd parity
+            }
+        }
+        cpu::write!(usart.cr2[STOP;2], config.stop_bits as u32);
+
+        match config.flow_control {
+            FlowControl::None => {
+                cpu::write!(usart.cr3[RTSE;1], 0);
+                cpu::write!(usart.cr3[CTSE;1], 0);
             }
-            if cpu::read!( DMA2.hisr[DMEIF7; 1]) == 0b1 {
-                panic!("DMA transfer direct mode error interrupt");
+            FlowControl::RtsCts { rts_pin, cts_pin } => {
+                rts_pin.alt_fun(af, false);
+                cts_pin.alt_fun(af, false);
+                cpu::write!(usart.cr3[RTSE;1], 1);
+                cpu::write!(usart.cr3[CTSE;1], 1);
             }
         }
 
-        // Clear the transfer complete flag
-        cpu::write!(DMA2.hifcr[CTCIF7;1], 1);
+        // received bytes go into the buffer from the interrupt
+        self.rx().clear();
+        cpu::write!(usart.cr1[RXNEIE;1], 1);
+
+        cpu::write!(usart.cr1[TE;1], 1); // transmit enable
+        cpu::write!(usart.cr1[RE;1], 1); // receive enable
+        cpu::write!(usart.cr1[UE;1], 1); // uart enable
+
+        interrupt::enable(self.irq_num());
+    }
+
+    /// Bytes received on this USART.
+    pub fn rx(&self) -> &'static RxBuffer {
+        &cpu::local!(ref RX)[self.instance as usize]
+    }
+
+    /// Bytes waiting to be sent on this USART.
+    pub fn tx(&self) -> &'static TxBuffer {
+        &cpu::local!(ref TX)[self.instance as usize]
+    }
+
+    /// Makes `write` queue bytes in `buf` to be sent by the USART interrupt
+    /// instead of waiting for each byte to be sent. The length of `buf` must be
+    /// a power of 2. Call after `init`.
+    #[inline(never)]
+    pub fn init_tx(&self, buf: &'static mut [u8], policy: TxPolicy) {
+        assert!(buf.len().is_power_of_two());
+
+        // send anything left in the old buffer first
+        self.flush();
+
+        let tx = self.tx();
+        tx.buf.store(buf.as_mut_ptr(), Ordering::Relaxed);
+        tx.block.store(policy == TxPolicy::Block, Ordering::Relaxed);
+        tx.head.store(0, Ordering::Relaxed);
+        tx.tail.store(0, Ordering::Relaxed);
+        tx.dropped.store(0, Ordering::Relaxed);
+        tx.size.store(buf.len(), Ordering::Release);
+    }
+
+    /// Sends a byte. Once `init_tx` has been called this queues the byte,
+    /// otherwise it waits for the byte before to be sent.
+    pub fn write(&self, c: u8) {
+        let usart = self.reg();
+        let tx = self.tx();
+        if tx.is_set_up() {
+            queue(usart, tx, c);
+            return;
+        }
+
+        while !tx_ready(usart) {}
+        tx_put(usart, c);
+    }
+
+    /// Waits until everything written has been sent. This does not need
+    /// interrupts so it can be used in the panic handler.
+    #[inline(never)]
+    pub fn flush(&self) {
+        flush(self.reg(), self.tx());
+    }
+
+    /// Returns true if no bytes have been received.
+    pub fn empty(&self) -> bool {
+        self.rx().is_empty()
+    }
+
+    /// Waits for and returns the next byte received.
+    pub fn read(&self) -> u8 {
+        loop {
+            if let Some(c) = self.rx().read() {
+                return c;
+            }
+        }
     }
 }
 
-#[cfg(not(feature = "std"))]
-#[cfg(feature = "stm32f405")]
+/// Handles the receive and transmit interrupts of a USART. Call this from the
+/// USART interrupt handler.
 #[inline(never)]
-pub fn dma_uart1_irq() {
-    if cpu::read!( DMA2.hisr[TEIF7; 1]) == 0b1 {
-        panic!("DMA transfer error interrupt");
-    }
-    if cpu::read!( DMA2.hisr[TCIF7; 1]) == 0b1 {
-        // Clear the transfer complete flag
-        cpu::write!( DMA2.hifcr[CTCIF7; 1], 0b1);
+pub fn handle_irq(instance: Instance) {
+    let uart = Uart::new(instance);
+    receive(uart.reg(), uart.rx());
+    transmit(uart.reg(), uart.tx());
+}
+
+// adds a byte to the transmit buffer and makes sure the interrupt will send it
+fn queue(usart: *mut cpu::UsartReg, tx: &TxBuffer, c: u8) {
+    if !tx.push(c) {
+        if !tx.block.load(Ordering::Relaxed) {
+            increment(&tx.dropped);
+            return;
+        }
 
-        // TODO
-    } else {
-        panic!("DMA transfer complete flag not set");
+        // take over from the interrupt and send bytes from here until there is
+        // room, this also works when interrupts are off
+        set_tx_irq(usart, false);
+        while !tx.push(c) {
+            if tx_ready(usart) {
+                if let Some(b) = tx.pop() {
+                    tx_put(usart, b);
+                }
+            }
+        }
     }
+    set_tx_irq(usart, true);
 }
 
-#[cfg(feature = "stm32f072")]
-pub fn write1(c: u8) {
-    // Wait until transmit data register is empty
-    while cpu::read!(USART1.isr[TXE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::write!(USART1.tdr, c as u32);
+// sends the next byte from the transmit buffer, called from the interrupt handler
+fn transmit(usart: *mut cpu::UsartReg, tx: &TxBuffer) {
+    if !tx_irq_on(usart) || !tx_ready(usart) {
+        return;
+    }
+    match tx.pop() {
+        Some(c) => tx_put(usart, c),
+        None => {
+            set_tx_irq(usart, false);
+            // a byte may have been queued after the pop
+            if !tx.is_empty() {
+                set_tx_irq(usart, true);
+            }
+        }
+    }
 }
 
-#[cfg(feature = "stm32f072")]
-pub fn write2(c: u8) {
-    // Wait until transmit data register is empty
-    while cpu::read!(USART2.isr[TXE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::write!(USART2.tdr, c as u32);
+// sends everything in the transmit buffer without using the interrupt
+fn flush(usart: *mut cpu::UsartReg, tx: &TxBuffer) {
+    set_tx_irq(usart, false);
+    while let Some(c) = tx.pop() {
+        while !tx_ready(usart) {}
+        tx_put(usart, c);
+    }
+    while !tx_idle(usart) {}
 }
 
-#[cfg(feature = "stm32f072")]
-pub fn empty2() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART2.isr[RXNE;1]) == 0
+#[cfg(feature = "stm32f405")]
+// transmit data register is empty
+fn tx_ready(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.sr[TXE;1]) == 1
 }
 
-#[cfg(feature = "stm32f072")]
-pub fn empty1() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART1.isr[RXNE;1]) == 0
+#[cfg(feature = "stm32f405")]
+// last byte has been sent
+fn tx_idle(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.sr[TC;1]) == 1
 }
 
 #[cfg(feature = "stm32f405")]
-pub fn empty1() -> bool {
-    // Wait until transmit data register is empty
-    cpu::read!(USART1.sr[RXNE;1]) == 0
+fn tx_put(usart: *mut cpu::UsartReg, c: u8) {
+    cpu::write!(usart.dr, c as u32);
 }
 
 #[cfg(feature = "stm32f072")]
-pub fn read2() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART2.isr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART2.rdr) as u8
+// transmit data register is empty
+fn tx_ready(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.isr[TXE;1]) == 1
 }
 
 #[cfg(feature = "stm32f072")]
-pub fn read1() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART1.isr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART1.rdr) as u8
+// last byte has been sent
+fn tx_idle(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.isr[TC;1]) == 1
+}
+
+#[cfg(feature = "stm32f072")]
+fn tx_put(usart: *mut cpu::UsartReg, c: u8) {
+    cpu::write!(usart.tdr, c as u32);
+}
+
+// turns the transmit data register empty interrupt on or off
+fn set_tx_irq(usart: *mut cpu::UsartReg, on: bool) {
+    cpu::write!(usart.cr1[TXEIE;1], on as u32);
+}
+
+fn tx_irq_on(usart: *mut cpu::UsartReg) -> bool {
+    cpu::read!(usart.cr1[TXEIE;1]) == 1
 }
 
 #[cfg(feature = "stm32f405")]
-pub fn read1() -> u8 {
-    // Wait until receive data register is empty
-    while cpu::read!(USART1.sr[RXNE;1]) == 0 {}
-    // Write the byte to the data register
-    cpu::read!(USART1.dr) as u8
+// moves a received byte into the buffer and counts errors
+fn receive(usart: *mut cpu::UsartReg, rx: &RxBuffer) {
+    let sr = cpu::read!(usart.sr);
+    if sr & ((1 << USART::sr::RXNE) | (1 << USART::sr::ORE)) == 0 {
+        return;
+    }
+
+    // reading DR after SR clears RXNE and the error flags
+    let c = cpu::read!(usart.dr) as u8;
+
+    if sr & (1 << USART::sr::ORE) != 0 {
+        increment(&rx.overruns);
+    }
+    if sr & (1 << USART::sr::FE) != 0 {
+        increment(&rx.framing_errors);
+        return;
+    }
+    if sr & (1 << USART::sr::RXNE) != 0 {
+        rx.push(c);
+    }
+}
+
+#[cfg(feature = "stm32f072")]
+// moves a received byte into the buffer and counts errors
+fn receive(usart: *mut cpu::UsartReg, rx: &RxBuffer) {
+    let isr = cpu::read!(usart.isr);
+
+    if isr & (1 << USART::isr::ORE) != 0 {
+        increment(&rx.overruns);
+    }
+    if isr & (1 << USART::isr::FE) != 0 {
+        increment(&rx.framing_errors);
+    }
+    // clear the error flags, ORE would keep the interrupt on
+    cpu::write!(
+        usart.icr,
+        (1 << USART::icr::ORECF) | (1 << USART::icr::FECF) | (1 << USART::icr::NCF)
+    );
+
+    if isr & (1 << USART::isr::RXNE) != 0 {
+        // reading RDR clears RXNE
+        let c = cpu::read!(usart.rdr) as u8;
+        if isr & (1 << USART::isr::FE) == 0 {
+            rx.push(c);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::clock;
 
     #[test]
     fn test_uart() {
-        clock::init(16_000_000);
+        cpu::init();
+        clock::init(&clock::ClockConfig::new(16_000_000));
 
         let tx = gpio::Pin(cpu::GPIOA, 9);
         let rx = gpio::Pin(cpu::GPIOA, 10);
-        let baud_rate: u64 = 115200;
+        let uart = Uart::new(Instance::Usart1);
 
-        init1(baud_rate, tx, rx);
-        write1(b'O');
-        write1(b'K');
+        uart.init(&Config::new(115_200), tx, rx);
+        uart.write(b'O');
+        uart.write(b'K');
     }
 }
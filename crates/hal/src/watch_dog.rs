@@ -1,8 +1,54 @@
+//! # Watch Dog Module
+//!
+//! This module provides functions to start and feed the two watchdogs.
+//!
+//! The window watchdog (WWDG) runs from the APB clock and has a timeout of under 100 ms. The
+//! independent watchdog (IWDG) runs from the internal LSI oscillator so it keeps working if the
+//! main clock fails, and can have a timeout of up to about 30 seconds. Once started, neither can
+//! be stopped except by a reset. Both are frozen while the core is halted by a debugger.
+//!
+//! ## Functions
+//!
+//! - `start`: Starts the WWDG.
+//! - `alive`: Resets the WWDG counter.
+//! - `is_enabled`: Returns true if the WWDG is running.
+//! - `start_independent`: Starts the IWDG with a timeout in milliseconds.
+//! - `alive_independent`: Reloads the IWDG counter.
+//!
+//! ## Usage
+//!
+//! Start a watchdog once the system is up, then call its alive function more often than the
+//! timeout. `hal::reset::read_cause` tells which watchdog, if any, caused the last reset.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use hal::watch_dog;
+//!
+//! hal::init(16_000_000);
+//! watch_dog::start_independent(2_000);
+//!
+//! // call at least every 2 seconds from the main loop
+//! watch_dog::alive_independent();
+//! ```
+//!
+
 use core::ptr;
 
 use super::cpu;
 pub use super::cpu::*;
 
+// frequency of the LSI oscillator that clocks the IWDG, it is only accurate to about 30%
+#[cfg(feature = "stm32f405")]
+const LSI_FREQ: u32 = 32_000;
+#[cfg(feature = "stm32f072")]
+const LSI_FREQ: u32 = 40_000;
+
+// IWDG key register values
+const KEY_RELOAD: u32 = 0xAAAA;
+const KEY_UNLOCK: u32 = 0x5555;
+const KEY_START: u32 = 0xCCCC;
+
 pub fn init() {}
 
 pub fn is_enabled() -> bool {
@@ -46,3 +92,59 @@ pub fn start() {
 pub fn alive() {
     cpu::write!(WWDG.cr[T;7], 0x7F);
 }
+
+// IWDG prescaler setting and reload value for a timeout, the prescaler
+// divides by 4 << PR and the reload counter is 12 bits
+fn independent_timing(timeout_ms: u32) -> (u32, u32) {
+    let ticks = timeout_ms as u64 * LSI_FREQ as u64 / 1000;
+    for pr in 0..7 {
+        let reload = ticks / (4 << pr);
+        if reload <= 0x1000 {
+            if reload == 0 {
+                panic!("IWDG timeout too short");
+            }
+            return (pr, reload as u32 - 1);
+        }
+    }
+    panic!("IWDG timeout too long");
+}
+
+#[cfg(feature = "stm32f072")]
+fn freeze_independent_in_debug() {
+    cpu::write!( DBGMCU.apb1_fz[DBG_IWDG_STOP;1], 0b1);
+}
+
+#[cfg(feature = "stm32f405")]
+fn freeze_independent_in_debug() {
+    cpu::write!( DBG.dbgmcu_apb1_fz[DBG_IWDEG_STOP;1], 0b1);
+}
+
+#[inline(never)]
+/// Starts the IWDG (Independent Watchdog) so it resets the system if `alive_independent` is not
+/// called within `timeout_ms`. Panics if the timeout is shorter than a tick or longer than the
+/// IWDG can count, about 32 seconds.
+pub fn start_independent(timeout_ms: u32) {
+    let (pr, reload) = independent_timing(timeout_ms);
+
+    freeze_independent_in_debug();
+
+    // starting the IWDG also turns on the LSI
+    cpu::write!(IWDG.kr, KEY_START);
+
+    // the prescaler and reload registers are write protected until unlocked
+    cpu::write!(IWDG.kr, KEY_UNLOCK);
+    cpu::write!(IWDG.pr[PR;3], pr);
+    cpu::write!(IWDG.rlr[RL;12], reload);
+
+    // wait for the new values to reach the LSI clock domain
+    #[cfg(not(feature = "std"))]
+    while cpu::read!(IWDG.sr) != 0 {}
+
+    cpu::write!(IWDG.kr, KEY_RELOAD);
+}
+
+/// Reloads the IWDG (Independent Watchdog) counter to prevent a system reset.
+///
+pub fn alive_independent() {
+    cpu::write!(IWDG.kr, KEY_RELOAD);
+}
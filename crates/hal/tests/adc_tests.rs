@@ -0,0 +1,52 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::adc;
+use hal::cpu;
+use hal::cpu::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        hal::init(16_000_000);
+        adc::init();
+
+        assert_eq!(hal::read!(C_ADC.ccr[ADCPRE;2]), 0b01);
+        assert_eq!(hal::read!(ADC1.cr2[ADON;1]), 1);
+
+        adc::set_sample_time(14, adc::SampleTime::Cycles84);
+        assert_eq!(hal::read!(ADC1.smpr1[4*3;3]), 0b100);
+
+        hal::write!(ADC1.dr, 1234);
+        assert_eq!(adc::read(14), 1234);
+        assert_eq!(hal::read!(ADC1.sqr3[SQ1;5]), 14);
+
+        // VREFINT reads the same as its calibration so VDDA is 3.3V
+        hal::write!(ADC1.dr, 1210 * 4095 / 3300);
+        assert_eq!(adc::vdda_mv(), 3300);
+        let mv = adc::read_mv(14);
+        assert!((1200..=1210).contains(&mv));
+    }
+    #[test]
+    fn test_set_analog() {
+        hal::init(16_000_000);
+        adc::init();
+
+        hal::sim::set_analog(14, 1650);
+        assert_eq!(adc::read(14), 2047);
+        assert_eq!(hal::read!(ADC1.sr[EOC;1]), 1);
+        assert_eq!(hal::read!(ADC1.cr2[SWSTART;1]), 0);
+
+        // VREFINT is at its typical value
+        assert_eq!(adc::vdda_mv(), 3300);
+        let mv = adc::read_mv(14);
+        assert!((1640..=1650).contains(&mv));
+    }
+}
@@ -0,0 +1,195 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::cell::Cell;
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use hal::alarm::{Alarm, Timer, MAX_TIMERS};
+use hal::cpu;
+use hal::cpu::TIM2;
+use hal::timer;
+use hal::timer::{Duration, Instant};
+
+// lets `TIM2.reg[FIELD;w]` find the field offsets
+use hal::timer::TIM2;
+
+// each test runs on its own thread with its own counts
+hal::local! {
+    static ALARM_RUNS: AtomicU32 = AtomicU32::new(0);
+}
+hal::local! {
+    static TIMER_RUNS: AtomicU32 = AtomicU32::new(0);
+}
+
+hal::local! {
+    // the two timers `cancel_other` picks from, and the timer it starts
+    static PAIR: Cell<Option<(Timer, Timer)>> = Cell::new(None);
+}
+hal::local! {
+    static REPLACEMENT: Cell<Option<Timer>> = Cell::new(None);
+}
+
+fn count_alarm(_alarm: Alarm) {
+    hal::local!(ref ALARM_RUNS).fetch_add(1, Ordering::Relaxed);
+}
+
+fn count_timer(_timer: Timer) {
+    hal::local!(ref TIMER_RUNS).fetch_add(1, Ordering::Relaxed);
+}
+
+// cancels the other timer of the pair if it has not run yet, and starts a new timer that can
+// take its place
+fn cancel_other(timer: Timer) {
+    count_timer(timer);
+    let (a, b) = hal::local!(ref PAIR).get().unwrap();
+    let other = if timer == a { b } else { a };
+    if other.is_active() {
+        other.cancel();
+        let new = Timer::once(Duration::from_secs(60), count_timer);
+        hal::local!(ref REPLACEMENT).set(Some(new));
+    }
+}
+
+fn setup() {
+    hal::init(16_000_000);
+    timer::init2();
+    hal::local!(ref ALARM_RUNS).store(0, Ordering::Relaxed);
+    hal::local!(ref TIMER_RUNS).store(0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_once() {
+        setup();
+
+        Alarm::Alarm1.once(Instant::now(), count_alarm);
+        assert!(Alarm::Alarm1.is_active());
+        assert_eq!(hal::read!(TIM2.dier[CC1IE;1]), 1);
+
+        // the sim has no counter to match, so raise the flag by hand
+        hal::write!(TIM2.sr[CC1IF;1], 1);
+        timer::handle_tim2_irq();
+
+        assert_eq!(hal::local!(ref ALARM_RUNS).load(Ordering::Relaxed), 1);
+        assert!(!Alarm::Alarm1.is_active());
+        assert_eq!(hal::read!(TIM2.sr[CC1IF;1]), 0);
+        assert_eq!(hal::read!(TIM2.dier[CC1IE;1]), 0);
+    }
+
+    #[test]
+    fn test_not_due() {
+        setup();
+
+        // a match in an earlier hour of the counter does not run the alarm
+        Alarm::Alarm2.once(Instant::now() + Duration::from_secs(7_200), count_alarm);
+        hal::write!(TIM2.sr[CC2IF;1], 1);
+        timer::handle_tim2_irq();
+
+        assert_eq!(hal::local!(ref ALARM_RUNS).load(Ordering::Relaxed), 0);
+        assert!(Alarm::Alarm2.is_active());
+        assert_eq!(hal::read!(TIM2.dier[CC2IE;1]), 1);
+
+        Alarm::Alarm2.cancel();
+        assert!(!Alarm::Alarm2.is_active());
+        assert_eq!(hal::read!(TIM2.dier[CC2IE;1]), 0);
+    }
+
+    #[test]
+    fn test_periodic() {
+        setup();
+
+        Alarm::Alarm3.periodic(Duration::from_micros(100), count_alarm);
+        for _ in 0..3 {
+            std::thread::sleep(std::time::Duration::from_micros(200));
+            hal::write!(TIM2.sr[CC3IF;1], 1);
+            timer::handle_tim2_irq();
+        }
+
+        // still armed after each run
+        assert_eq!(hal::local!(ref ALARM_RUNS).load(Ordering::Relaxed), 3);
+        assert!(Alarm::Alarm3.is_active());
+        assert_eq!(hal::read!(TIM2.dier[CC3IE;1]), 1);
+
+        Alarm::Alarm3.cancel();
+    }
+
+    #[test]
+    fn test_timer_wheel() {
+        setup();
+
+        let once = Timer::once(Duration::from_millis(1), count_timer);
+        let periodic = Timer::periodic(Duration::from_millis(2), count_timer);
+        let later = Timer::once(Duration::from_secs(60), count_timer);
+        assert_eq!(hal::read!(TIM2.dier[CC4IE;1]), 1);
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        hal::write!(TIM2.sr[CC4IF;1], 1);
+        timer::handle_tim2_irq();
+
+        assert_eq!(hal::local!(ref TIMER_RUNS).load(Ordering::Relaxed), 2);
+        assert!(!once.is_active());
+        assert!(periodic.is_active());
+        assert!(later.is_active());
+
+        periodic.cancel();
+        later.cancel();
+        assert_eq!(hal::read!(TIM2.dier[CC4IE;1]), 0);
+    }
+
+    #[test]
+    fn test_stale_handle() {
+        setup();
+
+        let old = Timer::once(Duration::from_millis(1), count_timer);
+        std::thread::sleep(std::time::Duration::from_millis(3));
+        hal::write!(TIM2.sr[CC4IF;1], 1);
+        timer::handle_tim2_irq();
+        assert_eq!(hal::local!(ref TIMER_RUNS).load(Ordering::Relaxed), 1);
+
+        // the new timer reuses the slot, cancelling the old handle leaves it running
+        let new = Timer::once(Duration::from_secs(60), count_timer);
+        old.cancel();
+        assert!(!old.is_active());
+        assert!(new.is_active());
+
+        new.cancel();
+    }
+
+    #[test]
+    fn test_cancel_due_timer() {
+        setup();
+
+        // both are due in the same run of the wheel, whichever runs first cancels the other
+        let a = Timer::once(Duration::from_millis(1), cancel_other);
+        let b = Timer::once(Duration::from_millis(1), cancel_other);
+        hal::local!(ref PAIR).set(Some((a, b)));
+
+        std::thread::sleep(std::time::Duration::from_millis(3));
+        hal::write!(TIM2.sr[CC4IF;1], 1);
+        timer::handle_tim2_irq();
+
+        // the cancelled timer does not run, and the new timer in its slot is left alone
+        assert_eq!(hal::local!(ref TIMER_RUNS).load(Ordering::Relaxed), 1);
+        let new = hal::local!(ref REPLACEMENT).get().unwrap();
+        assert!(new.is_active());
+
+        new.cancel();
+        assert_eq!(hal::read!(TIM2.dier[CC4IE;1]), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many timers")]
+    fn test_too_many_timers() {
+        setup();
+
+        for _ in 0..MAX_TIMERS + 1 {
+            Timer::once(Duration::from_secs(60), count_timer);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::clock;
+use hal::cpu;
+use hal::cpu::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        hal::init(16_000_000);
+
+        assert_eq!(clock::sysclk(), 168_000_000);
+        assert_eq!(clock::hclk(), 168_000_000);
+        assert_eq!(clock::pclk1(), 42_000_000);
+        assert_eq!(clock::pclk2(), 84_000_000);
+        assert_eq!(clock::timclk1(), 84_000_000);
+        assert_eq!(clock::timclk2(), 168_000_000);
+        assert_eq!(clock::pll48clk(), 48_000_000);
+
+        // 2 MHz PLL input, 336 MHz VCO
+        let pll = clock::ClockConfig::new(16_000_000).pll();
+        assert_eq!(
+            pll,
+            clock::Pll {
+                m: 8,
+                n: 168,
+                p: 2,
+                q: 7
+            }
+        );
+
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLN0;9]), 168);
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLP0;2]), 0b00);
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLQ0;4]), 7);
+        assert_eq!(hal::read!(RCC.cfgr[HPRE;4]), 0b0000);
+        assert_eq!(hal::read!(RCC.cfgr[PPRE1;3]), 0b101);
+        assert_eq!(hal::read!(RCC.cfgr[PPRE2;3]), 0b100);
+        assert_eq!(hal::read!(FLASH.acr[LATENCY;3]), 5);
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let mut config = clock::ClockConfig::new(8_000_000);
+        config.sysclk = 84_000_000;
+        config.apb1_div = 2;
+        config.apb2_div = 1;
+        hal::init_with(&config);
+
+        assert_eq!(clock::sysclk(), 84_000_000);
+        assert_eq!(clock::pclk1(), 42_000_000);
+        assert_eq!(clock::pclk2(), 84_000_000);
+        assert_eq!(clock::timclk1(), 84_000_000);
+        assert_eq!(clock::timclk2(), 84_000_000);
+        assert_eq!(clock::pll48clk(), 42_000_000);
+
+        // 2 MHz PLL input, 168 MHz VCO
+        assert_eq!(
+            config.pll(),
+            clock::Pll {
+                m: 4,
+                n: 84,
+                p: 2,
+                q: 4
+            }
+        );
+
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLN0;9]), 84);
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLP0;2]), 0b00);
+        assert_eq!(hal::read!(RCC.pllcfgr[PLLQ0;4]), 4);
+        assert_eq!(hal::read!(RCC.cfgr[PPRE1;3]), 0b100);
+        assert_eq!(hal::read!(RCC.cfgr[PPRE2;3]), 0b000);
+        assert_eq!(hal::read!(FLASH.acr[LATENCY;3]), 2);
+
+        // timer prescaler follows the APB1 timer clock
+        hal::timer::init2();
+        assert_eq!(hal::read!(TIM2.psc), 84 - 1);
+
+        hal::init(16_000_000);
+        assert_eq!(clock::sysclk(), 168_000_000);
+    }
+
+    #[test]
+    fn test_validate() {
+        hal::init(16_000_000);
+
+        // the simulator sets the ready flags as the clocks are turned on
+        assert_eq!(hal::read!(RCC.cr[HSERDY;1]), 1);
+        assert_eq!(hal::read!(RCC.cr[PLLRDY;1]), 1);
+        assert_eq!(hal::read!(RCC.cfgr[SWS0;2]), 0b10);
+        clock::validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "APB1 clock too fast")]
+    fn test_apb1_too_fast() {
+        let mut config = clock::ClockConfig::new(16_000_000);
+        config.apb1_div = 2;
+        hal::init_with(&config);
+    }
+
+    #[test]
+    #[should_panic(expected = "SYSCLK not supported")]
+    fn test_bad_sysclk() {
+        let mut config = clock::ClockConfig::new(16_000_000);
+        config.sysclk = 1_000_000;
+        config.pll();
+    }
+}
@@ -0,0 +1,76 @@
+#![no_std]
+#![allow(non_snake_case)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+#[cfg(feature = "stm32f405")]
+use hal::cpu::*;
+
+// lets `usart.reg.FIELD` find the field offsets and widths
+#[cfg(feature = "stm32f405")]
+use hal::cpu::USART::{self as usart};
+
+// a register definition in the form svd2rusty generates
+mod TEST {
+    pub mod cr {
+        pub const EN: u8 = 0;
+        pub const MODE: u8 = 4;
+        pub const COUNT: u8 = 16;
+        pub mod width {
+            pub const EN: u8 = 1;
+            pub const MODE: u8 = 3;
+            pub const COUNT: u8 = 16;
+        }
+    }
+}
+
+#[repr(C)]
+struct TestReg {
+    cr: u32,
+}
+
+const TEST: *mut TestReg = 0x5000_0000 as *mut TestReg;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_width() {
+        hal::init(16_000_000);
+
+        hal::write!(TEST.cr.EN, 1);
+        hal::write!(TEST.cr.MODE, 0xFF);
+        hal::write!(TEST.cr.COUNT, 0x1_2345);
+        assert_eq!(hal::read!(TEST.cr), 0x2345_0071);
+
+        assert_eq!(hal::read!(TEST.cr.EN), 1);
+        assert_eq!(hal::read!(TEST.cr.MODE), 0b111);
+        assert_eq!(hal::read!(TEST.cr.COUNT), 0x2345);
+
+        // the same as giving the width
+        hal::write!(TEST.cr.MODE, 0b010);
+        assert_eq!(hal::read!(TEST.cr[MODE;3]), 0b010);
+        assert_eq!(hal::read!(TEST.cr), 0x2345_0021);
+    }
+
+    #[test]
+    #[cfg(feature = "stm32f405")]
+    fn test_generated_width() {
+        hal::init(16_000_000);
+
+        hal::write!(RCC.cr.HSEON, 1);
+        assert_eq!(hal::read!(RCC.cr[HSEON;1]), 1);
+
+        let usart = cpu::USART1;
+        hal::write!(usart.brr, 0);
+        hal::write!(usart.brr.DIV_Mantissa, 0x1_2345);
+        hal::write!(usart.brr.DIV_Fraction, 0x1F);
+        assert_eq!(hal::read!(usart.brr), 0x345F);
+        assert_eq!(hal::read!(usart.brr.DIV_Mantissa), 0x345);
+    }
+}
@@ -0,0 +1,65 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use hal::crc::{self, Config, CRC32, CRC32_MPEG2, TEST_VECTORS};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vectors() {
+        hal::init(16_000_000);
+
+        for (config, data, expected) in TEST_VECTORS.iter() {
+            assert_eq!(crc::compute_sw(config, data), *expected);
+            assert_eq!(crc::compute(config, data), *expected);
+        }
+        assert!(crc::self_test());
+    }
+
+    #[test]
+    fn test_lengths() {
+        hal::init(16_000_000);
+
+        // every length of tail after the whole words
+        let data = [0x5Au8; 11];
+        for len in 0..data.len() {
+            for config in [CRC32, CRC32_MPEG2] {
+                assert_eq!(
+                    crc::compute(&config, &data[..len]),
+                    crc::compute_sw(&config, &data[..len])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom() {
+        // CRC-32/JAMCRC is CRC32 without the final XOR
+        let jamcrc = Config {
+            xor_out: 0,
+            ..CRC32
+        };
+        assert_eq!(crc::compute(&jamcrc, b"123456789"), !0xCBF4_3926u32);
+
+        // CRC-32/POSIX as used by cksum, without the length
+        let posix = Config {
+            init: 0,
+            xor_out: 0xFFFF_FFFF,
+            ..CRC32_MPEG2
+        };
+        assert_eq!(crc::compute(&posix, b"123456789"), 0x765E_7680);
+    }
+
+    #[test]
+    fn test_detects_change() {
+        let mut data = *b"hello world";
+        let good = crc::compute(&CRC32, &data);
+
+        data[3] ^= 0x10;
+        assert_ne!(crc::compute(&CRC32, &data), good);
+    }
+}
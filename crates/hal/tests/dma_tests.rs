@@ -0,0 +1,149 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::dma;
+use hal::interrupt::{self, Irq};
+
+// lets `dma.reg[FIELD;w]` find the field offsets
+use hal::cpu::DMA::{self as dma_reg};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static mut EVENTS: u32 = 0;
+
+    fn count_events(_stream: dma::Stream, event: dma::Event) {
+        assert_eq!(event, dma::Event::TransferComplete);
+        unsafe {
+            EVENTS += 1;
+        }
+    }
+
+    #[test]
+    fn test_configure() {
+        hal::init(16_000_000);
+
+        let stream = dma::Stream::claim(cpu::DMA2, 7);
+        let dma_reg = cpu::DMA2;
+
+        let mut config = dma::Config::new(4, dma::Direction::MemoryToPeripheral);
+        config.memory_width = dma::Width::Word;
+        config.circular = true;
+        config.priority = dma::Priority::High;
+        stream.configure(&config);
+
+        assert_eq!(hal::read!(dma_reg.s7cr[CHSEL;3]), 4);
+        assert_eq!(hal::read!(dma_reg.s7cr[DIR;2]), 0b01);
+        assert_eq!(hal::read!(dma_reg.s7cr[MSIZE;2]), 0b10);
+        assert_eq!(hal::read!(dma_reg.s7cr[PSIZE;2]), 0b00);
+        assert_eq!(hal::read!(dma_reg.s7cr[MINC;1]), 1);
+        assert_eq!(hal::read!(dma_reg.s7cr[PINC;1]), 0);
+        assert_eq!(hal::read!(dma_reg.s7cr[CIRC;1]), 1);
+        assert_eq!(hal::read!(dma_reg.s7cr[PL;2]), 0b10);
+
+        stream.set_peripheral(0x4001_1004);
+        stream.set_memory(0x2000_0000);
+        stream.set_count(12);
+        assert_eq!(hal::read!(dma_reg.s7par), 0x4001_1004);
+        assert_eq!(hal::read!(dma_reg.s7m0ar), 0x2000_0000);
+        assert_eq!(stream.remaining(), 12);
+
+        stream.start();
+        assert!(stream.is_enabled());
+        stream.release();
+        assert!(!stream.is_enabled());
+    }
+
+    #[test]
+    fn test_poll() {
+        hal::init(16_000_000);
+
+        // stream 1 flags are in the low registers at bit 6
+        let stream = dma::Stream::claim(cpu::DMA1, 1);
+        let dma_reg = cpu::DMA1;
+        stream.configure(&dma::Config::new(0, dma::Direction::PeripheralToMemory));
+        stream.start();
+
+        assert_eq!(stream.poll(), None);
+
+        hal::write!(dma_reg.lisr[HTIF1;1], 1);
+        assert_eq!(stream.poll(), Some(dma::Event::HalfTransfer));
+        assert_eq!(hal::read!(dma_reg.lifcr[CHTIF1;1]), 1);
+
+        // with both halves done, the first half is reported first and only its
+        // flag is cleared
+        hal::write!(dma_reg.lisr, 0);
+        hal::write!(dma_reg.lifcr, 0);
+        hal::write!(dma_reg.lisr[HTIF1;1], 1);
+        hal::write!(dma_reg.lisr[TCIF1;1], 1);
+        assert_eq!(stream.poll(), Some(dma::Event::HalfTransfer));
+        assert_eq!(hal::read!(dma_reg.lifcr[CHTIF1;1]), 1);
+        assert_eq!(hal::read!(dma_reg.lifcr[CTCIF1;1]), 0);
+
+        // errors are reported before transfer complete
+        hal::write!(dma_reg.lisr, 0);
+        hal::write!(dma_reg.lisr[TCIF1;1], 1);
+        hal::write!(dma_reg.lisr[TEIF1;1], 1);
+        assert_eq!(stream.poll(), Some(dma::Event::TransferError));
+        assert_eq!(stream.wait(), Err(dma::Event::TransferError));
+
+        stream.release();
+    }
+
+    #[test]
+    fn test_callback() {
+        hal::init(16_000_000);
+
+        let stream = dma::Stream::claim(cpu::DMA2, 5);
+        let dma_reg = cpu::DMA2;
+        stream.configure(&dma::Config::new(4, dma::Direction::PeripheralToMemory));
+        stream.set_callback(count_events);
+        assert_eq!(hal::read!(dma_reg.s5cr[TCIE;1]), 1);
+        assert_eq!(stream.irq_num(), Irq::Dma2Stream5);
+        assert_eq!(stream.irq_num().number(), 68);
+        assert!(interrupt::is_enabled(Irq::Dma2Stream5));
+
+        stream.start();
+        hal::write!(dma_reg.hisr[TCIF5;1], 1);
+        dma::handle_irq(cpu::DMA2, 5);
+        assert_eq!(unsafe { EVENTS }, 1);
+
+        stream.release();
+        assert_eq!(hal::read!(dma_reg.s5cr[TCIE;1]), 0);
+        assert!(!interrupt::is_enabled(Irq::Dma2Stream5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_claim_twice() {
+        hal::init(16_000_000);
+
+        let _first = dma::Stream::claim(cpu::DMA1, 6);
+        let _second = dma::Stream::claim(cpu::DMA1, 6);
+    }
+    #[test]
+    fn test_any_enabled() {
+        hal::init(16_000_000);
+        assert!(!dma::any_enabled());
+
+        let stream = dma::Stream::claim(cpu::DMA2, 3);
+        stream.configure(&dma::Config::new(3, dma::Direction::MemoryToPeripheral));
+        assert!(!dma::any_enabled());
+
+        stream.start();
+        assert!(dma::any_enabled());
+
+        stream.stop();
+        assert!(!dma::any_enabled());
+
+        stream.start();
+        stream.release();
+        assert!(!dma::any_enabled());
+    }
+}
@@ -0,0 +1,55 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::*;
+use hal::dwt;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init() {
+        hal::init(16_000_000);
+
+        // stopped until init
+        assert_eq!(dwt::cycles(), 0);
+
+        dwt::init();
+        assert_eq!(hal::read!(DCB.demcr[TRCENA;1]), 1);
+        assert_eq!(hal::read!(DWT.ctrl[CYCCNTENA;1]), 1);
+
+        // hal::init resets the counter
+        hal::init(16_000_000);
+        assert_eq!(dwt::cycles(), 0);
+    }
+
+    #[test]
+    fn test_measure() {
+        hal::init(16_000_000);
+        dwt::init();
+
+        let start = dwt::cycles();
+        let (_, cycles) = dwt::measure(|| std::thread::sleep(std::time::Duration::from_millis(2)));
+        assert!(dwt::since(start) >= cycles);
+
+        // 2 ms is 336,000 cycles at 168 MHz
+        assert_eq!(hal::clock::hclk(), 168_000_000);
+        assert!(cycles >= 336_000);
+        assert!(dwt::to_nanos(cycles) >= 2_000_000);
+        assert_eq!(dwt::to_nanos(168), 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "no cycle counter")]
+    fn test_no_cycle_counter() {
+        hal::init(16_000_000);
+        hal::write!(DWT.ctrl[NOCYCCNT;1], 1);
+        dwt::init();
+    }
+}
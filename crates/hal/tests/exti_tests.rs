@@ -0,0 +1,91 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use hal::cpu;
+use hal::cpu::{EXTI, NVIC, RCC, SYSCFG};
+use hal::exti::{self, Edge};
+use hal::gpio;
+use hal::interrupt::{self, Irq};
+
+hal::local! {
+    // bit set for each line whose callback ran
+    static LINES: AtomicU32 = AtomicU32::new(0);
+}
+
+fn on_line(line: u8) {
+    hal::local!(ref LINES).fetch_or(1 << line, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listen() {
+        hal::init(16_000_000);
+        hal::local!(ref LINES).store(0, Ordering::Relaxed);
+
+        let pin = gpio::Pin(cpu::GPIOC, 6);
+        pin.input();
+        exti::listen(pin, Edge::Falling, on_line);
+
+        assert_eq!(hal::read!(RCC.apb2enr[SYSCFGEN;1]), 1);
+
+        // port C is 2, line 6 is the third field of EXTICR2
+        assert_eq!(hal::read!(SYSCFG.exticr2[EXTI6;4]), 2);
+        assert_eq!(hal::read!(EXTI.rtsr[TR6;1]), 0);
+        assert_eq!(hal::read!(EXTI.ftsr[TR6;1]), 1);
+        assert_eq!(hal::read!(EXTI.imr[MR6;1]), 1);
+
+        // lines 5 to 9 share an interrupt
+        assert!(interrupt::is_enabled(Irq::Exti9_5));
+        let iser = unsafe { cpu::read_reg(ptr::addr_of_mut!((*NVIC).iser[0])) };
+        assert_ne!(iser & (1 << Irq::Exti9_5.number()), 0);
+
+        exti::unlisten(pin);
+        assert_eq!(hal::read!(EXTI.imr[MR6;1]), 0);
+    }
+
+    #[test]
+    fn test_edges() {
+        hal::init(16_000_000);
+
+        let pin = gpio::Pin(cpu::GPIOA, 12);
+        exti::listen(pin, Edge::Both, on_line);
+        assert_eq!(hal::read!(SYSCFG.exticr4[EXTI12;4]), 0);
+        assert_eq!(hal::read!(EXTI.rtsr[TR12;1]), 1);
+        assert_eq!(hal::read!(EXTI.ftsr[TR12;1]), 1);
+
+        exti::listen(pin, Edge::Rising, on_line);
+        assert_eq!(hal::read!(EXTI.rtsr[TR12;1]), 1);
+        assert_eq!(hal::read!(EXTI.ftsr[TR12;1]), 0);
+        assert!(interrupt::is_enabled(Irq::Exti15_10));
+    }
+
+    #[test]
+    fn test_handle_irq() {
+        hal::init(16_000_000);
+        hal::local!(ref LINES).store(0, Ordering::Relaxed);
+
+        exti::listen(gpio::Pin(cpu::GPIOB, 0), Edge::Rising, on_line);
+        exti::listen(gpio::Pin(cpu::GPIOB, 3), Edge::Rising, on_line);
+
+        // line 3 pending, and line 7 pending but not listened to
+        hal::write!(EXTI.pr, (1 << 3) | (1 << 7));
+        exti::handle_irq();
+
+        assert_eq!(hal::local!(ref LINES).load(Ordering::Relaxed), 1 << 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "EXTI line out of range")]
+    fn test_bad_line() {
+        hal::init(16_000_000);
+        exti::listen(gpio::Pin(cpu::GPIOA, 16), Edge::Rising, on_line);
+    }
+}
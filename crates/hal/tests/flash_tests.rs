@@ -0,0 +1,138 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::FLASH;
+use hal::flash::{self, Error, Parallelism};
+
+// the last sector, well away from the firmware
+const SECTOR: u8 = 11;
+
+fn setup() {
+    hal::init(16_000_000);
+    flash::set_parallelism(Parallelism::X32);
+    flash::unlock();
+    flash::erase_sector(SECTOR).unwrap();
+}
+
+fn read_word(addr: u32) -> u32 {
+    let mut buf = [0u8; 4];
+    flash::read(addr, &mut buf);
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sectors() {
+        assert_eq!(flash::sector_address(0), 0x0800_0000);
+        assert_eq!(flash::sector_address(3), 0x0800_C000);
+        assert_eq!(flash::sector_address(4), 0x0801_0000);
+        assert_eq!(flash::sector_address(5), 0x0802_0000);
+        assert_eq!(flash::sector_address(11), 0x080E_0000);
+        assert_eq!(
+            flash::sector_address(11) as usize + flash::sector_size(11),
+            0x0810_0000
+        );
+    }
+
+    #[test]
+    fn test_lock() {
+        hal::init(16_000_000);
+
+        flash::lock();
+        assert!(flash::is_locked());
+        assert_eq!(flash::erase_sector(SECTOR), Err(Error::Locked));
+        assert_eq!(
+            flash::program_word(flash::sector_address(SECTOR), 0),
+            Err(Error::Locked)
+        );
+
+        flash::unlock();
+        assert!(!flash::is_locked());
+        assert_eq!(hal::read!(FLASH.keyr), 0xCDEF_89AB);
+    }
+
+    #[test]
+    fn test_erase() {
+        setup();
+        let addr = flash::sector_address(SECTOR);
+
+        flash::program_word(addr, 0).unwrap();
+        flash::erase_sector(SECTOR).unwrap();
+        assert_eq!(read_word(addr), 0xFFFF_FFFF);
+
+        assert_eq!(hal::read!(FLASH.cr[SNB;4]), SECTOR as u32);
+        assert_eq!(hal::read!(FLASH.cr[PSIZE;2]), 0b10);
+        assert_eq!(hal::read!(FLASH.cr[SER;1]), 0);
+
+        assert_eq!(flash::erase_sector(12), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_program() {
+        setup();
+        let addr = flash::sector_address(SECTOR);
+
+        flash::program_word(addr, 0x1234_5678).unwrap();
+        flash::program_half_word(addr + 4, 0xABCD).unwrap();
+        assert_eq!(read_word(addr), 0x1234_5678);
+        assert_eq!(read_word(addr + 4), 0xFFFF_ABCD);
+        assert_eq!(hal::read!(FLASH.cr[PG;1]), 0);
+
+        // programming can only clear bits
+        flash::program_word(addr, 0xFFFF_0000).unwrap();
+        assert_eq!(read_word(addr), 0x1234_0000);
+    }
+
+    #[test]
+    fn test_parallelism() {
+        setup();
+        let addr = flash::sector_address(SECTOR);
+
+        // a word is written as two half words
+        flash::set_parallelism(Parallelism::X16);
+        flash::program_word(addr, 0x8765_4321).unwrap();
+        assert_eq!(read_word(addr), 0x8765_4321);
+        assert_eq!(hal::read!(FLASH.cr[PSIZE;2]), 0b01);
+
+        flash::set_parallelism(Parallelism::X8);
+        flash::program_half_word(addr + 4, 0x00FF).unwrap();
+        assert_eq!(read_word(addr + 4), 0xFFFF_00FF);
+        assert_eq!(hal::read!(FLASH.cr[PSIZE;2]), 0b00);
+
+        flash::set_parallelism(Parallelism::X32);
+    }
+
+    #[test]
+    fn test_bad_address() {
+        setup();
+        let addr = flash::sector_address(SECTOR);
+
+        assert_eq!(flash::program_word(addr + 2, 0), Err(Error::Alignment));
+        assert_eq!(flash::program_word(0x0810_0000, 0), Err(Error::OutOfRange));
+        assert_eq!(
+            flash::program_half_word(0x2000_0000, 0),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_error_flags() {
+        setup();
+        let addr = flash::sector_address(SECTOR);
+
+        // the sim has no option bytes, so raise the flag by hand
+        hal::write!(FLASH.sr[WRPERR;1], 1);
+        assert_eq!(flash::program_word(addr, 0), Err(Error::WriteProtected));
+
+        hal::write!(FLASH.sr, 1 << FLASH::sr::PGSERR);
+        assert_eq!(flash::erase_sector(SECTOR), Err(Error::Sequence));
+    }
+}
@@ -0,0 +1,198 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+// lets `dev.reg[FIELD;w]` find the field offsets in the I2C register module
+use hal::cpu::I2C as dev;
+use hal::gpio;
+use hal::i2c;
+use hal::sim;
+
+#[cfg(test)]
+#[cfg(feature = "stm32f405")]
+mod tests {
+    use super::*;
+
+    // SB | ADDR | BTF | RxNE | TxE
+    const SR1_ALL_DONE: u32 = 0b1100_0111;
+
+    fn setup() -> i2c::I2c {
+        hal::init(16_000_000);
+
+        let bus = i2c::I2c(cpu::I2C1);
+        let scl = gpio::Pin(cpu::GPIOB, 6);
+        let sda = gpio::Pin(cpu::GPIOB, 7);
+        bus.init(100_000, scl, sda);
+        bus
+    }
+
+    #[test]
+    fn test_init_timing() {
+        let bus = setup();
+        let dev = bus.0;
+
+        assert_eq!(hal::read!(dev.cr1[PE;1]), 1);
+        assert_eq!(hal::read!(dev.cr2[FREQ;6]), 42);
+        assert_eq!(hal::read!(dev.ccr[F_S;1]), 0);
+        assert_eq!(hal::read!(dev.ccr[CCR;12]), 210);
+        assert_eq!(hal::read!(dev.trise[TRISE;6]), 43);
+
+        let scl = gpio::Pin(cpu::GPIOB, 6);
+        let sda = gpio::Pin(cpu::GPIOB, 7);
+        bus.init(400_000, scl, sda);
+        assert_eq!(hal::read!(dev.ccr[F_S;1]), 1);
+        assert_eq!(hal::read!(dev.ccr[CCR;12]), 35);
+        assert_eq!(hal::read!(dev.trise[TRISE;6]), 13);
+
+        // pins are open drain on AF4
+        let gpio = cpu::GPIOB;
+        assert_eq!(hal::read!(gpio.otyper[6;1]), 1);
+        assert_eq!(hal::read!(gpio.afrl[7*4;4]), 4);
+    }
+
+    #[test]
+    fn test_write_ok() {
+        let bus = setup();
+        let dev = bus.0;
+
+        hal::write!(dev.sr1, SR1_ALL_DONE);
+        assert_eq!(bus.write(0x50, &[0x00, 0x42]), Ok(()));
+
+        // address byte then the data, and a stop was generated
+        assert_eq!(sim::i2c_sent(dev), [0xA0, 0x00, 0x42]);
+        assert_eq!(hal::read!(dev.cr1[STOP;1]), 1);
+    }
+
+    #[test]
+    fn test_probe() {
+        let bus = setup();
+        let dev = bus.0;
+
+        // no byte is sent so BTF never sets
+        hal::write!(dev.sr1, (1 << 1) | (1 << 0));
+        assert_eq!(bus.write(0x50, &[]), Ok(()));
+
+        assert_eq!(sim::i2c_sent(dev), [0xA0]);
+        assert_eq!(hal::read!(dev.cr1[STOP;1]), 1);
+    }
+
+    #[test]
+    fn test_read_ok() {
+        let bus = setup();
+        let dev = bus.0;
+
+        hal::write!(dev.sr1, SR1_ALL_DONE);
+        sim::i2c_receive(dev, &[0x12, 0x34]);
+
+        let mut data = [0u8; 2];
+        assert_eq!(bus.write_read(0x50, &[0x10], &mut data), Ok(()));
+        assert_eq!(data, [0x12, 0x34]);
+
+        // write address, register, then the read address after the restart
+        assert_eq!(sim::i2c_sent(dev), [0xA0, 0x10, 0xA1]);
+
+        // last byte was NACKed and a stop was generated
+        assert_eq!(hal::read!(dev.cr1[ACK;1]), 0);
+        assert_eq!(hal::read!(dev.cr1[STOP;1]), 1);
+    }
+
+    #[test]
+    fn test_nack() {
+        let bus = setup();
+        let dev = bus.0;
+
+        // start sent but address not acknowledged
+        hal::write!(dev.sr1, (1 << 10) | (1 << 0));
+        assert_eq!(bus.write(0x50, &[0x00]), Err(i2c::Error::Nack));
+
+        // AF is cleared and the bus released
+        assert_eq!(hal::read!(dev.sr1[AF;1]), 0);
+        assert_eq!(hal::read!(dev.cr1[STOP;1]), 1);
+
+        // the next transfer is not affected
+        sim::i2c_sent(dev);
+        hal::write!(dev.sr1, SR1_ALL_DONE);
+        sim::i2c_receive(dev, &[0x56]);
+        let mut data = [0u8; 1];
+        assert_eq!(bus.read(0x50, &mut data), Ok(()));
+        assert_eq!(data, [0x56]);
+        assert_eq!(sim::i2c_sent(dev), [0xA1]);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let bus = setup();
+        let dev = bus.0;
+
+        hal::write!(dev.sr1, 0);
+        let mut data = [0u8; 1];
+        assert_eq!(bus.read(0x50, &mut data), Err(i2c::Error::Timeout));
+    }
+
+    #[test]
+    fn test_busy_timeout() {
+        let bus = setup();
+        let dev = bus.0;
+
+        assert!(ptr::eq(dev, cpu::I2C1));
+        hal::write!(dev.sr2[BUSY;1], 1);
+        assert_eq!(bus.write(0x50, &[0x00]), Err(i2c::Error::Timeout));
+        hal::write!(dev.sr2[BUSY;1], 0);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "stm32f072")]
+mod tests {
+    use super::*;
+
+    fn setup() -> i2c::I2c {
+        hal::init(16_000_000);
+
+        let bus = i2c::I2c(cpu::I2C1);
+        let scl = gpio::Pin(cpu::GPIOB, 6);
+        let sda = gpio::Pin(cpu::GPIOB, 7);
+        bus.init(100_000, scl, sda);
+        bus
+    }
+
+    #[test]
+    fn test_write_read() {
+        let bus = setup();
+        let dev = bus.0;
+
+        sim::i2c_receive(dev, &[0x12, 0x34]);
+        let mut data = [0u8; 2];
+        assert_eq!(bus.write_read(0x50, &[0x10], &mut data), Ok(()));
+        assert_eq!(data, [0x12, 0x34]);
+        assert_eq!(sim::i2c_sent(dev), [0x10]);
+
+        // the read was 2 bytes from address 0x50 and ended with a stop
+        assert_eq!(hal::read!(dev.cr2[NBYTES;8]), 2);
+        assert_eq!(hal::read!(dev.cr2[SADD;10]), 0x50 << 1);
+        assert_eq!(hal::read!(dev.isr[STOPF;1]), 0);
+    }
+
+    #[test]
+    fn test_nack_then_write() {
+        let bus = setup();
+        let dev = bus.0;
+
+        // address not acknowledged
+        hal::write!(dev.isr[NACKF;1], 1);
+        assert_eq!(bus.write(0x50, &[0x00]), Err(i2c::Error::Nack));
+
+        // the stop that released the bus is cleared with the NACK, so the next transfer does
+        // not see it and finish before its own stop
+        assert_eq!(hal::read!(dev.isr[NACKF;1]), 0);
+        assert_eq!(hal::read!(dev.isr[STOPF;1]), 0);
+
+        assert_eq!(bus.write(0x51, &[0x01, 0x02]), Ok(()));
+        assert_eq!(sim::i2c_sent(dev), [0x01, 0x02]);
+        assert_eq!(hal::read!(dev.isr[STOPF;1]), 0);
+    }
+}
@@ -0,0 +1,163 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::gpio;
+use hal::i2s;
+
+// lets `spi.reg[FIELD;w]` and `dma.reg[FIELD;w]` find the field offsets
+use hal::cpu::DMA::{self as dma};
+use hal::cpu::SPI1::{self as spi};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(config: &i2s::Config) -> i2s::I2s {
+        hal::init(16_000_000);
+
+        i2s::I2s::init(
+            cpu::SPI2,
+            config,
+            gpio::Pin(cpu::GPIOB, 12),
+            gpio::Pin(cpu::GPIOB, 13),
+            gpio::Pin(cpu::GPIOB, 15),
+            gpio::Pin(cpu::GPIOC, 6),
+        )
+    }
+
+    #[test]
+    fn test_init_stereo_16k() {
+        let config = i2s::Config {
+            sample_rate: 16_000,
+            frame: i2s::Frame::Stereo,
+            direction: i2s::Direction::Transmit,
+        };
+        setup(&config);
+        let spi = cpu::SPI2;
+
+        assert_eq!(hal::read!(spi.i2scfgr[I2SMOD;1]), 1);
+        assert_eq!(hal::read!(spi.i2scfgr[I2SCFG;2]), 0b10);
+        assert_eq!(hal::read!(spi.i2scfgr[I2SSTD;2]), 0b00);
+
+        // 86 MHz / (256 * 21) = 15997 Hz
+        assert_eq!(hal::read!(spi.i2spr[I2SDIV;8]), 10);
+        assert_eq!(hal::read!(spi.i2spr[ODD;1]), 1);
+        assert_eq!(hal::read!(spi.i2spr[MCKOE;1]), 1);
+    }
+
+    #[test]
+    fn test_init_mono_8k() {
+        let config = i2s::Config {
+            sample_rate: 8_000,
+            frame: i2s::Frame::Mono,
+            direction: i2s::Direction::Receive,
+        };
+        setup(&config);
+        let spi = cpu::SPI2;
+
+        assert_eq!(hal::read!(spi.i2scfgr[I2SCFG;2]), 0b11);
+        assert_eq!(hal::read!(spi.i2scfgr[I2SSTD;2]), 0b11);
+
+        // 86 MHz / (256 * 42) = 7998 Hz, the same as a stereo frame
+        assert_eq!(hal::read!(spi.i2spr[I2SDIV;8]), 21);
+        assert_eq!(hal::read!(spi.i2spr[ODD;1]), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_init_bad_rate() {
+        let config = i2s::Config {
+            sample_rate: 44_100,
+            frame: i2s::Frame::Stereo,
+            direction: i2s::Direction::Transmit,
+        };
+        setup(&config);
+    }
+
+    #[test]
+    #[should_panic(expected = "I2S already initialized")]
+    fn test_init_twice() {
+        let config = i2s::Config {
+            sample_rate: 16_000,
+            frame: i2s::Frame::Stereo,
+            direction: i2s::Direction::Transmit,
+        };
+        setup(&config);
+        setup(&config);
+    }
+
+    #[test]
+    fn test_start_and_poll() {
+        static mut BUF: [i16; 8] = [0; 8];
+
+        let config = i2s::Config {
+            sample_rate: 48_000,
+            frame: i2s::Frame::Stereo,
+            direction: i2s::Direction::Transmit,
+        };
+        let mut i2s = setup(&config);
+        let spi = cpu::SPI2;
+
+        #[allow(static_mut_refs)]
+        i2s.start(unsafe { &mut BUF }, None);
+
+        // SPI2 TX is on DMA1 stream 4
+        let dma = cpu::DMA1;
+        assert_eq!(hal::read!(dma.s4cr[CIRC;1]), 1);
+        assert_eq!(hal::read!(dma.s4cr[DIR;2]), 0b01);
+        assert_eq!(hal::read!(dma.s4cr[MSIZE;2]), 0b01);
+        assert_eq!(hal::read!(dma.s4cr[EN;1]), 1);
+        assert_eq!(hal::read!(dma.s4ndtr), 8);
+        let dr_addr = unsafe { ptr::addr_of_mut!((*spi).dr) } as u32;
+        assert_eq!(hal::read!(dma.s4par), dr_addr);
+        assert_eq!(hal::read!(spi.cr2[TXDMAEN;1]), 1);
+        assert_eq!(hal::read!(spi.i2scfgr[I2SE;1]), 1);
+
+        assert!(i2s.poll().is_none());
+
+        // DMA finished the first half
+        hal::write!(dma.hisr[HTIF4;1], 1);
+        let (half, first) = i2s.poll().unwrap();
+        assert_eq!(half, i2s::Half::First);
+        assert_eq!(hal::read!(dma.hifcr[CHTIF4;1]), 1);
+        assert_eq!(first.len(), 4);
+        first.fill(7);
+
+        // then the second half
+        hal::write!(dma.hisr[HTIF4;1], 0);
+        hal::write!(dma.hisr[TCIF4;1], 1);
+        let (half, second) = i2s.poll().unwrap();
+        assert_eq!(half, i2s::Half::Second);
+        assert_eq!(second, &[0, 0, 0, 0]);
+
+        #[allow(static_mut_refs)]
+        let buf = unsafe { &BUF };
+        assert_eq!(buf, &[7, 7, 7, 7, 0, 0, 0, 0]);
+
+        i2s.stop();
+        assert_eq!(hal::read!(dma.s4cr[EN;1]), 0);
+        assert_eq!(hal::read!(spi.i2scfgr[I2SE;1]), 0);
+        assert!(i2s.poll().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "DMA stream already claimed")]
+    fn test_stop_without_start() {
+        let config = i2s::Config {
+            sample_rate: 16_000,
+            frame: i2s::Frame::Stereo,
+            direction: i2s::Direction::Transmit,
+        };
+        let i2s = setup(&config);
+
+        // another driver has the stream, stop must not give it back
+        let _other = hal::dma::Stream::claim(cpu::DMA1, 4);
+        i2s.stop();
+        hal::dma::Stream::claim(cpu::DMA1, 4);
+    }
+}
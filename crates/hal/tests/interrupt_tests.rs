@@ -0,0 +1,98 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::interrupt::{self, Irq};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nvic_layout() {
+        let nvic = cpu::NVIC;
+        unsafe {
+            assert_eq!(ptr::addr_of!((*nvic).icer) as usize, 0xE000_E180);
+            assert_eq!(ptr::addr_of!((*nvic).ispr) as usize, 0xE000_E200);
+            assert_eq!(ptr::addr_of!((*nvic).icpr) as usize, 0xE000_E280);
+            assert_eq!(ptr::addr_of!((*nvic).iabr) as usize, 0xE000_E300);
+            assert_eq!(ptr::addr_of!((*nvic).ipr) as usize, 0xE000_E400);
+            assert_eq!(ptr::addr_of!((*nvic).stir) as usize, 0xE000_EF00);
+        }
+    }
+
+    #[test]
+    fn test_enable() {
+        hal::init(16_000_000);
+
+        interrupt::enable(Irq::Tim2);
+        interrupt::enable(Irq::Usart6);
+        assert!(interrupt::is_enabled(Irq::Tim2));
+        assert!(interrupt::is_enabled(Irq::Usart6));
+        assert!(!interrupt::is_enabled(Irq::Usart1));
+
+        // TIM2 is IRQ 28, USART6 is 71
+        let nvic = cpu::NVIC;
+        let iser0 = cpu::read_reg(unsafe { ptr::addr_of_mut!((*nvic).iser[0]) });
+        assert_eq!(iser0, 1 << 28);
+
+        interrupt::disable(Irq::Tim2);
+        assert!(!interrupt::is_enabled(Irq::Tim2));
+        assert!(interrupt::is_enabled(Irq::Usart6));
+
+        interrupt::pend(Irq::Exti0);
+        assert!(interrupt::is_pending(Irq::Exti0));
+        interrupt::unpend(Irq::Exti0);
+        assert!(!interrupt::is_pending(Irq::Exti0));
+    }
+
+    #[test]
+    fn test_priority() {
+        hal::init(16_000_000);
+
+        // USART1 to USART3 are IRQ 37 to 39, all in the same IPR word as IRQ 36
+        interrupt::set_priority(Irq::Usart1, 2);
+        interrupt::set_priority(Irq::Usart2, 15);
+        interrupt::set_priority(Irq::Usart3, 7);
+        assert_eq!(interrupt::priority(Irq::Usart1), 2);
+        assert_eq!(interrupt::priority(Irq::Usart2), 15);
+        assert_eq!(interrupt::priority(Irq::Usart3), 7);
+        assert_eq!(interrupt::priority(Irq::Spi2), 0);
+
+        let nvic = cpu::NVIC;
+        let ipr9 = cpu::read_reg(unsafe { ptr::addr_of_mut!((*nvic).ipr[9]) });
+        assert_eq!(ipr9, 0x70F0_2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Interrupt priority too large")]
+    fn test_priority_too_large() {
+        hal::init(16_000_000);
+        interrupt::set_priority(Irq::Usart1, 16);
+    }
+
+    #[test]
+    fn test_free() {
+        assert!(!interrupt::is_masked());
+
+        let v = interrupt::free(|_cs| {
+            assert!(interrupt::is_masked());
+
+            // nested sections keep interrupts masked until the outer one ends
+            interrupt::free(|_cs| {});
+            assert!(interrupt::is_masked());
+            42
+        });
+        assert_eq!(v, 42);
+        assert!(!interrupt::is_masked());
+
+        interrupt::mask_below(4, || {
+            assert!(interrupt::is_masked());
+        });
+        assert!(!interrupt::is_masked());
+    }
+}
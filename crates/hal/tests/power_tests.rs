@@ -0,0 +1,56 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::{EXTI, PWR, RCC, RTC, SCB};
+use hal::power;
+use hal::timer;
+use hal::timer::{Duration, Instant};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_until() {
+        hal::init(16_000_000);
+        timer::init2();
+
+        let deadline = Instant::now() + Duration::from_millis(2);
+        power::sleep_until(deadline);
+        assert!(Instant::now() >= deadline);
+
+        // a deadline that has passed returns straight away
+        power::sleep_until(Instant::from_micros(0));
+    }
+
+    #[test]
+    fn test_stop_until() {
+        hal::init(16_000_000);
+        timer::init2();
+
+        let start = Instant::now();
+        power::stop_until(start + Duration::from_millis(3));
+        assert!(start.elapsed() >= Duration::from_millis(2));
+
+        // the RTC runs from the LSI with a 2 kHz wakeup timer
+        assert_eq!(hal::read!(RCC.bdcr[RTCEN;1]), 1);
+        assert_eq!(hal::read!(RCC.bdcr[RTCSEL0;2]), 0b10);
+        let ticks = hal::read!(RTC.wutr[WUT;16]) + 1;
+        assert!(ticks > 1 && ticks <= 6);
+
+        // STOP with the low power regulator, and back to run mode after
+        assert_eq!(hal::read!(PWR.cr[LPDS;1]), 1);
+        assert_eq!(hal::read!(PWR.cr[PDDS;1]), 0);
+        assert_eq!(hal::read!(SCB.scr[SLEEPDEEP;1]), 0);
+
+        // the wakeup timer is off again
+        assert_eq!(hal::read!(RTC.cr[WUTE;1]), 0);
+        assert_eq!(hal::read!(EXTI.imr[MR22;1]), 0);
+        assert_eq!(hal::read!(EXTI.rtsr[TR22;1]), 1);
+    }
+}
@@ -0,0 +1,54 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::*;
+use hal::reset;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_cause() {
+        hal::init(16_000_000);
+        assert_eq!(reset::read_cause(), reset::ResetCause::Unknown);
+        assert_eq!(hal::read!(RCC.csr[RMVF;1]), 1);
+
+        // a power on reset also sets the brown out and pin flags
+        hal::write!(RCC.csr, 0);
+        hal::write!(RCC.csr[PORRSTF;1], 1);
+        hal::write!(RCC.csr[BORRSTF;1], 1);
+        hal::write!(RCC.csr[PADRSTF;1], 1);
+        assert_eq!(reset::read_cause(), reset::ResetCause::PowerOn);
+
+        hal::write!(RCC.csr, 0);
+        hal::write!(RCC.csr[WDGRSTF;1], 1);
+        hal::write!(RCC.csr[PADRSTF;1], 1);
+        assert_eq!(reset::read_cause(), reset::ResetCause::IndependentWatchdog);
+        assert_eq!(
+            reset::ResetCause::IndependentWatchdog.as_str(),
+            "independent watchdog"
+        );
+
+        hal::write!(RCC.csr, 0);
+        hal::write!(RCC.csr[SFTRSTF;1], 1);
+        hal::write!(RCC.csr[PADRSTF;1], 1);
+        assert_eq!(reset::read_cause(), reset::ResetCause::Software);
+
+        hal::write!(RCC.csr, 0);
+        hal::write!(RCC.csr[PADRSTF;1], 1);
+        assert_eq!(reset::read_cause(), reset::ResetCause::Pin);
+    }
+
+    #[test]
+    #[should_panic(expected = "software reset")]
+    fn test_software_reset() {
+        hal::init(16_000_000);
+        reset::software_reset();
+    }
+}
@@ -0,0 +1,45 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::*;
+use hal::rng;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        hal::init(16_000_000);
+        rng::init();
+
+        assert_eq!(hal::read!(RCC.ahb2enr[RNGEN;1]), 1);
+        assert_eq!(hal::read!(RNG.cr[RNGEN;1]), 1);
+
+        // nothing ready
+        assert_eq!(rng::read(), Err(rng::Error::Timeout));
+
+        hal::write!(RNG.sr[DRDY;1], 1);
+        hal::write!(RNG.dr, 0x1234_5678);
+        assert_eq!(rng::read(), Ok(0x1234_5678));
+
+        // continuous test catches the same number twice
+        assert_eq!(rng::read(), Err(rng::Error::Repeated));
+        hal::write!(RNG.dr, 0x8765_4321);
+        assert_eq!(rng::read(), Ok(0x8765_4321));
+
+        // seed error clears the flag and restarts
+        hal::write!(RNG.sr[SEIS;1], 1);
+        assert_eq!(rng::read(), Err(rng::Error::Seed));
+        assert_eq!(hal::read!(RNG.sr[SEIS;1]), 0);
+        assert_eq!(hal::read!(RNG.cr[RNGEN;1]), 1);
+
+        hal::write!(RNG.sr[CEIS;1], 1);
+        assert_eq!(rng::read(), Err(rng::Error::Clock));
+    }
+}
@@ -0,0 +1,183 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use hal::cpu;
+use hal::cpu::{EXTI, PWR, RCC, RTC};
+use hal::interrupt::{self, Irq};
+use hal::rtc::{self, DateTime, Source};
+
+hal::local! {
+    static ALARM_RUNS: AtomicU32 = AtomicU32::new(0);
+}
+
+fn count_alarm() {
+    hal::local!(ref ALARM_RUNS).fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_time() {
+        let time = DateTime::new(2024, 2, 29, 23, 59, 30);
+        assert!(time.is_valid());
+        assert_eq!(time.weekday(), 4); // Thursday
+        assert_eq!(time.to_unix_secs(), 1_709_251_170);
+
+        assert_eq!(DateTime::new(2000, 1, 1, 0, 0, 0).weekday(), 6);
+        assert_eq!(DateTime::new(2023, 10, 1, 0, 0, 0).weekday(), 7);
+
+        assert!(!DateTime::new(2023, 2, 29, 0, 0, 0).is_valid());
+        assert!(!DateTime::new(1999, 12, 31, 0, 0, 0).is_valid());
+        assert!(!DateTime::new(2024, 4, 31, 0, 0, 0).is_valid());
+        assert!(!DateTime::new(2024, 1, 1, 24, 0, 0).is_valid());
+    }
+
+    #[test]
+    fn test_init() {
+        hal::init(16_000_000);
+
+        assert_eq!(rtc::init(Source::Lse), Source::Lse);
+        assert_eq!(hal::read!(PWR.cr[DBP;1]), 1);
+        assert_eq!(hal::read!(RCC.bdcr[LSEON;1]), 1);
+        assert_eq!(hal::read!(RCC.bdcr[RTCSEL0;2]), 0b01);
+        assert_eq!(hal::read!(RCC.bdcr[RTCEN;1]), 1);
+        assert_eq!(rtc::clock_freq(), 32_768);
+
+        // 32768 Hz / 128 / 256 is 1 Hz
+        assert_eq!(hal::read!(RTC.prer[PREDIV_A;7]), 127);
+        assert_eq!(hal::read!(RTC.prer[PREDIV_S;15]), 255);
+        assert_eq!(hal::read!(RTC.wpr), 0xFF);
+    }
+
+    #[test]
+    fn test_lsi() {
+        hal::init(16_000_000);
+
+        // the RTC starts from the LSI when something needs it before init
+        assert_eq!(rtc::clock_freq(), 32_000);
+        assert_eq!(hal::read!(RCC.csr[LSION;1]), 1);
+        assert_eq!(hal::read!(RCC.bdcr[RTCSEL0;2]), 0b10);
+        assert_eq!(hal::read!(RTC.prer[PREDIV_S;15]), 249);
+
+        // changing to the LSE resets the backup domain
+        rtc::init(Source::Lse);
+        assert_eq!(hal::read!(RCC.bdcr[RTCSEL0;2]), 0b01);
+        assert_eq!(hal::read!(RCC.bdcr[BDRST;1]), 0);
+    }
+
+    #[test]
+    fn test_set() {
+        hal::init(16_000_000);
+        rtc::init(Source::Lse);
+        assert!(!rtc::is_set());
+
+        let time = DateTime::new(2024, 6, 1, 12, 30, 45);
+        rtc::set(&time);
+        assert!(rtc::is_set());
+        assert_eq!(hal::read!(RTC.tr), 0x12_30_45);
+        assert_eq!(hal::read!(RTC.dr), 0x24_C6_01); // Saturday is 6
+        assert_eq!(hal::read!(RTC.isr[INIT;1]), 0);
+
+        assert_eq!(rtc::now(), time);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid RTC date time")]
+    fn test_set_invalid() {
+        hal::init(16_000_000);
+        rtc::set(&DateTime::new(2024, 13, 1, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_alarm() {
+        hal::init(16_000_000);
+        rtc::init(Source::Lse);
+        hal::local!(ref ALARM_RUNS).store(0, Ordering::Relaxed);
+
+        rtc::set_alarm(&DateTime::new(2024, 6, 2, 7, 0, 0), count_alarm);
+        assert_eq!(hal::read!(RTC.alrmar), 0x02_07_00_00);
+        assert_eq!(hal::read!(RTC.cr[ALRAE;1]), 1);
+        assert_eq!(hal::read!(RTC.cr[ALRAIE;1]), 1);
+        assert_eq!(hal::read!(EXTI.imr[MR17;1]), 1);
+        assert!(interrupt::is_enabled(Irq::RtcAlarm));
+
+        // no flag, no callback
+        rtc::handle_alarm_irq();
+        assert_eq!(hal::local!(ref ALARM_RUNS).load(Ordering::Relaxed), 0);
+
+        hal::write!(RTC.isr[ALRAF;1], 1);
+        rtc::handle_alarm_irq();
+        assert_eq!(hal::local!(ref ALARM_RUNS).load(Ordering::Relaxed), 1);
+        assert_eq!(hal::read!(RTC.isr[ALRAF;1]), 0);
+
+        rtc::cancel_alarm();
+        assert_eq!(hal::read!(RTC.cr[ALRAE;1]), 0);
+        assert_eq!(hal::read!(EXTI.imr[MR17;1]), 0);
+    }
+
+    #[test]
+    fn test_time_of_day() {
+        hal::init(16_000_000);
+        rtc::init(Source::Lse);
+        rtc::set(&DateTime::new(2024, 6, 1, 12, 30, 15));
+
+        // the prescaler counts down from 255, so at 63 it is 3/4 of the way through the second
+        assert_eq!(hal::read!(RTC.prer[PREDIV_S;15]), 255);
+        hal::write!(RTC.ssr, 63);
+        let us = ((12 * 60 + 30) * 60 + 15) * 1_000_000 + 750_000;
+        assert_eq!(rtc::time_of_day_us(), us);
+    }
+
+    #[test]
+    fn test_wakeup() {
+        hal::init(16_000_000);
+
+        rtc::start_wakeup(100);
+        assert_eq!(hal::read!(RTC.wutr[WUT;16]), 99);
+        assert_eq!(hal::read!(RTC.cr[WUTE;1]), 1);
+        assert_eq!(hal::read!(EXTI.imr[MR22;1]), 1);
+
+        hal::write!(RTC.isr[WUTF;1], 1);
+        rtc::handle_wkup_irq();
+        assert_eq!(hal::read!(RTC.isr[WUTF;1]), 0);
+        assert_eq!(hal::read!(EXTI.pr), 1 << 22);
+
+        rtc::stop_wakeup();
+        assert_eq!(hal::read!(RTC.cr[WUTE;1]), 0);
+        assert_eq!(hal::read!(EXTI.imr[MR22;1]), 0);
+    }
+
+    #[test]
+    fn test_backup() {
+        hal::init(16_000_000);
+
+        rtc::write_backup(0, 0x1234_5678);
+        rtc::write_backup(rtc::NUM_BACKUP - 1, 42);
+        assert_eq!(rtc::read_backup(0), 0x1234_5678);
+        assert_eq!(rtc::read_backup(rtc::NUM_BACKUP - 1), 42);
+        assert_eq!(hal::read!(RTC.bkp19r), 42);
+
+        rtc::enable_backup_sram(true);
+        assert_eq!(hal::read!(RCC.ahb1enr[BKPSRAMEN;1]), 1);
+        assert_eq!(hal::read!(PWR.csr[BRE;1]), 1);
+
+        rtc::write_backup_sram(0, 7);
+        rtc::write_backup_sram(rtc::BACKUP_SRAM_WORDS - 1, 8);
+        assert_eq!(rtc::read_backup_sram(0), 7);
+        assert_eq!(rtc::read_backup_sram(rtc::BACKUP_SRAM_WORDS - 1), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "Backup register out of range")]
+    fn test_backup_range() {
+        hal::init(16_000_000);
+        rtc::write_backup(rtc::NUM_BACKUP, 0);
+    }
+}
@@ -0,0 +1,146 @@
+#![no_std]
+#![allow(non_snake_case)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::sim::{self, Access, Expect};
+
+// a register definition in the form svd2rusty generates
+mod TEST {
+    pub mod cr {
+        pub const EN: u8 = 0;
+        pub const MODE: u8 = 4;
+        pub mod width {
+            pub const EN: u8 = 1;
+            pub const MODE: u8 = 3;
+        }
+    }
+}
+
+#[repr(C)]
+struct TestReg {
+    cr: u32,
+    dr: u32,
+}
+
+const TEST: *mut TestReg = 0x5000_0000 as *mut TestReg;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        hal::init(16_000_000);
+        let cr = unsafe { ptr::addr_of_mut!((*TEST).cr) };
+        let dr = unsafe { ptr::addr_of_mut!((*TEST).dr) };
+
+        let trace = sim::trace(|| {
+            hal::write!(TEST.cr, 0x10);
+            hal::write!(TEST.cr.EN, 1);
+            let _ = hal::read!(TEST.dr);
+        });
+        assert_eq!(
+            trace.0,
+            [
+                Access::Write {
+                    addr: cr,
+                    value: 0x10
+                },
+                Access::Update {
+                    addr: cr,
+                    mask: 0x1,
+                    value: 0x1
+                },
+                Access::Read { addr: dr, value: 0 },
+            ]
+        );
+        assert_eq!(trace.accesses_to(dr), [Access::Read { addr: dr, value: 0 }]);
+
+        // nothing is recorded once the trace has finished
+        hal::write!(TEST.cr, 0);
+        assert_eq!(sim::trace(|| {}).0, []);
+    }
+
+    #[test]
+    fn test_expect() {
+        hal::init(16_000_000);
+        let cr = unsafe { ptr::addr_of_mut!((*TEST).cr) };
+
+        // all the forms give the same bits
+        let mode = Expect {
+            addr: cr,
+            mask: 0x70,
+            value: 0x20,
+        };
+        assert_eq!(hal::expect!(TEST.cr[MODE;3], 0b010), mode);
+        assert_eq!(hal::expect!(TEST.cr[4;3], 0b010), mode);
+        assert_eq!(hal::expect!(TEST.cr.MODE, 0b010), mode);
+        assert_eq!(hal::expect!(TEST.cr, 0x21), Expect::register(cr, 0x21));
+
+        let trace = sim::trace(|| {
+            hal::write!(TEST.cr, 0x21);
+            hal::write!(TEST.cr.MODE, 0b100);
+        });
+
+        // a write of the whole register sets every field in it
+        assert!(trace.was_written(&hal::expect!(TEST.cr.EN, 1)));
+        assert!(trace.was_written(&hal::expect!(TEST.cr.MODE, 0b010)));
+        assert!(trace.was_written(&hal::expect!(TEST.cr.MODE, 0b100)));
+        assert!(!trace.was_written(&hal::expect!(TEST.cr.MODE, 0b111)));
+
+        // an update only sets the fields in its mask
+        let trace = sim::trace(|| hal::write!(TEST.cr.MODE, 0b100));
+        assert!(!trace.was_written(&hal::expect!(TEST.cr.EN, 1)));
+        assert!(!trace.was_written(&hal::expect!(TEST.cr, 0x41)));
+        trace.assert_written(&hal::expect!(TEST.cr.MODE, 0b100));
+    }
+
+    #[test]
+    fn test_sequence() {
+        hal::init(16_000_000);
+
+        let trace = sim::trace(|| {
+            hal::write!(TEST.cr.EN, 0);
+            hal::write!(TEST.dr, 0xAA);
+            hal::write!(TEST.cr.MODE, 0b011);
+            hal::write!(TEST.cr.EN, 1);
+        });
+
+        trace.assert_sequence(&[
+            hal::expect!(TEST.cr.EN, 0),
+            hal::expect!(TEST.cr.MODE, 0b011),
+            hal::expect!(TEST.cr.EN, 1),
+        ]);
+        trace.assert_sequence(&[hal::expect!(TEST.dr, 0xAA), hal::expect!(TEST.cr.EN, 1)]);
+        assert_eq!(trace.find(&hal::expect!(TEST.cr.EN, 1)), Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "step 1 of the sequence")]
+    fn test_sequence_order() {
+        hal::init(16_000_000);
+
+        let trace = sim::trace(|| {
+            hal::write!(TEST.cr.EN, 1);
+            hal::write!(TEST.cr.MODE, 0b011);
+        });
+        trace.assert_sequence(&[
+            hal::expect!(TEST.cr.MODE, 0b011),
+            hal::expect!(TEST.cr.EN, 1),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "was not written")]
+    fn test_assert_written() {
+        hal::init(16_000_000);
+
+        let trace = sim::trace(|| hal::write!(TEST.cr.EN, 0));
+        trace.assert_written(&hal::expect!(TEST.cr.EN, 1));
+    }
+}
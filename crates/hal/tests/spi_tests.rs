@@ -0,0 +1,204 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::gpio;
+use hal::sim;
+use hal::spi;
+
+// lets `dma.reg[FIELD;w]` and `spi.reg[FIELD;w]` find the field offsets
+use hal::cpu::DMA::{self as dma};
+use hal::cpu::SPI1::{self as spi_reg};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_wide_dma() {
+        hal::init(16_000_000);
+        let disp = spi::Spi(cpu::SPI1);
+        disp.init(
+            &spi::Config::new(10_500_000, spi::Mode::Mode0),
+            gpio::Pin(cpu::GPIOA, 5),
+            gpio::Pin(cpu::GPIOA, 7),
+            None,
+        );
+
+        static DATA: [u16; 6] = [0xF800; 6];
+        unsafe { disp.write_wide(&DATA) };
+
+        // SPI1 TX is on DMA2 stream 3 channel 3
+        let dma = cpu::DMA2;
+        let spi_reg = cpu::SPI1;
+        assert_eq!(hal::read!(dma.s3cr[CHSEL;3]), 3);
+        assert_eq!(hal::read!(dma.s3cr[DIR;2]), 0b01);
+        assert_eq!(hal::read!(dma.s3cr[MSIZE;2]), 0b01);
+        assert_eq!(hal::read!(dma.s3cr[PSIZE;2]), 0b01);
+        assert_eq!(hal::read!(dma.s3cr[EN;1]), 1);
+        assert_eq!(hal::read!(dma.s3ndtr), 6);
+        assert_eq!(hal::read!(dma.s3m0ar), DATA.as_ptr() as u32);
+        let dr_addr = unsafe { ptr::addr_of_mut!((*spi_reg).dr) } as u32;
+        assert_eq!(hal::read!(dma.s3par), dr_addr);
+        assert_eq!(hal::read!(spi_reg.cr2[TXDMAEN;1]), 1);
+        assert_eq!(hal::read!(spi_reg.cr1[DFF;1]), 1);
+
+        // the simulator has no DMA so the transfer is done straight away
+        assert!(!disp.busy());
+        assert_eq!(hal::read!(dma.s3cr[EN;1]), 0);
+        assert_eq!(hal::read!(spi_reg.cr2[TXDMAEN;1]), 0);
+        assert_eq!(hal::read!(spi_reg.cr1[DFF;1]), 0);
+
+        // a second transfer can claim the stream again
+        unsafe { disp.write_wide(&DATA) };
+        disp.wait();
+        assert!(!disp.busy());
+    }
+
+    #[test]
+    fn test_init() {
+        hal::init(16_000_000);
+        let spi_reg = cpu::SPI1;
+
+        // 84 MHz / 8 is over 10 MHz so it is 84 MHz / 16
+        let disp = spi::Spi(cpu::SPI1);
+        let config = spi::Config::new(10_000_000, spi::Mode::Mode0);
+        disp.init(
+            &config,
+            gpio::Pin(cpu::GPIOA, 5),
+            gpio::Pin(cpu::GPIOA, 7),
+            None,
+        );
+        assert_eq!(hal::read!(spi_reg.cr1[BR;3]), 0b011);
+        assert_eq!(disp.freq(), 5_250_000);
+        assert_eq!(hal::read!(spi_reg.cr1[BIDIMODE;1]), 1);
+        assert_eq!(hal::read!(spi_reg.cr1[BIDIOE;1]), 1);
+        assert_eq!(hal::read!(spi_reg.cr1[MSTR;1]), 1);
+        assert_eq!(hal::read!(spi_reg.cr1[SPE;1]), 1);
+
+        // SPI3 is on the 42 MHz bus and uses AF6
+        let flash = spi::Spi(cpu::SPI3);
+        let spi_reg = cpu::SPI3;
+        let config = spi::Config::new(21_000_000, spi::Mode::Mode3);
+        flash.init(
+            &config,
+            gpio::Pin(cpu::GPIOC, 10),
+            gpio::Pin(cpu::GPIOC, 12),
+            Some(gpio::Pin(cpu::GPIOC, 11)),
+        );
+        assert_eq!(hal::read!(spi_reg.cr1[BR;3]), 0b000);
+        assert_eq!(flash.freq(), 21_000_000);
+        assert_eq!(hal::read!(spi_reg.cr1[BIDIMODE;1]), 0);
+        assert_eq!(hal::read!(spi_reg.cr1[CPOL;1]), 1);
+        assert_eq!(hal::read!(spi_reg.cr1[CPHA;1]), 1);
+        let gpio_c = cpu::GPIOC;
+        assert_eq!(hal::read!(gpio_c.afrh), 0x0006_6600);
+    }
+
+    #[test]
+    fn test_init_sequence() {
+        hal::init(16_000_000);
+        let spi_reg = cpu::SPI1;
+        let disp = spi::Spi(cpu::SPI1);
+        let config = spi::Config::new(10_000_000, spi::Mode::Mode3);
+
+        let trace = sim::trace(|| {
+            disp.init(
+                &config,
+                gpio::Pin(cpu::GPIOA, 5),
+                gpio::Pin(cpu::GPIOA, 7),
+                None,
+            )
+        });
+
+        // the SPI is only enabled once it is set up
+        trace.assert_sequence(&[
+            hal::expect!(spi_reg.cr1[SPE;1], 0),
+            hal::expect!(spi_reg.cr1[BR;3], 0b011),
+            hal::expect!(spi_reg.cr1[MSTR;1], 1),
+            hal::expect!(spi_reg.cr1[CPOL;1], 1),
+            hal::expect!(spi_reg.cr1[CPHA;1], 1),
+            hal::expect!(spi_reg.cr1[SPE;1], 1),
+        ]);
+        let enable = trace.find(&hal::expect!(spi_reg.cr1[SPE;1], 1));
+        assert_eq!(enable, Some(trace.0.len() - 1));
+    }
+
+    #[test]
+    fn test_display_command() {
+        hal::init(16_000_000);
+        let spi_reg = cpu::SPI1;
+        let disp = spi::Spi(cpu::SPI1);
+        disp.init(
+            &spi::Config::new(10_500_000, spi::Mode::Mode0),
+            gpio::Pin(cpu::GPIOA, 5),
+            gpio::Pin(cpu::GPIOA, 7),
+            None,
+        );
+        let dc = gpio::Pin(cpu::GPIOA, 6);
+        dc.output();
+
+        // an ILI9341 column address set, the command with DC low then its parameters with DC high
+        let trace = sim::trace(|| {
+            dc.low();
+            disp.write(&[0x2A]);
+            dc.high();
+            disp.write(&[0x00, 0x00, 0x00, 0xEF]);
+        });
+
+        let gpio_a = cpu::GPIOA;
+        trace.assert_sequence(&[
+            hal::expect!(gpio_a.bsrr, 1 << (6 + 16)),
+            hal::expect!(spi_reg.dr, 0x2A),
+            hal::expect!(gpio_a.bsrr, 1 << 6),
+            hal::expect!(spi_reg.dr, 0x00),
+            hal::expect!(spi_reg.dr, 0x00),
+            hal::expect!(spi_reg.dr, 0x00),
+            hal::expect!(spi_reg.dr, 0xEF),
+        ]);
+    }
+
+    #[test]
+    fn test_transfer() {
+        hal::init(16_000_000);
+        let flash = spi::Spi(cpu::SPI2);
+        let config = spi::Config::new(1_000_000, spi::Mode::Mode0);
+        flash.init(
+            &config,
+            gpio::Pin(cpu::GPIOB, 13),
+            gpio::Pin(cpu::GPIOB, 15),
+            Some(gpio::Pin(cpu::GPIOB, 14)),
+        );
+        assert_eq!(flash.freq(), 656_250);
+
+        // the simulator reads back the last byte written to DR
+        let spi_reg = cpu::SPI2;
+        let mut buf = [0x9F, 1, 2];
+        flash.transfer(&mut buf);
+        assert_eq!(buf, [0x9F, 1, 2]);
+        assert_eq!(hal::read!(spi_reg.dr), 2);
+
+        let mut buf = [0; 2];
+        flash.read(&mut buf);
+        assert_eq!(buf, [0xFF, 0xFF]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_transfer_without_miso() {
+        hal::init(16_000_000);
+        let disp = spi::Spi(cpu::SPI1);
+        let config = spi::Config::new(10_000_000, spi::Mode::Mode0);
+        disp.init(
+            &config,
+            gpio::Pin(cpu::GPIOA, 5),
+            gpio::Pin(cpu::GPIOA, 7),
+            None,
+        );
+        disp.transfer(&mut [0; 2]);
+    }
+}
@@ -3,7 +3,15 @@
 #[cfg(feature = "std")]
 extern crate std;
 
-use hal::timer::MicroSeconds;
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::TIM2;
+use hal::timer;
+use hal::timer::{Duration, Instant, MicroSeconds};
+
+// lets `TIM2.reg[FIELD;w]` find the field offsets
+use hal::timer::TIM2;
 
 #[cfg(test)]
 mod tests {
@@ -23,6 +31,89 @@ mod tests {
         let time1 = MicroSeconds::new(5000);
         let time2 = MicroSeconds::new(max - 5000);
         let result = time1.sub(time2);
-        assert_eq!(result, MicroSeconds::new(10001));
+        assert_eq!(result, MicroSeconds::new(10000));
+
+        // one tick from the top value of the counter back to 0
+        let time1 = MicroSeconds::new(0);
+        let time2 = MicroSeconds::new(max - 1);
+        assert_eq!(time1.sub(time2), MicroSeconds::new(1));
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(Duration::from_millis(3).as_micros(), 3_000);
+        assert_eq!(Duration::from_secs(2).as_millis(), 2_000);
+        assert_eq!(Duration::from_micros(2_999_999).as_secs(), 2);
+        assert_eq!(Duration::from_secs(u64::MAX), Duration::MAX);
+
+        let d = Duration::from_millis(5) + Duration::from_micros(10);
+        assert_eq!(d.as_micros(), 5_010);
+        assert_eq!(d - Duration::from_millis(5), Duration::from_micros(10));
+
+        assert_eq!(Duration::MAX.checked_add(Duration::from_micros(1)), None);
+        assert_eq!(Duration::ZERO.checked_sub(Duration::from_micros(1)), None);
+        assert_eq!(
+            Duration::ZERO.saturating_sub(Duration::from_micros(1)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            Duration::from_micros(7).checked_mul(3),
+            Some(Duration::from_micros(21))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when subtracting durations")]
+    fn test_duration_underflow() {
+        let _ = Duration::from_micros(1) - Duration::from_micros(2);
+    }
+
+    #[test]
+    fn test_instant() {
+        // longer than the 1 hour TIM2 wrap
+        let start = Instant::from_micros(3_599_999_000);
+        let later = start + Duration::from_secs(7_200);
+        assert_eq!(later.as_micros(), 10_799_999_000);
+        assert_eq!(later - start, Duration::from_secs(7_200));
+        assert_eq!(later.duration_since(start), Duration::from_secs(7_200));
+
+        // going backwards is zero, or None when checked
+        assert_eq!(start.duration_since(later), Duration::ZERO);
+        assert_eq!(start.checked_duration_since(later), None);
+        assert_eq!(later - Duration::from_secs(7_200), start);
+        assert_eq!(
+            Instant::from_micros(5).checked_sub(Duration::from_micros(6)),
+            None
+        );
+        assert!(later > start);
+    }
+
+    #[test]
+    fn test_now() {
+        hal::init(16_000_000);
+        timer::init2();
+
+        let start = Instant::now();
+        let mut now = start;
+        while now == start {
+            now = Instant::now();
+        }
+        assert!(now > start);
+        assert!(start.elapsed() > Duration::ZERO);
+        assert!(timer::current_time().as_u64() >= now.as_micros());
+    }
+
+    #[test]
+    fn test_wrap_interrupt() {
+        hal::init(16_000_000);
+        timer::init2();
+
+        // the update flag from loading the prescaler is not counted as a wrap
+        assert_eq!(hal::read!(TIM2.sr[UIF;1]), 0);
+        assert_eq!(hal::read!(TIM2.dier[UIE;1]), 1);
+
+        hal::write!(TIM2.sr[UIF;1], 1);
+        timer::handle_tim2_irq();
+        assert_eq!(hal::read!(TIM2.sr[UIF;1]), 0);
     }
 }
@@ -0,0 +1,245 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::gpio;
+use hal::interrupt::{self, Irq};
+use hal::sim;
+use hal::uart;
+use hal::uart::{Config, Instance, Uart};
+
+// lets `usart.reg[FIELD;w]` find the field offsets in the USART register module
+use hal::cpu::USART::{self as usart};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UART1: Uart = Uart::new(Instance::Usart1);
+
+    fn setup() {
+        hal::init(16_000_000);
+        UART1.init(
+            &Config::new(115_200),
+            gpio::Pin(cpu::GPIOA, 9),
+            gpio::Pin(cpu::GPIOA, 10),
+        );
+    }
+
+    // a byte arrives, with any error flags in `sr`
+    fn receive(c: u8, sr: u32) {
+        let usart = cpu::USART1;
+        sim::uart_receive(usart, c);
+        hal::write!(usart.sr, hal::read!(usart.sr) | sr);
+        uart::handle_irq(Instance::Usart1);
+    }
+
+    #[test]
+    fn test_receive() {
+        setup();
+        let usart = cpu::USART1;
+        assert_eq!(hal::read!(usart.cr1[RXNEIE;1]), 1);
+
+        assert!(UART1.empty());
+        assert_eq!(UART1.rx().read(), None);
+
+        receive(b'h', 0);
+        receive(b'i', 0);
+        assert!(!UART1.empty());
+        assert_eq!(UART1.rx().len(), 2);
+
+        assert_eq!(UART1.rx().read(), Some(b'h'));
+        assert_eq!(UART1.read(), b'i');
+        assert_eq!(UART1.rx().read(), None);
+    }
+
+    #[test]
+    fn test_read_slice() {
+        setup();
+
+        for c in b"hello" {
+            receive(*c, 0);
+        }
+
+        let mut buf = [0u8; 3];
+        assert_eq!(UART1.rx().read_slice(&mut buf), 3);
+        assert_eq!(&buf, b"hel");
+        assert_eq!(UART1.rx().read_slice(&mut buf), 2);
+        assert_eq!(&buf[..2], b"lo");
+        assert_eq!(UART1.rx().read_slice(&mut buf), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        setup();
+
+        // overrun still has a good byte in DR
+        receive(b'a', 1 << usart::sr::ORE);
+        assert_eq!(UART1.rx().overruns(), 1);
+
+        // framing error bytes are thrown away
+        receive(b'b', 1 << usart::sr::FE);
+        assert_eq!(UART1.rx().framing_errors(), 1);
+
+        assert_eq!(UART1.rx().read(), Some(b'a'));
+        assert_eq!(UART1.rx().read(), None);
+    }
+
+    #[test]
+    fn test_full() {
+        setup();
+
+        for i in 0..uart::RX_BUF_SIZE + 3 {
+            receive(i as u8, 0);
+        }
+        assert_eq!(UART1.rx().len(), uart::RX_BUF_SIZE);
+        assert_eq!(UART1.rx().dropped(), 3);
+
+        // oldest bytes are kept
+        assert_eq!(UART1.rx().read(), Some(0));
+        receive(0xAA, 0);
+        assert_eq!(UART1.rx().len(), uart::RX_BUF_SIZE);
+    }
+
+    // the interrupt for the USART being ready for the next byte, returning the last byte sent
+    fn transmit() -> Option<u8> {
+        uart::handle_irq(Instance::Usart1);
+        sim::uart_sent(cpu::USART1).last().copied()
+    }
+
+    #[test]
+    fn test_transmit() {
+        setup();
+        static mut BUF: [u8; 8] = [0; 8];
+        UART1.init_tx(
+            unsafe { &mut *ptr::addr_of_mut!(BUF) },
+            uart::TxPolicy::Block,
+        );
+        assert_eq!(UART1.tx().capacity(), 8);
+
+        let usart = cpu::USART1;
+        for c in b"abc" {
+            UART1.write(*c);
+        }
+        assert_eq!(UART1.tx().len(), 3);
+        assert_eq!(hal::read!(usart.cr1[TXEIE;1]), 1);
+
+        // one byte is sent for each interrupt
+        assert_eq!(transmit(), Some(b'a'));
+        assert_eq!(transmit(), Some(b'b'));
+        assert_eq!(transmit(), Some(b'c'));
+        assert!(UART1.tx().is_empty());
+
+        // the interrupt is turned off once there is nothing to send
+        assert_eq!(transmit(), None);
+        assert_eq!(hal::read!(usart.cr1[TXEIE;1]), 0);
+
+        // a full buffer with the block policy sends bytes to make room
+        for i in 0..10 {
+            UART1.write(i);
+        }
+        assert_eq!(UART1.tx().len(), 8);
+        assert_eq!(UART1.tx().dropped(), 0);
+        assert_eq!(transmit(), Some(2));
+
+        UART1.flush();
+        assert!(UART1.tx().is_empty());
+        assert_eq!(sim::uart_sent(usart), [3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(hal::read!(usart.cr1[TXEIE;1]), 0);
+    }
+
+    #[test]
+    fn test_transmit_drop() {
+        setup();
+        static mut BUF: [u8; 4] = [0; 4];
+        UART1.init_tx(
+            unsafe { &mut *ptr::addr_of_mut!(BUF) },
+            uart::TxPolicy::Drop,
+        );
+
+        for c in b"abcdef" {
+            UART1.write(*c);
+        }
+        assert_eq!(UART1.tx().len(), 4);
+        assert_eq!(UART1.tx().dropped(), 2);
+
+        // oldest bytes are kept
+        assert_eq!(transmit(), Some(b'a'));
+        UART1.flush();
+        assert_eq!(sim::uart_sent(cpu::USART1), b"bcd");
+    }
+
+    #[test]
+    fn test_config() {
+        hal::init(16_000_000);
+        let uart = Uart::new(Instance::Usart2);
+        let mut config = Config::new(115_200);
+        config.parity = uart::Parity::Odd;
+        config.stop_bits = uart::StopBits::Two;
+        config.flow_control = uart::FlowControl::RtsCts {
+            rts_pin: gpio::Pin(cpu::GPIOA, 1),
+            cts_pin: gpio::Pin(cpu::GPIOA, 0),
+        };
+        uart.init(&config, gpio::Pin(cpu::GPIOA, 2), gpio::Pin(cpu::GPIOA, 3));
+
+        // USART2 is on the 42 MHz APB1 bus
+        let usart = cpu::USART2;
+        assert_eq!(hal::read!(usart.brr), 365);
+        assert_eq!(hal::read!(usart.cr1[M;1]), 1);
+        assert_eq!(hal::read!(usart.cr1[PCE;1]), 1);
+        assert_eq!(hal::read!(usart.cr1[PS;1]), 1);
+        assert_eq!(hal::read!(usart.cr2[STOP;2]), 0b10);
+        assert_eq!(hal::read!(usart.cr3[RTSE;1]), 1);
+        assert_eq!(hal::read!(usart.cr3[CTSE;1]), 1);
+        assert_eq!(hal::read!(usart.cr1[UE;1]), 1);
+        assert_eq!(uart.irq_num(), Irq::Usart2);
+        assert!(interrupt::is_enabled(Irq::Usart2));
+
+        // RTS and CTS use the same alternate function as TX and RX
+        let gpio_a = cpu::GPIOA;
+        assert_eq!(hal::read!(gpio_a.afrl), 0x7777);
+
+        // bytes on USART2 do not end up in the USART1 buffer
+        sim::uart_receive(usart, b'x');
+        uart::handle_irq(Instance::Usart2);
+        assert_eq!(uart.rx().read(), Some(b'x'));
+        assert!(UART1.rx().is_empty());
+    }
+
+    #[test]
+    fn test_usart6() {
+        hal::init(16_000_000);
+        let uart = Uart::new(Instance::Usart6);
+        uart.init(
+            &Config::new(9600),
+            gpio::Pin(cpu::GPIOC, 6),
+            gpio::Pin(cpu::GPIOC, 7),
+        );
+
+        // USART6 is on the 84 MHz APB2 bus and uses AF8
+        let usart = cpu::USART6;
+        assert_eq!(hal::read!(usart.brr), 8750);
+        assert_eq!(hal::read!(usart.cr1[M;1]), 0);
+        assert_eq!(hal::read!(usart.cr1[PCE;1]), 0);
+        assert_eq!(hal::read!(usart.cr2[STOP;2]), 0);
+        let gpio_c = cpu::GPIOC;
+        assert_eq!(hal::read!(gpio_c.afrl), 0x8800_0000);
+        assert_eq!(uart.irq_num(), Irq::Usart6);
+        assert_eq!(uart.irq_num().number(), 71);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_baud_rate() {
+        hal::init(16_000_000);
+        Uart::new(Instance::Usart3).init(
+            &Config::new(10_000_000),
+            gpio::Pin(cpu::GPIOB, 10),
+            gpio::Pin(cpu::GPIOB, 11),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::ptr;
+
+use hal::cpu;
+use hal::cpu::*;
+use hal::watch_dog;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_independent() {
+        hal::init(16_000_000);
+
+        // smallest prescaler that fits, 32 kHz LSI / 8 is 4 ticks per ms
+        watch_dog::start_independent(1_000);
+        assert_eq!(hal::read!(IWDG.pr[PR;3]), 1);
+        assert_eq!(hal::read!(IWDG.rlr[RL;12]), 4_000 - 1);
+        assert_eq!(hal::read!(IWDG.kr), 0xAAAA);
+        assert_eq!(hal::read!(DBG.dbgmcu_apb1_fz[DBG_IWDEG_STOP;1]), 1);
+
+        hal::write!(IWDG.kr, 0);
+        watch_dog::alive_independent();
+        assert_eq!(hal::read!(IWDG.kr), 0xAAAA);
+
+        // longest timeout uses the divide by 256 prescaler
+        watch_dog::start_independent(32_000);
+        assert_eq!(hal::read!(IWDG.pr[PR;3]), 6);
+        assert_eq!(hal::read!(IWDG.rlr[RL;12]), 4_000 - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "IWDG timeout too long")]
+    fn test_independent_too_long() {
+        hal::init(16_000_000);
+        watch_dog::start_independent(60_000);
+    }
+}
@@ -5,6 +5,7 @@
 
 extern crate hal;
 
+use hal::uart::{self, Instance, Uart};
 use hal::{cpu, gpio, watch_dog};
 
 mod stack;
@@ -42,9 +43,11 @@ fn my_main() {
 
     hal::init(CLOCK_HSE_FREQ);
 
-    hal::uart::init1(115_200, CONSOLE_TX, CONSOLE_RX);
+    let console = Uart::new(Instance::Usart1);
+    console.init(&uart::Config::new(115_200), CONSOLE_TX, CONSOLE_RX);
 
-    hal::uart::init2(115200, UI_RX, UI_TX);
+    let ui = Uart::new(Instance::Usart2);
+    ui.init(&uart::Config::new(115_200), UI_RX, UI_TX);
 
     hal::watch_dog::init();
 
@@ -80,7 +83,7 @@ fn my_main() {
     {
         let str = "MGMT: Starting\r\n";
         for c in str.bytes() {
-            hal::uart::write1(c);
+            console.write(c);
         }
     }
     // take chips out of reset
@@ -93,7 +96,7 @@ fn my_main() {
     } else {
         let str = "MGMT: in DEBUG mode\r\n";
         for c in str.bytes() {
-            hal::uart::write1(c);
+            console.write(c);
             watch_dog::alive();
         }
     }
@@ -102,13 +105,13 @@ fn my_main() {
     if !w {
         let str = "MGMT: No Watchdog\r\n";
         for c in str.bytes() {
-            hal::uart::write1(c);
+            console.write(c);
             watch_dog::alive();
         }
     } else {
         let str = "MGMT: Watchdog Enabled\r\n";
         for c in str.bytes() {
-            hal::uart::write1(c);
+            console.write(c);
             watch_dog::alive();
         }
     }
@@ -121,22 +124,18 @@ fn my_main() {
     let _ = (stack_usage, stack_current, stack_reserved);
 
     loop {
-        if !hal::uart::empty2() {
-            let c: u8;
-            c = hal::uart::read2();
+        while let Some(c) = ui.rx().read() {
             if c != 0 {
-                hal::uart::write1(c);
+                console.write(c);
             }
         }
-        if !hal::uart::empty1() {
-            let c: u8;
-            c = hal::uart::read1();
+        while let Some(c) = console.rx().read() {
             if c != 0 {
-                hal::uart::write2(c);
+                ui.write(c);
             }
             let local_echo = false;
             if local_echo {
-                hal::uart::write1(c);
+                console.write(c);
             }
         }
         watch_dog::alive();
@@ -195,13 +195,19 @@ pub extern "C" fn Default_Handler6() {
 }
 
 #[no_mangle]
-pub extern "C" fn TIM1_UP_TIM10_IRQHandler() {
-    //hal::timer::handle_tim1_irq();
+pub extern "C" fn USART1_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart1);
 }
 
 #[no_mangle]
-pub extern "C" fn TIM2_IRQHandler() {
-    hal::timer::handle_tim2_irq();
+pub extern "C" fn USART2_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart2);
+}
+
+#[no_mangle]
+pub extern "C" fn USART3_4_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart3);
+    hal::uart::handle_irq(hal::uart::Instance::Usart4);
 }
 
 #[allow(dead_code)]
@@ -218,264 +224,125 @@ pub static Reset_Vector: extern "C" fn() -> ! = Reset_Handler;
 #[cfg(target_arch = "arm")]
 #[link_section = ".vector_table.exceptions"]
 #[no_mangle]
-pub static Exceptions: [IrqVector; 5 + 81] = [
+pub static Exceptions: [IrqVector; 14 + 32] = [
     IrqVector {
         handler: Default_HandlerA,
     }, // NMI
     IrqVector {
         handler: Default_HandlerB,
     }, // hard fault
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
     IrqVector {
         handler: Default_HandlerC,
     }, // SVC
+    IrqVector { not_used: 0 }, // reserved
+    IrqVector { not_used: 0 }, // reserved
     IrqVector {
         handler: Default_HandlerD,
-    }, // Pend  SV
+    }, // Pend SV
     IrqVector {
         handler: Default_HandlerE,
     }, // Sys Timer
-    // TODO - int vectors are wrong
     IrqVector {
         handler: Default_Handler1a,
-    }, // WWDG
+    }, // WWDG - IRQ 0
     IrqVector {
         handler: Default_Handler1b,
-    }, // PVD
-    IrqVector {
-        handler: Default_Handler,
-    }, // RTC_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // FLASH
-    IrqVector {
-        handler: Default_Handler,
-    }, // RCC
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI0
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI1
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI2
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI3
-    IrqVector {
-        handler: Default_Handler,
-    }, // EXTI4
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream0
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream1
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream2
+    }, // PVD_VDDIO2 - IRQ 1
     IrqVector {
         handler: Default_Handler,
-    }, // DMA1_Stream3
+    }, // RTC - IRQ 2
     IrqVector {
         handler: Default_Handler,
-    }, // DMA1_Stream4
+    }, // FLASH - IRQ 3
     IrqVector {
         handler: Default_Handler,
-    }, // DMA1_Stream5
+    }, // RCC_CRS - IRQ 4
     IrqVector {
         handler: Default_Handler,
-    }, // DMA1_Stream6
+    }, // EXTI0_1 - IRQ 5
     IrqVector {
         handler: Default_Handler,
-    }, // ADC
+    }, // EXTI2_3 - IRQ 6
     IrqVector {
         handler: Default_Handler,
-    }, // CAN1_TX
+    }, // EXTI4_15 - IRQ 7
     IrqVector {
         handler: Default_Handler,
-    }, // CAN1_RX0
+    }, // TSC - IRQ 8
     IrqVector {
         handler: Default_Handler,
-    }, // CAN1_RX1
+    }, // DMA1_CH1 - IRQ 9
     IrqVector {
         handler: Default_Handler,
-    }, // CAN1_SCE
+    }, // DMA1_CH2_3 - IRQ 10
     IrqVector {
         handler: Default_Handler,
-    }, // EXTI9_5
-    IrqVector {
-        handler: Default_Handler1c,
-    }, // TIM1_BRK_TIM9
-    IrqVector {
-        handler: TIM1_UP_TIM10_IRQHandler,
-    }, // TIM1_UP_TIM10
-    IrqVector {
-        handler: Default_Handler2,
-    }, // TIM1_TRG_COM_TIM11
-    IrqVector {
-        handler: Default_Handler3,
-    }, // TIM1_CC
-    IrqVector {
-        handler: TIM2_IRQHandler,
-    }, // TIM2
+    }, // DMA1_CH4_5_6_7 - IRQ 11
     IrqVector {
         handler: Default_Handler,
-    }, // TIM3
+    }, // ADC_COMP - IRQ 12
     IrqVector {
         handler: Default_Handler,
-    }, // TIM4
+    }, // TIM1_BRK_UP_TRG_COM - IRQ 13
     IrqVector {
         handler: Default_Handler,
-    }, // I2C1_EV
+    }, // TIM1_CC - IRQ 14
     IrqVector {
         handler: Default_Handler,
-    }, // I2C1_ER
+    }, // TIM2 - IRQ 15
     IrqVector {
         handler: Default_Handler,
-    }, // I2C2_EV
+    }, // TIM3 - IRQ 16
     IrqVector {
         handler: Default_Handler,
-    }, // I2C2_ER
+    }, // TIM6_DAC - IRQ 17
     IrqVector {
         handler: Default_Handler,
-    }, // SPI1
+    }, // TIM7 - IRQ 18
     IrqVector {
         handler: Default_Handler,
-    }, // SPI2
+    }, // TIM14 - IRQ 19
     IrqVector {
         handler: Default_Handler,
-    }, // USART1
+    }, // TIM15 - IRQ 20
     IrqVector {
         handler: Default_Handler,
-    }, // USART2
+    }, // TIM16 - IRQ 21
     IrqVector {
         handler: Default_Handler,
-    }, // USART3
+    }, // TIM17 - IRQ 22
     IrqVector {
         handler: Default_Handler,
-    }, // EXTI15_10
+    }, // I2C1 - IRQ 23
     IrqVector {
         handler: Default_Handler,
-    }, // RTC_Alarm
+    }, // I2C2 - IRQ 24
     IrqVector {
         handler: Default_Handler,
-    }, // OTG_FS_WKUP
+    }, // SPI1 - IRQ 25
     IrqVector {
         handler: Default_Handler,
-    }, // TIM8_BRK_TIM12
+    }, // SPI2 - IRQ 26
     IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_UP_TIM13
+        handler: USART1_IRQHandler,
+    }, // USART1 - IRQ 27
     IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_TRG_COM_TIM14
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM8_CC
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA1_Stream7
-    IrqVector {
-        handler: Default_Handler,
-    }, // FSMC
-    IrqVector {
-        handler: Default_Handler,
-    }, // SDIO
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM5
-    IrqVector {
-        handler: Default_Handler,
-    }, // SPI3
-    IrqVector {
-        handler: Default_Handler,
-    }, // UART4
-    IrqVector {
-        handler: Default_Handler,
-    }, // UART5
-    IrqVector {
-        handler: Default_Handler,
-    }, // TIM6_DAC
+        handler: USART2_IRQHandler,
+    }, // USART2 - IRQ 28
     IrqVector {
-        handler: Default_Handler,
-    }, // TIM7
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream0
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream1
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream2
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream3
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream4
-    IrqVector {
-        handler: Default_Handler,
-    }, // ETH
-    IrqVector {
-        handler: Default_Handler,
-    }, // ETH_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_TX
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_RX0
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_RX1
-    IrqVector {
-        handler: Default_Handler,
-    }, // CAN2_SCE
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_FS
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream5
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream6
-    IrqVector {
-        handler: Default_Handler,
-    }, // DMA2_Stream7
-    IrqVector {
-        handler: Default_Handler,
-    }, // USART6
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C3_EV
-    IrqVector {
-        handler: Default_Handler,
-    }, // I2C3_ER
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_EP1_OUT
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_EP1_IN
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS_WKUP
-    IrqVector {
-        handler: Default_Handler,
-    }, // OTG_HS
-    IrqVector {
-        handler: Default_Handler,
-    }, // DCMI
-    IrqVector {
-        handler: Default_Handler,
-    }, // CRYP
+        handler: USART3_4_IRQHandler,
+    }, // USART3_4 - IRQ 29
     IrqVector {
         handler: Default_Handler,
-    }, // HASH_RNG
+    }, // CEC_CAN - IRQ 30
     IrqVector {
         handler: Default_Handler,
-    }, // FPU
+    }, // USB - IRQ 31
 ];
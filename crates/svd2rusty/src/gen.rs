@@ -0,0 +1,240 @@
+//! # Gen Module
+//!
+//! This module turns a `Device` into the Rust source of `svd_stm32f405.rs` and
+//! `svd_stm32f0x2.rs` in the hal crate.
+//!
+//! For each peripheral with its own registers there is a module of bit offsets, and a
+//! `#[repr(C)]` struct of its registers. Peripherals derived from another share its module and
+//! struct. Each selected peripheral then gets a pointer constant at its base address. For example:
+//!
+//! ```text
+//! pub mod USART {
+//!     pub mod cr1 {
+//!         pub const UE: u8 = 13;
+//!         pub mod width {
+//!             pub const UE: u8 = 1;
+//!         }
+//!     }
+//! }
+//!
+//! #[repr(C)]
+//! pub struct UsartReg {
+//!     pub sr: u32,
+//!     pub dr: u32,
+//! }
+//!
+//! pub const USART1: *mut UsartReg = 0x40011000 as *mut UsartReg;
+//! ```
+//!
+//! The `width` module holds the number of bits in each field, so `cpu::write!(USART1.cr1.UE, 1)`
+//! does not need to be told it.
+//!
+//! The module is named after the group of the peripheral if no other group of registers has the
+//! same group name, otherwise after the peripheral. Names can be changed with `Options::renames`.
+//!
+//! ## Structures
+//!
+//! - `Options`: Which peripherals to include and what to call them.
+//!
+//! ## Functions
+//!
+//! - `generate`: Returns the Rust source for a device.
+//!
+
+use std::fmt::Write;
+
+use crate::svd::{Device, Field, Peripheral};
+
+/// Choices for the generated code.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Names of the peripherals to include, all of them if `None`. The others are listed in
+    /// comments so it is clear they were left out.
+    pub only: Option<Vec<String>>,
+    /// Pairs of a peripheral name and the module name to use for its registers.
+    pub renames: Vec<(String, String)>,
+}
+
+const HEADER: &str = "// DO NOT EDIT. This was generated by svd2rusty
+//! This module provides definitions for various hardware registers and their fields.
+
+#![allow(unused)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+";
+
+const KEYWORDS: [&str; 12] = [
+    "as", "fn", "for", "if", "impl", "in", "loop", "match", "mod", "ref", "type", "use",
+];
+
+// the name of the module for a register, which must not be a keyword
+fn register_name(name: &str) -> String {
+    let name = name.to_lowercase();
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+// the struct name for a module, so `TIM_ADV` gives `TimAdvReg`
+fn struct_name(module: &str) -> String {
+    let mut name = String::new();
+    for part in module.split('_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(chars.map(|c| c.to_ascii_lowercase()));
+        }
+    }
+    name.push_str("Reg");
+    name
+}
+
+// the module name for a peripheral with its own registers
+fn module_name(device: &Device, base: &Peripheral, options: &Options) -> String {
+    if let Some((_, to)) = options.renames.iter().find(|(from, _)| *from == base.name) {
+        return to.clone();
+    }
+
+    if let Some(group) = &base.group {
+        let shared = device
+            .peripherals
+            .iter()
+            .filter(|p| p.derived_from.is_none() && p.group.as_ref() == Some(group))
+            .count()
+            > 1;
+        if !shared {
+            return group.clone();
+        }
+    }
+    base.name.clone()
+}
+
+fn is_selected(peripheral: &Peripheral, options: &Options) -> bool {
+    match &options.only {
+        Some(only) => only.contains(&peripheral.name),
+        None => true,
+    }
+}
+
+// the module of bit offsets and widths for the registers of `base`
+fn write_module(out: &mut String, module: &str, base: &Peripheral) {
+    writeln!(out, "\npub mod {} {{", module).unwrap();
+    for register in &base.registers {
+        writeln!(out, "    pub mod {} {{", register_name(&register.name)).unwrap();
+
+        // some files repeat a field, keep the first
+        let mut fields: Vec<&Field> = Vec::new();
+        for field in &register.fields {
+            if !fields.iter().any(|f| f.name == field.name) {
+                fields.push(field);
+            }
+        }
+
+        for field in &fields {
+            writeln!(
+                out,
+                "        pub const {}: u8 = {};",
+                field.name, field.offset
+            )
+            .unwrap();
+        }
+        if !fields.is_empty() {
+            writeln!(out, "        pub mod width {{").unwrap();
+            for field in &fields {
+                writeln!(
+                    out,
+                    "            pub const {}: u8 = {};",
+                    field.name, field.width
+                )
+                .unwrap();
+            }
+            writeln!(out, "        }}").unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+// the struct of the registers of `base`, with gaps filled by reserved words
+fn write_struct(out: &mut String, module: &str, base: &Peripheral) {
+    let mut registers: Vec<_> = base
+        .registers
+        .iter()
+        .filter(|r| r.offset % 4 == 0)
+        .collect();
+    registers.sort_by_key(|r| r.offset);
+    // alternate views of one register, only the first is in the struct
+    registers.dedup_by_key(|r| r.offset);
+
+    writeln!(out, "\n#[repr(C)]").unwrap();
+    writeln!(out, "pub struct {} {{", struct_name(module)).unwrap();
+    let mut offset = 0;
+    let mut reserved = 0;
+    for register in registers {
+        let gap = (register.offset - offset) / 4;
+        if gap == 1 {
+            writeln!(out, "    reserved{}: u32,", reserved).unwrap();
+            reserved += 1;
+        } else if gap > 1 {
+            writeln!(out, "    reserved{}: [u32; {}],", reserved, gap).unwrap();
+            reserved += 1;
+        }
+        writeln!(out, "    pub {}: u32,", register_name(&register.name)).unwrap();
+        offset = register.offset + 4;
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+/// Returns the Rust source of the register definitions of `device`.
+pub fn generate(device: &Device, options: &Options) -> String {
+    let mut out = String::from(HEADER);
+
+    // the peripherals with their own registers that are needed, in file order
+    let mut bases: Vec<&Peripheral> = Vec::new();
+    for peripheral in &device.peripherals {
+        if !is_selected(peripheral, options) {
+            continue;
+        }
+        let base = device.registers_of(peripheral);
+        if !bases.iter().any(|b| b.name == base.name) {
+            bases.push(base);
+        }
+    }
+    let modules: Vec<String> = bases
+        .iter()
+        .map(|b| module_name(device, b, options))
+        .collect();
+
+    for (base, module) in bases.iter().zip(&modules) {
+        write_module(&mut out, module, base);
+    }
+
+    for (base, module) in bases.iter().zip(&modules) {
+        write_struct(&mut out, module, base);
+    }
+
+    out.push('\n');
+    for peripheral in &device.peripherals {
+        if !is_selected(peripheral, options) {
+            writeln!(out, "// Skipped peripheral: {}", peripheral.name).unwrap();
+            continue;
+        }
+
+        let base = device.registers_of(peripheral);
+        let index = bases.iter().position(|b| b.name == base.name).unwrap();
+        let name = struct_name(&modules[index]);
+        if !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+        writeln!(
+            out,
+            "pub const {}: *mut {} = 0x{:08X} as *mut {};",
+            peripheral.name, name, peripheral.base_address, name
+        )
+        .unwrap();
+    }
+
+    out
+}
@@ -0,0 +1,27 @@
+//! # svd2rusty Crate
+//!
+//! This crate generates the register definitions in the hal crate, `svd_stm32f405.rs` and
+//! `svd_stm32f0x2.rs`, from the System View Description (SVD) files that ST publishes for each
+//! chip. It runs on the host, not the target.
+//!
+//! The generated code has a module of bit offsets and field widths for each peripheral, a struct
+//! of its registers, and a pointer to each peripheral. See `gen` for the layout.
+//!
+//! ## Modules
+//!
+//! - `gen`: Generates the Rust source.
+//! - `svd`: Reads the peripherals, registers and fields from an SVD file.
+//! - `xml`: A small XML reader.
+//!
+//! ## Usage
+//!
+//! ```sh
+//! cargo run -p svd2rusty -- doc/STM32F405.svd --only RCC,GPIOA,USART1 | rustfmt > svd.rs
+//! ```
+//!
+//! See `crates/hal/gen.sh` for the options used for the hal crate.
+//!
+
+pub mod gen;
+pub mod svd;
+pub mod xml;
@@ -0,0 +1,53 @@
+//! Command line for svd2rusty.
+//!
+//! ```sh
+//! svd2rusty FILE.svd [--only NAME,NAME,...] [--rename PERIPHERAL=MODULE]...
+//! ```
+//!
+//! Writes the generated Rust to stdout.
+
+use std::env;
+use std::fs;
+use std::process;
+
+use svd2rusty::gen::{self, Options};
+use svd2rusty::svd;
+
+fn usage() -> ! {
+    eprintln!("usage: svd2rusty FILE.svd [--only NAME,NAME,...] [--rename PERIPHERAL=MODULE]...");
+    process::exit(2);
+}
+
+fn main() {
+    let mut file = None;
+    let mut options = Options::default();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--only" => {
+                let names = args.next().unwrap_or_else(|| usage());
+                options.only = Some(names.split(',').map(|s| s.trim().to_string()).collect());
+            }
+            "--rename" => {
+                let rename = args.next().unwrap_or_else(|| usage());
+                let (from, to) = rename.split_once('=').unwrap_or_else(|| usage());
+                options.renames.push((from.to_string(), to.to_string()));
+            }
+            _ if arg.starts_with('-') || file.is_some() => usage(),
+            _ => file = Some(arg),
+        }
+    }
+    let file = file.unwrap_or_else(|| usage());
+
+    let text = fs::read_to_string(&file).unwrap_or_else(|e| {
+        eprintln!("svd2rusty: can not read {}: {}", file, e);
+        process::exit(1);
+    });
+    let device = svd::parse(&text).unwrap_or_else(|e| {
+        eprintln!("svd2rusty: {}: {}", file, e);
+        process::exit(1);
+    });
+
+    print!("{}", gen::generate(&device, &options));
+}
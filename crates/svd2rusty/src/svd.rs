@@ -0,0 +1,266 @@
+//! # SVD Module
+//!
+//! This module reads the parts of a CMSIS System View Description (SVD) file that are needed to
+//! generate register definitions: the peripherals, their registers, and the fields in each
+//! register.
+//!
+//! Registers with a `dim` are expanded into one register per index. Clusters, enumerated values
+//! and access rights are ignored.
+//!
+//! ## Structures
+//!
+//! - `Device`: The name of the chip and its peripherals.
+//! - `Peripheral`: A peripheral at a base address, possibly with the registers of another.
+//! - `Register`: A register at an offset from the base address.
+//! - `Field`: A range of bits in a register.
+//!
+//! ## Functions
+//!
+//! - `parse`: Reads an SVD file.
+//! - `parse_number`: Reads a number in any of the forms SVD allows.
+//!
+
+use crate::xml::{self, Element};
+
+/// A chip and its peripherals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    /// Name of the chip.
+    pub name: String,
+    /// Peripherals in the order they are in the file.
+    pub peripherals: Vec<Peripheral>,
+}
+
+/// A peripheral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Peripheral {
+    /// Name, such as `USART1`.
+    pub name: String,
+    /// Name of the group of similar peripherals, such as `USART`.
+    pub group: Option<String>,
+    /// Name of the peripheral this one has the same registers as.
+    pub derived_from: Option<String>,
+    /// Address of the first register.
+    pub base_address: u64,
+    /// Registers, empty when the peripheral is derived from another.
+    pub registers: Vec<Register>,
+}
+
+/// A register of a peripheral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Register {
+    /// Name, such as `CR1`.
+    pub name: String,
+    /// Offset in bytes from the base address of the peripheral.
+    pub offset: u32,
+    /// Size in bits.
+    pub size: u32,
+    /// Fields in the order they are in the file.
+    pub fields: Vec<Field>,
+}
+
+/// A range of bits in a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// Name, such as `UE`.
+    pub name: String,
+    /// Number of the lowest bit.
+    pub offset: u8,
+    /// Number of bits.
+    pub width: u8,
+}
+
+impl Device {
+    /// Returns the peripheral called `name`.
+    pub fn peripheral(&self, name: &str) -> Option<&Peripheral> {
+        self.peripherals.iter().find(|p| p.name == name)
+    }
+
+    /// Returns the peripheral that has the registers of `peripheral`, following `derived_from`.
+    pub fn registers_of<'a>(&'a self, peripheral: &'a Peripheral) -> &'a Peripheral {
+        let mut p = peripheral;
+        // a limit in case the file derives in a loop
+        for _ in 0..self.peripherals.len() {
+            match p
+                .derived_from
+                .as_deref()
+                .and_then(|name| self.peripheral(name))
+            {
+                Some(base) => p = base,
+                None => break,
+            }
+        }
+        p
+    }
+}
+
+/// Reads a number, which can be decimal, hex starting with `0x`, or binary starting with `#` or
+/// `0b`.
+pub fn parse_number(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let result = if let Some(hex) = text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(bin) = text.strip_prefix('#').or(text.strip_prefix("0b")) {
+        u64::from_str_radix(bin, 2)
+    } else {
+        text.parse()
+    };
+    result.map_err(|_| format!("bad number: {}", text))
+}
+
+// the text of the child `name`, which must be there
+fn required<'a>(element: &'a Element, name: &str) -> Result<&'a str, String> {
+    element.child_text(name).ok_or_else(|| {
+        format!(
+            "missing <{}> in <{}> {}",
+            name,
+            element.name,
+            element.child_text("name").unwrap_or("")
+        )
+    })
+}
+
+fn optional_number(element: &Element, name: &str) -> Result<Option<u64>, String> {
+    element.child_text(name).map(parse_number).transpose()
+}
+
+fn parse_field(element: &Element) -> Result<Field, String> {
+    let name = required(element, "name")?.to_string();
+
+    let (offset, width) = if let Some(offset) = optional_number(element, "bitOffset")? {
+        let width = optional_number(element, "bitWidth")?.unwrap_or(1);
+        (offset, width)
+    } else if let Some(lsb) = optional_number(element, "lsb")? {
+        let msb = parse_number(required(element, "msb")?)?;
+        (lsb, (msb + 1).saturating_sub(lsb))
+    } else if let Some(range) = element.child_text("bitRange") {
+        // [msb:lsb]
+        let inner = range.trim_start_matches('[').trim_end_matches(']');
+        let (msb, lsb) = inner
+            .split_once(':')
+            .ok_or_else(|| format!("bad bitRange for field {}: {}", name, range))?;
+        let msb = parse_number(msb)?;
+        let lsb = parse_number(lsb)?;
+        (lsb, (msb + 1).saturating_sub(lsb))
+    } else {
+        return Err(format!("no bit position for field {}", name));
+    };
+
+    if width == 0 || offset + width > 32 {
+        return Err(format!("field {} does not fit in 32 bits", name));
+    }
+
+    Ok(Field {
+        name,
+        offset: offset as u8,
+        width: width as u8,
+    })
+}
+
+// the indexes a `dim` register is repeated for
+fn dim_indexes(element: &Element, dim: u64) -> Result<Vec<String>, String> {
+    let Some(index) = element.child_text("dimIndex") else {
+        return Ok((0..dim).map(|i| i.to_string()).collect());
+    };
+
+    let indexes: Vec<String> = if let Some((first, last)) = index.split_once('-') {
+        let first = parse_number(first)?;
+        let last = parse_number(last)?;
+        (first..=last).map(|i| i.to_string()).collect()
+    } else {
+        index.split(',').map(|s| s.trim().to_string()).collect()
+    };
+
+    if indexes.len() as u64 != dim {
+        return Err(format!("dimIndex {} does not have {} entries", index, dim));
+    }
+    Ok(indexes)
+}
+
+fn parse_registers(element: &Element, default_size: u32) -> Result<Vec<Register>, String> {
+    let name = required(element, "name")?;
+    let offset = parse_number(required(element, "addressOffset")?)? as u32;
+    let size = optional_number(element, "size")?
+        .map(|s| s as u32)
+        .unwrap_or(default_size);
+
+    let fields = match element.child("fields") {
+        Some(fields) => fields
+            .children_named("field")
+            .map(parse_field)
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let Some(dim) = optional_number(element, "dim")? else {
+        return Ok(vec![Register {
+            name: name.to_string(),
+            offset,
+            size,
+            fields,
+        }]);
+    };
+
+    // an array of registers, `%s` in the name is replaced by the index
+    let increment = parse_number(required(element, "dimIncrement")?)? as u32;
+    let name = name.replace("[%s]", "%s");
+    let registers = dim_indexes(element, dim)?
+        .iter()
+        .enumerate()
+        .map(|(i, index)| Register {
+            name: name.replace("%s", index),
+            offset: offset + i as u32 * increment,
+            size,
+            fields: fields.clone(),
+        })
+        .collect();
+    Ok(registers)
+}
+
+fn parse_peripheral(element: &Element, default_size: u32) -> Result<Peripheral, String> {
+    let name = required(element, "name")?.to_string();
+    let base_address = parse_number(required(element, "baseAddress")?)?;
+    let default_size = optional_number(element, "size")?
+        .map(|s| s as u32)
+        .unwrap_or(default_size);
+
+    let mut registers = Vec::new();
+    if let Some(regs) = element.child("registers") {
+        for register in regs.children_named("register") {
+            registers.extend(parse_registers(register, default_size)?);
+        }
+    }
+
+    Ok(Peripheral {
+        name,
+        group: element.child_text("groupName").map(str::to_string),
+        derived_from: element.attr("derivedFrom").map(str::to_string),
+        base_address,
+        registers,
+    })
+}
+
+/// Reads the SVD file in `input`.
+pub fn parse(input: &str) -> Result<Device, String> {
+    let root = xml::parse(input)?;
+    if root.name != "device" {
+        return Err(format!("expected <device> but found <{}>", root.name));
+    }
+
+    let default_size = optional_number(&root, "size")?
+        .map(|s| s as u32)
+        .unwrap_or(32);
+
+    let peripherals = match root.child("peripherals") {
+        Some(peripherals) => peripherals
+            .children_named("peripheral")
+            .map(|p| parse_peripheral(p, default_size))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    Ok(Device {
+        name: required(&root, "name")?.to_string(),
+        peripherals,
+    })
+}
@@ -0,0 +1,258 @@
+//! # XML Module
+//!
+//! This module reads an XML document into a tree of elements. It only handles what SVD files use:
+//! elements, attributes, text, comments, CDATA and the standard entities. There is no support for
+//! namespaces or a DTD, which are skipped.
+//!
+//! ## Structures
+//!
+//! - `Element`: An element with its attributes, text and child elements.
+//!
+//! ## Functions
+//!
+//! - `parse`: Reads a document and returns the root element.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use svd2rusty::xml;
+//!
+//! let root = xml::parse("<device><name>STM32F405</name></device>").unwrap();
+//! assert_eq!(root.child_text("name"), Some("STM32F405"));
+//! ```
+//!
+
+/// An XML element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+    /// Tag name.
+    pub name: String,
+    /// Attributes, in the order they were given.
+    pub attrs: Vec<(String, String)>,
+    /// Child elements.
+    pub children: Vec<Element>,
+    /// All the text directly inside the element, with entities replaced.
+    pub text: String,
+}
+
+impl Element {
+    /// Returns the value of the attribute `name`.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first child element called `name`.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Returns all the child elements called `name`.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Returns the text of the first child element called `name`, without surrounding space.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim())
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> String {
+        let line = self.input[..self.pos].matches('\n').count() + 1;
+        format!("XML error on line {}: {}", line, msg)
+    }
+
+    fn skip_space(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    // moves past `end`, which must come later in the input
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, String> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.pos += i + end.len();
+                Ok(skipped)
+            }
+            None => Err(self.error(&format!("missing {}", end))),
+        }
+    }
+
+    // skips the XML declaration, processing instructions, comments and DOCTYPE
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_space();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, String> {
+        let len = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=')
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = &self.rest()[..len];
+        self.pos += len;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, String> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        self.pos += 1;
+
+        let mut element = Element {
+            name: self.name()?.to_string(),
+            ..Default::default()
+        };
+
+        // attributes
+        loop {
+            self.skip_space();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+
+            let name = self.name()?.to_string();
+            self.skip_space();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected = after attribute name"));
+            }
+            self.pos += 1;
+            self.skip_space();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let value = self.skip_past(&quote.to_string())?;
+            element.attrs.push((name, unescape(value)));
+        }
+
+        // content
+        loop {
+            let text_len = self.rest().find('<').unwrap_or(self.rest().len());
+            element.text.push_str(&unescape(&self.rest()[..text_len]));
+            self.pos += text_len;
+
+            if self.rest().is_empty() {
+                return Err(self.error(&format!("missing </{}>", element.name)));
+            } else if self.rest().starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "expected </{}> but found </{}>",
+                        element.name, name
+                    )));
+                }
+                self.skip_space();
+                self.skip_past(">")?;
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.skip_past("]]>")?;
+                element.text.push_str(text);
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else {
+                element.children.push(self.element()?);
+            }
+        }
+    }
+}
+
+// replaces the standard entities and character references
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match c {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                // not an entity we know, keep it as it is
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Reads the XML document in `input` and returns its root element.
+pub fn parse(input: &str) -> Result<Element, String> {
+    let mut parser = Parser { input, pos: 0 };
+
+    // a byte order mark is allowed at the start
+    if parser.rest().starts_with('\u{feff}') {
+        parser.pos += '\u{feff}'.len_utf8();
+    }
+
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    if !parser.rest().is_empty() {
+        return Err(parser.error("content after the root element"));
+    }
+    Ok(root)
+}
@@ -0,0 +1,171 @@
+use svd2rusty::gen::{self, Options};
+use svd2rusty::svd::{self, Field};
+use svd2rusty::xml;
+
+const TEST_SVD: &str = include_str!("test.svd");
+
+fn only(names: &[&str]) -> Options {
+    Options {
+        only: Some(names.iter().map(|s| s.to_string()).collect()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml() {
+        let root = xml::parse(TEST_SVD).unwrap();
+        assert_eq!(root.name, "device");
+        assert_eq!(root.attr("schemaVersion"), Some("1.1"));
+        assert_eq!(root.child_text("description"), Some("Test <chip> & more"));
+        assert_eq!(root.child("peripherals").unwrap().children.len(), 6);
+
+        let root = xml::parse("<a x='1&lt;2'><b/>&#65;&#x42;</a>").unwrap();
+        assert_eq!(root.attr("x"), Some("1<2"));
+        assert_eq!(root.children[0].name, "b");
+        assert_eq!(root.text, "AB");
+    }
+
+    #[test]
+    fn test_xml_errors() {
+        assert!(xml::parse("<a><b></a>").is_err());
+        assert!(xml::parse("<a>").is_err());
+        assert!(xml::parse("<a></a><b></b>").is_err());
+
+        let err = xml::parse("<a>\n<b x=1></b></a>").unwrap_err();
+        assert!(err.contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(svd::parse_number("0x40023800"), Ok(0x4002_3800));
+        assert_eq!(svd::parse_number(" 0X1F "), Ok(31));
+        assert_eq!(svd::parse_number("32"), Ok(32));
+        assert_eq!(svd::parse_number("#101"), Ok(5));
+        assert_eq!(svd::parse_number("0b11"), Ok(3));
+        assert!(svd::parse_number("0xZZ").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let device = svd::parse(TEST_SVD).unwrap();
+        assert_eq!(device.name, "TEST32");
+        assert_eq!(device.peripherals.len(), 6);
+
+        // the three ways of giving the bits of a field
+        let rcc = device.peripheral("RCC").unwrap();
+        assert_eq!(rcc.base_address, 0x4002_3800);
+        let field = |reg: usize, i: usize| rcc.registers[reg].fields[i].clone();
+        let expected = |name: &str, offset, width| Field {
+            name: name.to_string(),
+            offset,
+            width,
+        };
+        assert_eq!(field(0, 0), expected("HSEON", 16, 1));
+        assert_eq!(field(0, 1), expected("HSITRIM", 3, 5));
+        assert_eq!(field(1, 0), expected("SW", 0, 2));
+
+        // the registers of a derived peripheral are those it is derived from
+        let usart2 = device.peripheral("USART2").unwrap();
+        assert!(usart2.registers.is_empty());
+        assert_eq!(device.registers_of(usart2).name, "USART1");
+
+        // a register array is one register per index
+        let tim1 = device.peripheral("TIM1").unwrap();
+        let names: Vec<_> = tim1.registers.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["CCR1", "CCR2"]);
+        assert_eq!(tim1.registers[1].offset, 0x38);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(svd::parse("<notadevice/>").is_err());
+
+        let bad_field =
+            TEST_SVD.replace("<bitRange>[1:0]</bitRange>", "<bitRange>[40:0]</bitRange>");
+        let err = svd::parse(&bad_field).unwrap_err();
+        assert!(err.contains("SW"), "{}", err);
+
+        let backwards =
+            TEST_SVD.replace("<bitRange>[1:0]</bitRange>", "<bitRange>[0:1]</bitRange>");
+        assert!(svd::parse(&backwards).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let device = svd::parse(TEST_SVD).unwrap();
+        let out = gen::generate(&device, &only(&["RCC"]));
+
+        let expected = "// DO NOT EDIT. This was generated by svd2rusty
+//! This module provides definitions for various hardware registers and their fields.
+
+#![allow(unused)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+
+pub mod RCC {
+    pub mod cr {
+        pub const HSEON: u8 = 16;
+        pub const HSITRIM: u8 = 3;
+        pub mod width {
+            pub const HSEON: u8 = 1;
+            pub const HSITRIM: u8 = 5;
+        }
+    }
+    pub mod cfgr {
+        pub const SW: u8 = 0;
+        pub mod width {
+            pub const SW: u8 = 2;
+        }
+    }
+    pub mod bdcr {
+    }
+}
+
+#[repr(C)]
+pub struct RccReg {
+    pub cr: u32,
+    reserved0: u32,
+    pub cfgr: u32,
+    reserved1: [u32; 2],
+    pub bdcr: u32,
+}
+
+pub const RCC: *mut RccReg = 0x40023800 as *mut RccReg;
+// Skipped peripheral: USART1
+// Skipped peripheral: USART2
+// Skipped peripheral: TIM1
+// Skipped peripheral: TIM2
+// Skipped peripheral: DAC
+";
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_names() {
+        let device = svd::parse(TEST_SVD).unwrap();
+        let out = gen::generate(&device, &only(&["USART2", "TIM1", "TIM2"]));
+
+        // a group with one set of registers is named after the group
+        assert!(out.contains("pub mod USART {"));
+        assert!(out.contains("pub struct UsartReg {"));
+        assert!(out.contains("pub const USART2: *mut UsartReg = 0x40004400 as *mut UsartReg;"));
+        assert!(out.contains("// Skipped peripheral: USART1"));
+
+        // TIM1 and TIM2 have different registers in the same group
+        assert!(out.contains("pub mod TIM1 {"));
+        assert!(out.contains("pub mod TIM2 {"));
+        assert!(out.contains("    reserved0: [u32; 13],\n    pub ccr1: u32,\n    pub ccr2: u32,"));
+
+        let mut options = only(&["TIM1"]);
+        options
+            .renames
+            .push(("TIM1".to_string(), "TIM_ADV".to_string()));
+        let out = gen::generate(&device, &options);
+        assert!(out.contains("pub mod TIM_ADV {"));
+        assert!(out.contains("pub const TIM1: *mut TimAdvReg = 0x40010000 as *mut TimAdvReg;"));
+    }
+}
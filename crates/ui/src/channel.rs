@@ -49,6 +49,7 @@ use crate::msg::Msg;
 pub mod mpsc {
     use super::Msg;
     use crate::vec::VecMsg;
+    use hal::interrupt;
 
     const Q_SIZE: usize = 10;
 
@@ -57,11 +58,16 @@ pub mod mpsc {
     #[cfg(not(feature = "std"))]
     const NUM_QUEUES: usize = 2;
 
-    static mut Q: [VecMsg<Q_SIZE>; NUM_QUEUES] = [{ VecMsg::new() }, { VecMsg::new() }];
+    // in the simulator each thread has its own queues, so tests each start with none
+    hal::local! {
+        static mut Q: [VecMsg<Q_SIZE>; NUM_QUEUES] = [{ VecMsg::new() }, { VecMsg::new() }];
+    }
 
     //static mut Q: [[Msg; Q_SIZE]; NUM_QUEUES] = [[Msg::None; Q_SIZE]; NUM_QUEUES];
     //static mut Q_LEN: [usize; NUM_QUEUES] = [0; NUM_QUEUES];
-    static mut NUM_Q: usize = 0;
+    hal::local! {
+        static mut NUM_Q: usize = 0;
+    }
 
     /// A sender for a message channel.
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -83,7 +89,8 @@ pub mod mpsc {
             // if q_len >= Q_SIZE {
             //    panic!("Queue full");
             //}
-            unsafe { Q[ch].push(msg) };
+            // an interrupt handler may be sending too
+            interrupt::free(|_cs| unsafe { hal::local!(mut Q)[ch].push(msg) });
         }
     }
 
@@ -105,23 +112,24 @@ pub mod mpsc {
         pub fn recv(&self) -> Msg {
             let ch = self.ch;
 
-            if unsafe { Q[ch].len() } == 0 {
-                return Msg::None;
-            }
-            let msg = unsafe { Q[ch].pop() };
-
-            msg
+            interrupt::free(|_cs| {
+                let q = unsafe { &mut hal::local!(mut Q)[ch] };
+                if q.len() == 0 {
+                    return Msg::None;
+                }
+                q.pop()
+            })
         }
     }
 
     #[allow(dead_code)]
     pub fn init() {
-        unsafe {
-            for i in 0..NUM_QUEUES {
-                Q[i].clear();
+        interrupt::free(|_cs| {
+            for q in unsafe { hal::local!(mut Q) }.iter_mut() {
+                q.clear();
             }
-            NUM_Q = 0;
-        }
+            unsafe { *hal::local!(mut NUM_Q) = 0 };
+        });
     }
 
     /// Creates a new message channel.
@@ -131,8 +139,12 @@ pub mod mpsc {
     /// * `(Sender<T>, Receiver<T>)` - A tuple containing the sender and receiver for the channel.
     ///
     pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-        let ch = unsafe { NUM_Q };
-        unsafe { NUM_Q += 1 };
+        let ch = interrupt::free(|_cs| {
+            let num_q = unsafe { hal::local!(mut NUM_Q) };
+            let ch = *num_q;
+            *num_q += 1;
+            ch
+        });
         if ch >= NUM_QUEUES {
             panic!("Too many channels");
         }
@@ -186,6 +198,4 @@ pub fn test_channel() {
             }
         }
     }
-
-    mpsc::init(); // clean up after test
 }
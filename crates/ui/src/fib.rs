@@ -21,7 +21,7 @@ pub fn fib_test() {
 
     debug::set(0, true);
     let start_time = hal::timer::current_time();
-    fib(34);
+    let (_, cycles) = hal::dwt::measure(|| fib(34));
     let end_time = hal::timer::current_time();
     debug::set(0, false);
 
@@ -31,7 +31,9 @@ pub fn fib_test() {
     b"  Duration fib(34): ".print_console();
     let duration_ms = (duration.as_u64()) / 1000; // convert to mS
     duration_ms.print_console();
-    b" mS\r\n".print_console();
+    b" mS, ".print_console();
+    cycles.print_console();
+    b" cycles\r\n".print_console();
 }
 
 #[inline(never)]
@@ -21,6 +21,7 @@ mod fib;
 mod font;
 mod metrics;
 mod msg;
+mod profile;
 mod semihost;
 mod stack;
 mod startup;
@@ -48,14 +49,19 @@ fn main() {
     my_main();
 }
 
+/// How long the main loop can go without feeding the watchdog before it resets the system.
+/// The IWDG keeps counting in STOP mode, so this must be longer than the longest idle plus the
+/// slowest task, allowing for the LSI running up to 30% fast.
+const WATCH_DOG_TIMEOUT_MS: u32 = 4_000;
+
 //#[link_section = ".data"]
-static mut HEAP_TASK_DATA: tasks::TaskData = tasks::TaskData::new();
+hal::local! {
+    static mut HEAP_TASK_DATA: tasks::TaskData = tasks::TaskData::new();
+}
 
-fn alloc_task_data() -> &'static mut tasks::TaskData {
-    #[allow(static_mut_refs)]
-    unsafe {
-        &mut HEAP_TASK_DATA
-    }
+// must be called only once on a thread, as each call returns the same data
+unsafe fn alloc_task_data() -> &'static mut tasks::TaskData {
+    unsafe { hal::local!(mut HEAP_TASK_DATA) }
 }
 
 #[cfg(feature = "std")]
@@ -97,17 +103,17 @@ fn my_main() {
 
     b"Starting\r\n".print_console();
 
+    b"  Reset cause: ".print_console();
+    hal::reset::read_cause().as_str().as_bytes().print_console();
+    b"\r\n".print_console();
+
     // TODO remove - just testing
     if false {
         if cfg!(not(feature = "std")) {
             b"  Pre  DMA\r\n".print_console();
             //let data = b"TEST DMA \r\n";
             //let static const test_print_data = b"1234";
-            #[allow(unused_unsafe)]
-            unsafe {
-                // TODO remove unsafe
-                hal::uart::write1_dma(TEST_PRINT_DATA);
-            }
+            bsp::console::UART.write_dma(TEST_PRINT_DATA);
 
             fib::fib_test();
             b"  Post  DMA\r\n".print_console();
@@ -122,7 +128,7 @@ fn my_main() {
 
     let mut metrics = metrics::Metrics::new();
 
-    let mut data: &mut tasks::TaskData = alloc_task_data();
+    let mut data: &mut tasks::TaskData = unsafe { alloc_task_data() };
 
     data.junk_data[0] = 1;
 
@@ -132,9 +138,11 @@ fn my_main() {
 
     let mut task_mgr = tasks::TaskMgr::new(&mut sender, &mut bsp, &mut data, &mut metrics);
 
-    // this is removed for now as using button for mock keyboard
-    //let button_task = tasks::buttons_task::ButtonTask {};
-    //task_mgr.add_task(&button_task);
+    let button_task = tasks::buttons_task::ButtonTask {};
+    task_mgr.add_task(&button_task);
+
+    let battery_task = tasks::battery_task::BatteryTask {};
+    task_mgr.add_task(&battery_task);
 
     let chat_task = tasks::chat_task::ChatTask {};
     task_mgr.add_task(&chat_task);
@@ -181,7 +189,12 @@ fn my_main() {
     #[cfg(feature = "exit")]
     task_mgr.sender.send(Msg::Keyboard { key: '\r' });
 
+    // from here on a hung task or a lost wake up resets the system
+    hal::watch_dog::start_independent(WATCH_DOG_TIMEOUT_MS);
+
     loop {
+        hal::watch_dog::alive_independent();
+
         task_mgr.run();
         dispatch::process(receiver, &mut task_mgr);
 
@@ -198,6 +211,10 @@ fn my_main() {
         {
             break;
         }
+
+        // nothing to do until the next task is due
+        #[allow(unreachable_code)]
+        task_mgr.idle();
     }
 }
 
@@ -225,13 +242,16 @@ mod tests {
 
         let mut metrics = metrics::Metrics::new();
 
-        let mut data: &mut tasks::TaskData = alloc_task_data();
+        let mut data: &mut tasks::TaskData = unsafe { alloc_task_data() };
 
         let mut task_mgr = tasks::TaskMgr::new(&mut sender, &mut bsp, &mut data, &mut metrics);
 
         let button_task = tasks::buttons_task::ButtonTask {};
         task_mgr.add_task(&button_task);
 
+        let battery_task = tasks::battery_task::BatteryTask {};
+        task_mgr.add_task(&battery_task);
+
         let chat_task = tasks::chat_task::ChatTask {};
         task_mgr.add_task(&chat_task);
 
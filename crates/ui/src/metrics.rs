@@ -1,6 +1,8 @@
 //! Metrics module that holds all the metrics for the system.
 
+use crate::profile::MAX_SCOPES;
 use crate::tasks::MAX_TASKS;
+use hal::timer::Instant;
 
 /// Structure to hold all the metrics for the system.
 pub struct Metrics {
@@ -8,6 +10,17 @@ pub struct Metrics {
     pub task_run_count: [u32; MAX_TASKS],
     pub task_max_stack: [u32; MAX_TASKS],
     pub task_max_duration_us: [u32; MAX_TASKS],
+    /// Start of the time the idle metrics cover.
+    pub idle_since: Instant,
+    /// Time spent asleep waiting for the next task, in microseconds.
+    pub idle_us: u64,
+    /// Number of times the CPU went into STOP mode.
+    pub stop_count: u32,
+    /// Names of the scopes timed with `profile::scope`, unused entries start with 0.
+    pub scope_name: [[u8; 8]; MAX_SCOPES],
+    pub scope_count: [u32; MAX_SCOPES],
+    pub scope_total_cycles: [u64; MAX_SCOPES],
+    pub scope_max_cycles: [u32; MAX_SCOPES],
 }
 
 impl Metrics {
@@ -18,6 +31,27 @@ impl Metrics {
             task_run_count: [0; MAX_TASKS],
             task_max_stack: [0; MAX_TASKS],
             task_max_duration_us: [0; MAX_TASKS],
+            idle_since: Instant::from_micros(0),
+            idle_us: 0,
+            stop_count: 0,
+            scope_name: [[0; 8]; MAX_SCOPES],
+            scope_count: [0; MAX_SCOPES],
+            scope_total_cycles: [0; MAX_SCOPES],
+            scope_max_cycles: [0; MAX_SCOPES],
+        }
+    }
+
+    /// Adds one run of the scope `name` that took `cycles`. Once all `MAX_SCOPES` entries are
+    /// used, runs of scopes with new names are not recorded.
+    pub fn record_scope(&mut self, name: &'static [u8; 8], cycles: u32) {
+        let Some(i) = self.scope_name.iter().position(|n| n == name || n[0] == 0) else {
+            return;
+        };
+        self.scope_name[i] = *name;
+        self.scope_count[i] += 1;
+        self.scope_total_cycles[i] += cycles as u64;
+        if cycles > self.scope_max_cycles[i] {
+            self.scope_max_cycles[i] = cycles;
         }
     }
 }
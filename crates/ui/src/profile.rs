@@ -0,0 +1,80 @@
+//! Profiling of short stretches of code with the DWT cycle counter.
+//!
+//! `scope` runs some code and adds the cycles it took to the entry for its name in `Metrics`.
+//! The metrics task prints the number of runs, the average and the most cycles of each scope,
+//! which shows the hot spots in code that is too quick to time with `timer`. The scopes of the
+//! task manager include the scopes inside them.
+//!
+//! ```rust
+//! let width = profile::scope(metrics, b"Glyph___", |_| render_glyph(c, x, y, data));
+//! ```
+
+use crate::metrics::Metrics;
+use hal::dwt;
+
+/// Most scopes with different names that `Metrics` keeps.
+pub const MAX_SCOPES: usize = 16;
+
+#[inline(always)]
+/// Runs `f` and records the cycles it took in `metrics` under `name`. `f` is given the metrics
+/// so the scopes inside it can be recorded too.
+pub fn scope<R>(
+    metrics: &mut Metrics,
+    name: &'static [u8; 8],
+    f: impl FnOnce(&mut Metrics) -> R,
+) -> R {
+    let (result, cycles) = dwt::measure(|| f(metrics));
+    metrics.record_scope(name, cycles);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        hal::init(16_000_000);
+        dwt::init();
+        let mut metrics = Metrics::new();
+
+        let sum = scope(&mut metrics, b"Outer___", |metrics| {
+            scope(metrics, b"Inner___", |_| 1) + scope(metrics, b"Inner___", |_| 2)
+        });
+        assert_eq!(sum, 3);
+
+        // the inner scope is recorded first as it finishes first
+        assert_eq!(&metrics.scope_name[0], b"Inner___");
+        assert_eq!(metrics.scope_count[0], 2);
+        assert_eq!(&metrics.scope_name[1], b"Outer___");
+        assert_eq!(metrics.scope_count[1], 1);
+        assert!(metrics.scope_max_cycles[1] as u64 >= metrics.scope_total_cycles[0] / 2);
+        assert_eq!(metrics.scope_name[2][0], 0);
+    }
+
+    #[test]
+    fn test_full() {
+        static NAMES: [[u8; 8]; MAX_SCOPES + 1] = {
+            let mut names = [*b"Scope__ "; MAX_SCOPES + 1];
+            let mut i = 0;
+            while i < names.len() {
+                names[i][7] = b'A' + i as u8;
+                i += 1;
+            }
+            names
+        };
+
+        let mut metrics = Metrics::new();
+        for name in &NAMES {
+            metrics.record_scope(name, 10);
+        }
+        metrics.record_scope(&NAMES[0], 30);
+
+        // the scope after the table is full is dropped
+        assert_eq!(metrics.scope_name, NAMES[..MAX_SCOPES]);
+        assert_eq!(metrics.scope_count[0], 2);
+        assert_eq!(metrics.scope_total_cycles[0], 40);
+        assert_eq!(metrics.scope_max_cycles[0], 30);
+        assert_eq!(metrics.scope_count[MAX_SCOPES - 1], 1);
+    }
+}
@@ -50,6 +50,7 @@ use core::panic::PanicInfo;
 #[panic_handler]
 fn panic(_panic: &PanicInfo) -> ! {
     led::set(Color::Red);
+    bsp::console::flush();
     loop {
         #[cfg(feature = "exit")]
         hal::semihost::exit(0);
@@ -116,9 +117,8 @@ pub extern "C" fn Default_Handler() {
 #[cfg(not(feature = "std"))]
 #[inline(never)]
 #[no_mangle]
-pub extern "C" fn Dma_Uart1_Handler() {
-    led::set(Color::White); // TODO: remove this
-    hal::uart::dma_uart1_irq();
+pub extern "C" fn DMA2_Stream7_IRQHandler() {
+    hal::dma::handle_irq(hal::cpu::DMA2, 7);
 }
 
 #[cfg(not(feature = "std"))]
@@ -198,18 +198,93 @@ pub extern "C" fn Default_Handler6() {
     Default_Handler();
 }
 
+#[cfg(target_arch = "arm")]
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+#[no_mangle]
+pub extern "C" fn DMA1_Stream0_IRQHandler() {
+    hal::dma::handle_irq(hal::cpu::DMA1, 0);
+}
+
+#[cfg(target_arch = "arm")]
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+#[no_mangle]
+pub extern "C" fn DMA1_Stream3_IRQHandler() {
+    hal::dma::handle_irq(hal::cpu::DMA1, 3);
+}
+
+#[cfg(target_arch = "arm")]
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+#[no_mangle]
+pub extern "C" fn DMA1_Stream4_IRQHandler() {
+    hal::dma::handle_irq(hal::cpu::DMA1, 4);
+}
+
+#[cfg(target_arch = "arm")]
+#[cfg(not(feature = "std"))]
+#[inline(never)]
+#[no_mangle]
+pub extern "C" fn DMA1_Stream5_IRQHandler() {
+    hal::dma::handle_irq(hal::cpu::DMA1, 5);
+}
+
 #[cfg(not(feature = "std"))]
 #[no_mangle]
 pub extern "C" fn TIM1_UP_TIM10_IRQHandler() {
     //hal::timer::handle_tim1_irq();
 }
 
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+// shared by all the EXTI interrupts, `hal::exti` works out which lines triggered
+pub extern "C" fn EXTI_IRQHandler() {
+    hal::exti::handle_irq();
+}
+
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn RTC_WKUP_IRQHandler() {
+    hal::rtc::handle_wkup_irq();
+}
+
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn RTC_Alarm_IRQHandler() {
+    hal::rtc::handle_alarm_irq();
+}
+
 #[cfg(not(feature = "std"))]
 #[no_mangle]
 pub extern "C" fn TIM2_IRQHandler() {
     hal::timer::handle_tim2_irq();
 }
 
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn USART1_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart1);
+}
+
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn USART2_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart2);
+}
+
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn USART3_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart3);
+}
+
+#[cfg(not(feature = "std"))]
+#[no_mangle]
+pub extern "C" fn USART6_IRQHandler() {
+    hal::uart::handle_irq(hal::uart::Instance::Usart6);
+}
+
 #[allow(dead_code)]
 pub union IrqVector {
     not_used: u32,
@@ -265,7 +340,7 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // TAMP_STAMP
     IrqVector {
-        handler: Default_Handler,
+        handler: RTC_WKUP_IRQHandler,
     }, // RTC_WKUP
     IrqVector {
         handler: Default_Handler,
@@ -274,22 +349,22 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // RCC
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI0
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI1
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI2
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI3
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI4
     IrqVector {
-        handler: Default_Handler,
+        handler: DMA1_Stream0_IRQHandler,
     }, // DMA1_Stream0
     IrqVector {
         handler: Default_Handler,
@@ -298,13 +373,13 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // DMA1_Stream2
     IrqVector {
-        handler: Default_Handler,
+        handler: DMA1_Stream3_IRQHandler,
     }, // DMA1_Stream3
     IrqVector {
-        handler: Default_Handler,
+        handler: DMA1_Stream4_IRQHandler,
     }, // DMA1_Stream4
     IrqVector {
-        handler: Default_Handler,
+        handler: DMA1_Stream5_IRQHandler,
     }, // DMA1_Stream5
     IrqVector {
         handler: Default_Handler,
@@ -325,7 +400,7 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // CAN1_SCE
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI9_5
     IrqVector {
         handler: Default_Handler1,
@@ -367,19 +442,19 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // SPI2
     IrqVector {
-        handler: Default_Handler,
+        handler: USART1_IRQHandler,
     }, // USART1
     IrqVector {
-        handler: Default_Handler,
+        handler: USART2_IRQHandler,
     }, // USART2
     IrqVector {
-        handler: Default_Handler,
+        handler: USART3_IRQHandler,
     }, // USART3
     IrqVector {
-        handler: Default_Handler,
+        handler: EXTI_IRQHandler,
     }, // EXTI15_10
     IrqVector {
-        handler: Default_Handler,
+        handler: RTC_Alarm_IRQHandler,
     }, // RTC_Alarm
     IrqVector {
         handler: Default_Handler,
@@ -466,10 +541,10 @@ pub static Exceptions: [IrqVector; 14 + 82] = [
         handler: Default_Handler,
     }, // DMA2_Stream6
     IrqVector {
-        handler: Dma_Uart1_Handler,
+        handler: DMA2_Stream7_IRQHandler,
     }, // DMA2_Stream7
     IrqVector {
-        handler: Default_Handler,
+        handler: USART6_IRQHandler,
     }, // USART6
     IrqVector {
         handler: Default_Handler,
@@ -0,0 +1,77 @@
+//! The `battery_task` module contains the implementation of the battery task.
+//! This task periodically measures the battery voltage and warns on the console
+//! when the battery becomes low.
+
+use super::{Task, TaskData};
+use crate::metrics::Metrics;
+use crate::msg::Msg;
+use crate::tasks::TaskInfo;
+
+use bsp::console::Print;
+
+/// Structure representing the battery task.
+pub struct BatteryTask {}
+
+/// Information about the battery task.
+const BATTERY_TASK_INFO: TaskInfo = TaskInfo {
+    name: b"Battery_",
+    run_every_us: 1_000_000,
+    time_budget_us: 1_000,
+    mem_budget_bytes: 200,
+};
+
+impl Task for BatteryTask {
+    /// Method to execute the battery task.
+    /// Takes a new battery reading and prints a warning when the battery goes low.
+    fn run(
+        &self,
+        _sender: &mut crate::mpsc::Sender<Msg>,
+        bsp: &mut bsp::BSP,
+        _task_data: &mut TaskData,
+        _metrics: &mut Metrics,
+    ) {
+        let was_low = bsp.battery.is_low();
+        bsp.battery.update();
+
+        if bsp.battery.is_low() && !was_low {
+            b"Battery low: ".print_console();
+            bsp.battery.get_battery_mv().print_console();
+            b" mV\r\n".print_console();
+        }
+    }
+
+    /// Returns the information about the battery task.
+    #[allow(dead_code)]
+    fn info(&self) -> &'static TaskInfo {
+        &BATTERY_TASK_INFO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_battery_task() {
+        let mut bsp = bsp::BSP::new();
+        bsp.init();
+
+        let (mut sender, _receiver): (mpsc::Sender<msg::Msg>, mpsc::Receiver<msg::Msg>) =
+            mpsc::channel();
+        let mut metrics = metrics::Metrics::new();
+        let mut data = tasks::TaskData::new();
+
+        let battery_task = tasks::battery_task::BatteryTask {};
+        battery_task.run(&mut sender, &mut bsp, &mut data, &mut metrics);
+        assert!(!bsp.battery.is_low());
+
+        // the battery drops to 3.4 V and each run pulls the reading down
+        let channel = bsp::board::info::BATTERY_ADC_CHANNEL;
+        hal::sim::set_analog(channel, 3400 / bsp::board::info::BATTERY_DIVIDER);
+        for _ in 0..50 {
+            battery_task.run(&mut sender, &mut bsp, &mut data, &mut metrics);
+        }
+        assert!(bsp.battery.is_low());
+    }
+}
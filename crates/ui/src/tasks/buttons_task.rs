@@ -1,12 +1,16 @@
 //! The `buttons_task` module contains the implementation of the button task.
 //!
-//! This task is responsible for reading the state of a button and sending a message
-//! if the button state changes.
+//! This task is responsible for sending a message for each press and release of a
+//! button. The presses are found by interrupt, this task passes them on.
+//!
+//! For now the PTT button mocks the keyboard: a press sends 'A' and a release sends
+//! a return.
 
 use super::{Task, TaskData};
 use crate::metrics::Metrics;
 use crate::msg::Msg;
 use crate::tasks::TaskInfo;
+use bsp::buttons::Button;
 
 /// Structure representing the button task.
 pub struct ButtonTask {}
@@ -21,7 +25,7 @@ const BUTTON_TASK_INFO: TaskInfo = TaskInfo {
 
 impl Task for ButtonTask {
     /// Method to execute the button task.
-    /// Reads the state of the button and sends a message if the state has changed.
+    /// Sends a message for each button press or release since the last run.
     fn run(
         &self,
         sender: &mut crate::mpsc::Sender<Msg>,
@@ -30,9 +34,20 @@ impl Task for ButtonTask {
         _metrics: &mut Metrics,
     ) {
         // junk sender.send(crate::msg::Msg::None );
-        let (state, changed) = bsp.buttons.read_ptt();
-        if changed {
-            sender.send(Msg::PttButton(state));
+        // this uses the PTT button to mock the keyboard
+        let button_mock_keyboard = true;
+
+        while let Some(event) = bsp.buttons.next_event() {
+            if event.button != Button::Ptt {
+                continue;
+            }
+            if !button_mock_keyboard {
+                sender.send(Msg::PttButton(event.pressed));
+            } else if event.pressed {
+                sender.send(Msg::Keyboard { key: 'A' });
+            } else {
+                sender.send(Msg::Keyboard { key: '\r' });
+            }
         }
     }
 
@@ -16,6 +16,9 @@ use crate::tasks::TaskInfo;
 /// Structure representing the keyboard task.
 pub struct KeyboardTask {}
 
+/// Most serial port keys to send in one run so the message queue does not fill up.
+const MAX_SERIAL_KEYS_PER_RUN: usize = 8;
+
 /// Information about the keyboard task.
 const KEYBOARD_TASK_INFO: TaskInfo = TaskInfo {
     name: b"Keyboard",
@@ -34,34 +37,20 @@ impl Task for KeyboardTask {
         _task_data: &mut TaskData,
         _metrics: &mut Metrics,
     ) {
-        // this uses the PTT button to mock the keyboard
-        let button_moch_keyboard = true;
-        if button_moch_keyboard {
-            let (state, changed) = bsp.buttons.read_ptt();
-            if changed {
-                if state {
-                    let keyboard_msg = Msg::Keyboard { key: 'A' };
-                    sender.send(keyboard_msg);
-                } else {
-                    let keyboard_msg = Msg::Keyboard { key: '\r' };
-                    sender.send(keyboard_msg);
-                }
-            }
-        }
-
         let key = bsp.keyboard.get_key();
         if key != 0 {
             let keyboard_msg = Msg::Keyboard { key: key as char };
             sender.send(keyboard_msg);
         }
 
-        // check if key from serial port
-        if !hal::uart::empty1() {
-            let c: u8;
-            c = hal::uart::read1();
+        // check for keys from serial port, the rest wait in the UART receive buffer
+        for _ in 0..MAX_SERIAL_KEYS_PER_RUN {
+            let Some(c) = bsp::console::UART.rx().read() else {
+                break;
+            };
             let echo: bool = true; // TODO - turn off echo
             if echo && (c != 0) {
-                hal::uart::write1(c);
+                bsp::console::UART.write(c);
             }
 
             let keyboard_msg = Msg::Keyboard { key: c as char };
@@ -1,15 +1,18 @@
 //! The `metrics_task` module contains the implementation of the metrics task.
-//! This task is responsible for periodically printing all metrics to the console
-//! and then resetting the metrics.
+//! This task is responsible for periodically printing all metrics to the console,
+//! including how much of the time the CPU was idle and the cycles taken by each profiled
+//! scope, and then resetting the metrics.
 
 use super::{Task, TaskData};
 use crate::metrics::Metrics;
 use crate::msg::Msg;
 use crate::tasks::TaskInfo;
 
+use crate::profile::MAX_SCOPES;
 use crate::tasks::MAX_TASKS;
 use bsp::console::print_pad;
 use bsp::console::Print;
+use hal::timer::Instant;
 
 /// Structure representing the metrics task.
 pub struct MetricsTask {}
@@ -62,6 +65,46 @@ impl Task for MetricsTask {
                 metrics.task_max_duration_us[i] = 0;
             }
         }
+
+        for i in 0..MAX_SCOPES {
+            if metrics.scope_count[i] == 0 {
+                continue;
+            }
+
+            let avg = (metrics.scope_total_cycles[i] / metrics.scope_count[i] as u64) as u32;
+            b"Scope ".print_console();
+            metrics.scope_name[i].print_console();
+            b": ".print_console();
+            print_pad(metrics.scope_count[i], 6);
+            metrics.scope_count[i].print_console();
+            b" runs, ".print_console();
+            print_pad(avg, 9);
+            avg.print_console();
+            b" avg, ".print_console();
+            print_pad(metrics.scope_max_cycles[i], 10);
+            metrics.scope_max_cycles[i].print_console();
+            b" max cycles\r\n".print_console();
+
+            metrics.scope_count[i] = 0;
+            metrics.scope_total_cycles[i] = 0;
+            metrics.scope_max_cycles[i] = 0;
+        }
+
+        // share of the time since the last print spent asleep
+        let now = Instant::now();
+        let total_us = now.duration_since(metrics.idle_since).as_micros();
+        if total_us > 0 {
+            b"Idle: ".print_console();
+            ((metrics.idle_us * 100 / total_us) as u32).print_console();
+            b"% of ".print_console();
+            (total_us / 1000).print_console();
+            b" ms, ".print_console();
+            metrics.stop_count.print_console();
+            b" stops\r\n".print_console();
+        }
+        metrics.idle_since = now;
+        metrics.idle_us = 0;
+        metrics.stop_count = 0;
     }
 
     /// Returns the information about the metrics task.
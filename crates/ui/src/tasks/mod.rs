@@ -4,6 +4,7 @@
 
 mod no_task;
 
+pub mod battery_task;
 pub mod buttons_task;
 pub mod chat_task;
 pub mod crypto_task;
@@ -16,8 +17,11 @@ pub mod text_edit_task;
 
 use crate::metrics::Metrics;
 use crate::msg::Msg;
+use crate::profile;
 use crate::stack;
 use bsp::console::Print;
+use hal::power;
+use hal::timer::{Duration, Instant};
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -80,12 +84,17 @@ pub trait Task {
 /// The maximum number of tasks that can be managed by the `TaskMgr`.
 pub const MAX_TASKS: usize = 10;
 
+/// Shortest idle time to use STOP mode for. STOP takes longer to wake from than
+/// sleep and the console byte that wakes it is lost. A task that runs
+/// more often than this keeps the system out of STOP mode.
+const STOP_MIN_IDLE: Duration = Duration::from_millis(20);
+
 /// Structure that manages the execution of tasks.
 pub struct TaskMgr<'a> {
     /// An array of tasks to be managed.
     tasks: [&'a dyn Task; MAX_TASKS],
     /// An array of timestamps indicating the last run time of each task.
-    last_run: [hal::timer::MicroSeconds; MAX_TASKS],
+    last_run: [Instant; MAX_TASKS],
     /// The number of tasks currently managed.
     num_tasks: usize,
     /// A message sender for inter-task communication.
@@ -111,7 +120,7 @@ impl<'a> TaskMgr<'a> {
     ) -> TaskMgr<'a> {
         TaskMgr {
             tasks: [&NO_TASK; MAX_TASKS],
-            last_run: [hal::timer::MicroSeconds(0); MAX_TASKS],
+            last_run: [Instant::from_micros(0); MAX_TASKS],
             num_tasks: 0,
             sender: s,
             bsp: bsp,
@@ -129,6 +138,38 @@ impl<'a> TaskMgr<'a> {
         self.num_tasks += 1;
     }
 
+    /// Returns the time the next task is due to run.
+    pub fn next_deadline(&self) -> Instant {
+        let mut next = Instant::from_micros(u64::MAX);
+        for i in 0..self.num_tasks {
+            let every = Duration::from_micros(self.tasks[i].info().run_every_us as u64);
+            let due = self.last_run[i].checked_add(every).unwrap_or(next);
+            if due < next {
+                next = due;
+            }
+        }
+        next
+    }
+
+    /// Sleeps until the next task is due or an interrupt comes in, and adds the
+    /// time asleep to the metrics. Long idles use STOP mode if the BSP allows it.
+    pub fn idle(&mut self) {
+        let start = Instant::now();
+        let deadline = self.next_deadline();
+        if deadline <= start {
+            return;
+        }
+
+        if deadline.duration_since(start) >= STOP_MIN_IDLE && self.bsp.can_stop() {
+            self.bsp.stop_until(deadline);
+            self.metrics.stop_count += 1;
+        } else {
+            power::sleep_until(deadline);
+        }
+
+        self.metrics.idle_us += start.elapsed().as_micros();
+    }
+
     /// Runs all the tasks managed by the `TaskMgr`, ensuring
     /// they adhere to their time and memory budgets.
     /// The method also updates the task metrics.
@@ -140,29 +181,34 @@ impl<'a> TaskMgr<'a> {
             let t = self.tasks[i];
             let info = t.info();
 
-            let now = hal::timer::current_time();
+            let now = Instant::now();
 
-            if now.sub(self.last_run[i]).as_u64() < info.run_every_us as u64 {
+            if now.duration_since(self.last_run[i])
+                < Duration::from_micros(info.run_every_us as u64)
+            {
                 continue;
             }
 
-            let start_time = hal::timer::current_time();
-            t.run(self.sender, self.bsp, self.data, self.metrics);
-            let end_time = hal::timer::current_time();
+            // the cycles go in the metrics under the task name, for tasks too quick to time in us
+            let start_time = Instant::now();
+            profile::scope(self.metrics, info.name, |metrics| {
+                t.run(self.sender, self.bsp, self.data, metrics)
+            });
+            let end_time = Instant::now();
             let (end_stack_usage, ..) = stack::usage(false);
 
             self.last_run[i] = start_time;
 
-            let duration = end_time.sub(start_time).as_u64();
+            let duration = end_time.duration_since(start_time).as_micros();
             if duration > info.time_budget_us {
                 b"Exceeded time budget\r\n".print_console();
 
                 b" start=".print_console();
-                start_time.as_u64().print_console();
+                start_time.as_micros().print_console();
                 b" us\r\n".print_console();
 
                 b" end=".print_console();
-                end_time.as_u64().print_console();
+                end_time.as_micros().print_console();
                 b" us\r\n".print_console();
 
                 b" duration=".print_console();
@@ -197,3 +243,4 @@ impl<'a> TaskMgr<'a> {
         }
     }
 }
+
@@ -1,6 +1,7 @@
 use super::{Task, TaskData};
 use crate::metrics::Metrics;
 use crate::msg::Msg;
+use crate::profile;
 use crate::tasks::TaskInfo;
 use bsp::board;
 
@@ -198,10 +199,15 @@ impl Task for RenderTask {
         _sender: &mut crate::mpsc::Sender<Msg>,
         bsp: &mut bsp::BSP,
         task_data: &mut TaskData,
-        _metrics: &mut Metrics,
+        metrics: &mut Metrics,
     ) {
         let data = &mut task_data.render;
 
+        // the bitmap is still being sent to the display, try again next time
+        if !bsp.display.ready() {
+            return;
+        }
+
         if data.current_band == 0 {
             data.current_band = DISPLAY_HEIGHT / DISPLAY_BAND_HEIGHT - 1;
         } else {
@@ -248,7 +254,7 @@ impl Task for RenderTask {
 
                     let glyph = &font::GLYPH_METRICS[index];
 
-                    render_glyph(c as u8, x, y, data);
+                    profile::scope(metrics, b"Glyph___", |_| render_glyph(c as u8, x, y, data));
 
                     x += glyph.width as i32 + 1;
                 }
@@ -266,13 +272,17 @@ impl Task for RenderTask {
             }
         }
 
-        bsp.display.draw_bitmap(
-            &data.bitmap,
-            0,                                       // x
-            data.current_band * DISPLAY_BAND_HEIGHT, //y
-            DISPLAY_WIDTH,
-            DISPLAY_BAND_HEIGHT,
-        );
+        // the task data lives for the whole program, and the bitmap is not drawn into
+        // again until the display is ready
+        unsafe {
+            bsp.display.draw_bitmap(
+                &data.bitmap,
+                0,                                       // x
+                data.current_band * DISPLAY_BAND_HEIGHT, //y
+                DISPLAY_WIDTH,
+                DISPLAY_BAND_HEIGHT,
+            )
+        };
     }
 
     /// Returns the information about the render task.